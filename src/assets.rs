@@ -77,6 +77,12 @@ pub enum CustomIconName {
     ListX,
     Snail,
     Rss,
+    Undo2,
+    Keyboard,
+    Regex,
+    Shuffle,
+    Unplug,
+    Download,
 }
 
 impl CustomIconName {
@@ -117,6 +123,12 @@ impl CustomIconName {
             CustomIconName::ListX => "icons/list-x.svg",
             CustomIconName::Snail => "icons/snail.svg",
             CustomIconName::Rss => "icons/rss.svg",
+            CustomIconName::Undo2 => "icons/undo-2.svg",
+            CustomIconName::Keyboard => "icons/keyboard.svg",
+            CustomIconName::Regex => "icons/regex.svg",
+            CustomIconName::Shuffle => "icons/shuffle.svg",
+            CustomIconName::Unplug => "icons/unplug.svg",
+            CustomIconName::Download => "icons/download.svg",
         }
         .into()
     }
@@ -13,8 +13,11 @@
 // limitations under the License.
 
 use super::{KvTableColumn, KvTableColumnType};
-use crate::states::{KeyType, RedisValue, ZedisServerState, i18n_common};
-use gpui::{App, ClipboardItem, Edges, Entity, SharedString, Window, div, prelude::*, px};
+use crate::states::{KeyType, RedisValue, SortOrder, ZedisServerState, i18n_common};
+use gpui::{
+    App, ClipboardItem, Edges, Entity, FontWeight, HighlightStyle, SharedString, StyledText, Window, div, prelude::*,
+    px,
+};
 use gpui_component::{
     ActiveTheme, IconName, StyledExt, WindowExt,
     button::{Button, ButtonVariants},
@@ -23,10 +26,34 @@ use gpui_component::{
     notification::Notification,
     table::{Column, TableDelegate, TableState},
 };
-use std::{cell::Cell, rc::Rc, sync::Arc};
+use std::{cell::Cell, ops::Range, rc::Rc, sync::Arc};
 
 pub const INDEX_COLUMN_NAME: &str = "#";
 
+/// Byte ranges of `text` matching the literal parts of the filter `keyword`.
+///
+/// The keyword is a MATCH pattern, its glob wildcards split it into the parts
+/// that are searched for. Overlapping matches are merged.
+fn keyword_ranges(text: &str, keyword: &str) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = keyword
+        .split(['*', '?'])
+        .filter(|part| !part.is_empty())
+        .flat_map(|part| {
+            text.match_indices(part)
+                .map(|(start, matched)| start..start + matched.len())
+        })
+        .collect();
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
 /// Trait defining the data fetching and manipulation interface for Key-Value data.
 /// Implementers allow the `ZedisKvDelegate` to display and edit various Redis data types (Hash, Set, List, ZSet).
 pub trait ZedisKvFetcher: 'static {
@@ -76,6 +103,15 @@ pub trait ZedisKvFetcher: 'static {
         false
     }
 
+    /// Values of the row at `index` to add it back with `restore` once removed,
+    /// `None` when the removal can't be undone.
+    fn restore_values(&self, _index: usize) -> Option<Vec<SharedString>> {
+        None
+    }
+
+    /// Adds back a removed row from the values returned by `restore_values`.
+    fn restore(&self, _values: Vec<SharedString>, _cx: &mut App) {}
+
     /// Filters data based on a keyword.
     ///
     /// Filtering strategy varies by data type:
@@ -85,6 +121,37 @@ pub trait ZedisKvFetcher: 'static {
     ///   resets scan cursor and loads matching results via SCAN commands.
     fn filter(&self, keyword: SharedString, _cx: &mut App);
 
+    /// Whether a random sample can be loaded instead of scanning (Hash, Set).
+    fn can_sample(&self, _cx: &App) -> bool {
+        false
+    }
+
+    /// Replaces the loaded rows with a random sample of `count` items.
+    fn sample(&self, _count: usize, _cx: &mut App) {}
+
+    /// Returns true if the loaded rows are a random sample, not the full contents.
+    fn is_sample(&self) -> bool {
+        false
+    }
+
+    /// Current order of the rows when it can be reversed (Zset).
+    fn sort_order(&self) -> Option<SortOrder> {
+        None
+    }
+
+    /// Reloads the rows in the given order.
+    fn sort(&self, _sort_order: SortOrder, _cx: &mut App) {}
+
+    /// Keyword of the active server-side filter, its matches are highlighted in the cells (Hash).
+    fn keyword(&self) -> Option<SharedString> {
+        None
+    }
+
+    /// Whether a cell should be flagged, e.g. a field about to expire.
+    fn is_warning(&self, _row_ix: usize, _col_ix: usize) -> bool {
+        false
+    }
+
     /// Adds values for a new row.
     fn handle_add_value(&self, _values: Vec<SharedString>, _window: &mut Window, _cx: &mut App);
 
@@ -220,34 +287,56 @@ impl<T: ZedisKvFetcher + 'static> TableDelegate for ZedisKvDelegate<T> {
 
         // Default: Render value as label with copy button on hover
         let value = self.fetcher.get(row_ix, col_ix).unwrap_or_else(|| "--".into());
+        let is_warning = self.fetcher.is_warning(row_ix, col_ix);
         let group_name: SharedString = format!("td-{}-{}", row_ix, col_ix).into();
         let copied_message = i18n_common(cx, "copied_to_clipboard");
-        base.group(group_name.clone())
-            .overflow_hidden()
-            .child(
-                Label::new(value.clone())
-                    .text_align(column.align)
-                    .text_ellipsis()
-                    .flex_1()
-                    .min_w_0(),
-            )
-            .child(
-                div()
-                    .id(("copy-wrapper", row_ix * 100 + col_ix))
-                    .invisible()
-                    .group_hover(group_name, |style| style.visible())
-                    .flex_none()
-                    .on_click(|_, _, cx: &mut App| cx.stop_propagation())
-                    .child(
-                        Button::new(("copy-cell", row_ix * 100 + col_ix))
-                            .ghost()
-                            .icon(IconName::Copy)
-                            .on_click(move |_, window, cx: &mut App| {
-                                cx.write_to_clipboard(ClipboardItem::new_string(value.to_string()));
-                                window.push_notification(Notification::info(copied_message.clone()), cx);
-                            }),
-                    ),
-            )
+        let highlights = self
+            .fetcher
+            .keyword()
+            .map(|keyword| keyword_ranges(&value, &keyword))
+            .unwrap_or_default();
+        let text = if highlights.is_empty() {
+            Label::new(value.clone())
+                .text_align(column.align)
+                .text_ellipsis()
+                .when(is_warning, |this| this.text_color(cx.theme().warning))
+                .flex_1()
+                .min_w_0()
+                .into_any_element()
+        } else {
+            let style = HighlightStyle {
+                background_color: Some(cx.theme().warning.opacity(0.3)),
+                font_weight: Some(FontWeight::SEMIBOLD),
+                ..Default::default()
+            };
+            div()
+                .flex_1()
+                .min_w_0()
+                .overflow_hidden()
+                .text_ellipsis()
+                .whitespace_nowrap()
+                .child(
+                    StyledText::new(value.clone()).with_highlights(highlights.into_iter().map(|range| (range, style))),
+                )
+                .into_any_element()
+        };
+        base.group(group_name.clone()).overflow_hidden().child(text).child(
+            div()
+                .id(("copy-wrapper", row_ix * 100 + col_ix))
+                .invisible()
+                .group_hover(group_name, |style| style.visible())
+                .flex_none()
+                .on_click(|_, _, cx: &mut App| cx.stop_propagation())
+                .child(
+                    Button::new(("copy-cell", row_ix * 100 + col_ix))
+                        .ghost()
+                        .icon(IconName::Copy)
+                        .on_click(move |_, window, cx: &mut App| {
+                            cx.write_to_clipboard(ClipboardItem::new_string(value.to_string()));
+                            window.push_notification(Notification::info(copied_message.clone()), cx);
+                        }),
+                ),
+        )
     }
     /// Returns whether all data has been loaded (end of file).
     fn has_more(&self, _: &App) -> bool {
@@ -66,6 +66,10 @@ pub struct KvTableColumn {
     pub align: Option<TextAlign>,
     /// Whether the column is auto-created
     pub auto_created: bool,
+    /// Whether the column can be left empty in the edit form
+    pub optional: bool,
+    /// Validation of the edit form field, returns the error message
+    pub validate: Option<fn(&str) -> Option<SharedString>>,
 }
 
 impl KvTableColumn {
@@ -95,4 +99,12 @@ impl KvTableColumn {
         self.field_type = Some(field_type);
         self
     }
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+    pub fn validate(mut self, validate: fn(&str) -> Option<SharedString>) -> Self {
+        self.validate = Some(validate);
+        self
+    }
 }
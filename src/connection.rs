@@ -15,6 +15,7 @@
 use tracing::info;
 
 mod async_connection;
+mod capability;
 mod command;
 mod config;
 mod manager;
@@ -22,8 +23,12 @@ mod ssh_cluster_connection;
 mod ssh_stream;
 mod ssh_tunnel;
 
-pub use async_connection::{RedisAsyncConn, set_redis_connection_timeout, set_redis_response_timeout};
-pub use config::{RedisServer, get_server, get_servers, save_servers};
+pub use async_connection::{RedisAsyncConn, set_redis_connection_timeout, set_redis_response_timeout, test_connection};
+pub use capability::Capability;
+pub use config::{
+    DEFAULT_SCAN_COUNT, MAX_POOL_SIZE, MAX_SCAN_COUNT, RedisServer, get_server, get_servers, save_servers,
+    validate_connection_url,
+};
 pub use manager::{AccessMode, RedisClientDescription, SlowLogEntry, get_connection_manager};
 pub fn clear_expired_cache() {
     let (removed_count, total_count) = async_connection::clear_expired_connection_pool();
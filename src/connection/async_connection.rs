@@ -18,17 +18,20 @@ use super::ssh_tunnel::open_single_ssh_tunnel_connection;
 use crate::error::Error;
 use crate::helpers::{TtlCache, now_secs};
 use arc_swap::ArcSwap;
+use dashmap::DashSet;
 use futures::future::try_join_all;
 use redis::{
-    AsyncConnectionConfig, Client, Cmd, FromRedisValue, Pipeline, RedisFuture, Value,
+    AsyncConnectionConfig, Client, Cmd, FromRedisValue, Pipeline, ProtocolVersion, RedisFuture, Value,
     aio::{ConnectionLike, MultiplexedConnection},
+    caching::CacheConfig,
     cluster_async::ClusterConnection,
     cmd,
 };
 use std::sync::{
-    Arc,
-    atomic::{AtomicU64, Ordering},
+    Arc, RwLock,
+    atomic::{AtomicU64, AtomicUsize, Ordering},
 };
+use std::time::Instant;
 use std::{sync::LazyLock, time::Duration};
 use tracing::debug;
 
@@ -60,9 +63,40 @@ impl MultiplexedConnectionCache {
     }
 }
 
+/// A round-robin set of multiplexed connections for one server and database.
+/// Slots are filled lazily, so idle browsing only ever opens a single connection.
+struct MultiplexedConnectionPool {
+    next: AtomicUsize,
+    conns: RwLock<Vec<Option<Arc<MultiplexedConnectionCache>>>>,
+}
+
+impl MultiplexedConnectionPool {
+    fn new(size: usize) -> Self {
+        Self {
+            next: AtomicUsize::new(0),
+            conns: RwLock::new(vec![None; size.max(1)]),
+        }
+    }
+    fn next_slot(&self) -> usize {
+        let size = self.conns.read().map(|conns| conns.len()).unwrap_or(1);
+        self.next.fetch_add(1, Ordering::Relaxed) % size
+    }
+    fn get(&self, slot: usize) -> Option<Arc<MultiplexedConnectionCache>> {
+        self.conns.read().ok()?.get(slot).cloned().flatten()
+    }
+    fn set(&self, slot: usize, conn: Option<Arc<MultiplexedConnectionCache>>) {
+        if let Ok(mut conns) = self.conns.write()
+            && let Some(item) = conns.get_mut(slot)
+        {
+            *item = conn;
+        }
+    }
+}
+
 /// Global connection pool that caches Redis connections.
-/// Key: (config_hash, database_number), Value: MultiplexedConnection
-static CONNECTION_POOL: LazyLock<TtlCache<u64, Arc<MultiplexedConnectionCache>>> =
+/// Key: (config_hash, database_number), Value: pool of MultiplexedConnection
+/// Each entry expires after the server's idle timeout.
+static CONNECTION_POOL: LazyLock<TtlCache<u64, Arc<MultiplexedConnectionPool>>> =
     LazyLock::new(|| TtlCache::new(Duration::from_secs(5 * 60)));
 
 /// Clears expired connections from the connection pool.
@@ -107,11 +141,87 @@ pub fn get_redis_response_timeout() -> Duration {
     GLOBAL_REDIS_CONFIG.load().response_timeout
 }
 
+/// Addresses (host:port) of servers that rejected `HELLO 3`.
+/// These servers (Redis < 6.0 or some proxies) are connected with RESP2.
+static RESP2_SERVERS: LazyLock<DashSet<String>> = LazyLock::new(DashSet::new);
+
+/// Returns true if the server answered the `HELLO 3` handshake with an error.
+///
+/// Network failures and timeouts are not a reason to fall back to RESP2, the
+/// reply text differs between servers and proxies, so it is not inspected.
+fn is_handshake_rejected(err: &redis::RedisError) -> bool {
+    !(err.is_io_error() || err.is_timeout() || err.is_connection_refusal() || err.is_connection_dropped())
+}
+
+/// Returns the protocol used to talk to the server.
+///
+/// RESP3 is negotiated for direct connections unless the server rejected it before.
+/// Connections through an SSH tunnel authenticate manually and stay on RESP2.
+pub(crate) fn negotiated_protocol(config: &RedisServer) -> ProtocolVersion {
+    if config.is_ssh_tunnel() || RESP2_SERVERS.contains(&format!("{}:{}", config.host, config.port)) {
+        ProtocolVersion::RESP2
+    } else {
+        ProtocolVersion::RESP3
+    }
+}
+
+/// Opens a direct multiplexed connection, trying RESP3 first and
+/// falling back to RESP2 when the server rejects `HELLO 3`.
+///
+/// The server is only remembered as RESP2 once the RESP2 connection succeeds,
+/// so a handshake failing for another reason (e.g. a wrong password) is reported as is.
+/// With `client_cache`, RESP3 connections enable client-side caching, the server
+/// invalidates the cached replies through `CLIENT TRACKING` when the keys change.
+async fn open_direct_connection(config: &RedisServer, client_cache: bool) -> Result<MultiplexedConnection> {
+    let cfg = AsyncConnectionConfig::default()
+        .set_connection_timeout(Some(config.connection_timeout()))
+        .set_response_timeout(Some(config.response_timeout()));
+    if negotiated_protocol(config) == ProtocolVersion::RESP3 {
+        let client = open_single_client(config, ProtocolVersion::RESP3)?;
+        let resp3_cfg = if client_cache {
+            cfg.clone().set_cache_config(CacheConfig::new())
+        } else {
+            cfg.clone()
+        };
+        match client.get_multiplexed_async_connection_with_config(&resp3_cfg).await {
+            Ok(conn) => return Ok(conn),
+            Err(e) if !is_handshake_rejected(&e) => return Err(e.into()),
+            Err(e) => {
+                debug!(name = config.name, error = %e, "resp3 handshake rejected, fallback to resp2");
+                let client = open_single_client(config, ProtocolVersion::RESP2)?;
+                let conn = client.get_multiplexed_async_connection_with_config(&cfg).await?;
+                RESP2_SERVERS.insert(format!("{}:{}", config.host, config.port));
+                return Ok(conn);
+            }
+        }
+    }
+    let client = open_single_client(config, ProtocolVersion::RESP2)?;
+    let conn = client.get_multiplexed_async_connection_with_config(&cfg).await?;
+    Ok(conn)
+}
+
+/// Creates a new connection, through the SSH tunnel or direct, and selects `db`.
+async fn connect(config: &RedisServer, db: usize, client_cache: bool) -> Result<MultiplexedConnection> {
+    let mut conn = if config.is_ssh_tunnel() {
+        open_single_ssh_tunnel_connection(config).await?
+    } else {
+        open_direct_connection(config, client_cache).await?
+    };
+
+    // Select the specified database if not the default (db 0)
+    if db != 0 {
+        let _: () = cmd("SELECT").arg(db).query_async(&mut conn).await?;
+        debug!(name = config.name, db, "select database");
+    }
+    Ok(conn)
+}
+
 /// Opens a single Redis connection with connection pooling support.
 ///
 /// This function attempts to reuse an existing connection from the pool if available
-/// and healthy (when caching is enabled). If not, or if caching is bypassed, it creates
-/// a new connection (either through SSH tunnel or direct).
+/// and healthy (when caching is enabled). Pool slots are picked round-robin, up to the
+/// server's `pool_size`. If the slot is empty or its connection is dead, or if caching
+/// is bypassed, it creates a new connection (either through SSH tunnel or direct).
 /// The connection is then configured to use the specified database.
 ///
 /// # Arguments
@@ -128,46 +238,56 @@ pub async fn open_single_connection(config: &RedisServer, db: usize, use_cache:
     let key = config.get_hash(db);
 
     // Try to reuse an existing connection from the pool if caching is enabled
-    if use_cache
-        && let Some(conn) = CONNECTION_POOL.get(&key)
-        && let Some(conn) = conn.get_connection().await
-    {
-        debug!(name = config.name, "get connection from pool");
-        return Ok(conn);
-    }
-
-    // Create a new connection: SSH tunnel or direct connection
-    let mut conn = if config.is_ssh_tunnel() {
-        open_single_ssh_tunnel_connection(config).await?
+    let pool = if use_cache {
+        let pool = CONNECTION_POOL.get(&key).unwrap_or_else(|| {
+            let pool = Arc::new(MultiplexedConnectionPool::new(config.pool_size()));
+            CONNECTION_POOL.insert_with_idle(key, pool.clone(), config.idle_timeout());
+            pool
+        });
+        let slot = pool.next_slot();
+        if let Some(cached) = pool.get(slot) {
+            if let Some(conn) = cached.get_connection().await {
+                debug!(name = config.name, slot, "get connection from pool");
+                return Ok(conn);
+            }
+            // The connection is dead (e.g. after sleep), drop it and reconnect
+            pool.set(slot, None);
+        }
+        Some((pool, slot))
     } else {
-        let client = open_single_client(config)?;
-        // Configure connection with timeouts
-        let cfg = AsyncConnectionConfig::default()
-            .set_connection_timeout(Some(get_redis_connection_timeout()))
-            .set_response_timeout(Some(get_redis_response_timeout()));
-        client.get_multiplexed_async_connection_with_config(&cfg).await?
+        None
     };
 
-    // Select the specified database if not the default (db 0)
-    if db != 0 {
-        let _: () = cmd("SELECT").arg(db).query_async(&mut conn).await?;
-        debug!(name = config.name, db, "select database");
-    }
-    if !use_cache {
+    let conn = connect(config, db, true).await?;
+    let Some((pool, slot)) = pool else {
         return Ok(conn);
-    }
+    };
 
     // Cache the connection in the pool for future reuse if caching is enabled
-    CONNECTION_POOL.insert(
-        key,
-        Arc::new(MultiplexedConnectionCache {
+    pool.set(
+        slot,
+        Some(Arc::new(MultiplexedConnectionCache {
             conn: conn.clone(),
             check_time: AtomicU64::new(now_secs()),
-        }),
+        })),
     );
 
     Ok(conn)
 }
+/// Opens a connection which is neither pooled nor caching replies client-side,
+/// so that no other task writes to it and its reads always reach the server.
+pub async fn open_dedicated_connection(config: &RedisServer, db: usize) -> Result<MultiplexedConnection> {
+    connect(config, db, false).await
+}
+/// Connects with `config`, which may not be saved yet, and returns the latency of a `PING`.
+///
+/// The connection is not pooled, so testing a server doesn't leave it open.
+pub async fn test_connection(config: &RedisServer) -> Result<Duration> {
+    let mut conn = open_single_connection(config, config.db(), false).await?;
+    let start = Instant::now();
+    let _: () = cmd("PING").query_async(&mut conn).await?;
+    Ok(start.elapsed())
+}
 pub fn remove_connection_from_pool(config: &RedisServer, db: usize) {
     let key = config.get_hash(db);
     CONNECTION_POOL.remove(&key);
@@ -180,12 +300,17 @@ pub fn remove_connection_from_pool(config: &RedisServer, db: usize) {
 /// # Arguments
 ///
 /// * `config` - Redis server configuration
+/// * `protocol` - Protocol version to negotiate
 ///
 /// # Returns
 ///
 /// A Redis client ready to establish connections
-fn open_single_client(config: &RedisServer) -> Result<Client> {
-    let url = config.get_connection_url();
+fn open_single_client(config: &RedisServer, protocol: ProtocolVersion) -> Result<Client> {
+    let url = if protocol == ProtocolVersion::RESP3 {
+        config.get_resp3_connection_url()
+    } else {
+        config.get_connection_url()
+    };
     // Build client with TLS if certificates are provided
     let client = if let Some(certificates) = config.tls_certificates() {
         Client::build_with_tls(url, certificates)?
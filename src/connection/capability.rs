@@ -0,0 +1,101 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::Error;
+use semver::Version;
+
+/// Server features that depend on the `redis_version` read from `INFO server`,
+/// or on a module reported by `MODULE LIST`. Valkey reports the Redis version
+/// it is compatible with there, so the same thresholds apply to it.
+///
+/// Commands typed in the console are checked against the bundled command docs
+/// instead, see `check_command_version`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// `MEMORY USAGE`
+    MemoryUsage,
+    /// `UNLINK`, falls back to `DEL`
+    Unlink,
+    /// `TYPE` option of `SCAN`
+    ScanType,
+    /// `KEEPTTL` option of `SET`
+    KeepTtl,
+    /// `GETDEL`
+    GetDel,
+    /// `GET` option of `SET`
+    SetGet,
+    /// `HRANDFIELD`
+    HRandField,
+    /// Hash field TTLs, `HTTL` / `HEXPIRE` / `HPERSIST`
+    HashFieldTtl,
+    /// RedisJSON documents, `JSON.GET` / `JSON.SET`
+    Json,
+}
+
+impl Capability {
+    /// Command or option providing the feature, used in messages.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Capability::MemoryUsage => "MEMORY USAGE",
+            Capability::Unlink => "UNLINK",
+            Capability::ScanType => "SCAN TYPE",
+            Capability::KeepTtl => "SET KEEPTTL",
+            Capability::GetDel => "GETDEL",
+            Capability::SetGet => "SET GET",
+            Capability::HRandField => "HRANDFIELD",
+            Capability::HashFieldTtl => "HTTL",
+            Capability::Json => "JSON.SET",
+        }
+    }
+    /// Names of the modules providing the feature in `MODULE LIST`, empty for built-in features.
+    pub fn modules(&self) -> &'static [&'static str] {
+        match self {
+            // RedisJSON and valkey-json
+            Capability::Json => &["ReJSON", "json"],
+            _ => &[],
+        }
+    }
+    /// First Redis version providing the feature.
+    pub fn since(&self) -> Version {
+        match self {
+            Capability::MemoryUsage | Capability::Unlink | Capability::Json => Version::new(4, 0, 0),
+            Capability::ScanType | Capability::KeepTtl => Version::new(6, 0, 0),
+            Capability::GetDel | Capability::SetGet | Capability::HRandField => Version::new(6, 2, 0),
+            Capability::HashFieldTtl => Version::new(7, 4, 0),
+        }
+    }
+    /// Whether the server version provides the feature, with its module among `modules` if any.
+    pub fn is_supported(&self, version: &Version, modules: &[String]) -> bool {
+        *version >= self.since() && self.has_module(modules)
+    }
+    fn has_module(&self, modules: &[String]) -> bool {
+        let required = self.modules();
+        required.is_empty()
+            || modules
+                .iter()
+                .any(|module| required.iter().any(|name| name.eq_ignore_ascii_case(module)))
+    }
+    /// Returns an error like `SCAN TYPE requires Redis 6.0.0 (server 5.0.7)` when unsupported.
+    pub fn ensure(&self, version: &Version, modules: &[String]) -> Result<(), Error> {
+        if self.is_supported(version, modules) {
+            return Ok(());
+        }
+        let message = if self.has_module(modules) {
+            format!("{} requires Redis {} (server {version})", self.name(), self.since())
+        } else {
+            format!("{} requires the {} module", self.name(), self.modules().join(" or "))
+        };
+        Err(Error::Invalid { message })
+    }
+}
@@ -12,18 +12,113 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::RedisAsyncConn;
 use crate::assets::Assets;
+use dashmap::DashMap;
 use gpui::SharedString;
+use redis::{Value, cmd};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 use std::fmt;
-use std::sync::OnceLock;
+use std::sync::{LazyLock, OnceLock};
 
 // Top-level Map: Key is the command name (e.g., "SET"), Value is the command details.
 type CommandsMap = HashMap<SharedString, Command>;
 
 static COMMANDS_MAP: OnceLock<CommandsMap> = OnceLock::new();
 
+/// Whether a command writes, by `(server id, command)`, so `COMMAND INFO` is asked once per command
+static WRITE_COMMAND_CACHE: LazyLock<DashMap<(String, String), bool>> = LazyLock::new(DashMap::new);
+
+/// `COMMAND INFO` flags of the commands rejected in read-only mode
+const WRITE_FLAGS: &[&str] = &["write", "noscript", "may_replicate", "admin"];
+
+/// Commands changing data, used when the server doesn't answer `COMMAND INFO`
+/// (e.g. the command is renamed or disabled by the provider).
+const WRITE_COMMANDS: &[&str] = &[
+    "APPEND",
+    "BLMOVE",
+    "BLPOP",
+    "BRPOP",
+    "BRPOPLPUSH",
+    "BZPOPMAX",
+    "BZPOPMIN",
+    "COPY",
+    "DECR",
+    "DECRBY",
+    "DEL",
+    "EVAL",
+    "EVALSHA",
+    "EXPIRE",
+    "EXPIREAT",
+    "FCALL",
+    "FLUSHALL",
+    "FLUSHDB",
+    "GETDEL",
+    "GETEX",
+    "GETSET",
+    "HDEL",
+    "HINCRBY",
+    "HINCRBYFLOAT",
+    "HMSET",
+    "HSET",
+    "HSETNX",
+    "INCR",
+    "INCRBY",
+    "INCRBYFLOAT",
+    "LINSERT",
+    "LMOVE",
+    "LPOP",
+    "LPUSH",
+    "LPUSHX",
+    "LREM",
+    "LSET",
+    "LTRIM",
+    "MIGRATE",
+    "MOVE",
+    "MSET",
+    "MSETNX",
+    "PERSIST",
+    "PEXPIRE",
+    "PEXPIREAT",
+    "PFADD",
+    "PFMERGE",
+    "PSETEX",
+    "RENAME",
+    "RENAMENX",
+    "RESTORE",
+    "RPOP",
+    "RPOPLPUSH",
+    "RPUSH",
+    "RPUSHX",
+    "SADD",
+    "SDIFFSTORE",
+    "SET",
+    "SETEX",
+    "SETNX",
+    "SETRANGE",
+    "SINTERSTORE",
+    "SMOVE",
+    "SPOP",
+    "SREM",
+    "SUNIONSTORE",
+    "SWAPDB",
+    "UNLINK",
+    "XADD",
+    "XDEL",
+    "XTRIM",
+    "ZADD",
+    "ZINCRBY",
+    "ZPOPMAX",
+    "ZPOPMIN",
+    "ZREM",
+    "ZREMRANGEBYLEX",
+    "ZREMRANGEBYRANK",
+    "ZREMRANGEBYSCORE",
+    "ZUNIONSTORE",
+    "ZINTERSTORE",
+];
+
 pub fn list_commands(version: &str) -> Vec<SharedString> {
     let version: Version = version.into();
     get_commands()
@@ -47,6 +142,132 @@ pub fn get_command_description(name: &str) -> Option<(SharedString, SharedString
     ))
 }
 
+/// Command groups whose first argument is a key.
+const KEY_COMMAND_GROUPS: &[&str] = &[
+    "generic",
+    "string",
+    "list",
+    "set",
+    "sorted_set",
+    "hash",
+    "stream",
+    "geo",
+    "bitmap",
+    "hyperloglog",
+];
+
+/// Commands wiping or stopping the server, confirmed before running them in the console.
+const DANGEROUS_COMMANDS: &[&str] = &["FLUSHALL", "FLUSHDB", "SWAPDB", "SHUTDOWN", "DEBUG", "FAILOVER"];
+
+/// Whether the console should ask before running the command.
+pub fn is_dangerous_command(name: &str) -> bool {
+    DANGEROUS_COMMANDS
+        .iter()
+        .any(|command| command.eq_ignore_ascii_case(name))
+}
+
+/// Commands of `KEY_COMMAND_GROUPS` whose first argument isn't a key.
+const NON_KEY_COMMANDS: &[&str] = &[
+    "KEYS",
+    "SCAN",
+    "RANDOMKEY",
+    "WAIT",
+    "WAITAOF",
+    "OBJECT",
+    "MIGRATE",
+    "BITOP",
+    "LMPOP",
+    "BLMPOP",
+    "ZMPOP",
+    "BZMPOP",
+    "SINTERCARD",
+    "ZINTERCARD",
+    "ZDIFF",
+    "ZINTER",
+    "ZUNION",
+    "XREAD",
+    "XREADGROUP",
+];
+
+/// Key referenced by a console command, e.g. `user:1` for `HGET user:1 name`.
+pub fn get_command_key(args: &[String]) -> Option<SharedString> {
+    let name = args.first()?.to_uppercase();
+    let key = args.get(1)?;
+    let group = get_commands().get(name.as_str())?.group.as_deref()?;
+    if !KEY_COMMAND_GROUPS.contains(&group) || NON_KEY_COMMANDS.contains(&name.as_str()) {
+        return None;
+    }
+    Some(key.clone().into())
+}
+
+/// Whether the command may change the server, either listed in the built-in
+/// list or flagged `write`, `noscript`, `may_replicate` or `admin` by `COMMAND INFO`.
+///
+/// The flags are cached by server, the built-in list still applies when the
+/// server hides the command from `COMMAND INFO` (e.g. renamed by the provider).
+pub async fn is_write_command(conn: &mut RedisAsyncConn, server_id: &str, name: &str) -> bool {
+    let name = name.to_uppercase();
+    if WRITE_COMMANDS.contains(&name.as_str()) {
+        return true;
+    }
+    let cache_key = (server_id.to_string(), name);
+    if let Some(write) = WRITE_COMMAND_CACHE.get(&cache_key) {
+        return *write;
+    }
+    let Ok(info) = cmd("COMMAND")
+        .arg("INFO")
+        .arg(&cache_key.1)
+        .query_async::<Value>(conn)
+        .await
+    else {
+        return false;
+    };
+    // Reply: [[name, arity, flags, ...]], flags is a set with RESP3
+    let write = if let Value::Array(items) = &info
+        && let Some(Value::Array(detail)) = items.first()
+        && let Some(Value::Array(flags) | Value::Set(flags)) = detail.get(2)
+    {
+        flags.iter().any(|flag| match flag {
+            Value::SimpleString(s) => WRITE_FLAGS.contains(&s.as_str()),
+            Value::BulkString(bytes) => WRITE_FLAGS
+                .iter()
+                .any(|write_flag| write_flag.as_bytes() == bytes.as_slice()),
+            _ => false,
+        })
+    } else {
+        false
+    };
+    WRITE_COMMAND_CACHE.insert(cache_key, write);
+    write
+}
+
+/// Checks that the server version provides the command, e.g. `["COPY", "a", "b"]`,
+/// based on the `since` field of the bundled command docs.
+///
+/// Subcommands such as `CLIENT NO-EVICT` are looked up first, unknown commands
+/// and unknown server versions are let through.
+pub fn check_command_version(args: &[String], server_version: &str) -> Result<(), String> {
+    let Some(name) = args.first().map(|s| s.to_uppercase()) else {
+        return Ok(());
+    };
+    let version: Version = server_version.into();
+    if version.major == 0 {
+        return Ok(());
+    }
+    let commands = get_commands();
+    let subcommand = args.get(1).map(|sub| format!("{name} {}", sub.to_uppercase()));
+    let Some((name, command)) = subcommand
+        .and_then(|sub| commands.get_key_value(sub.as_str()))
+        .or_else(|| commands.get_key_value(name.as_str()))
+    else {
+        return Ok(());
+    };
+    match command.since {
+        Some(since) if !since.le(&version) => Err(format!("{name} requires Redis {since} (server {version})")),
+        _ => Ok(()),
+    }
+}
+
 fn get_commands() -> &'static CommandsMap {
     COMMANDS_MAP.get_or_init(|| {
         let Some(data) = Assets::get("commands.json") else {
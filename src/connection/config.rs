@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::async_connection::{get_redis_connection_timeout, get_redis_response_timeout};
 use crate::{
     error::Error,
     helpers::{decrypt, encrypt, get_or_create_config_dir, is_development},
@@ -20,19 +21,32 @@ use arc_swap::ArcSwap;
 use gpui::SharedString;
 use indexmap::IndexMap;
 use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
-use redis::{ClientTlsConfig, TlsCertificates};
+use redis::{ClientTlsConfig, IntoConnectionInfo, TlsCertificates};
 use serde::{Deserialize, Serialize};
 use smol::fs;
-use std::collections::HashMap;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use std::time::Duration;
 use std::{fs::read_to_string, path::PathBuf, sync::LazyLock};
 use tracing::{debug, info};
 use url::Url;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Default number of multiplexed connections kept per server and database.
+/// A single multiplexed connection already pipelines concurrent requests,
+/// which is enough for browsing.
+pub const DEFAULT_POOL_SIZE: usize = 1;
+/// Upper bound for the per-server pool size.
+pub const MAX_POOL_SIZE: usize = 16;
+/// Default idle timeout (seconds) after which unused connections are closed.
+pub const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 5 * 60;
+/// Default COUNT hint of the HSCAN / SSCAN / ZSCAN calls loading values.
+pub const DEFAULT_SCAN_COUNT: usize = 100;
+/// Upper bound for the per-server scan count.
+pub const MAX_SCAN_COUNT: usize = 10_000;
+
 #[derive(Debug, Clone, Default)]
 struct RedisUrl {
     host: String,
@@ -40,6 +54,16 @@ struct RedisUrl {
     username: String,
     password: Option<String>,
     tls: bool,
+    /// Database index of the path, e.g. `/3`
+    db: Option<usize>,
+}
+
+/// Error of a `redis://` or `rediss://` URL entered as host, `None` for a plain host.
+pub fn validate_connection_url(host: &str) -> Option<String> {
+    if !host.contains("://") {
+        return None;
+    }
+    host.into_connection_info().err().map(|e| e.to_string())
 }
 
 fn parse_url(host: SharedString) -> RedisUrl {
@@ -57,6 +81,7 @@ fn parse_url(host: SharedString) -> RedisUrl {
             username: u.username().to_string(),
             password: u.password().map(|p| p.to_string()),
             tls: u.scheme() == "rediss",
+            db: u.path().trim_start_matches('/').parse().ok(),
         }
     } else {
         RedisUrl {
@@ -89,6 +114,18 @@ pub struct RedisServer {
     pub ssh_username: Option<String>,
     pub ssh_password: Option<String>,
     pub ssh_key: Option<String>,
+    /// Number of connections kept per database, larger values help heavy concurrent operations
+    pub pool_size: Option<usize>,
+    /// Idle time in seconds before pooled connections are closed
+    pub idle_timeout: Option<u64>,
+    /// COUNT hint of the scans loading hash, set and zset values, trades throughput for latency
+    pub scan_count: Option<usize>,
+    /// Database opened when selecting the server, e.g. from the path of a connection URL
+    pub db: Option<usize>,
+    /// Seconds to wait for the connection, overrides the global setting
+    pub connection_timeout: Option<u64>,
+    /// Seconds to wait for the reply of a command, overrides the global setting
+    pub response_timeout: Option<u64>,
 }
 impl RedisServer {
     pub fn from_form_data(id: &str, data: &IndexMap<SharedString, SharedString>) -> Self {
@@ -139,8 +176,47 @@ impl RedisServer {
             insecure: get_bool("insecure"),
             ssh_tunnel: get_bool("ssh_tunnel"),
             readonly: get_bool("readonly"),
+
+            pool_size: get_str("pool_size").and_then(|s| s.parse().ok()),
+            idle_timeout: get_str("idle_timeout").and_then(|s| s.parse().ok()),
+            scan_count: get_str("scan_count").and_then(|s| s.parse().ok()),
+            db: redis_url.db.or_else(|| get_str("db").and_then(|s| s.parse().ok())),
+            connection_timeout: get_str("connection_timeout").and_then(|s| s.parse().ok()),
+            response_timeout: get_str("response_timeout").and_then(|s| s.parse().ok()),
         }
     }
+    /// Returns the configured pool size, clamped to `1..=MAX_POOL_SIZE`.
+    pub fn pool_size(&self) -> usize {
+        self.pool_size.unwrap_or(DEFAULT_POOL_SIZE).clamp(1, MAX_POOL_SIZE)
+    }
+    /// Returns the idle timeout for pooled connections and cached clients.
+    pub fn idle_timeout(&self) -> Duration {
+        let secs = self
+            .idle_timeout
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_IDLE_TIMEOUT_SECS);
+        Duration::from_secs(secs)
+    }
+    /// Returns the configured scan count, clamped to `1..=MAX_SCAN_COUNT`.
+    pub fn scan_count(&self) -> usize {
+        self.scan_count.unwrap_or(DEFAULT_SCAN_COUNT).clamp(1, MAX_SCAN_COUNT)
+    }
+    /// Returns the connect timeout of the server, the global setting unless overridden.
+    pub fn connection_timeout(&self) -> Duration {
+        self.connection_timeout
+            .map(Duration::from_secs)
+            .unwrap_or_else(get_redis_connection_timeout)
+    }
+    /// Returns the command timeout of the server, the global setting unless overridden.
+    pub fn response_timeout(&self) -> Duration {
+        self.response_timeout
+            .map(Duration::from_secs)
+            .unwrap_or_else(get_redis_response_timeout)
+    }
+    /// Returns the database opened when selecting the server.
+    pub fn db(&self) -> usize {
+        self.db.unwrap_or_default()
+    }
     pub fn get_hash(&self, db: usize) -> u64 {
         let mut hasher = DefaultHasher::new();
         self.hash(&mut hasher);
@@ -152,6 +228,13 @@ impl RedisServer {
     }
     /// Generates the connection URL based on host, port, and optional password.
     pub fn get_connection_url(&self) -> String {
+        self.build_connection_url(false)
+    }
+    /// Generates the connection URL that negotiates RESP3 via `HELLO 3`.
+    pub fn get_resp3_connection_url(&self) -> String {
+        self.build_connection_url(true)
+    }
+    fn build_connection_url(&self, resp3: bool) -> String {
         let tls = self.tls.unwrap_or(false);
         let scheme = if tls { "rediss" } else { "redis" };
 
@@ -167,8 +250,12 @@ impl RedisServer {
             }
             _ => format!("{scheme}://{}:{}", self.host, self.port),
         };
+        let query = if resp3 { "?protocol=resp3" } else { "" };
         if tls && self.insecure.unwrap_or(false) {
-            return format!("{url}/#insecure");
+            return format!("{url}/{query}#insecure");
+        }
+        if resp3 {
+            return format!("{url}/{query}");
         }
 
         url
@@ -215,14 +302,13 @@ fn get_or_create_server_config() -> Result<PathBuf> {
     Ok(path)
 }
 
-static SERVER_CONFIG_MAP: LazyLock<ArcSwap<HashMap<String, RedisServer>>> =
-    LazyLock::new(|| ArcSwap::from_pointee(HashMap::new()));
+/// Servers by id, in the order of the config file which is the display order
+static SERVER_CONFIG_MAP: LazyLock<ArcSwap<IndexMap<String, RedisServer>>> =
+    LazyLock::new(|| ArcSwap::from_pointee(IndexMap::new()));
 
 pub fn get_servers() -> Result<Vec<RedisServer>> {
     if !SERVER_CONFIG_MAP.load().is_empty() {
-        let mut servers: Vec<RedisServer> = SERVER_CONFIG_MAP.load().values().cloned().collect();
-        servers.sort_by(|a, b| a.id.cmp(&b.id));
-        return Ok(servers);
+        return Ok(SERVER_CONFIG_MAP.load().values().cloned().collect());
     }
     let path = get_or_create_server_config()?;
     let value = read_to_string(path)?;
@@ -231,7 +317,7 @@ pub fn get_servers() -> Result<Vec<RedisServer>> {
     }
     let configs: RedisServers = toml::from_str(&value)?;
     let mut servers = configs.servers;
-    let mut configs = HashMap::new();
+    let mut configs = IndexMap::new();
     for server in servers.iter_mut() {
         if let Some(password) = &server.password {
             server.password = Some(decrypt(password).unwrap_or(password.clone()));
@@ -250,7 +336,7 @@ pub fn get_servers() -> Result<Vec<RedisServer>> {
 
 /// Saves the server configuration to the file.
 pub async fn save_servers(mut servers: Vec<RedisServer>) -> Result<()> {
-    let mut configs = HashMap::new();
+    let mut configs = IndexMap::new();
     for server in servers.iter_mut() {
         configs.insert(server.id.clone(), server.clone());
         if let Some(password) = &server.password {
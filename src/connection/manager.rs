@@ -14,9 +14,10 @@
 
 use super::{
     async_connection::{
-        RedisAsyncConn, get_redis_connection_timeout, get_redis_response_timeout, open_single_connection,
-        query_async_masters, remove_connection_from_pool,
+        RedisAsyncConn, negotiated_protocol, open_dedicated_connection, open_single_connection, query_async_masters,
+        remove_connection_from_pool,
     },
+    capability::Capability,
     config::{RedisServer, get_server},
     ssh_cluster_connection::SshMultiplexedConnection,
 };
@@ -24,7 +25,9 @@ use crate::error::Error;
 use crate::helpers::TtlCache;
 use futures::future::try_join_all;
 use gpui::SharedString;
-use redis::{Cmd, FromRedisValue, InfoDict, ParsingError, Role, Value, aio::MultiplexedConnection, cluster, cmd};
+use redis::{
+    Cmd, FromRedisValue, InfoDict, ParsingError, ProtocolVersion, Role, Value, aio::MultiplexedConnection, cluster, cmd,
+};
 use regex::Regex;
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -189,10 +192,8 @@ async fn get_async_connection(client: &RClient, db: usize, use_cache: bool) -> R
             Ok(RedisAsyncConn::Single(conn))
         }
         RClient::Cluster(client) => {
-            let cfg = cluster::ClusterConfig::default()
-                .set_connection_timeout(get_redis_connection_timeout())
-                .set_response_timeout(get_redis_response_timeout());
-            let conn = client.get_async_connection_with_config(cfg).await?;
+            // The timeouts of the server are set when building the client
+            let conn = client.get_async_connection().await?;
             Ok(RedisAsyncConn::Cluster(conn))
         }
         RClient::SshCluster(client) => {
@@ -287,18 +288,25 @@ impl FromRedisValue for SlowLogEntry {
 // TODO 是否在client中保存connection
 #[derive(Clone)]
 pub struct RedisClient {
+    server_id: String,
     access_mode: AccessMode,
     db: usize,
     server_type: ServerType,
     nodes: Vec<RedisNode>,
     master_nodes: Vec<RedisNode>,
     version: Version,
+    /// `redis_version` of `INFO server`, Valkey reports the Redis version it is compatible with
+    redis_version: Version,
+    /// Modules reported by `MODULE LIST`
+    modules: Vec<String>,
     is_valkey: bool,
+    protocol: ProtocolVersion,
     connection: RedisAsyncConn,
 }
 #[derive(Debug, Clone, Default)]
 pub struct RedisClientDescription {
     pub is_valkey: bool,
+    pub protocol: SharedString,
     pub server_type: SharedString,
     pub master_nodes: SharedString,
     pub slave_nodes: SharedString,
@@ -310,6 +318,13 @@ impl RedisClient {
     pub fn version(&self) -> String {
         self.version.to_string()
     }
+    /// Redis version the server is compatible with, the capabilities are gated on it
+    pub fn redis_version(&self) -> String {
+        self.redis_version.to_string()
+    }
+    pub fn modules(&self) -> Vec<String> {
+        self.modules.clone()
+    }
     pub fn supports_db_selection(&self) -> bool {
         self.server_type != ServerType::Cluster
     }
@@ -325,8 +340,13 @@ impl RedisClient {
             .filter(|node| !master_nodes.contains(&node.host_port()))
             .map(|node| node.host_port().clone())
             .collect();
+        let protocol = match self.protocol {
+            ProtocolVersion::RESP3 => "RESP3",
+            _ => "RESP2",
+        };
         RedisClientDescription {
             is_valkey: self.is_valkey,
+            protocol: protocol.into(),
             server_type: format!("{:?}", self.server_type).into(),
             master_nodes: master_nodes.join(",").into(),
             slave_nodes: slave_nodes.join(",").into(),
@@ -345,26 +365,34 @@ impl RedisClient {
     pub fn is_cluster(&self) -> bool {
         self.server_type == ServerType::Cluster
     }
-    /// Checks if the client version is at least the given version.
-    /// # Arguments
-    /// * `version` - The version to check.
-    /// # Returns
-    /// * `bool` - True if the client version is at least the given version, false otherwise.
-    pub fn is_at_least_version(&self, version: &str) -> bool {
-        self.version >= Version::parse(version).unwrap_or(Version::new(0, 0, 0))
+    /// Checks if the server version provides the capability.
+    pub fn supports(&self, capability: Capability) -> bool {
+        capability.is_supported(&self.redis_version, &self.modules)
+    }
+    /// Returns a "requires Redis X.Y" error when the server version lacks the capability.
+    pub fn ensure(&self, capability: Capability) -> Result<()> {
+        capability.ensure(&self.redis_version, &self.modules)
     }
 
     pub async fn unlike_keys(&self, keys: Vec<SharedString>) -> Result<(), Error> {
         if keys.is_empty() {
             return Ok(());
         }
+        // UNLINK frees memory in the background, older servers only have DEL
+        let delete_cmd = if self.supports(Capability::Unlink) {
+            "UNLINK"
+        } else {
+            "DEL"
+        };
         if !self.is_cluster() {
             let mut conn = self.connection();
-            let mut pipe = redis::pipe();
-            for key in keys {
-                pipe.cmd("UNLINK").arg(key.as_str());
+            for chunk in keys.chunks(10_000) {
+                let mut pipe = redis::pipe();
+                for key in chunk {
+                    pipe.cmd(delete_cmd).arg(key.as_str());
+                }
+                let _: () = pipe.query_async(&mut conn).await?;
             }
-            let _: () = pipe.query_async(&mut conn).await?;
             return Ok(());
         }
 
@@ -374,7 +402,7 @@ impl RedisClient {
             let futures = chunk.iter().map(|key| {
                 let mut conn_clone = conn.clone();
                 async move {
-                    let _: () = cmd("UNLINK").arg(key.as_str()).query_async(&mut conn_clone).await?;
+                    let _: () = cmd(delete_cmd).arg(key.as_str()).query_async(&mut conn_clone).await?;
                     Ok::<(), Error>(())
                 }
             });
@@ -387,7 +415,7 @@ impl RedisClient {
         let mut conn = self.connection.clone();
         let key_type = key_type.to_lowercase();
 
-        if self.is_at_least_version("4.0.0") {
+        if self.supports(Capability::MemoryUsage) {
             let memory_usage: u64 = cmd("MEMORY").arg("USAGE").arg(key).query_async(&mut conn).await?;
             return Ok(memory_usage);
         }
@@ -529,6 +557,29 @@ impl RedisClient {
     pub fn count_masters(&self) -> Result<usize> {
         Ok(self.master_nodes.len())
     }
+    /// Checks if the server supports the `TYPE` option of SCAN (Redis 6.0+).
+    pub fn supports_scan_type(&self) -> bool {
+        self.supports(Capability::ScanType)
+    }
+    /// Scans the keys matching the pattern with at most `rounds` SCAN calls.
+    /// # Arguments
+    /// * `pattern` - The pattern to match keys.
+    /// * `count` - The count of keys to return per call.
+    /// * `rounds` - The max number of SCAN calls.
+    /// # Returns
+    /// * `(Vec<SharedString>, bool)` - A tuple containing the keys and whether the scan cycle finished.
+    pub async fn scan_rounds(&self, pattern: &str, count: u64, rounds: usize) -> Result<(Vec<SharedString>, bool)> {
+        let (mut cursors, mut keys) = self.first_scan(pattern, count).await?;
+        for _ in 1..rounds {
+            if cursors.iter().sum::<u64>() == 0 {
+                break;
+            }
+            let (new_cursors, new_keys) = self.scan(cursors, pattern, count).await?;
+            keys.extend(new_keys);
+            cursors = new_cursors;
+        }
+        Ok((keys, cursors.iter().sum::<u64>() == 0))
+    }
     /// Initiates a SCAN operation across all masters.
     /// # Arguments
     /// * `pattern` - The pattern to match keys.
@@ -536,10 +587,25 @@ impl RedisClient {
     /// # Returns
     /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys.
     pub async fn first_scan(&self, pattern: &str, count: u64) -> Result<(Vec<u64>, Vec<SharedString>)> {
+        self.first_scan_with_type(pattern, count, None).await
+    }
+    /// Initiates a SCAN operation across all masters, limited to one key type.
+    /// # Arguments
+    /// * `pattern` - The pattern to match keys.
+    /// * `count` - The count of keys to return.
+    /// * `key_type` - The Redis type name passed to `SCAN ... TYPE`, ignored if unsupported.
+    /// # Returns
+    /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys.
+    pub async fn first_scan_with_type(
+        &self,
+        pattern: &str,
+        count: u64,
+        key_type: Option<&str>,
+    ) -> Result<(Vec<u64>, Vec<SharedString>)> {
         let master_count = self.count_masters()?;
         let cursors = vec![0; master_count];
 
-        let (cursors, keys) = self.scan(cursors, pattern, count).await?;
+        let (cursors, keys) = self.scan_with_type(cursors, pattern, count, key_type).await?;
         Ok((cursors, keys))
     }
     /// Continues a SCAN operation.
@@ -550,17 +616,34 @@ impl RedisClient {
     /// # Returns
     /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys.
     pub async fn scan(&self, cursors: Vec<u64>, pattern: &str, count: u64) -> Result<(Vec<u64>, Vec<SharedString>)> {
-        debug!("scan, cursors: {cursors:?}, pattern: {pattern}, count: {count}");
+        self.scan_with_type(cursors, pattern, count, None).await
+    }
+    /// Continues a SCAN operation, limited to one key type.
+    /// # Arguments
+    /// * `cursors` - A vector of cursors for each master.
+    /// * `pattern` - The pattern to match keys.
+    /// * `count` - The count of keys to return.
+    /// * `key_type` - The Redis type name passed to `SCAN ... TYPE`, ignored if unsupported.
+    /// # Returns
+    /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys.
+    pub async fn scan_with_type(
+        &self,
+        cursors: Vec<u64>,
+        pattern: &str,
+        count: u64,
+        key_type: Option<&str>,
+    ) -> Result<(Vec<u64>, Vec<SharedString>)> {
+        let key_type = key_type.filter(|_| self.supports_scan_type());
+        debug!("scan, cursors: {cursors:?}, pattern: {pattern}, count: {count}, type: {key_type:?}");
         let cmds: Vec<Cmd> = cursors
             .iter()
             .map(|cursor| {
-                cmd("SCAN")
-                    .cursor_arg(*cursor)
-                    .arg("MATCH")
-                    .arg(pattern)
-                    .arg("COUNT")
-                    .arg(count)
-                    .clone()
+                let mut c = cmd("SCAN");
+                c.cursor_arg(*cursor).arg("MATCH").arg(pattern).arg("COUNT").arg(count);
+                if let Some(key_type) = key_type {
+                    c.arg("TYPE").arg(key_type);
+                }
+                c
             })
             .collect();
         let values: Vec<(u64, Vec<Vec<u8>>)> = self.query_async_masters(cmds).await?;
@@ -659,6 +742,69 @@ async fn safe_check_user_readonly(mut conn: RedisAsyncConn) -> bool {
     }
 }
 
+/// Collects the `name` fields of a `MODULE LIST` reply: maps with RESP3, flat
+/// `[field, value, ...]` lists with RESP2, keyed by node for a cluster.
+fn collect_module_names(value: &Value, names: &mut Vec<String>) {
+    let as_string = |value: &Value| match value {
+        Value::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        Value::SimpleString(s) => Some(s.clone()),
+        _ => None,
+    };
+    let is_name = |value: &Value| as_string(value).is_some_and(|field| field == "name");
+    match value {
+        Value::Map(items) => {
+            for (field, value) in items {
+                if is_name(field) {
+                    names.extend(as_string(value));
+                } else {
+                    collect_module_names(value, names);
+                }
+            }
+        }
+        Value::Array(items) if items.iter().step_by(2).any(is_name) => {
+            for pair in items.chunks(2) {
+                if let [field, value] = pair
+                    && is_name(field)
+                {
+                    names.extend(as_string(value));
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_module_names(item, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Names of the loaded modules. Managed services often reject `MODULE LIST`,
+/// RedisJSON is then detected by `COMMAND INFO JSON.GET` answering a command.
+async fn get_module_names(conn: &mut RedisAsyncConn) -> Vec<String> {
+    match cmd("MODULE").arg("LIST").query_async::<Value>(conn).await {
+        Ok(value) => {
+            let mut names = vec![];
+            collect_module_names(&value, &mut names);
+            names
+        }
+        Err(e) => {
+            debug!(error = %e, "module list fail");
+            match cmd("COMMAND")
+                .arg("INFO")
+                .arg("JSON.GET")
+                .query_async::<Value>(conn)
+                .await
+            {
+                Ok(Value::Array(items)) if items.first().is_some_and(|item| !matches!(item, Value::Nil)) => {
+                    vec!["ReJSON".to_string()]
+                }
+                _ => vec![],
+            }
+        }
+    }
+}
+
 impl ConnectionManager {
     pub fn new() -> Self {
         Self {
@@ -799,6 +945,17 @@ impl ConnectionManager {
         self.clients.remove(&key);
         remove_connection_from_pool(&config, db);
     }
+    /// Drops the cached clients of every db of the server and their pooled connections,
+    /// the sockets are closed once the tasks still using them are done.
+    pub fn disconnect(&self, server_id: &str) {
+        let clients = self.clients.remove_if(|client| client.server_id == server_id);
+        for client in clients.iter() {
+            for node in client.nodes.iter() {
+                remove_connection_from_pool(&node.server, client.db);
+            }
+        }
+        info!(server_id, clients = clients.len(), "disconnect server");
+    }
     pub async fn get_pubsub_connection(&self, server_id: &str) -> Result<redis::aio::PubSub> {
         let config = get_server(server_id)?;
         let url = config.get_connection_url();
@@ -810,6 +967,18 @@ impl ConnectionManager {
         let pubsub = client.get_async_pubsub().await?;
         Ok(pubsub)
     }
+    /// Opens a dedicated connection for `MONITOR`, which can't share the pooled connections.
+    pub async fn get_monitor_connection(&self, server_id: &str) -> Result<redis::aio::Monitor> {
+        let config = get_server(server_id)?;
+        let url = config.get_connection_url();
+        let client = if let Some(certificates) = config.tls_certificates() {
+            redis::Client::build_with_tls(url, certificates)
+        } else {
+            redis::Client::open(url)
+        }?;
+        let monitor = client.get_async_monitor().await?;
+        Ok(monitor)
+    }
     /// Retrieves or creates a RedisClient for the given configuration name without caching.
     pub async fn get_client_without_cache(&self, server_id: &str, db: usize) -> Result<RedisClient> {
         let config = get_server(server_id)?;
@@ -820,10 +989,15 @@ impl ConnectionManager {
                 message: "no nodes found".to_string(),
             });
         };
+        // The seed connection in get_redis_nodes already found out whether RESP3 is supported
+        let protocol = negotiated_protocol(&config);
         let client = match server_type {
             ServerType::Cluster => {
                 let addrs: Vec<String> = nodes.iter().map(|n| n.server.get_connection_url()).collect();
-                let mut builder = cluster::ClusterClientBuilder::new(addrs);
+                let mut builder = cluster::ClusterClientBuilder::new(addrs)
+                    .use_protocol(protocol)
+                    .connection_timeout(config.connection_timeout())
+                    .response_timeout(config.response_timeout());
                 if let Some(certificates) = first_node.server.tls_certificates() {
                     builder = builder.certs(certificates);
                 }
@@ -856,52 +1030,59 @@ impl ConnectionManager {
             AccessMode::ReadWrite
         };
         let mut client = RedisClient {
+            server_id: server_id.to_string(),
             db,
             access_mode,
             server_type: server_type.clone(),
             nodes,
             master_nodes,
             version: Version::new(0, 0, 0),
+            redis_version: Version::new(0, 0, 0),
+            modules: vec![],
             is_valkey: false,
+            protocol,
             connection,
         };
         let mut conn = client.connection.clone();
-        let get_version = |info: InfoDict| -> (bool, Option<Version>) {
-            if let Some(v) = info.get::<String>("valkey_version") {
-                return (true, Version::parse(&v).ok());
+        // Valkey reports its own version besides the Redis version it is compatible with
+        let get_version = |info: InfoDict| -> Option<(bool, Version, Version)> {
+            let redis_version = Version::parse(&info.get::<String>("redis_version")?).ok()?;
+            match info.get::<String>("valkey_version") {
+                Some(v) => Some((true, Version::parse(&v).ok()?, redis_version)),
+                None => Some((false, redis_version.clone(), redis_version)),
             }
-            if let Some(v) = info.get::<String>("redis_version") {
-                return (false, Version::parse(&v).ok());
-            }
-            (false, None)
         };
 
-        (client.is_valkey, client.version) = match server_type {
+        let versions = match server_type {
             ServerType::Cluster => {
                 let info: redis::Value = cmd("INFO").arg("server").query_async(&mut conn).await?;
-                let mut version = None;
-                let mut is_valkey = false;
+                let mut versions = None;
                 if let redis::Value::Map(items) = info {
                     for (_, node_info_val) in items {
                         if let Ok(info) = InfoDict::from_redis_value(node_info_val)
-                            && let (valkey, Some(v)) = get_version(info)
+                            && let Some(v) = get_version(info)
                         {
-                            version = Some(v);
-                            is_valkey = valkey;
+                            versions = Some(v);
                             break;
                         }
                     }
                 }
-                (is_valkey, version.unwrap_or(Version::new(0, 0, 0)))
+                versions
             }
             _ => {
                 let info: InfoDict = cmd("INFO").arg("server").query_async(&mut conn).await?;
-                let (is_valkey, version) = get_version(info);
-                (is_valkey, version.unwrap_or(Version::new(0, 0, 0)))
+                get_version(info)
             }
         };
+        if let Some((is_valkey, version, redis_version)) = versions {
+            client.is_valkey = is_valkey;
+            client.version = version;
+            client.redis_version = redis_version;
+        }
+
+        client.modules = get_module_names(&mut conn).await;
 
-        debug!(server_id, version = client.version(), db, access_mode = ?client.access_mode(), "create redis client success");
+        debug!(server_id, version = client.version(), redis_version = client.redis_version(), modules = ?client.modules, db, access_mode = ?client.access_mode(), protocol = ?client.protocol, "create redis client success");
         Ok(client)
     }
     /// Retrieves or creates a RedisClient for the given configuration name.
@@ -913,13 +1094,38 @@ impl ConnectionManager {
             return Ok(client.clone());
         }
         let client = self.get_client_without_cache(server_id, db).await?;
-        // Cache the client
-        self.clients.insert(key, client.clone());
+        // Cache the client, it is closed after the server's idle timeout
+        self.clients
+            .insert_with_idle(key, client.clone(), config.idle_timeout());
         Ok(client)
     }
+    /// Opens a connection which is not shared with the other tasks, e.g. to hold a `WATCH`.
+    ///
+    /// Cluster connections route every command to its node, they are not supported.
+    pub async fn get_dedicated_connection(&self, server_id: &str, db: usize) -> Result<MultiplexedConnection> {
+        let client = self.get_client(server_id, db).await?;
+        let node = client
+            .nodes
+            .first()
+            .filter(|_| !client.is_cluster())
+            .ok_or_else(|| Error::Invalid {
+                message: "Dedicated connections are not supported by cluster servers".to_string(),
+            })?;
+        open_dedicated_connection(&node.server, db).await
+    }
     /// Shorthand to get an async connection directly.
+    ///
+    /// When the server is configured with a pool size greater than one,
+    /// connections of non-cluster servers are taken from the pool in turn.
     pub async fn get_connection(&self, server_id: &str, db: usize) -> Result<RedisAsyncConn> {
         let client = self.get_client(server_id, db).await?;
+        if !client.is_cluster()
+            && let Some(node) = client.nodes.first()
+            && node.server.pool_size() > 1
+        {
+            let conn = open_single_connection(&node.server, db, true).await?;
+            return Ok(RedisAsyncConn::Single(conn));
+        }
         Ok(client.connection.clone())
     }
 }
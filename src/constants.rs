@@ -13,6 +13,8 @@
 // limitations under the License.
 use gpui::{Pixels, px};
 
+/// Rem size of the medium font size, the other sizes scale from it
+pub const DEFAULT_REM_SIZE: f32 = 16.0;
 pub const SIDEBAR_WIDTH: Pixels = px(80.0);
 pub const KEY_TREE_MIN_WIDTH: Pixels = px(275.0);
 pub const KEY_TREE_MAX_WIDTH: Pixels = px(800.0);
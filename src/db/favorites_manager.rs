@@ -16,7 +16,11 @@ use super::FAVORITY_TABLE;
 use super::history_manager::HistoryManager;
 use std::sync::LazyLock;
 
-static FAVORITES_MANAGER: LazyLock<HistoryManager> = LazyLock::new(|| HistoryManager::new(FAVORITY_TABLE));
+/// Pinned keys are only removed by the user, unlike the history the list is not meant to roll over
+const MAX_FAVORITES: usize = 200;
+
+static FAVORITES_MANAGER: LazyLock<HistoryManager> =
+    LazyLock::new(|| HistoryManager::new(FAVORITY_TABLE).set_max_history_size(MAX_FAVORITES));
 
 pub fn get_favorites_manager() -> &'static HistoryManager {
     &FAVORITES_MANAGER
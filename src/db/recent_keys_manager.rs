@@ -0,0 +1,27 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::RECENT_KEYS_TABLE;
+use super::history_manager::HistoryManager;
+use std::sync::LazyLock;
+
+/// Number of recently viewed keys remembered per server
+const MAX_RECENT_KEYS: usize = 30;
+
+static RECENT_KEYS_MANAGER: LazyLock<HistoryManager> =
+    LazyLock::new(|| HistoryManager::new(RECENT_KEYS_TABLE).set_max_history_size(MAX_RECENT_KEYS));
+
+pub fn get_recent_keys_manager() -> &'static HistoryManager {
+    &RECENT_KEYS_MANAGER
+}
@@ -20,6 +20,10 @@ pub enum Error {
     Invalid { message: String },
     #[snafu(display("Redis error: {source}"))]
     Redis { source: redis::RedisError },
+    #[snafu(display(
+        "Timed out: {source}, check that the server is reachable or raise its timeouts in the server settings"
+    ))]
+    Timeout { source: redis::RedisError },
     #[snafu(display("IO error: {source}"))]
     Io { source: std::io::Error },
     #[snafu(display("Serde json error: {source}"))]
@@ -59,6 +63,9 @@ pub enum Error {
 
 impl From<redis::RedisError> for Error {
     fn from(source: redis::RedisError) -> Self {
+        if source.is_timeout() {
+            return Error::Timeout { source };
+        }
         Error::Redis { source }
     }
 }
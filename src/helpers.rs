@@ -15,20 +15,24 @@
 use std::env;
 
 mod action;
+mod command;
 mod common;
 mod font;
 mod fs;
+mod logger;
 mod string;
 mod time;
 mod ttl_cache;
 mod validate;
 
 pub use action::*;
+pub use command::*;
 pub use common::*;
 pub use font::*;
 pub use fs::*;
+pub use logger::*;
 pub use string::*;
-pub use time::{parse_duration, unix_ts, unix_ts_millis};
+pub use time::{TTL_UNITS, parse_duration, unix_ts, unix_ts_millis};
 pub use ttl_cache::*;
 pub use validate::*;
 pub fn is_development() -> bool {
@@ -12,26 +12,97 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::get_or_create_config_dir;
+use crate::error::Error;
+use crate::states::FontSizeAction;
 use gpui::Action;
-use gpui::KeyBinding;
+use gpui::{KeyBinding, Keystroke};
 use schemars::JsonSchema;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use tracing::warn;
+
+const KEYMAP_FILE: &str = "keymap.toml";
+
+/// Default keymap as `(action name, keystrokes)`, the action names are the keys used in `keymap.toml`.
+const DEFAULT_KEYMAP: &[(&str, &str)] = &[
+    ("add_key", "cmd-n"),
+    ("save", "cmd-s"),
+    ("reload", "cmd-r"),
+    ("refresh_keys", "cmd-shift-r"),
+    ("focus_filter", "cmd-f"),
+    ("quick_open", "cmd-p"),
+    ("cycle_query_mode", "cmd-shift-m"),
+    ("delete_key", "cmd-backspace"),
+    ("update_ttl", "cmd-t"),
+    ("toggle_terminal", "cmd-j"),
+    ("next_server", "ctrl-tab"),
+    ("previous_server", "ctrl-shift-tab"),
+    ("new_window", "cmd-shift-n"),
+    ("show_shortcuts", "cmd-/"),
+    ("command_palette", "cmd-shift-p"),
+    ("zoom_in", "cmd-="),
+    ("zoom_out", "cmd--"),
+    ("reset_zoom", "cmd-0"),
+    ("quit", "cmd-q"),
+];
+
+/// A resolved keyboard shortcut, listed in the shortcuts cheat-sheet.
+#[derive(Debug, Clone)]
+pub struct Shortcut {
+    pub name: &'static str,
+    pub keystrokes: String,
+}
+
+#[derive(Default)]
+struct Keymap {
+    shortcuts: Vec<Shortcut>,
+    warnings: Vec<String>,
+}
+
+static KEYMAP: LazyLock<Mutex<Keymap>> = LazyLock::new(|| Mutex::new(Keymap::default()));
 
 #[derive(Clone, Copy, PartialEq, Debug, Deserialize, JsonSchema, Action)]
 pub enum MemuAction {
     Quit,
     About,
+    NewWindow,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug, Deserialize, JsonSchema, Action)]
 pub enum EditorAction {
     Create,
     Save,
+    GetSet,
+    GetDel,
+    CopyRaw,
+    CopyBase64,
+    CopyHex,
+    CopyJson,
+    ExportBinary,
+    FormatJson,
+    MinifyJson,
     Reload,
     UpdateTtl,
     Cmd,
     Search,
+    /// Open a key by its exact name
+    QuickOpen,
     AutoRefresh(u32),
+    RefreshKeys,
+    DeleteKey,
+    RenameKey,
+    CycleQueryMode,
+    ToggleSoftWrap,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Deserialize, JsonSchema, Action)]
+pub enum ShortcutAction {
+    NextServer,
+    PreviousServer,
+    ShowShortcuts,
+    ShowCommandPalette,
 }
 
 pub fn humanize_keystroke(keystroke: &str) -> String {
@@ -112,14 +183,124 @@ pub fn humanize_keystroke(keystroke: &str) -> String {
     display_text
 }
 
+fn new_key_binding(name: &str, keystrokes: &str) -> Option<KeyBinding> {
+    let binding = match name {
+        "add_key" => KeyBinding::new(keystrokes, EditorAction::Create, None),
+        "save" => KeyBinding::new(keystrokes, EditorAction::Save, None),
+        "reload" => KeyBinding::new(keystrokes, EditorAction::Reload, None),
+        "refresh_keys" => KeyBinding::new(keystrokes, EditorAction::RefreshKeys, None),
+        "focus_filter" => KeyBinding::new(keystrokes, EditorAction::Search, None),
+        "quick_open" => KeyBinding::new(keystrokes, EditorAction::QuickOpen, None),
+        "cycle_query_mode" => KeyBinding::new(keystrokes, EditorAction::CycleQueryMode, None),
+        "delete_key" => KeyBinding::new(keystrokes, EditorAction::DeleteKey, None),
+        "update_ttl" => KeyBinding::new(keystrokes, EditorAction::UpdateTtl, None),
+        "toggle_terminal" => KeyBinding::new(keystrokes, EditorAction::Cmd, None),
+        "next_server" => KeyBinding::new(keystrokes, ShortcutAction::NextServer, None),
+        "previous_server" => KeyBinding::new(keystrokes, ShortcutAction::PreviousServer, None),
+        "new_window" => KeyBinding::new(keystrokes, MemuAction::NewWindow, None),
+        "show_shortcuts" => KeyBinding::new(keystrokes, ShortcutAction::ShowShortcuts, None),
+        "command_palette" => KeyBinding::new(keystrokes, ShortcutAction::ShowCommandPalette, None),
+        "zoom_in" => KeyBinding::new(keystrokes, FontSizeAction::ZoomIn, None),
+        "zoom_out" => KeyBinding::new(keystrokes, FontSizeAction::ZoomOut, None),
+        "reset_zoom" => KeyBinding::new(keystrokes, FontSizeAction::Medium, None),
+        "quit" => KeyBinding::new(keystrokes, MemuAction::Quit, None),
+        _ => return None,
+    };
+    Some(binding)
+}
+
+fn is_valid_keystrokes(keystrokes: &str) -> bool {
+    let mut parts = keystrokes.split_whitespace().peekable();
+    parts.peek().is_some() && parts.all(|part| Keystroke::parse(part).is_ok())
+}
+
+/// Reads the user overrides of the keymap from `keymap.toml` in the config directory.
+///
+/// Each entry maps an action name to its keystrokes, e.g. `save = "ctrl-s"`,
+/// an empty string removes the binding. A missing file means no overrides.
+pub fn read_keymap_file() -> Result<HashMap<String, String>, Error> {
+    let path = get_or_create_config_dir()?.join(KEYMAP_FILE);
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    Ok(toml::from_str(&content)?)
+}
+
+/// Writes the keymap overrides to `keymap.toml`, they are applied by `new_hot_keys`.
+pub fn save_keymap_file(keymap: &HashMap<String, String>) -> Result<(), Error> {
+    let path = get_or_create_config_dir()?.join(KEYMAP_FILE);
+    std::fs::write(path, toml::to_string(keymap)?)?;
+    Ok(())
+}
+
+fn read_keymap_overrides(warnings: &mut Vec<String>) -> HashMap<String, String> {
+    match read_keymap_file() {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            warnings.push(format!("{KEYMAP_FILE}: {e}"));
+            HashMap::new()
+        }
+    }
+}
+
+/// Builds the key bindings from the default keymap and the user overrides.
+///
+/// Invalid entries fall back to the default keystrokes, and when several actions
+/// share the same keystrokes only the first one is bound. The problems found are
+/// kept and can be taken with `take_keymap_warnings`.
 pub fn new_hot_keys() -> Vec<KeyBinding> {
-    vec![
-        KeyBinding::new("cmd-q", MemuAction::Quit, None),
-        KeyBinding::new("cmd-s", EditorAction::Save, None),
-        KeyBinding::new("cmd-r", EditorAction::Reload, None),
-        KeyBinding::new("cmd-n", EditorAction::Create, None),
-        KeyBinding::new("cmd-t", EditorAction::UpdateTtl, None),
-        KeyBinding::new("cmd-j", EditorAction::Cmd, None),
-        KeyBinding::new("cmd-f", EditorAction::Search, None),
-    ]
+    let mut warnings = vec![];
+    let mut overrides = read_keymap_overrides(&mut warnings);
+    let mut shortcuts: Vec<Shortcut> = vec![];
+    let mut bindings = vec![];
+    for (name, default_keystrokes) in DEFAULT_KEYMAP {
+        let keystrokes = match overrides.remove(*name) {
+            Some(keystrokes) if keystrokes.trim().is_empty() => continue,
+            Some(keystrokes) if is_valid_keystrokes(&keystrokes) => keystrokes.trim().to_string(),
+            Some(keystrokes) => {
+                warnings.push(format!("{name}: invalid keystrokes \"{keystrokes}\""));
+                default_keystrokes.to_string()
+            }
+            None => default_keystrokes.to_string(),
+        };
+        if let Some(existing) = shortcuts.iter().find(|item| item.keystrokes == keystrokes) {
+            warnings.push(format!(
+                "{keystrokes} is bound to both {} and {name}, {name} is ignored",
+                existing.name
+            ));
+            continue;
+        }
+        if let Some(binding) = new_key_binding(name, &keystrokes) {
+            bindings.push(binding);
+            shortcuts.push(Shortcut { name, keystrokes });
+        }
+    }
+    for name in overrides.keys() {
+        warnings.push(format!("{name}: unknown action"));
+    }
+    for warning in &warnings {
+        warn!(warning = %warning, "invalid keymap entry");
+    }
+    if let Ok(mut keymap) = KEYMAP.lock() {
+        *keymap = Keymap { shortcuts, warnings };
+    }
+    bindings
+}
+
+/// Returns the bound shortcuts in the order of the default keymap.
+pub fn get_shortcuts() -> Vec<Shortcut> {
+    KEYMAP.lock().map(|keymap| keymap.shortcuts.clone()).unwrap_or_default()
+}
+
+/// Takes the problems found while loading the keymap, they are reported only once.
+pub fn take_keymap_warnings() -> Vec<String> {
+    KEYMAP
+        .lock()
+        .map(|mut keymap| std::mem::take(&mut keymap.warnings))
+        .unwrap_or_default()
+}
+
+pub fn get_keymap_path() -> Option<std::path::PathBuf> {
+    get_or_create_config_dir().ok().map(|dir| dir.join(KEYMAP_FILE))
 }
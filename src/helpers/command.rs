@@ -0,0 +1,189 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Write commands allowed when importing keys from pasted commands,
+/// with the minimum number of arguments (excluding the command name)
+/// and whether the arguments after the key must come in pairs.
+const IMPORT_COMMANDS: &[(&str, usize, bool)] = &[
+    ("SET", 2, false),
+    ("SETEX", 3, false),
+    ("PSETEX", 3, false),
+    ("SETNX", 2, false),
+    ("APPEND", 2, false),
+    ("MSET", 2, false),
+    ("HSET", 3, true),
+    ("HMSET", 3, true),
+    ("HSETNX", 3, false),
+    ("LPUSH", 2, false),
+    ("RPUSH", 2, false),
+    ("SADD", 2, false),
+    ("ZADD", 3, false),
+    ("XADD", 4, false),
+    ("EXPIRE", 2, false),
+    ("PEXPIRE", 2, false),
+    ("EXPIREAT", 2, false),
+    ("PEXPIREAT", 2, false),
+];
+
+/// A write command parsed from pasted text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportCommand {
+    /// 1-based line number of the command in the pasted text
+    pub line: usize,
+    /// Upper-cased command name
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+impl ImportCommand {
+    /// Keys written by the command.
+    pub fn keys(&self) -> Vec<&str> {
+        if self.name == "MSET" {
+            return self.args.iter().step_by(2).map(|s| s.as_str()).collect();
+        }
+        self.args.first().map(|s| vec![s.as_str()]).unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for ImportCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let args = shlex::try_join(self.args.iter().map(|s| s.as_str())).unwrap_or_else(|_| self.args.join(" "));
+        write!(f, "{} {}", self.name, args)
+    }
+}
+
+/// Parses a block of Redis write commands, one command per line.
+///
+/// Empty lines and lines starting with `#` are skipped, arguments support
+/// shell-like quoting. Only the commands in `IMPORT_COMMANDS` are accepted,
+/// the error message points to the first invalid line.
+pub fn parse_import_commands(text: &str) -> Result<Vec<ImportCommand>, String> {
+    let mut commands = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some(mut parts) = shlex::split(line) else {
+            return Err(format!("Line {line_no}: unbalanced quotes"));
+        };
+        if parts.is_empty() {
+            continue;
+        }
+        let name = parts.remove(0).to_uppercase();
+        let Some((_, min_args, pairs)) = IMPORT_COMMANDS.iter().find(|(cmd, _, _)| *cmd == name) else {
+            return Err(format!("Line {line_no}: {name} is not a supported write command"));
+        };
+        if parts.len() < *min_args {
+            return Err(format!(
+                "Line {line_no}: {name} requires at least {min_args} arguments, got {}",
+                parts.len()
+            ));
+        }
+        let unpaired = match name.as_str() {
+            "MSET" => parts.len() % 2 != 0,
+            _ => *pairs && (parts.len() - 1) % 2 != 0,
+        };
+        if unpaired {
+            return Err(format!("Line {line_no}: {name} requires field value pairs"));
+        }
+        commands.push(ImportCommand {
+            line: line_no,
+            name,
+            args: parts,
+        });
+    }
+    if commands.is_empty() {
+        return Err("No command found".to_string());
+    }
+    Ok(commands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quoted_arguments() {
+        let commands = parse_import_commands(r#"set user:1 "hello world""#).unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].name, "SET");
+        assert_eq!(commands[0].args, vec!["user:1", "hello world"]);
+
+        let commands = parse_import_commands(r#"HSET user:2 name 'Tree xie' note "say \"hi\"""#).unwrap();
+        assert_eq!(
+            commands[0].args,
+            vec!["user:2", "name", "Tree xie", "note", "say \"hi\""]
+        );
+
+        assert_eq!(
+            parse_import_commands(r#"SET user:1 "hello"#).unwrap_err(),
+            "Line 1: unbalanced quotes"
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_comments_and_blank_lines() {
+        let text = "# users\n\nSET user:1 a\n   \n  # counters\nINCR_NOT_HERE\n";
+        assert_eq!(
+            parse_import_commands(text).unwrap_err(),
+            "Line 6: INCR_NOT_HERE is not a supported write command"
+        );
+
+        let text = "# users\n\nSET user:1 a\n   \n  # counters\nSADD tags a b\n";
+        let commands = parse_import_commands(text).unwrap();
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].line, 3);
+        assert_eq!(commands[1].line, 6);
+        assert_eq!(commands[1].name, "SADD");
+
+        assert_eq!(parse_import_commands("# nothing\n\n").unwrap_err(), "No command found");
+    }
+
+    #[test]
+    fn test_parse_rejects_disallowed_commands() {
+        assert_eq!(
+            parse_import_commands("SET a 1\nFLUSHALL").unwrap_err(),
+            "Line 2: FLUSHALL is not a supported write command"
+        );
+        assert_eq!(
+            parse_import_commands("del a").unwrap_err(),
+            "Line 1: DEL is not a supported write command"
+        );
+    }
+
+    #[test]
+    fn test_parse_checks_arguments() {
+        assert_eq!(
+            parse_import_commands("SET a").unwrap_err(),
+            "Line 1: SET requires at least 2 arguments, got 1"
+        );
+        assert_eq!(
+            parse_import_commands("HSET h f1 v1 f2").unwrap_err(),
+            "Line 1: HSET requires field value pairs"
+        );
+        assert_eq!(
+            parse_import_commands("MSET a 1 b").unwrap_err(),
+            "Line 1: MSET requires field value pairs"
+        );
+    }
+
+    #[test]
+    fn test_command_keys() {
+        let commands = parse_import_commands("MSET a 1 b 2\nHSET h f v").unwrap();
+        assert_eq!(commands[0].keys(), vec!["a", "b"]);
+        assert_eq!(commands[1].keys(), vec!["h"]);
+    }
+}
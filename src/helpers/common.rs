@@ -24,7 +24,7 @@ type Result<T, E = Error> = std::result::Result<T, E>;
 pub fn get_key_tree_widths(width: Pixels) -> (Pixels, Pixels, Pixels) {
     let min_width = KEY_TREE_MIN_WIDTH;
     let max_width = KEY_TREE_MAX_WIDTH;
-    (width.max(min_width), min_width, max_width)
+    (width.max(min_width).min(max_width), min_width, max_width)
 }
 
 pub fn decompress_zstd(bytes: &[u8]) -> Result<Vec<u8>> {
@@ -18,6 +18,7 @@
 //! - Directory copying operations
 //! - App Store build detection (for macOS sandboxing)
 //! - Configuration directory management with migration support
+//! - Log directory management
 
 use crate::error::Error;
 use directories::{ProjectDirs, UserDirs};
@@ -163,6 +164,34 @@ pub fn get_or_create_config_dir() -> Result<PathBuf> {
     Ok(config_dir.to_path_buf())
 }
 
+/// Returns the directory of the log files, in the platform cache directory,
+/// e.g. `~/.cache/zedis/logs/` on Linux.
+pub fn get_or_create_log_dir() -> Result<PathBuf> {
+    let Some(project_dirs) = ProjectDirs::from("com", "bigtree", "zedis") else {
+        return Err(Error::Invalid {
+            message: "project directories not found".to_string(),
+        });
+    };
+    let log_dir = project_dirs.cache_dir().join("logs");
+    if !log_dir.exists() {
+        fs::create_dir_all(&log_dir)?;
+    }
+    Ok(log_dir)
+}
+
+/// Replaces the characters not allowed in file names, keys often contain `:` or `/`.
+pub fn to_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
 pub fn resolve_path(path: &str) -> String {
     if path.is_empty() {
         return "".to_string();
@@ -0,0 +1,189 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recent `tracing` events kept in memory for the log viewer, the rotating
+//! log file, and the log level which can be changed while the app is running.
+
+use chrono::Local;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::{Registry, reload};
+
+/// Max events kept in memory, the oldest are dropped first
+const MAX_LOG_RECORDS: usize = 5_000;
+
+/// Size of the log file before it is rotated to `zedis.log.1`
+const MAX_LOG_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+const LOG_FILE_NAME: &str = "zedis.log";
+
+/// Levels from the most to the least severe
+pub const LOG_LEVELS: [Level; 5] = [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG, Level::TRACE];
+
+static LOG_RECORDS: LazyLock<Mutex<VecDeque<LogRecord>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(MAX_LOG_RECORDS)));
+
+/// Bumped on every event, so the viewer only refreshes when something was logged
+static LOG_VERSION: AtomicU64 = AtomicU64::new(0);
+
+static LOG_LEVEL_HANDLE: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
+/// A captured `tracing` event.
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub time: String,
+    pub level: Level,
+    /// Module path of the event, e.g. `zedis::connection::manager`
+    pub target: String,
+    /// Message followed by the other fields as `name=value`
+    pub message: String,
+}
+
+impl std::fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:>5} {}: {}", self.time, self.level, self.target, self.message)
+    }
+}
+
+#[derive(Default)]
+struct LogVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for LogVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+/// Layer writing every event to the in-memory ring buffer.
+pub struct LogBufferLayer;
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = LogVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let record = LogRecord {
+            time: Local::now().format("%H:%M:%S%.3f").to_string(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message + &visitor.fields,
+        };
+        let mut records = LOG_RECORDS.lock();
+        if records.len() >= MAX_LOG_RECORDS {
+            records.pop_front();
+        }
+        records.push_back(record);
+        LOG_VERSION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Log file written by the subscriber, e.g. to attach it to a bug report.
+///
+/// Once it exceeds `MAX_LOG_FILE_SIZE` it is renamed to `zedis.log.1`, so at
+/// most two files are kept.
+pub struct RotatingLogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingLogFile {
+    /// Opens `zedis.log` of the directory, appending to the previous logs.
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        let path = dir.join(LOG_FILE_NAME);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, file, size })
+    }
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        fs::rename(&self.path, self.path.with_extension("log.1"))?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > MAX_LOG_FILE_SIZE {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Creates the level filter of the subscriber, its level can be changed by `set_log_level`.
+pub fn new_log_level_layer(level: Level) -> reload::Layer<LevelFilter, Registry> {
+    let (layer, handle) = reload::Layer::new(LevelFilter::from_level(level));
+    let _ = LOG_LEVEL_HANDLE.set(handle);
+    layer
+}
+
+/// Changes the level of the logs written to stdout and captured for the viewer.
+pub fn set_log_level(level: Level) {
+    if let Some(handle) = LOG_LEVEL_HANDLE.get() {
+        let _ = handle.modify(|filter| *filter = LevelFilter::from_level(level));
+    }
+}
+
+/// Current log level, `INFO` if the logger isn't initialized.
+pub fn get_log_level() -> Level {
+    LOG_LEVEL_HANDLE
+        .get()
+        .and_then(|handle| handle.clone_current())
+        .and_then(|filter| filter.into_level())
+        .unwrap_or(Level::INFO)
+}
+
+/// Number of events logged so far, changes whenever a new event is captured.
+pub fn log_version() -> u64 {
+    LOG_VERSION.load(Ordering::Relaxed)
+}
+
+/// Captured events, from the oldest to the newest.
+pub fn get_log_records() -> Vec<LogRecord> {
+    LOG_RECORDS.lock().iter().cloned().collect()
+}
+
+pub fn clear_log_records() {
+    LOG_RECORDS.lock().clear();
+    LOG_VERSION.fetch_add(1, Ordering::Relaxed);
+}
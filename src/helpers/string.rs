@@ -197,6 +197,17 @@ pub fn format_duration(duration: Duration) -> String {
     format!("{}s", seconds)
 }
 
+/// Format a count in a short human readable form, e.g. `950`, `12.3k`, `4.5M`.
+pub fn format_count(count: usize) -> String {
+    if count >= 1_000_000 {
+        return format!("{:.1}M", count as f64 / 1_000_000.0);
+    }
+    if count >= 1_000 {
+        return format!("{:.1}k", count as f64 / 1_000.0);
+    }
+    count.to_string()
+}
+
 pub fn redis_value_to_string(v: &Value) -> String {
     match v {
         Value::Nil => "(nil)".to_string(),
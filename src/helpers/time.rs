@@ -34,3 +34,6 @@ pub fn parse_duration(s: &str) -> Result<Duration> {
     }
     humantime::parse_duration(s).map_err(|e| Error::Invalid { message: e.to_string() })
 }
+
+/// Units offered for TTL inputs as `(name, seconds)`.
+pub const TTL_UNITS: &[(&str, u64)] = &[("seconds", 1), ("minutes", 60), ("hours", 3600), ("days", 86400)];
@@ -30,6 +30,7 @@ pub fn now_secs() -> u64 {
 
 struct TtlCacheItem<V> {
     value: V,
+    idle_secs: u64,
     expired_at: AtomicU64,
 }
 
@@ -51,22 +52,39 @@ impl<K: Eq + Hash + Debug, V: Clone> TtlCache<K, V> {
         if item.expired_at.load(Ordering::Acquire) < now {
             return None;
         }
-        item.expired_at.store(now + self.idle.as_secs(), Ordering::Release);
+        item.expired_at.store(now + item.idle_secs, Ordering::Release);
         Some(item.value.clone())
     }
     pub fn insert(&self, key: K, value: V) {
-        let expired_at = now_secs() + self.idle.as_secs();
+        self.insert_with_idle(key, value, self.idle);
+    }
+    /// Inserts a value with its own idle timeout, overriding the cache default.
+    pub fn insert_with_idle(&self, key: K, value: V, idle: Duration) {
+        let idle_secs = idle.as_secs();
         self.cache.insert(
             key,
             TtlCacheItem {
                 value,
-                expired_at: AtomicU64::new(expired_at),
+                idle_secs,
+                expired_at: AtomicU64::new(now_secs() + idle_secs),
             },
         );
     }
     pub fn remove(&self, key: &K) {
         self.cache.remove(key);
     }
+    /// Removes the values matching `f` and returns them.
+    pub fn remove_if(&self, f: impl Fn(&V) -> bool) -> Vec<V> {
+        let mut removed = vec![];
+        self.cache.retain(|_, item| {
+            if f(&item.value) {
+                removed.push(item.value.clone());
+                return false;
+            }
+            true
+        });
+        removed
+    }
     pub fn clear_expired(&self) -> (usize, usize) {
         let now = now_secs();
         let mut count = 0;
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+/// Validate a TTL input, either a number or a human duration like `30m`, `2h` or `7d`.
 pub fn validate_ttl(s: &str) -> bool {
+    let s = s.trim();
     if s.is_empty() || s.parse::<usize>().is_ok() {
         return true;
     }
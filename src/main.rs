@@ -3,22 +3,25 @@ use crate::connection::{clear_expired_cache, get_servers};
 use crate::constants::SIDEBAR_WIDTH;
 use crate::db::{ProtoManager, init_database};
 use crate::helpers::{
-    MemuAction, get_default_font_family, get_or_create_config_dir, is_app_store_build, is_development, new_hot_keys,
+    LogBufferLayer, MemuAction, RotatingLogFile, ShortcutAction, get_default_font_family, get_or_create_config_dir,
+    get_or_create_log_dir, is_app_store_build, is_development, new_hot_keys, new_log_level_layer, take_keymap_warnings,
 };
 use crate::states::{
     FontSize, FontSizeAction, GlobalEvent, LocaleAction, NotificationCategory, Route, SettingsAction, ThemeAction,
-    ZedisAppState, ZedisGlobalStore, save_app_state, update_app_state_and_save,
+    WindowState, ZedisAppState, ZedisGlobalStore, i18n_shortcuts, save_app_state, update_app_state_and_save,
+};
+use crate::views::{
+    ZedisContent, ZedisSidebar, ZedisTitleBar, open_about_window, open_command_palette, open_shortcuts_dialog,
 };
-use crate::views::{ZedisContent, ZedisSidebar, ZedisTitleBar, open_about_window};
 use gpui::{
     App, Bounds, Entity, Menu, MenuItem, Pixels, Task, TitlebarOptions, Window, WindowAppearance, WindowBounds,
-    WindowOptions, div, prelude::*, px, size,
+    WindowId, WindowOptions, div, prelude::*, px, size,
 };
 use gpui_component::{ActiveTheme, Root, Theme, ThemeMode, WindowExt, h_flex, notification::Notification, v_flex};
-use std::{env, str::FromStr, time::Duration};
+use std::{env, str::FromStr, sync::Mutex, time::Duration};
 use sys_locale::get_locale;
 use tracing::{Level, error, info};
-use tracing_subscriber::FmtSubscriber;
+use tracing_subscriber::prelude::*;
 
 #[cfg(feature = "mimalloc")]
 #[global_allocator]
@@ -27,6 +30,7 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 rust_i18n::i18n!("locales", fallback = "en");
 
 const PKG_NAME: &str = env!("CARGO_PKG_NAME");
+const NEW_WINDOW_OFFSET: f32 = 30.0;
 
 mod assets;
 mod components;
@@ -90,23 +94,27 @@ impl Zedis {
             }
         }));
         let title_bar = Some(cx.new(|cx| ZedisTitleBar::new(window, cx)));
+        // Report keymap problems found at startup in the first window
+        let keymap_warnings = take_keymap_warnings();
+        let pending_notification = (!keymap_warnings.is_empty())
+            .then(|| Notification::warning(keymap_warnings.join("\n")).title(i18n_shortcuts(cx, "keymap_warning")));
 
         Self {
             sidebar,
             save_task: None,
             content,
-            pending_notification: None,
+            pending_notification,
             title_bar,
             theme_update_task: None,
             _clear_expired_cache: clear_expired_cache,
             last_bounds: Bounds::default(),
         }
     }
-    fn persist_window_state(&mut self, new_bounds: Bounds<Pixels>, cx: &mut Context<Self>) {
+    fn persist_window_state(&mut self, window_id: WindowId, new_bounds: Bounds<Pixels>, cx: &mut Context<Self>) {
         self.last_bounds = new_bounds;
         let store = cx.global::<ZedisGlobalStore>().clone();
         let mut value = store.value(cx);
-        value.set_bounds(new_bounds);
+        value.set_bounds(window_id, new_bounds);
         let task = cx.spawn(async move |_, cx| {
             // wait 500ms
             cx.background_executor()
@@ -114,7 +122,7 @@ impl Zedis {
                 .await;
 
             store.update(cx, move |state, cx| {
-                state.set_bounds(new_bounds);
+                state.set_bounds(window_id, new_bounds);
                 cx.notify();
             });
 
@@ -129,6 +137,36 @@ impl Zedis {
         });
         self.save_task = Some(task);
     }
+    /// Select the next (or previous if `step` is negative) server of the list in this window
+    fn switch_server(&mut self, step: isize, window: &mut Window, cx: &mut Context<Self>) {
+        let Ok(servers) = get_servers() else {
+            return;
+        };
+        if servers.is_empty() {
+            return;
+        }
+        let window_id = window.window_handle().window_id();
+        let store = cx.global::<ZedisGlobalStore>().clone();
+        let state = store.read(cx);
+        let current = if state.route(window_id) == Route::Editor {
+            state
+                .selected_server(window_id)
+                .and_then(|(server_id, _)| servers.iter().position(|server| &server.id == server_id))
+        } else {
+            None
+        };
+        let len = servers.len() as isize;
+        let index = match current {
+            Some(index) => (index as isize + step).rem_euclid(len),
+            None if step < 0 => len - 1,
+            None => 0,
+        };
+        let server_id = servers[index as usize].id.clone();
+        store.update(cx, |state, cx| {
+            state.go_to(window_id, Route::Editor, cx);
+            state.set_selected_server(window_id, (server_id, 0), cx);
+        });
+    }
     fn render_titlebar(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
         let Some(title_bar) = self.title_bar.as_ref() else {
             return h_flex().into_any_element();
@@ -143,14 +181,13 @@ impl Render for Zedis {
         let notification_layer = Root::render_notification_layer(window, cx);
         let current_bounds = window.bounds();
         if current_bounds != self.last_bounds {
-            self.persist_window_state(current_bounds, cx);
+            self.persist_window_state(window.window_handle().window_id(), current_bounds, cx);
         }
         if let Some(notification) = self.pending_notification.take() {
             window.push_notification(notification, cx);
         }
-        if let Some(font_size) = cx.global::<ZedisGlobalStore>().read(cx).font_size().to_pixels() {
-            window.set_rem_size(font_size);
-        }
+        let rem_size = cx.global::<ZedisGlobalStore>().read(cx).font_size().to_pixels();
+        window.set_rem_size(rem_size);
 
         let content = v_flex()
             .id(PKG_NAME)
@@ -209,29 +246,44 @@ impl Render for Zedis {
             }))
             .on_action(cx.listener(move |_this, e: &FontSizeAction, _window, cx| {
                 let action = *e;
+                let current = cx.global::<ZedisGlobalStore>().read(cx).font_size();
 
                 let font_size = match action {
-                    FontSizeAction::Large => Some(FontSize::Large),
-                    FontSizeAction::Small => Some(FontSize::Small),
-                    _ => None,
+                    FontSizeAction::ExtraLarge => FontSize::ExtraLarge,
+                    FontSizeAction::Large => FontSize::Large,
+                    FontSizeAction::Medium => FontSize::Medium,
+                    FontSizeAction::Small => FontSize::Small,
+                    FontSizeAction::ZoomIn => current.larger(),
+                    FontSizeAction::ZoomOut => current.smaller(),
                 };
+                // The default size is not stored
+                let font_size = (font_size != FontSize::Medium).then_some(font_size);
                 // Save locale preference and refresh UI
                 update_app_state_and_save(cx, "save_font_size", move |state, _cx| {
                     state.set_font_size(font_size);
                 });
             }))
-            .on_action(cx.listener(move |_this, e: &SettingsAction, _window, cx| {
+            .on_action(cx.listener(|this, e: &ShortcutAction, window, cx| match e {
+                ShortcutAction::NextServer => this.switch_server(1, window, cx),
+                ShortcutAction::PreviousServer => this.switch_server(-1, window, cx),
+                ShortcutAction::ShowShortcuts => open_shortcuts_dialog(window, cx),
+                ShortcutAction::ShowCommandPalette => open_command_palette(window, cx),
+            }))
+            .on_action(cx.listener(move |_this, e: &SettingsAction, window, cx| {
                 let action = *e;
                 let mut route = None;
                 if action == SettingsAction::Editor {
                     route = Some(Route::Settings);
                 } else if action == SettingsAction::Protos {
                     route = Some(Route::Protos);
+                } else if action == SettingsAction::Logs {
+                    route = Some(Route::Logs);
                 }
                 if let Some(route) = route {
+                    let window_id = window.window_handle().window_id();
                     cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
                         store.update(cx, |state, cx| {
-                            state.go_to(route, cx);
+                            state.go_to(window_id, route, cx);
                         });
                     });
                 }
@@ -253,22 +305,105 @@ fn init_logger() -> Result<(), Box<dyn std::error::Error>> {
         )
     });
 
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(level)
+    // The log file is optional, e.g. the cache directory may not be writable
+    let log_file = get_or_create_log_dir()
+        .ok()
+        .and_then(|dir| RotatingLogFile::open(&dir).ok());
+    let file_layer = log_file.map(|file| {
+        tracing_subscriber::fmt::layer()
+            .with_timer(timer.clone())
+            .with_ansi(false)
+            .with_writer(Mutex::new(file))
+    });
+    let fmt_layer = tracing_subscriber::fmt::layer()
         .with_timer(timer)
         .with_ansi(is_development())
-        .finish();
-    tracing::subscriber::set_global_default(subscriber)?;
+        .with_writer(std::io::stderr);
+    // The events are also kept in memory for the log viewer
+    tracing_subscriber::registry()
+        .with(new_log_level_layer(level))
+        .with(fmt_layer)
+        .with(file_layer)
+        .with(LogBufferLayer)
+        .try_init()?;
     Ok(())
 }
 
+/// Open a main window with its own sidebar, content and server state.
+///
+/// The first window keeps the app alive on macOS when closed, extra windows
+/// are really closed and forget their route and selected server.
+/// `restored` is the saved state of an extra window reopened at launch.
+fn open_main_window(window_bounds: Bounds<Pixels>, primary: bool, restored: Option<WindowState>, cx: &mut App) {
+    cx.spawn(async move |cx| {
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(window_bounds)),
+                #[cfg(not(target_os = "linux"))]
+                titlebar: Some(TitlebarOptions {
+                    title: None,
+                    appears_transparent: true,
+                    traffic_light_position: Some(gpui::point(px(9.0), px(9.0))),
+                }),
+                show: true,
+                window_min_size: Some(size(px(600.), px(400.))),
+                ..Default::default()
+            },
+            |window, cx| {
+                let window_id = window.window_handle().window_id();
+                cx.global::<ZedisGlobalStore>().clone().update(cx, |state, _cx| {
+                    state.add_window(window_id, primary, restored);
+                });
+                if primary {
+                    #[cfg(target_os = "macos")]
+                    window.on_window_should_close(cx, move |_window, cx| {
+                        cx.hide();
+                        false
+                    });
+                } else {
+                    window.on_window_should_close(cx, move |_window, cx| {
+                        update_app_state_and_save(cx, "close_window", move |state, _cx| {
+                            state.remove_window(window_id);
+                        });
+                        true
+                    });
+                }
+                let zedis_view = cx.new(|cx| Zedis::new(window, cx));
+                cx.new(|cx| Root::new(zedis_view, window, cx))
+            },
+        )?;
+
+        Ok::<_, anyhow::Error>(())
+    })
+    .detach();
+}
+
+/// Open another main window next to the active one, e.g. to compare two servers.
+fn open_new_window(cx: &mut App) {
+    let offset = px(NEW_WINDOW_OFFSET);
+    let bounds = cx
+        .active_window()
+        .and_then(|window| window.update(cx, |_, window, _cx| window.bounds()).ok())
+        .or_else(|| cx.global::<ZedisGlobalStore>().read(cx).bounds().copied());
+    let window_bounds = match bounds {
+        Some(mut bounds) => {
+            bounds.origin.x += offset;
+            bounds.origin.y += offset;
+            bounds
+        }
+        None => Bounds::centered(None, size(px(1200.), px(750.)), cx),
+    };
+    open_main_window(window_bounds, false, None, cx);
+}
+
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const GIT_SHA: &str = env!("VERGEN_GIT_SHA");
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     init_logger()?;
     let app = gpui_platform::application().with_assets(assets::Assets);
-    let app_state = ZedisAppState::try_new().unwrap_or_else(|_| ZedisAppState::new());
+    let mut app_state = ZedisAppState::try_new().unwrap_or_else(|_| ZedisAppState::new());
+    let other_windows = app_state.take_other_windows();
     if let Err(e) = get_servers() {
         error!(error = %e, "get servers fail",);
     }
@@ -298,7 +433,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         gpui_component::init(cx);
 
         cx.activate(true);
-        let window_bounds = if let Some(bounds) = app_state.bounds() {
+        // Saved bounds on a display that is no longer connected would open off-screen
+        let saved_bounds = app_state
+            .bounds()
+            .filter(|bounds| cx.displays().iter().any(|display| display.bounds().intersects(bounds)));
+        let window_bounds = if let Some(bounds) = saved_bounds {
             info!(bounds = ?bounds, "get window bounds from setting");
             *bounds
         } else {
@@ -311,6 +450,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             Bounds::centered(None, window_size, cx)
         };
         let app_state = cx.new(|_| app_state);
+        // Recorded once for every window, the notification center lists them
+        cx.subscribe(&app_state, |app_state, event, cx| {
+            if let GlobalEvent::Notification(notification) = event {
+                let notification = notification.clone();
+                app_state.update(cx, |state, cx| {
+                    state.add_notification_record(notification);
+                    cx.notify();
+                });
+            }
+        })
+        .detach();
         let app_store = ZedisGlobalStore::new(app_state);
         if let Some(theme) = app_store.read(cx).theme() {
             Theme::change(theme, None, cx);
@@ -324,43 +474,36 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             MemuAction::About => {
                 open_about_window(cx);
             }
+            MemuAction::NewWindow => {
+                open_new_window(cx);
+            }
         });
         cx.set_menus(vec![Menu {
             name: "Zedis".into(),
             items: vec![
                 MenuItem::action("About Zedis", MemuAction::About),
+                MenuItem::action("New Window", MemuAction::NewWindow),
+                MenuItem::action("Keyboard Shortcuts", ShortcutAction::ShowShortcuts),
                 MenuItem::action("Quit", MemuAction::Quit),
             ],
         }]);
 
-        cx.spawn(async move |cx| {
-            cx.open_window(
-                WindowOptions {
-                    window_bounds: Some(WindowBounds::Windowed(window_bounds)),
-                    #[cfg(not(target_os = "linux"))]
-                    titlebar: Some(TitlebarOptions {
-                        title: None,
-                        appears_transparent: true,
-                        traffic_light_position: Some(gpui::point(px(9.0), px(9.0))),
-                    }),
-                    show: true,
-                    window_min_size: Some(size(px(600.), px(400.))),
-                    ..Default::default()
-                },
-                |window, cx| {
-                    #[cfg(target_os = "macos")]
-                    window.on_window_should_close(cx, move |_window, cx| {
-                        cx.hide();
-                        false
-                    });
-                    let zedis_view = cx.new(|cx| Zedis::new(window, cx));
-                    cx.new(|cx| Root::new(zedis_view, window, cx))
-                },
-            )?;
-
-            Ok::<_, anyhow::Error>(())
-        })
-        .detach();
+        open_main_window(window_bounds, true, None, cx);
+        // Reopen the other windows of the last session where they were
+        for (index, window_state) in other_windows.into_iter().enumerate() {
+            let bounds = window_state
+                .bounds()
+                .filter(|bounds| cx.displays().iter().any(|display| display.bounds().intersects(bounds)))
+                .copied()
+                .unwrap_or_else(|| {
+                    let offset = px(NEW_WINDOW_OFFSET * (index + 1) as f32);
+                    let mut bounds = window_bounds;
+                    bounds.origin.x += offset;
+                    bounds.origin.y += offset;
+                    bounds
+                });
+            open_main_window(bounds, false, Some(window_state), cx);
+        }
         cx.spawn(async move |cx| {
             cx.background_spawn(async move {
                 if let Err(e) = ProtoManager::init() {
@@ -13,31 +13,43 @@
 // limitations under the License.
 
 mod app;
+mod backup;
 mod i18n;
 mod server;
 mod session;
 
 pub use app::*;
+pub use backup::*;
+pub use i18n::i18n_command_palette;
 pub use i18n::i18n_common;
+pub use i18n::i18n_config_editor;
 pub use i18n::i18n_editor;
 pub use i18n::i18n_hash_editor;
 pub use i18n::i18n_key_tree;
 pub use i18n::i18n_kv_table;
 pub use i18n::i18n_list_editor;
+pub use i18n::i18n_log_viewer;
 pub use i18n::i18n_metrics;
+pub use i18n::i18n_monitor;
+pub use i18n::i18n_notification_center;
 pub use i18n::i18n_proto_editor;
 pub use i18n::i18n_pubsub_editor;
+pub use i18n::i18n_search_editor;
 pub use i18n::i18n_servers;
 pub use i18n::i18n_set_editor;
 pub use i18n::i18n_settings;
+pub use i18n::i18n_shortcuts;
 pub use i18n::i18n_sidebar;
 pub use i18n::i18n_status_bar;
 pub use i18n::i18n_zset_editor;
+pub use server::ConnectionStatus;
 pub use server::ErrorMessage;
 pub use server::ZedisServerState;
+pub use server::config::ServerConfig;
 pub use server::event::ServerEvent;
 pub use server::event::ServerTask;
-pub use server::stat::{RedisMetrics, get_metrics_cache};
-pub use server::string::detect_and_decode;
+pub use server::search::{SearchPage, SearchResult};
+pub use server::stat::{RedisInfo, RedisMetrics, get_metrics_cache};
+pub use server::string::{decode_as, detect_and_decode, looks_like_json};
 pub use server::value::*;
 pub use session::*;
@@ -13,22 +13,28 @@
 // limitations under the License.
 
 use crate::connection::{
-    RedisServer, get_servers, save_servers, set_redis_connection_timeout, set_redis_response_timeout,
+    RedisServer, get_connection_manager, get_servers, save_servers, set_redis_connection_timeout,
+    set_redis_response_timeout,
 };
-use crate::constants::SIDEBAR_WIDTH;
+use crate::constants::{DEFAULT_REM_SIZE, SIDEBAR_WIDTH};
 use crate::error::Error;
-use crate::helpers::{get_key_tree_widths, get_or_create_config_dir};
-use crate::states::i18n_common;
+use crate::helpers::{get_key_tree_widths, get_or_create_config_dir, new_hot_keys, set_log_level};
+use crate::states::{
+    ImportMode, ImportedSettings, ViewMode, export_settings, i18n_common, i18n_settings, import_settings,
+};
+use ahash::AHashMap;
 use chrono::Local;
-use gpui::{Action, App, AppContext, Bounds, Context, Entity, EventEmitter, Global, Pixels, SharedString};
-use gpui_component::{ThemeMode, dialog::DialogButtonProps};
+use gpui::{Action, App, AppContext, Bounds, Context, Entity, EventEmitter, Global, Pixels, SharedString, WindowId};
+use gpui_component::{Theme, ThemeMode, dialog::DialogButtonProps};
+use rust_i18n::t;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
 use std::path::PathBuf;
 use std::time::Duration;
 use sys_locale::get_locale;
-use tracing::{error, info};
+use tracing::{Level, error, info};
 use uuid::Uuid;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
@@ -41,6 +47,7 @@ pub enum Route {
     Settings,
     Protos,
     Metrics,
+    Logs,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
@@ -49,13 +56,32 @@ pub enum FontSize {
     #[default]
     Medium,
     Large,
+    ExtraLarge,
 }
 impl FontSize {
-    pub fn to_pixels(self) -> Option<f32> {
+    /// Rem size of the window, the whole UI is sized in rems
+    pub fn to_pixels(self) -> f32 {
         match self {
-            FontSize::Small => Some(14.0),
-            FontSize::Medium => None,
-            FontSize::Large => Some(18.0),
+            FontSize::Small => 14.0,
+            FontSize::Medium => DEFAULT_REM_SIZE,
+            FontSize::Large => 18.0,
+            FontSize::ExtraLarge => 20.0,
+        }
+    }
+    /// One step larger, used to zoom in
+    pub fn larger(self) -> Self {
+        match self {
+            FontSize::Small => FontSize::Medium,
+            FontSize::Medium => FontSize::Large,
+            _ => FontSize::ExtraLarge,
+        }
+    }
+    /// One step smaller, used to zoom out
+    pub fn smaller(self) -> Self {
+        match self {
+            FontSize::ExtraLarge => FontSize::Large,
+            FontSize::Large => FontSize::Medium,
+            _ => FontSize::Small,
         }
     }
 }
@@ -82,16 +108,23 @@ pub enum LocaleAction {
 
 #[derive(Clone, Copy, PartialEq, Debug, Deserialize, JsonSchema, Action)]
 pub enum FontSizeAction {
+    ExtraLarge,
     Large,
     Medium,
     Small,
+    ZoomIn,
+    ZoomOut,
 }
 #[derive(Clone, Copy, PartialEq, Debug, Deserialize, JsonSchema, Action)]
 pub enum SettingsAction {
     Editor,
     Protos,
+    Logs,
 }
 
+/// App state fields bound to this machine or session, never exported with the preferences
+const LOCAL_STATE_FIELDS: &[&str] = &["route", "bounds", "selected_server", "other_windows"];
+
 const LIGHT_THEME_MODE: &str = "light";
 const DARK_THEME_MODE: &str = "dark";
 
@@ -121,6 +154,8 @@ pub struct NotificationAction {
     pub title: Option<SharedString>,
     pub category: NotificationCategory,
     pub message: SharedString,
+    /// Server id and db the notification is about, e.g. the server of a failed command
+    pub server: Option<(SharedString, usize)>,
 }
 
 impl NotificationAction {
@@ -165,20 +200,58 @@ impl NotificationAction {
         self.title = Some(title);
         self
     }
+
+    /// Sets the server the notification is about
+    pub fn with_server(mut self, server_id: SharedString, db: usize) -> Self {
+        self.server = Some((server_id, db));
+        self
+    }
+}
+
+/// Notifications kept in the history of the session
+const MAX_NOTIFICATION_HISTORY: usize = 100;
+
+/// A notification shown during the session, listed by the notification center
+#[derive(Clone, Debug)]
+pub struct NotificationRecord {
+    /// Local time it was shown
+    pub time: SharedString,
+    pub notification: NotificationAction,
 }
 
 pub enum GlobalEvent {
     /// A notification has been emitted.
     Notification(NotificationAction),
-    /// User selected a different server
-    ServerSelected(SharedString, usize),
+    /// User selected a different server in the window
+    ServerSelected(WindowId, SharedString, usize),
     /// Server list config has been modified (add/remove/edit).
     ServerListUpdated,
-    /// Route has been changed.
-    RouteChanged(Route),
+    /// Settings of an existing server have been edited, its cached clients are dropped.
+    ServerUpdated(SharedString),
+    /// Route of the window has been changed.
+    RouteChanged(WindowId, Route),
+    /// Keys have been written to a server and db from another window or server.
+    KeysAdded(SharedString, usize, Vec<SharedString>),
+}
+
+/// Route, selected server and bounds of a window, so that every window
+/// can browse a different server independently.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowState {
+    route: Route,
+    selected_server: Option<(String, usize)>,
+    bounds: Option<Bounds<Pixels>>,
+}
+
+impl WindowState {
+    pub fn bounds(&self) -> Option<&Bounds<Pixels>> {
+        self.bounds.as_ref()
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ZedisAppState {
     route: Route,
     locale: Option<String>,
@@ -193,7 +266,28 @@ pub struct ZedisAppState {
     max_truncate_length: Option<usize>,
     redis_connection_timeout: Option<Duration>,
     redis_response_timeout: Option<Duration>,
+    optimistic_save: Option<bool>,
+    allow_dangerous_commands: Option<bool>,
+    confirm_remove_value: Option<bool>,
+    large_value_threshold: Option<usize>,
+    /// Collections with more members than this are held back until they are asked for
+    large_collection_threshold: Option<usize>,
+    /// Keys expiring within this many seconds are highlighted in the key tree, 0 disables it
+    ttl_warning_threshold: Option<u64>,
     selected_server: Option<(String, usize)>,
+    /// "View as" choice of string values by key pattern, e.g. `session:*` -> `MessagePack`
+    view_modes: Option<BTreeMap<String, String>>,
+    /// Level of the logs, overrides `RUST_LOG` once chosen in the log viewer
+    log_level: Option<String>,
+    /// Windows opened besides the primary one, reopened at the next launch
+    other_windows: Vec<WindowState>,
+    #[serde(skip)]
+    windows: AHashMap<WindowId, WindowState>,
+    #[serde(skip)]
+    primary_window: Option<WindowId>,
+    /// Notifications of the session, oldest first, never persisted
+    #[serde(skip)]
+    notification_history: VecDeque<NotificationRecord>,
 }
 
 impl EventEmitter<GlobalEvent> for ZedisAppState {}
@@ -270,12 +364,56 @@ impl ZedisAppState {
         if let Some(redis_response_timeout) = state.redis_response_timeout {
             set_redis_response_timeout(redis_response_timeout);
         }
+        state.apply_log_level();
 
         Ok(state)
     }
     pub fn new() -> Self {
         Self { ..Default::default() }
     }
+    /// Portable preferences as a JSON object, without window and session related fields.
+    pub fn export_preferences(&self) -> Result<serde_json::Value> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(object) = value.as_object_mut() {
+            for field in LOCAL_STATE_FIELDS {
+                object.remove(*field);
+            }
+            object.retain(|_, v| !v.is_null());
+        }
+        Ok(value)
+    }
+    /// Apply imported preferences, either on top of the current ones or replacing them.
+    ///
+    /// Window and session related fields are always kept.
+    pub fn apply_preferences(&mut self, preferences: serde_json::Value, replace: bool) -> Result<()> {
+        let serde_json::Value::Object(preferences) = preferences else {
+            return Ok(());
+        };
+        let mut value = serde_json::to_value(&*self)?;
+        let Some(object) = value.as_object_mut() else {
+            return Ok(());
+        };
+        if replace {
+            object.retain(|key, _| LOCAL_STATE_FIELDS.contains(&key.as_str()));
+        }
+        for (key, field) in preferences {
+            if !LOCAL_STATE_FIELDS.contains(&key.as_str()) {
+                object.insert(key, field);
+            }
+        }
+        let mut state: Self = serde_json::from_value(value)?;
+        state.windows = std::mem::take(&mut self.windows);
+        state.primary_window = self.primary_window;
+        *self = state;
+        if let Some(redis_connection_timeout) = self.redis_connection_timeout {
+            set_redis_connection_timeout(redis_connection_timeout);
+        }
+        if let Some(redis_response_timeout) = self.redis_response_timeout {
+            set_redis_response_timeout(redis_response_timeout);
+        }
+        self.apply_log_level();
+        Ok(())
+    }
     pub fn key_tree_width(&self) -> Pixels {
         self.key_tree_width
     }
@@ -288,19 +426,68 @@ impl ZedisAppState {
     pub fn set_key_tree_width(&mut self, width: Pixels) {
         self.key_tree_width = width;
     }
-    pub fn route(&self) -> Route {
-        self.route
+    /// Route of the window, windows that were never routed use the last saved route
+    pub fn route(&self, window_id: WindowId) -> Route {
+        self.windows.get(&window_id).map_or(self.route, |window| window.route)
     }
     pub fn bounds(&self) -> Option<&Bounds<Pixels>> {
         self.bounds.as_ref()
     }
-    pub fn go_to(&mut self, route: Route, cx: &mut Context<Self>) {
-        if self.route != route {
-            self.route = route;
-            cx.emit(GlobalEvent::RouteChanged(route));
+    pub fn go_to(&mut self, window_id: WindowId, route: Route, cx: &mut Context<Self>) {
+        if self.route(window_id) != route {
+            self.window_mut(window_id).route = route;
+            // The saved route is the one restored by the primary window at the next launch
+            if self.is_primary_window(window_id) {
+                self.route = route;
+            }
+            self.sync_other_windows();
+            cx.emit(GlobalEvent::RouteChanged(window_id, route));
             cx.notify();
         }
     }
+    /// State of the window, created from the saved route and server if missing
+    fn window_mut(&mut self, window_id: WindowId) -> &mut WindowState {
+        let route = self.route;
+        let selected_server = self.selected_server.clone();
+        self.windows.entry(window_id).or_insert_with(|| WindowState {
+            route,
+            selected_server,
+            ..Default::default()
+        })
+    }
+    /// Whether the window restores the saved route, server and bounds at launch
+    fn is_primary_window(&self, window_id: WindowId) -> bool {
+        self.primary_window.is_none_or(|primary| primary == window_id)
+    }
+    /// Register a newly opened window, the primary window restores the saved
+    /// route and server while other windows restore `restored` or start on the home page
+    pub fn add_window(&mut self, window_id: WindowId, primary: bool, restored: Option<WindowState>) {
+        if primary {
+            self.primary_window = Some(window_id);
+            self.window_mut(window_id);
+        } else {
+            self.windows.insert(window_id, restored.unwrap_or_default());
+        }
+        self.sync_other_windows();
+    }
+    /// Forget the route and selected server of a closed window
+    pub fn remove_window(&mut self, window_id: WindowId) {
+        self.windows.remove(&window_id);
+        self.sync_other_windows();
+    }
+    /// Take the windows saved at the last session, to be reopened at launch
+    pub fn take_other_windows(&mut self) -> Vec<WindowState> {
+        std::mem::take(&mut self.other_windows)
+    }
+    /// Mirror the state of the non primary windows into the persisted list
+    fn sync_other_windows(&mut self) {
+        self.other_windows = self
+            .windows
+            .iter()
+            .filter(|(window_id, _)| Some(**window_id) != self.primary_window)
+            .map(|(_, window)| window.clone())
+            .collect();
+    }
     pub fn font_size(&self) -> FontSize {
         self.font_size.unwrap_or(FontSize::Medium)
     }
@@ -340,8 +527,13 @@ impl ZedisAppState {
         self.locale.as_deref().unwrap_or("en")
     }
 
-    pub fn set_bounds(&mut self, bounds: Bounds<Pixels>) {
-        self.bounds = Some(bounds);
+    /// Bounds of the window, those of the primary window are also used for new windows
+    pub fn set_bounds(&mut self, window_id: WindowId, bounds: Bounds<Pixels>) {
+        if self.is_primary_window(window_id) {
+            self.bounds = Some(bounds);
+        }
+        self.window_mut(window_id).bounds = Some(bounds);
+        self.sync_other_windows();
     }
     pub fn set_theme(&mut self, theme: Option<ThemeMode>) {
         match theme {
@@ -369,6 +561,41 @@ impl ZedisAppState {
         }
         self.key_separator = Some(key_separator);
     }
+    /// Pattern the "View as" choice of `key` is remembered for: its parent
+    /// folder followed by `*`, or the key itself when it has no separator.
+    fn view_mode_pattern(key: &str, separator: &str) -> String {
+        match key.rsplit_once(separator) {
+            Some((parent, _)) => format!("{parent}{separator}*"),
+            None => key.to_string(),
+        }
+    }
+    /// Remembered view mode of the key, the longest matching pattern wins.
+    pub fn view_mode(&self, key: &str) -> ViewMode {
+        let Some(view_modes) = &self.view_modes else {
+            return ViewMode::Auto;
+        };
+        view_modes
+            .iter()
+            .filter(|(pattern, _)| match pattern.strip_suffix('*') {
+                Some(prefix) => key.starts_with(prefix),
+                None => key == pattern.as_str(),
+            })
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, mode)| ViewMode::from_str(mode))
+            .unwrap_or_default()
+    }
+    /// Remember the view mode for the keys sharing the pattern of `key`, `Auto` forgets it.
+    ///
+    /// `separator` is the key separator of the server the key belongs to.
+    pub fn set_view_mode(&mut self, key: &str, separator: &str, view_mode: ViewMode) {
+        let pattern = Self::view_mode_pattern(key, separator);
+        let view_modes = self.view_modes.get_or_insert_default();
+        if view_mode == ViewMode::Auto {
+            view_modes.remove(&pattern);
+        } else {
+            view_modes.insert(pattern, view_mode.as_str().to_string());
+        }
+    }
     pub fn max_truncate_length(&self) -> usize {
         self.max_truncate_length.unwrap_or(1000)
     }
@@ -385,6 +612,75 @@ impl ZedisAppState {
             .map(|timeout| timeout.as_secs().to_string())
             .unwrap_or_default()
     }
+    /// Whether saving a string value should abort if the key was changed by another client
+    pub fn optimistic_save(&self) -> bool {
+        self.optimistic_save.unwrap_or(false)
+    }
+    pub fn set_optimistic_save(&mut self, optimistic_save: bool) {
+        self.optimistic_save = Some(optimistic_save);
+    }
+    /// Whether the console runs commands like FLUSHALL without asking first
+    pub fn allow_dangerous_commands(&self) -> bool {
+        self.allow_dangerous_commands.unwrap_or(false)
+    }
+    pub fn set_allow_dangerous_commands(&mut self, allow_dangerous_commands: bool) {
+        self.allow_dangerous_commands = Some(allow_dangerous_commands);
+    }
+    /// Notifications of the session, oldest first
+    pub fn notification_history(&self) -> &VecDeque<NotificationRecord> {
+        &self.notification_history
+    }
+    /// Keep the notification in the history, dropping the oldest one beyond `MAX_NOTIFICATION_HISTORY`
+    pub fn add_notification_record(&mut self, notification: NotificationAction) {
+        if self.notification_history.len() >= MAX_NOTIFICATION_HISTORY {
+            self.notification_history.pop_front();
+        }
+        self.notification_history.push_back(NotificationRecord {
+            time: Local::now().format("%H:%M:%S").to_string().into(),
+            notification,
+        });
+    }
+    pub fn clear_notification_history(&mut self) {
+        self.notification_history.clear();
+    }
+    /// Whether removing a hash field, a member or a list item asks for confirmation first
+    pub fn confirm_remove_value(&self) -> bool {
+        self.confirm_remove_value.unwrap_or(true)
+    }
+    pub fn set_confirm_remove_value(&mut self, confirm_remove_value: bool) {
+        self.confirm_remove_value = Some(confirm_remove_value);
+    }
+    pub fn set_log_level(&mut self, level: Level) {
+        self.log_level = Some(level.to_string());
+        set_log_level(level);
+    }
+    fn apply_log_level(&self) {
+        if let Some(level) = self.log_level.as_deref().and_then(|level| level.parse::<Level>().ok()) {
+            set_log_level(level);
+        }
+    }
+    /// Strings larger than this (in bytes) are loaded partially, stored in KB
+    pub fn large_value_threshold(&self) -> usize {
+        self.large_value_threshold.unwrap_or(1024) * 1024
+    }
+    pub fn large_value_threshold_kb(&self) -> usize {
+        self.large_value_threshold.unwrap_or(1024)
+    }
+    pub fn set_large_value_threshold(&mut self, large_value_threshold: usize) {
+        self.large_value_threshold = Some(large_value_threshold);
+    }
+    pub fn large_collection_threshold(&self) -> usize {
+        self.large_collection_threshold.unwrap_or(10_000)
+    }
+    pub fn set_large_collection_threshold(&mut self, large_collection_threshold: usize) {
+        self.large_collection_threshold = Some(large_collection_threshold);
+    }
+    pub fn ttl_warning_threshold(&self) -> u64 {
+        self.ttl_warning_threshold.unwrap_or(60)
+    }
+    pub fn set_ttl_warning_threshold(&mut self, ttl_warning_threshold: u64) {
+        self.ttl_warning_threshold = Some(ttl_warning_threshold);
+    }
     pub fn key_scan_count(&self) -> usize {
         self.key_scan_count.unwrap_or(10_000)
     }
@@ -397,28 +693,92 @@ impl ZedisAppState {
     pub fn set_auto_expand_threshold(&mut self, auto_expand_threshold: usize) {
         self.auto_expand_threshold = Some(auto_expand_threshold);
     }
-    pub fn selected_server(&self) -> Option<&(String, usize)> {
-        self.selected_server.as_ref()
+    pub fn selected_server(&self, window_id: WindowId) -> Option<&(String, usize)> {
+        match self.windows.get(&window_id) {
+            Some(window) => window.selected_server.as_ref(),
+            None => self.selected_server.as_ref(),
+        }
     }
-    pub fn set_selected_server(&mut self, selected_server: (String, usize), cx: &mut Context<Self>) {
+    pub fn set_selected_server(
+        &mut self,
+        window_id: WindowId,
+        selected_server: (String, usize),
+        cx: &mut Context<Self>,
+    ) {
         let (server_id, db) = selected_server.clone();
-        cx.emit(GlobalEvent::ServerSelected(server_id.into(), db));
-        self.selected_server = Some(selected_server);
+        cx.emit(GlobalEvent::ServerSelected(window_id, server_id.into(), db));
+        self.window_mut(window_id).selected_server = Some(selected_server.clone());
+        if self.is_primary_window(window_id) {
+            self.selected_server = Some(selected_server);
+        }
+        self.sync_other_windows();
     }
+    /// Remove the server, closing its connections. Windows browsing it go back to the home page.
     pub fn remove_server(&mut self, id: &str, cx: &mut Context<Self>) {
         let id = id.to_string();
         cx.spawn(async move |handle, cx| {
+            let server_id = id.clone();
             let task = cx.background_spawn(async move {
                 let mut servers = get_servers()?;
                 servers.retain(|s| s.id != id);
                 save_servers(servers.clone()).await?;
+                get_connection_manager().disconnect(&id);
                 Ok(())
             });
             let result: Result<()> = task.await;
             if let Err(e) = &result {
                 error!(error = %e, "Failed to remove server");
             }
+            handle.update(cx, |this, cx| {
+                let window_ids: Vec<WindowId> = this
+                    .windows
+                    .iter()
+                    .filter(|(_, window)| window.selected_server.as_ref().is_some_and(|(id, _)| *id == server_id))
+                    .map(|(window_id, _)| *window_id)
+                    .collect();
+                for window_id in window_ids {
+                    this.go_to(window_id, Route::Home, cx);
+                    this.set_selected_server(window_id, (String::new(), 0), cx);
+                }
+                if this.selected_server.as_ref().is_some_and(|(id, _)| *id == server_id) {
+                    this.selected_server = None;
+                }
+                cx.emit(GlobalEvent::ServerListUpdated);
+                cx.notify();
+            })
+        })
+        .detach();
+    }
+    /// Move the server to the position of `target_id`, only the display order changes.
+    pub fn move_server(&mut self, id: &str, target_id: &str, cx: &mut Context<Self>) {
+        if id == target_id {
+            return;
+        }
+        let id = id.to_string();
+        let target_id = target_id.to_string();
+        cx.spawn(async move |handle, cx| {
+            let task = cx.background_spawn(async move {
+                let mut servers = get_servers()?;
+                let (Some(from), Some(to)) = (
+                    servers.iter().position(|s| s.id == id),
+                    servers.iter().position(|s| s.id == target_id),
+                ) else {
+                    return Ok(());
+                };
+                let server = servers.remove(from);
+                servers.insert(to, server);
+                save_servers(servers).await?;
+                Ok(())
+            });
+            let result: Result<()> = task.await;
             handle.update(cx, |_this, cx| {
+                if let Err(e) = &result {
+                    error!(error = %e, "Failed to move server");
+                    cx.emit(GlobalEvent::Notification(NotificationAction::new_error(
+                        e.to_string().into(),
+                    )));
+                    return;
+                }
                 cx.emit(GlobalEvent::ServerListUpdated);
                 cx.notify();
             })
@@ -438,15 +798,24 @@ impl ZedisAppState {
                     });
                 }
                 let mut servers = get_servers()?;
-                if let Some(existing_server) = servers.iter_mut().find(|s| s.id == server.id) {
+                let server_id = server.id.clone();
+                let updated = if let Some(existing_server) = servers.iter_mut().find(|s| s.id == server.id) {
                     *existing_server = server;
+                    true
                 } else {
                     servers.push(server);
-                }
+                    false
+                };
                 save_servers(servers.clone()).await?;
-                Ok(())
+                // Cached clients keep the old settings, the next selection reconnects with the new ones.
+                // The id doesn't change on edit, so favorites and history of the server are kept.
+                if !updated {
+                    return Ok(None);
+                }
+                get_connection_manager().disconnect(&server_id);
+                Ok(Some(server_id))
             });
-            let result: Result<()> = task.await;
+            let result: Result<Option<String>> = task.await;
 
             handle.update(cx, |_this, cx| {
                 if let Err(e) = &result {
@@ -456,12 +825,96 @@ impl ZedisAppState {
                     )));
                     return;
                 }
+                if let Ok(Some(server_id)) = result {
+                    cx.emit(GlobalEvent::ServerUpdated(server_id.into()));
+                }
                 cx.emit(GlobalEvent::ServerListUpdated);
                 cx.notify();
             })
         })
         .detach();
     }
+    /// Export servers, preferences, favorites and keymap to a JSON file.
+    pub fn export_settings(&mut self, path: PathBuf, encrypt_secrets: bool, cx: &mut Context<Self>) {
+        let preferences = match self.export_preferences() {
+            Ok(preferences) => preferences,
+            Err(e) => {
+                cx.emit(GlobalEvent::Notification(NotificationAction::new_error(
+                    e.to_string().into(),
+                )));
+                return;
+            }
+        };
+        cx.spawn(async move |handle, cx| {
+            let task = cx.background_spawn(async move { export_settings(&path, preferences, encrypt_secrets) });
+            let result: Result<()> = task.await;
+            handle.update(cx, |_this, cx| {
+                let action = match result {
+                    Ok(()) => NotificationAction::new_success(i18n_settings(cx, "export_settings_success")),
+                    Err(e) => {
+                        error!(error = %e, "Failed to export settings");
+                        NotificationAction::new_error(e.to_string().into())
+                    }
+                };
+                cx.emit(GlobalEvent::Notification(action));
+            })
+        })
+        .detach();
+    }
+    /// Import a JSON file created by `export_settings`, then apply and persist the preferences.
+    pub fn import_settings(&mut self, path: PathBuf, mode: ImportMode, cx: &mut Context<Self>) {
+        cx.spawn(async move |handle, cx| {
+            let task = cx.background_spawn(async move { import_settings(&path, mode).await });
+            let result: Result<ImportedSettings> = task.await;
+            let state = handle.update(cx, |this, cx| {
+                let imported = match result {
+                    Ok(imported) => imported,
+                    Err(e) => {
+                        error!(error = %e, "Failed to import settings");
+                        cx.emit(GlobalEvent::Notification(NotificationAction::new_error(
+                            e.to_string().into(),
+                        )));
+                        return None;
+                    }
+                };
+                if let Err(e) = this.apply_preferences(imported.preferences, mode == ImportMode::Replace) {
+                    cx.emit(GlobalEvent::Notification(NotificationAction::new_error(
+                        e.to_string().into(),
+                    )));
+                    return None;
+                }
+                match this.theme() {
+                    Some(theme) => Theme::change(theme, None, cx),
+                    None => Theme::change(cx.window_appearance(), None, cx),
+                }
+                cx.clear_key_bindings();
+                cx.bind_keys(new_hot_keys());
+                let message = t!(
+                    "settings.import_settings_success",
+                    servers = imported.servers,
+                    favorites = imported.favorites,
+                    locale = this.locale()
+                )
+                .to_string();
+                cx.emit(GlobalEvent::ServerListUpdated);
+                cx.emit(GlobalEvent::Notification(NotificationAction::new_success(
+                    message.into(),
+                )));
+                cx.refresh_windows();
+                Some(this.clone())
+            });
+            let Ok(Some(state)) = state else {
+                return;
+            };
+            cx.background_spawn(async move {
+                if let Err(e) = save_app_state(&state) {
+                    error!(error = %e, "Failed to save imported state");
+                }
+            })
+            .await;
+        })
+        .detach();
+    }
 }
 
 /// Update app state in background, persist to disk, and refresh UI
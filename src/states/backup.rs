@@ -0,0 +1,282 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Export and import of the whole Zedis configuration as a single JSON file.
+//!
+//! The file contains the servers, the portable preferences, the favorites and
+//! the keymap overrides. Secrets (passwords, SSH credentials and TLS client key)
+//! are either removed (`redacted`) or encrypted with the same built-in key as the
+//! local config (`encrypted`). The built-in key ships with every Zedis build, so
+//! an encrypted export must still be handled as a file containing passwords.
+
+use crate::{
+    connection::{RedisServer, get_servers, save_servers},
+    db::get_favorites_manager,
+    error::Error,
+    helpers::{decrypt, encrypt, read_keymap_file, save_keymap_file},
+    states::ZedisAppState,
+};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+const SETTINGS_FORMAT: &str = "zedis-settings";
+/// Version of the export format, bump it and extend `migrate` when the layout changes.
+const SETTINGS_VERSION: u32 = 1;
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const SECRETS_REDACTED: &str = "redacted";
+pub const SECRETS_ENCRYPTED: &str = "encrypted";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportMode {
+    /// Add imported items and override the existing ones with the same id
+    Merge,
+    /// Drop the existing items first
+    Replace,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SettingsBundle {
+    format: String,
+    version: u32,
+    app_version: String,
+    exported_at: String,
+    /// How the secrets of the servers are stored, `redacted` or `encrypted`
+    secrets: String,
+    servers: Vec<RedisServer>,
+    preferences: serde_json::Value,
+    /// Favorite keys of each server id
+    favorites: HashMap<String, Vec<String>>,
+    keymap: HashMap<String, String>,
+}
+
+/// Result of an import, the preferences are applied by the caller on the app state.
+#[derive(Debug, Default)]
+pub struct ImportedSettings {
+    pub servers: usize,
+    pub favorites: usize,
+    pub preferences: serde_json::Value,
+}
+
+/// Apply `f` to every secret of the server
+fn map_secrets(server: &mut RedisServer, f: impl Fn(&str) -> Option<String>) {
+    for secret in [
+        &mut server.password,
+        &mut server.ssh_password,
+        &mut server.ssh_key,
+        &mut server.client_key,
+    ] {
+        *secret = secret.as_deref().and_then(&f);
+    }
+}
+
+/// Export the configuration to `path`, see the module doc for how secrets are handled.
+pub fn export_settings(path: &Path, preferences: serde_json::Value, encrypt_secrets: bool) -> Result<()> {
+    let mut servers = get_servers()?;
+    let mut favorites = HashMap::new();
+    for server in servers.iter_mut() {
+        if encrypt_secrets {
+            map_secrets(server, |secret| encrypt(secret).ok());
+        } else {
+            map_secrets(server, |_| None);
+        }
+        let records = get_favorites_manager().records(&server.id)?;
+        if !records.is_empty() {
+            favorites.insert(server.id.clone(), records.iter().map(|s| s.to_string()).collect());
+        }
+    }
+    let bundle = SettingsBundle {
+        format: SETTINGS_FORMAT.to_string(),
+        version: SETTINGS_VERSION,
+        app_version: VERSION.to_string(),
+        exported_at: Local::now().to_rfc3339(),
+        secrets: if encrypt_secrets {
+            SECRETS_ENCRYPTED
+        } else {
+            SECRETS_REDACTED
+        }
+        .to_string(),
+        servers,
+        preferences,
+        favorites,
+        keymap: read_keymap_file()?,
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&bundle)?)?;
+    Ok(())
+}
+
+/// Upgrade an older export to the current layout.
+///
+/// Version 0 is a bare `{ "servers": [...] }` document (the server list of
+/// `redis-servers.toml` converted to JSON), its secrets may be plain or encrypted.
+/// Any other document without the `zedis-settings` format is rejected, so an
+/// unrelated JSON file is not imported as an empty configuration.
+fn migrate(mut bundle: SettingsBundle) -> Result<SettingsBundle> {
+    let legacy = bundle.version == 0 && bundle.format.is_empty() && !bundle.servers.is_empty();
+    if bundle.format != SETTINGS_FORMAT && !legacy {
+        return Err(Error::Invalid {
+            message: if bundle.format.is_empty() {
+                "Not a Zedis settings file".to_string()
+            } else {
+                format!("Unsupported settings format: {}", bundle.format)
+            },
+        });
+    }
+    if bundle.version > SETTINGS_VERSION {
+        return Err(Error::Invalid {
+            message: format!(
+                "Settings version {} was exported by a newer Zedis ({}), please upgrade",
+                bundle.version, bundle.app_version
+            ),
+        });
+    }
+    if bundle.version == 0 {
+        bundle.format = SETTINGS_FORMAT.to_string();
+        bundle.secrets = SECRETS_ENCRYPTED.to_string();
+        bundle.version = 1;
+    }
+    validate(&bundle)?;
+    Ok(bundle)
+}
+
+/// Check the whole bundle, nothing is written unless every part can be imported.
+fn validate(bundle: &SettingsBundle) -> Result<()> {
+    for (index, server) in bundle.servers.iter().enumerate() {
+        if server.id.is_empty() || server.host.is_empty() || server.port == 0 {
+            return Err(Error::Invalid {
+                message: format!("Server #{} is missing its id, host or port", index + 1),
+            });
+        }
+        if bundle.servers[..index].iter().any(|item| item.id == server.id) {
+            return Err(Error::Invalid {
+                message: format!("Server #{} has the same id as another server: {}", index + 1, server.id),
+            });
+        }
+    }
+    if !bundle.preferences.is_object() && !bundle.preferences.is_null() {
+        return Err(Error::Invalid {
+            message: "Preferences must be a JSON object".to_string(),
+        });
+    }
+    // Applied by the caller after the import, a wrong field must fail before anything is written
+    if bundle.preferences.is_object()
+        && let Err(e) = serde_json::from_value::<ZedisAppState>(bundle.preferences.clone())
+    {
+        return Err(Error::Invalid {
+            message: format!("Invalid preferences: {e}"),
+        });
+    }
+    if bundle.favorites.keys().any(|server_id| server_id.is_empty()) {
+        return Err(Error::Invalid {
+            message: "Favorites are missing their server id".to_string(),
+        });
+    }
+    if let Some((action, _)) = bundle
+        .keymap
+        .iter()
+        .find(|(action, keystrokes)| action.is_empty() || keystrokes.is_empty())
+    {
+        return Err(Error::Invalid {
+            message: format!("Keymap entry is missing its action or keystrokes: {action}"),
+        });
+    }
+    Ok(())
+}
+
+/// Import the configuration from `path`.
+///
+/// Servers exported with redacted secrets keep the secrets of the local server
+/// with the same id, so a round trip between machines doesn't wipe passwords.
+/// The bundle and the local configuration are fully read before the first write,
+/// a broken file leaves the local configuration untouched.
+pub async fn import_settings(path: &Path, mode: ImportMode) -> Result<ImportedSettings> {
+    let content = std::fs::read_to_string(path)?;
+    let bundle: SettingsBundle = serde_json::from_str(&content)?;
+    let bundle = migrate(bundle)?;
+
+    let existing = get_servers()?;
+    let mut imported_servers = bundle.servers;
+    for server in imported_servers.iter_mut() {
+        // Encrypted values are decrypted, plain values (older exports) are kept as is
+        map_secrets(server, |secret| Some(decrypt(secret).unwrap_or(secret.to_string())));
+        if let Some(local) = existing.iter().find(|item| item.id == server.id) {
+            server.password = server.password.take().or(local.password.clone());
+            server.ssh_password = server.ssh_password.take().or(local.ssh_password.clone());
+            server.ssh_key = server.ssh_key.take().or(local.ssh_key.clone());
+            server.client_key = server.client_key.take().or(local.client_key.clone());
+        }
+    }
+    let server_count = imported_servers.len();
+    let servers = match mode {
+        ImportMode::Replace => imported_servers,
+        ImportMode::Merge => {
+            let mut servers: Vec<RedisServer> = existing
+                .into_iter()
+                .filter(|server| !imported_servers.iter().any(|item| item.id == server.id))
+                .collect();
+            servers.extend(imported_servers);
+            servers
+        }
+    };
+
+    let favorites_manager = get_favorites_manager();
+    // Favorite keys to add for each server, from the oldest as records are prepended
+    let mut favorites = Vec::with_capacity(bundle.favorites.len());
+    for (server_id, keys) in bundle.favorites {
+        let keys: Vec<String> = match mode {
+            ImportMode::Replace => keys.into_iter().rev().collect(),
+            ImportMode::Merge => {
+                let current = favorites_manager.records(&server_id)?;
+                keys.into_iter()
+                    .rev()
+                    .filter(|key| !current.iter().any(|item| item == key))
+                    .collect()
+            }
+        };
+        favorites.push((server_id, keys));
+    }
+
+    let keymap = match mode {
+        ImportMode::Replace => bundle.keymap,
+        ImportMode::Merge => {
+            let mut keymap = read_keymap_file()?;
+            keymap.extend(bundle.keymap);
+            keymap
+        }
+    };
+
+    save_servers(servers).await?;
+    let mut favorite_count = 0;
+    for (server_id, keys) in favorites {
+        if mode == ImportMode::Replace {
+            favorites_manager.clear_history(&server_id)?;
+        }
+        for key in &keys {
+            favorites_manager.add_record(&server_id, key)?;
+            favorite_count += 1;
+        }
+    }
+    save_keymap_file(&keymap)?;
+
+    Ok(ImportedSettings {
+        servers: server_count,
+        favorites: favorite_count,
+        preferences: bundle.preferences,
+    })
+}
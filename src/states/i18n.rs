@@ -77,6 +77,11 @@ pub fn i18n_settings<'a>(cx: &'a App, key: &'a str) -> SharedString {
     t!(format!("settings.{key}"), locale = locale).into()
 }
 
+pub fn i18n_log_viewer<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("log_viewer.{key}"), locale = locale).into()
+}
+
 pub fn i18n_metrics<'a>(cx: &'a App, key: &'a str) -> SharedString {
     let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
     t!(format!("metrics.{key}"), locale = locale).into()
@@ -91,3 +96,33 @@ pub fn i18n_pubsub_editor<'a>(cx: &'a App, key: &'a str) -> SharedString {
     let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
     t!(format!("pubsub_editor.{key}"), locale = locale).into()
 }
+
+pub fn i18n_config_editor<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("config_editor.{key}"), locale = locale).into()
+}
+
+pub fn i18n_search_editor<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("search_editor.{key}"), locale = locale).into()
+}
+
+pub fn i18n_shortcuts<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("shortcuts.{key}"), locale = locale).into()
+}
+
+pub fn i18n_command_palette<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("command_palette.{key}"), locale = locale).into()
+}
+
+pub fn i18n_monitor<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("monitor.{key}"), locale = locale).into()
+}
+
+pub fn i18n_notification_center<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("notification_center.{key}"), locale = locale).into()
+}
@@ -12,28 +12,40 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::connection::{AccessMode, RedisClientDescription, SlowLogEntry, get_connection_manager};
+use crate::connection::{
+    AccessMode, Capability, DEFAULT_SCAN_COUNT, MAX_SCAN_COUNT, RedisClientDescription, SlowLogEntry,
+    get_connection_manager, get_server, get_servers, save_servers,
+};
 use crate::db::get_search_history_manager;
 use crate::error::Error;
 use crate::states::server::event::{ServerEvent, ServerTask};
 use crate::states::server::stat::{RedisInfo, get_metrics_cache};
-use crate::states::{QueryMode, get_session_option};
+use crate::states::{QueryMode, ZedisGlobalStore, get_session_option, save_session_option};
 use ahash::AHashMap;
 use ahash::AHashSet;
-use gpui::SharedString;
+use bytes::Bytes;
 use gpui::prelude::*;
+use gpui::{App, SharedString};
 use parking_lot::RwLock;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use tracing::Instrument;
 use tracing::debug;
 use tracing::error;
+use tracing::info_span;
 use uuid::Uuid;
-use value::{KeyType, RedisValue, RedisValueData};
+use value::{KeyMetadata, KeyType, RedisValue, RedisValueData};
 
+pub mod config;
 pub mod event;
+pub mod favorite;
 pub mod hash;
 pub mod key;
+pub mod key_event;
 pub mod list;
+pub mod recent;
+pub mod search;
 pub mod set;
 pub mod stat;
 pub mod stream;
@@ -66,6 +78,18 @@ pub enum RedisServerStatus {
     Loading,
 }
 
+/// Health of the connection, updated by the heartbeat PING
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub enum ConnectionStatus {
+    /// The last PING succeeded
+    #[default]
+    Connected,
+    /// PING failed, the client is recreated with an exponential backoff
+    Reconnecting,
+    /// Reconnecting failed too many times, still retried at the max backoff
+    Disconnected,
+}
+
 /// Main state management for Redis server operations
 ///
 /// This struct manages:
@@ -89,6 +113,18 @@ pub struct ZedisServerState {
     /// Search history
     search_history: Vec<SharedString>,
 
+    /// Pinned keys of the server
+    favorites: Vec<SharedString>,
+
+    /// Pinned keys found missing by `check_favorites`
+    missing_favorites: AHashSet<SharedString>,
+
+    /// Recently viewed keys, the last opened first
+    recent_keys: Vec<SharedString>,
+
+    /// Separator of the key namespaces, overrides the global setting
+    key_separator: Option<SharedString>,
+
     /// Whether the server supports database selection
     supports_db_selection: bool,
 
@@ -101,12 +137,30 @@ pub struct ZedisServerState {
     /// Query mode (All/Prefix/Exact) for key filtering
     query_mode: QueryMode,
 
+    /// Only show keys of this type in the key tree (None shows all types)
+    key_type_filter: Option<KeyType>,
+
     /// Whether to soft wrap the editor
     soft_wrap: bool,
 
+    /// Whether to show indent guides in the editor
+    indent_guides: bool,
+
+    /// Cursor of the value editor, 0-based line and column
+    editor_cursor: Option<(u32, u32)>,
+
     /// Current server status
     server_status: RedisServerStatus,
 
+    /// Health of the connection, see `refresh_redis_info`
+    connection_status: ConnectionStatus,
+
+    /// Number of consecutive failed heartbeats
+    ping_failures: u32,
+
+    /// Unix timestamp before which heartbeats are skipped (reconnect backoff)
+    next_ping_at: i64,
+
     /// Total number of keys in the database (from DBSIZE command)
     dbsize: Option<u64>,
 
@@ -117,6 +171,10 @@ pub struct ZedisServerState {
 
     /// Redis server version string
     version: SharedString,
+    /// Redis version the server is compatible with, differs from `version` on Valkey
+    redis_version: SharedString,
+    /// Modules loaded on the server, e.g. `ReJSON`
+    modules: Vec<String>,
 
     /// List of all configured servers
     // servers: Option<Vec<RedisServer>>,
@@ -127,6 +185,12 @@ pub struct ZedisServerState {
     /// Value data for the currently selected key
     value: Option<RedisValue>,
 
+    /// Internal details (encoding, idle time...) of the selected key
+    key_metadata: Option<KeyMetadata>,
+
+    /// Key and raw bytes before the last successful string save, used for one-shot undo
+    last_saved: Option<(SharedString, Bytes)>,
+
     // ===== Key scanning state =====
     /// Search keyword for filtering keys
     keyword: SharedString,
@@ -140,6 +204,9 @@ pub struct ZedisServerState {
     /// Whether the current scan has completed
     scan_completed: bool,
 
+    /// Set by `cancel_scan`, stops the running scan after its current SCAN call
+    scan_cancelled: bool,
+
     /// Number of scan iterations performed
     scan_times: usize,
 
@@ -152,6 +219,39 @@ pub struct ZedisServerState {
     /// Map of all loaded keys and their types
     keys: AHashMap<SharedString, KeyType>,
 
+    /// Estimated memory usage of folders, `None` while it is being loaded
+    folder_memory: AHashMap<SharedString, Option<u64>>,
+
+    /// MEMORY USAGE of keys, `None` when the command failed for the key
+    key_memory: AHashMap<SharedString, Option<u64>>,
+
+    /// Keys whose MEMORY USAGE is being loaded
+    key_memory_loading: AHashSet<SharedString>,
+
+    /// Expiration of keys as unix seconds, -1 without expiry
+    key_expire_at: AHashMap<SharedString, i64>,
+
+    /// Keys whose TTL is being loaded
+    key_expire_at_loading: AHashSet<SharedString>,
+
+    /// Keys waiting for a throttled TYPE lookup, see `drain_key_types`
+    pending_key_types: AHashSet<SharedString>,
+
+    /// Whether a batch of TYPE lookups is in progress
+    key_types_running: bool,
+
+    /// Bumped by `reset_scan`, background results of an older scan are dropped
+    scan_generation: u64,
+
+    /// Number of value loads in progress, shared with the TYPE lookup tasks
+    value_loading: Arc<AtomicUsize>,
+
+    /// Cancel flag and number of written keys of the running prefix export
+    prefix_export: Option<(Arc<AtomicBool>, Arc<AtomicUsize>)>,
+
+    /// Set while the key events are watched, dropping it stops the watcher
+    key_events_stop: Option<smol::channel::Sender<()>>,
+
     // ===== Error tracking =====
     /// Recent error messages (limited to MAX_ERROR_MESSAGES)
     error_messages: Arc<RwLock<Vec<ErrorMessage>>>,
@@ -173,14 +273,25 @@ impl ZedisServerState {
         self.key_tree_id = Uuid::now_v7().to_string().into();
         self.scanning = false;
         self.scan_completed = false;
+        self.scan_cancelled = false;
         self.scan_times = 0;
         self.loaded_prefixes.clear();
+        self.folder_memory.clear();
+        self.key_memory.clear();
+        self.key_memory_loading.clear();
+        self.key_expire_at.clear();
+        self.key_expire_at_loading.clear();
+        self.pending_key_types.clear();
+        self.key_types_running = false;
+        self.scan_generation += 1;
     }
 
     /// Reset all state when switching to a different server
     fn reset(&mut self) {
         self.server_id = SharedString::default();
         self.version = SharedString::default();
+        self.redis_version = SharedString::default();
+        self.modules.clear();
         self.nodes = (0, 0);
         self.keys.clear();
         self.key_tree_id = SharedString::default();
@@ -189,22 +300,42 @@ impl ZedisServerState {
         self.key = None;
         self.redis_info = None;
         self.value = None;
+        self.key_metadata = None;
+        self.last_saved = None;
+        self.key_type_filter = None;
+        self.connection_status = ConnectionStatus::Connected;
+        self.ping_failures = 0;
+        self.next_ping_at = 0;
         self.reset_scan();
+        self.favorites.clear();
+        self.missing_favorites.clear();
+        self.recent_keys.clear();
+        self.key_separator = None;
         self.terminal = false;
+        // The export belongs to the previous server
+        self.cancel_prefix_export();
+        self.prefix_export = None;
+        self.stop_watch_key_events();
     }
 
     /// Add new keys to the key map (deduplicating automatically)
     ///
+    /// `key_type` is known when the scan was filtered server-side (`SCAN ... TYPE`),
+    /// otherwise it is `KeyType::Unknown` and resolved later.
     /// If any new keys were added, generates a new tree ID to trigger UI refresh
-    fn extend_keys(&mut self, keys: Vec<SharedString>) {
+    fn extend_keys(&mut self, keys: Vec<SharedString>, key_type: KeyType) {
         self.keys.reserve(keys.len());
         let mut insert_count = 0;
 
         for key in keys {
-            self.keys.entry(key).or_insert_with(|| {
+            let value = self.keys.entry(key).or_insert_with(|| {
                 insert_count += 1;
-                KeyType::Unknown
+                key_type
             });
+            if *value == KeyType::Unknown && key_type != KeyType::Unknown {
+                *value = key_type;
+                insert_count += 1;
+            }
         }
 
         // Update tree ID only if new keys were added
@@ -260,19 +391,25 @@ impl ZedisServerState {
         Fut: Future<Output = Result<T>> + Send + 'static,
     {
         cx.emit(ServerEvent::TaskStarted(name.clone()));
-        debug!(name = name.as_str(), "Spawning background task");
+        debug!(
+            server_id = self.server_id.as_str(),
+            name = name.as_str(),
+            "Spawning background task"
+        );
         let server_id = self.server_id.clone();
 
+        // Events logged by the task, e.g. by the connection manager, are filterable by server
+        let span = info_span!("task", server_id = server_id.as_str(), name = name.as_str());
         cx.spawn(async move |handle, cx| {
             // Run task in background executor (thread pool)
-            let task = cx.background_spawn(async move { task().await });
+            let task = cx.background_spawn(async move { task().await }.instrument(span));
             let result: Result<T> = task.await;
 
             // Update state with result on main thread
             handle.update(cx, move |this, cx| {
                 if let Err(e) = &result {
                     let message = format!("{} failed", name.as_str());
-                    error!(error = %e, message);
+                    error!(server_id = server_id.as_str(), error = %e, message);
                     // only add error message if the server id is the same as the current server id
                     // ignore refresh redis info error
                     if this.server_id == server_id && name != ServerTask::RefreshRedisInfo {
@@ -324,6 +461,20 @@ impl ZedisServerState {
         self.search_history.clear();
     }
 
+    /// COUNT hint of the HSCAN / SSCAN / ZSCAN calls, configured per server
+    pub fn value_scan_count(&self) -> usize {
+        get_server(&self.server_id)
+            .map(|server| server.scan_count())
+            .unwrap_or(DEFAULT_SCAN_COUNT)
+    }
+    /// COUNT hint while filtering values, larger to reduce round trips
+    fn filter_scan_count(&self) -> usize {
+        (self.value_scan_count() * 10).min(MAX_SCAN_COUNT)
+    }
+    /// Get the health of the connection
+    pub fn connection_status(&self) -> ConnectionStatus {
+        self.connection_status
+    }
     /// Get whether the server is readonly
     pub fn readonly(&self) -> bool {
         matches!(self.access_mode, AccessMode::StrictReadOnly | AccessMode::SafeMode)
@@ -343,6 +494,21 @@ impl ZedisServerState {
             self.access_mode = AccessMode::ReadWrite;
         }
         cx.emit(ServerEvent::ServerInfoUpdated);
+        // Remember the lock in the server config, so it survives reconnects and restarts
+        let server_id = self.server_id.to_string();
+        let readonly = self.readonly();
+        self.spawn(
+            ServerTask::SaveReadonly,
+            move || async move {
+                let mut servers = get_servers()?;
+                if let Some(server) = servers.iter_mut().find(|server| server.id == server_id) {
+                    server.readonly = Some(readonly);
+                }
+                save_servers(servers).await
+            },
+            |_this, _result, _cx| {},
+            cx,
+        );
     }
 
     /// Set the query mode (All/Prefix/Exact)
@@ -354,10 +520,80 @@ impl ZedisServerState {
         self.soft_wrap = soft_wrap;
         cx.emit(ServerEvent::SoftWrapToggled(self.soft_wrap));
     }
+    /// Set whether to show indent guides in the editor
+    pub fn set_indent_guides(&mut self, indent_guides: bool, cx: &mut Context<Self>) {
+        self.indent_guides = indent_guides;
+        cx.emit(ServerEvent::IndentGuidesToggled(self.indent_guides));
+    }
+    /// Set the cursor of the value editor, `None` when no value editor is shown
+    pub fn set_editor_cursor(&mut self, cursor: Option<(u32, u32)>, cx: &mut Context<Self>) {
+        if self.editor_cursor != cursor {
+            self.editor_cursor = cursor;
+            cx.emit(ServerEvent::EditorCursorMoved);
+        }
+    }
+    /// Ask the value editor to move its cursor to the start of `line` (1-based)
+    pub fn go_to_line(&mut self, line: usize, cx: &mut Context<Self>) {
+        cx.emit(ServerEvent::GoToLineRequested(line));
+    }
+    /// Get the separator of the key namespaces, the global setting unless set for the server
+    pub fn key_separator(&self, cx: &App) -> SharedString {
+        self.key_separator.clone().unwrap_or_else(|| {
+            cx.global::<ZedisGlobalStore>()
+                .read(cx)
+                .key_separator()
+                .to_string()
+                .into()
+        })
+    }
+    /// Get the separator set for the server, `None` when it follows the global setting
+    pub fn server_key_separator(&self) -> Option<&SharedString> {
+        self.key_separator.as_ref()
+    }
+    /// Set the separator of the server, an empty one follows the global setting again
+    pub fn set_key_separator(&mut self, separator: SharedString, cx: &mut Context<Self>) {
+        let separator = (!separator.is_empty()).then_some(separator);
+        if self.key_separator == separator {
+            return;
+        }
+        self.key_separator = separator.clone();
+        // The estimates are keyed by the folders of the previous separator
+        self.folder_memory.clear();
+        if let Ok(mut option) = get_session_option(&self.server_id) {
+            option.key_separator = separator.map(|separator| separator.to_string());
+            save_session_option(&self.server_id, option, cx);
+        }
+        cx.emit(ServerEvent::KeySeparatorChanged);
+        cx.notify();
+    }
     /// Get the current query mode (All/Prefix/Exact)
     pub fn query_mode(&self) -> QueryMode {
         self.query_mode
     }
+    /// Get the estimated memory usage of a folder, if it has been loaded
+    pub fn folder_memory(&self, folder: &str) -> Option<u64> {
+        self.folder_memory.get(folder).copied().flatten()
+    }
+    /// Get the memory usage of a key, `Some(None)` when MEMORY USAGE failed for it
+    pub fn key_memory(&self, key: &str) -> Option<Option<u64>> {
+        self.key_memory.get(key).copied()
+    }
+    /// Get the expiration of a key as unix seconds, -1 when it has no expiry
+    pub fn key_expire_at(&self, key: &str) -> Option<i64> {
+        self.key_expire_at.get(key).copied()
+    }
+    /// Get the key type filter of the key tree
+    pub fn key_type_filter(&self) -> Option<KeyType> {
+        self.key_type_filter
+    }
+    /// Set the key type filter of the key tree
+    ///
+    /// Only rebuilds the tree, callers should run `handle_filter` again to rescan.
+    pub fn set_key_type_filter(&mut self, key_type: Option<KeyType>, cx: &mut Context<Self>) {
+        self.key_type_filter = key_type;
+        self.key_tree_id = Uuid::now_v7().to_string().into();
+        cx.notify();
+    }
 
     /// Check if the current scan has completed
     pub fn scan_completed(&self) -> bool {
@@ -369,6 +605,40 @@ impl ZedisServerState {
         self.scanning
     }
 
+    /// Whether the running scan was cancelled and waits for its current SCAN call
+    pub fn scan_cancelled(&self) -> bool {
+        self.scanning && self.scan_cancelled
+    }
+
+    /// Stop the running scan, the keys loaded so far are kept and
+    /// "load more" resumes from the last cursors
+    pub fn cancel_scan(&mut self, cx: &mut Context<Self>) {
+        if !self.scanning {
+            return;
+        }
+        self.scan_cancelled = true;
+        cx.notify();
+    }
+
+    /// Number of keys written by the running prefix export, `None` when no export runs
+    pub fn prefix_export_count(&self) -> Option<usize> {
+        self.prefix_export
+            .as_ref()
+            .map(|(_, exported)| exported.load(Ordering::Relaxed))
+    }
+
+    /// Stop the running prefix export after its current batch
+    pub fn cancel_prefix_export(&mut self) {
+        if let Some((cancelled, _)) = &self.prefix_export {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Get the keyword of the current scan
+    pub fn keyword(&self) -> &SharedString {
+        &self.keyword
+    }
+
     /// Get the total database size (number of keys)
     pub fn dbsize(&self) -> Option<u64> {
         self.dbsize
@@ -402,6 +672,16 @@ impl ZedisServerState {
     pub fn version(&self) -> &str {
         &self.version
     }
+    /// Redis version the server is compatible with, the same as `version` except on Valkey
+    pub fn redis_version(&self) -> &str {
+        &self.redis_version
+    }
+    /// Whether the server version provides the capability, assumed when the version is unknown
+    pub fn supports(&self, capability: Capability) -> bool {
+        semver::Version::parse(&self.redis_version)
+            .ok()
+            .is_none_or(|version| capability.is_supported(&version, &self.modules))
+    }
 
     /// Get the currently selected server id
     pub fn server_id(&self) -> &str {
@@ -422,6 +702,16 @@ impl ZedisServerState {
         self.soft_wrap
     }
 
+    /// Get whether to show indent guides in the editor
+    pub fn indent_guides(&self) -> bool {
+        self.indent_guides
+    }
+
+    /// Get the cursor of the value editor
+    pub fn editor_cursor(&self) -> Option<(u32, u32)> {
+        self.editor_cursor
+    }
+
     /// Get the currently selected key name
     pub fn key(&self) -> Option<SharedString> {
         self.key.clone()
@@ -435,10 +725,28 @@ impl ZedisServerState {
     pub fn value(&self) -> Option<&RedisValue> {
         self.value.as_ref()
     }
+    /// Whether the last save of the selected key can be undone
+    pub fn can_undo_save(&self) -> bool {
+        self.last_saved
+            .as_ref()
+            .is_some_and(|(key, _)| self.key.as_ref() == Some(key))
+    }
+    /// Get the metadata of the selected key, if it has been loaded
+    pub fn key_metadata(&self) -> Option<&KeyMetadata> {
+        self.key_metadata
+            .as_ref()
+            .filter(|metadata| self.key.as_ref() == Some(&metadata.key))
+    }
 
     pub fn set_search_history(&mut self, history: Vec<SharedString>) {
         self.search_history = history;
     }
+    /// Select the current server again, e.g. to load it with its edited settings.
+    pub fn reselect(&mut self, cx: &mut Context<Self>) {
+        let server_id = std::mem::take(&mut self.server_id);
+        let db = self.db;
+        self.select(server_id, db, cx);
+    }
 
     /// Select and connect to a Redis server
     ///
@@ -462,28 +770,36 @@ impl ZedisServerState {
             self.server_id = server_id.clone();
             self.db = db;
 
-            let (query_mode, soft_wrap) = get_session_option(&server_id)
-                .map(|option| {
-                    let mode = option
-                        .query_mode
-                        .as_deref()
-                        .and_then(|s| QueryMode::from_str(s).ok())
-                        .unwrap_or_default();
-
-                    let wrap = option.soft_wrap.unwrap_or(true);
-
-                    // 返回一个元组，包含所有需要更新的值
-                    (mode, wrap)
-                })
-                .unwrap_or((QueryMode::All, true));
+            let (query_mode, soft_wrap, indent_guides, key_separator, watch_key_events) =
+                get_session_option(&server_id)
+                    .map(|option| {
+                        let mode = option
+                            .query_mode
+                            .as_deref()
+                            .and_then(|s| QueryMode::from_str(s).ok())
+                            .unwrap_or_default();
+
+                        let wrap = option.soft_wrap.unwrap_or(true);
+                        let guides = option.indent_guides.unwrap_or(true);
+                        let separator = option.key_separator.filter(|s| !s.is_empty()).map(SharedString::from);
+                        let watch = option.watch_key_events.unwrap_or_default();
+
+                        // 返回一个元组，包含所有需要更新的值
+                        (mode, wrap, guides, separator, watch)
+                    })
+                    .unwrap_or((QueryMode::All, true, true, None, false));
             self.query_mode = query_mode;
+            self.key_separator = key_separator;
             self.soft_wrap = soft_wrap;
+            self.indent_guides = indent_guides;
 
             debug!(server_id = self.server_id.as_str(), "Selecting server");
             let search_history_manager = get_search_history_manager();
             if let Ok(history) = search_history_manager.records(server_id.as_str()) {
                 self.search_history = history;
             }
+            self.load_favorites();
+            self.load_recent_keys();
             cx.emit(ServerEvent::ServerSelected(server_id));
             cx.notify();
 
@@ -508,6 +824,8 @@ impl ZedisServerState {
                     // Gather server metadata
                     let dbsize = client.dbsize().await?;
                     let version = client.version().to_string();
+                    let redis_version = client.redis_version();
+                    let modules = client.modules();
                     let nodes = client.nodes();
                     let nodes_description = client.nodes_description();
                     let supports_db_selection = client.supports_db_selection();
@@ -516,7 +834,7 @@ impl ZedisServerState {
                         dbsize,
                         nodes,
                         nodes_description,
-                        version,
+                        (version, redis_version, modules),
                         supports_db_selection,
                         access_mode,
                     ))
@@ -528,14 +846,28 @@ impl ZedisServerState {
                     }
 
                     // Update metadata if successful
-                    if let Ok((dbsize, nodes, nodes_description, version, supports_db_selection, access_mode)) = result
+                    if let Ok((
+                        dbsize,
+                        nodes,
+                        nodes_description,
+                        (version, redis_version, modules),
+                        supports_db_selection,
+                        access_mode,
+                    )) = result
                     {
                         this.dbsize = Some(dbsize);
                         this.nodes = nodes;
                         this.nodes_description = Arc::new(nodes_description);
                         this.version = version.into();
+                        this.redis_version = redis_version.into();
+                        this.modules = modules;
                         this.supports_db_selection = supports_db_selection;
                         this.access_mode = access_mode;
+                        // Pinned keys may have been deleted since the last session
+                        this.check_favorites(cx);
+                        if watch_key_events {
+                            this.watch_key_events(cx);
+                        }
                     };
 
                     let server_id = this.server_id.clone();
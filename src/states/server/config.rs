@@ -0,0 +1,121 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ServerEvent, ServerTask, ZedisServerState};
+use crate::{
+    connection::get_connection_manager,
+    states::{ZedisGlobalStore, i18n_config_editor},
+};
+use ahash::AHashMap;
+use gpui::{SharedString, prelude::*};
+use redis::cmd;
+use rust_i18n::t;
+use std::sync::Arc;
+
+/// Runtime configuration parameters of the server, sorted by name
+pub type ServerConfig = Arc<Vec<(SharedString, SharedString)>>;
+
+impl ZedisServerState {
+    /// Load every parameter with `CONFIG GET *`
+    pub fn load_server_config(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::LoadServerConfig,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let config: AHashMap<String, String> = cmd("CONFIG").arg("GET").arg("*").query_async(&mut conn).await?;
+                let mut config: Vec<(SharedString, SharedString)> = config
+                    .into_iter()
+                    .map(|(name, value)| (name.into(), value.into()))
+                    .collect();
+                config.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+                Ok(Arc::new(config))
+            },
+            move |_this, result, cx| {
+                if let Ok(config) = result {
+                    cx.emit(ServerEvent::ServerConfigLoaded(config));
+                }
+            },
+            cx,
+        );
+    }
+    /// Apply a parameter with `CONFIG SET`, a rejected value shows an error notification
+    pub fn set_server_config(&mut self, name: SharedString, value: SharedString, cx: &mut Context<Self>) {
+        if self.readonly() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::SetServerConfig,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let _: () = cmd("CONFIG")
+                    .arg("SET")
+                    .arg(name.as_str())
+                    .arg(value.as_str())
+                    .query_async(&mut conn)
+                    .await?;
+                // Read it back, the server may normalize the value (e.g. memory units)
+                let config: AHashMap<String, String> = cmd("CONFIG")
+                    .arg("GET")
+                    .arg(name.as_str())
+                    .query_async(&mut conn)
+                    .await?;
+                let value = config
+                    .get(name.as_str())
+                    .cloned()
+                    .map(SharedString::from)
+                    .unwrap_or(value);
+                Ok((name, value))
+            },
+            move |this, result, cx| {
+                if let Ok((name, value)) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!("config_editor.applied", name = name, locale = locale).to_string();
+                    this.emit_success_notification(message.into(), i18n_config_editor(cx, "title"), cx);
+                    cx.emit(ServerEvent::ServerConfigUpdated(name, value));
+                }
+            },
+            cx,
+        );
+    }
+    /// Persist the running configuration to the config file with `CONFIG REWRITE`
+    pub fn rewrite_server_config(&mut self, cx: &mut Context<Self>) {
+        if self.readonly() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::RewriteServerConfig,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let _: () = cmd("CONFIG").arg("REWRITE").query_async(&mut conn).await?;
+                Ok(())
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    this.emit_success_notification(
+                        i18n_config_editor(cx, "rewritten"),
+                        i18n_config_editor(cx, "title"),
+                        cx,
+                    );
+                }
+            },
+            cx,
+        );
+    }
+}
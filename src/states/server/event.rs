@@ -13,9 +13,13 @@
 // limitations under the License.
 
 use crate::helpers::EditorAction;
-use crate::states::{ErrorMessage, GlobalEvent, NotificationAction, ZedisGlobalStore, ZedisServerState};
+use crate::states::{
+    BulkPreview, ColdKeysReport, ConnectionStatus, CopyTarget, DuplicateReport, ErrorMessage, GlobalEvent,
+    NotificationAction, SearchPage, ServerConfig, ZedisGlobalStore, ZedisServerState,
+};
 use gpui::prelude::*;
 use gpui::{EventEmitter, SharedString};
+use std::sync::Arc;
 
 /// Background task types for Redis operations
 ///
@@ -40,6 +44,9 @@ pub enum ServerTask {
     /// Delete a key from Redis
     DeleteKey,
 
+    /// Rename a key with RENAME / RENAMENX
+    RenameKey,
+
     /// Reload value data for a selected key
     ReloadValue,
 
@@ -54,8 +61,12 @@ pub enum ServerTask {
 
     /// Add a new key
     AddKey,
+    /// Create keys from pasted write commands
+    ImportCommands,
     /// Update TTL (time-to-live) for a key
     UpdateKeyTtl,
+    /// Set the TTL of multiple keys
+    ExpireKeys,
 
     /// Delete an item from a list
     RemoveListValue,
@@ -95,9 +106,76 @@ pub enum ServerTask {
 
     /// Save edited value back to Redis
     SaveValue,
+    /// Replace a string value and read the previous one with GETSET
+    GetSetValue,
+    /// Read and delete a string value with GETDEL
+    GetDelValue,
+    /// Copy the whole value to the clipboard
+    CopyValue,
+    /// Write the whole value to a file
+    ExportValue,
+    /// Write every key under a prefix to a file
+    ExportPrefix,
+    /// Recreate the keys of a prefix export
+    ImportKeys,
+    /// Copy a key to another server with DUMP and RESTORE
+    CopyKeyToServer,
+    /// Copy a key under a new name on the same server
+    DuplicateKey,
 
     /// Publish a message to a channel
     PublishMessage,
+
+    /// Load internal details of a key
+    LoadKeyMetadata,
+    /// Estimate the memory usage of key tree folders
+    LoadFolderMemory,
+    /// Load the memory usage of key tree keys
+    LoadKeyMemory,
+    /// Load the TTL of key tree keys
+    LoadKeyTtls,
+    /// Group the keys of a namespace holding identical values
+    FindDuplicates,
+    /// Rank the keys of a namespace by idle time or access frequency
+    FindColdKeys,
+    /// Scan the keys a bulk operation would affect, without writes
+    PreviewBulkOperation,
+
+    /// Load a window of a large string value
+    LoadStringRange,
+    /// Load a random sample of a hash or set
+    SampleValue,
+
+    /// Persist the read-only lock of the server
+    SaveReadonly,
+
+    /// Check and load a key to reveal it in the tree
+    RevealKey,
+
+    /// Check with EXISTS that the selected key has expired
+    CheckValueExpired,
+
+    /// Pin or unpin a key
+    ToggleFavorite,
+
+    /// Check with EXISTS which pinned keys are missing
+    CheckFavorites,
+
+    /// Remember a recently viewed key
+    SaveRecentKey,
+
+    /// Load the runtime configuration with CONFIG GET
+    LoadServerConfig,
+    /// Apply a configuration parameter with CONFIG SET
+    SetServerConfig,
+    /// Persist the configuration with CONFIG REWRITE
+    RewriteServerConfig,
+    /// Enable the key events of notify-keyspace-events
+    EnableKeyspaceEvents,
+    /// Scan a page of the advanced search
+    SearchKeys,
+    /// Write the results of the advanced search to a file
+    ExportSearchResults,
 }
 
 impl ServerTask {
@@ -110,16 +188,27 @@ impl ServerTask {
             ServerTask::FillKeyTypes => "fill_key_types",
             ServerTask::Selectkey => "select_key",
             ServerTask::DeleteKey => "delete_key",
+            ServerTask::RenameKey => "rename_key",
             ServerTask::ReloadValue => "reload_value",
             ServerTask::DeleteKeys => "delete_keys",
             ServerTask::ScanKeys => "scan_keys",
             ServerTask::ScanPrefix => "scan_prefix",
             ServerTask::AddKey => "add_key",
+            ServerTask::ImportCommands => "import_commands",
             ServerTask::UpdateKeyTtl => "update_key_ttl",
+            ServerTask::ExpireKeys => "expire_keys",
             ServerTask::RemoveListValue => "remove_list_value",
             ServerTask::UpdateListValue => "update_list_value",
             ServerTask::LoadMoreValue => "load_more_value",
             ServerTask::SaveValue => "save_value",
+            ServerTask::GetSetValue => "getset_value",
+            ServerTask::GetDelValue => "getdel_value",
+            ServerTask::CopyValue => "copy_value",
+            ServerTask::ExportValue => "export_value",
+            ServerTask::ExportPrefix => "export_prefix",
+            ServerTask::ImportKeys => "import_keys",
+            ServerTask::CopyKeyToServer => "copy_key_to_server",
+            ServerTask::DuplicateKey => "duplicate_key",
             ServerTask::PushListValue => "push_list_value",
             ServerTask::AddSetValue => "add_set_value",
             ServerTask::UpdateSetValue => "update_set_value",
@@ -132,6 +221,27 @@ impl ServerTask {
             ServerTask::AddStreamEntry => "add_stream_entry",
             ServerTask::RemoveStreamEntry => "remove_stream_entry",
             ServerTask::PublishMessage => "publish_message",
+            ServerTask::LoadKeyMetadata => "load_key_metadata",
+            ServerTask::LoadFolderMemory => "load_folder_memory",
+            ServerTask::LoadKeyMemory => "load_key_memory",
+            ServerTask::LoadKeyTtls => "load_key_ttls",
+            ServerTask::FindDuplicates => "find_duplicates",
+            ServerTask::FindColdKeys => "find_cold_keys",
+            ServerTask::PreviewBulkOperation => "preview_bulk_operation",
+            ServerTask::LoadStringRange => "load_string_range",
+            ServerTask::SampleValue => "sample_value",
+            ServerTask::SaveReadonly => "save_readonly",
+            ServerTask::RevealKey => "reveal_key",
+            ServerTask::CheckValueExpired => "check_value_expired",
+            ServerTask::ToggleFavorite => "toggle_favorite",
+            ServerTask::CheckFavorites => "check_favorites",
+            ServerTask::SaveRecentKey => "save_recent_key",
+            ServerTask::LoadServerConfig => "load_server_config",
+            ServerTask::SetServerConfig => "set_server_config",
+            ServerTask::RewriteServerConfig => "rewrite_server_config",
+            ServerTask::EnableKeyspaceEvents => "enable_keyspace_events",
+            ServerTask::SearchKeys => "search_keys",
+            ServerTask::ExportSearchResults => "export_search_results",
         }
     }
 }
@@ -147,6 +257,10 @@ pub enum ServerEvent {
 
     /// A key has been selected for viewing/editing
     KeySelected(SharedString),
+    /// A key has been revealed in the tree, its folders should be expanded and scrolled to
+    KeyRevealed(SharedString),
+    /// A key has been renamed
+    KeyRenamed { old: SharedString, new: SharedString },
     /// Key scan operation has started
     KeyScanStarted,
     /// Key scan found a new batch of keys.
@@ -160,6 +274,8 @@ pub enum ServerEvent {
     ValueLoaded,
     /// A key's value has been updated
     ValueUpdated,
+    /// Saving the value was aborted, the key was changed by another client: (key, edited value)
+    SaveConflict(SharedString, SharedString),
     /// A key's value view mode has been updated
     ValueModeViewUpdated,
     /// Load more value
@@ -168,6 +284,26 @@ pub enum ServerEvent {
     ValuePaginationFinished,
     /// Add a value to a set、list、hash、zset
     ValueAdded,
+    /// Internal details of the selected key have been loaded
+    KeyMetadataLoaded,
+    /// Memory usage estimates of key tree folders have been loaded
+    FolderMemoryLoaded,
+    /// The separator of the key namespaces has changed, the tree needs a rebuild
+    KeySeparatorChanged,
+    /// Memory usage of key tree keys has been loaded
+    KeyMemoryLoaded,
+    /// TTL of key tree keys has been loaded
+    KeyTtlsLoaded,
+    /// Pinned keys or their missing state have changed
+    FavoritesUpdated,
+    /// Duplicate detection of a namespace has finished
+    DuplicatesFound(Arc<DuplicateReport>),
+    /// Cold keys finder of a namespace has finished
+    ColdKeysFound(Arc<ColdKeysReport>),
+    /// The keys affected by a bulk operation have been scanned
+    BulkOperationPreviewed(Arc<BulkPreview>),
+    /// The key copied to another server already exists there, it may be replaced
+    KeyCopyConflict(SharedString, CopyTarget),
 
     /// User selected a different server
     ServerSelected(SharedString),
@@ -175,9 +311,27 @@ pub enum ServerEvent {
     ServerInfoUpdated,
     /// Periodic redis info updated.
     ServerRedisInfoUpdated,
+    /// The heartbeat detected a lost or restored connection
+    ConnectionStatusChanged(ConnectionStatus),
+    /// The runtime configuration of the server has been loaded
+    ServerConfigLoaded(ServerConfig),
+    /// A configuration parameter has been applied, with the value read back
+    ServerConfigUpdated(SharedString, SharedString),
+    /// The key events are watched but not published, with the current notify-keyspace-events
+    KeyspaceEventsDisabled(SharedString),
+    /// A page of the advanced search has been loaded
+    SearchResultsLoaded(Arc<SearchPage>),
+    /// A page of the advanced search failed to load
+    SearchFailed,
 
     /// Soft wrap changed
     SoftWrapToggled(bool),
+    /// Indent guides changed
+    IndentGuidesToggled(bool),
+    /// The cursor of the value editor moved
+    EditorCursorMoved,
+    /// Move the cursor of the value editor to a line (1-based)
+    GoToLineRequested(usize),
     /// An error occurred.
     ErrorOccurred(ErrorMessage),
 
@@ -196,33 +350,38 @@ impl ZedisServerState {
         if readonly
             && matches!(
                 event,
-                EditorAction::Create | EditorAction::Save | EditorAction::UpdateTtl
+                EditorAction::Create
+                    | EditorAction::Save
+                    | EditorAction::UpdateTtl
+                    | EditorAction::DeleteKey
+                    | EditorAction::RenameKey
             )
         {
             return;
         }
         cx.emit(ServerEvent::EditionActionTriggered(event));
     }
-    pub fn emit_info_notification(&self, message: SharedString, cx: &mut Context<Self>) {
+    /// Emit the notification, attributed to the selected server
+    fn emit_notification(&self, notification: NotificationAction, cx: &mut Context<Self>) {
+        let notification = if self.server_id.is_empty() {
+            notification
+        } else {
+            notification.with_server(self.server_id.clone(), self.db)
+        };
         cx.global::<ZedisGlobalStore>().clone().update(cx, |_state, cx| {
-            cx.emit(GlobalEvent::Notification(NotificationAction::new_info(message)));
+            cx.emit(GlobalEvent::Notification(notification));
         });
     }
+    pub fn emit_info_notification(&self, message: SharedString, cx: &mut Context<Self>) {
+        self.emit_notification(NotificationAction::new_info(message), cx);
+    }
     pub fn emit_success_notification(&self, message: SharedString, title: SharedString, cx: &mut Context<Self>) {
-        cx.global::<ZedisGlobalStore>().clone().update(cx, |_state, cx| {
-            cx.emit(GlobalEvent::Notification(
-                NotificationAction::new_success(message).with_title(title),
-            ));
-        });
+        self.emit_notification(NotificationAction::new_success(message).with_title(title), cx);
     }
     pub fn emit_warning_notification(&self, message: SharedString, cx: &mut Context<Self>) {
-        cx.global::<ZedisGlobalStore>().clone().update(cx, |_state, cx| {
-            cx.emit(GlobalEvent::Notification(NotificationAction::new_warning(message)));
-        });
+        self.emit_notification(NotificationAction::new_warning(message), cx);
     }
     pub fn emit_error_notification(&self, message: SharedString, cx: &mut Context<Self>) {
-        cx.global::<ZedisGlobalStore>().clone().update(cx, |_state, cx| {
-            cx.emit(GlobalEvent::Notification(NotificationAction::new_error(message)));
-        });
+        self.emit_notification(NotificationAction::new_error(message), cx);
     }
 }
@@ -0,0 +1,118 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ServerEvent, ServerTask, ZedisServerState};
+use crate::{connection::get_connection_manager, db::get_favorites_manager};
+use ahash::AHashSet;
+use gpui::{SharedString, prelude::*};
+use redis::pipe;
+
+impl ZedisServerState {
+    /// Pinned keys of the server, the most recently pinned first
+    pub fn favorites(&self) -> &[SharedString] {
+        &self.favorites
+    }
+    pub fn is_favorite(&self, key: &str) -> bool {
+        self.favorites.iter().any(|item| item.as_str() == key)
+    }
+    /// Whether the last `check_favorites` found that the pinned key doesn't exist
+    pub fn is_missing_favorite(&self, key: &str) -> bool {
+        self.missing_favorites.contains(key)
+    }
+    /// Load the pinned keys of the selected server from the local database
+    pub(super) fn load_favorites(&mut self) {
+        self.favorites = get_favorites_manager()
+            .records(self.server_id.as_str())
+            .unwrap_or_default();
+        self.missing_favorites.clear();
+    }
+    /// Pin the key, or unpin it when it is already a favorite
+    pub fn toggle_favorite(&mut self, key: SharedString, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let is_favorite = self.is_favorite(&key);
+        self.spawn(
+            ServerTask::ToggleFavorite,
+            move || async move {
+                let manager = get_favorites_manager();
+                let favorites = if is_favorite {
+                    manager.remove_record(&server_id, &key)?
+                } else {
+                    manager.add_record(&server_id, &key)?
+                };
+                Ok(favorites)
+            },
+            move |this, result, cx| {
+                if let Ok(favorites) = result {
+                    this.favorites = favorites;
+                    cx.emit(ServerEvent::FavoritesUpdated);
+                    cx.notify();
+                }
+            },
+            cx,
+        );
+    }
+    pub fn clear_favorites(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        self.spawn(
+            ServerTask::ToggleFavorite,
+            move || async move {
+                get_favorites_manager().clear_history(&server_id)?;
+                Ok(())
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    this.favorites.clear();
+                    this.missing_favorites.clear();
+                    cx.emit(ServerEvent::FavoritesUpdated);
+                    cx.notify();
+                }
+            },
+            cx,
+        );
+    }
+    /// Check with EXISTS which pinned keys were deleted or have expired
+    pub fn check_favorites(&mut self, cx: &mut Context<Self>) {
+        if self.favorites.is_empty() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let keys = self.favorites.clone();
+        self.spawn(
+            ServerTask::CheckFavorites,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut pipeline = pipe();
+                for key in keys.iter() {
+                    pipeline.cmd("EXISTS").arg(key.as_str());
+                }
+                let exists: Vec<bool> = pipeline.query_async(&mut conn).await?;
+                let missing: AHashSet<SharedString> = keys
+                    .into_iter()
+                    .zip(exists)
+                    .filter_map(|(key, exists)| (!exists).then_some(key))
+                    .collect();
+                Ok(missing)
+            },
+            move |this, result, cx| {
+                if let Ok(missing) = result {
+                    this.missing_favorites = missing;
+                    cx.emit(ServerEvent::FavoritesUpdated);
+                    cx.notify();
+                }
+            },
+            cx,
+        );
+    }
+}
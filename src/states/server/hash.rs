@@ -20,25 +20,45 @@
 //! - Removing fields from a HASH (HDEL)
 //! - Filtering HASH fields with pattern matching
 //! - Efficient incremental loading for large HASHes
+//! - Random sampling of huge HASHes (HRANDFIELD)
+//! - Field level TTLs (HTTL / HEXPIRE / HPERSIST, Redis 7.4+)
 
 use super::{
     KeyType, RedisValueData, ServerTask, ZedisServerState,
-    value::{RedisHashValue, RedisValue, RedisValueStatus},
+    value::{FieldTtl, RedisHashValue, RedisValue, RedisValueStatus, revert_failed_edit},
 };
 use crate::{
-    connection::{RedisAsyncConn, get_connection_manager},
+    connection::{Capability, RedisAsyncConn, get_connection_manager},
     error::Error,
     states::{SUCCESS_NOTIFY_THRESHOLD, ServerEvent, i18n_hash_editor},
 };
+use ahash::{AHashMap, AHashSet};
 use gpui::{SharedString, prelude::*};
-use redis::cmd;
+use redis::{Pipeline, cmd};
 use std::sync::Arc;
+use tracing::warn;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
 /// Type alias for HSCAN result: (cursor, vec of (field, value) pairs as bytes)
 type HashScanValue = (u64, Vec<(Vec<u8>, Vec<u8>)>);
 
+/// Converts the field-value pairs of a reply to strings, lossy for non-UTF8 data.
+///
+/// RESP2 replies flat arrays and RESP3 maps or nested pairs, both decode as pairs.
+/// Valid UTF-8 bytes are moved into the strings without a copy.
+fn into_field_values(raw_values: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<(SharedString, SharedString)> {
+    let to_string = |bytes: Vec<u8>| {
+        SharedString::from(
+            String::from_utf8(bytes).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()),
+        )
+    };
+    raw_values
+        .into_iter()
+        .map(|(field, value)| (to_string(field), to_string(value)))
+        .collect()
+}
+
 /// Retrieves HASH field-value pairs using Redis HSCAN command for cursor-based pagination.
 ///
 /// # Arguments
@@ -79,18 +99,95 @@ async fn get_redis_hash_value(
         return Ok((next_cursor, vec![]));
     }
 
-    // Convert bytes to UTF-8 strings (lossy conversion for non-UTF8 data)
-    let values = raw_values
+    Ok((next_cursor, into_field_values(raw_values)))
+}
+
+/// Retrieves the TTL of HASH fields using the Redis 7.4 HTTL command.
+///
+/// # Returns
+/// The TTL in seconds of each field with a TTL, fields without TTL (-1)
+/// or missing (-2) are left out. The TTLs are optional, when HTTL fails
+/// (e.g. denied by an ACL) the fields are shown without TTL.
+async fn get_hash_field_ttls(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    fields: &[SharedString],
+) -> AHashMap<SharedString, i64> {
+    if fields.is_empty() {
+        return AHashMap::new();
+    }
+    let mut ttl_cmd = cmd("HTTL");
+    ttl_cmd.arg(key).arg("FIELDS").arg(fields.len());
+    for field in fields {
+        ttl_cmd.arg(field.as_str());
+    }
+    let ttls: Vec<i64> = match ttl_cmd.query_async(conn).await {
+        Ok(ttls) => ttls,
+        Err(e) => {
+            warn!(key, error = %e, "Failed to get the TTL of hash fields");
+            return AHashMap::new();
+        }
+    };
+    fields
         .iter()
-        .map(|(field, value)| {
-            (
-                SharedString::new(String::from_utf8_lossy(field)),
-                SharedString::new(String::from_utf8_lossy(value)),
-            )
-        })
-        .collect();
-
-    Ok((next_cursor, values))
+        .zip(ttls)
+        .filter(|(_, ttl)| *ttl >= 0)
+        .map(|(field, ttl)| (field.clone(), ttl))
+        .collect()
+}
+
+/// Adds the HEXPIRE or HPERSIST command of a field TTL change to the pipeline.
+fn add_field_ttl_cmd(pipe: &mut Pipeline, key: &str, field: &str, ttl: FieldTtl) {
+    match ttl {
+        FieldTtl::Keep => {}
+        FieldTtl::Persist => {
+            pipe.cmd("HPERSIST").arg(key).arg("FIELDS").arg(1).arg(field).ignore();
+        }
+        FieldTtl::Expire(seconds) => {
+            pipe.cmd("HEXPIRE")
+                .arg(key)
+                .arg(seconds)
+                .arg("FIELDS")
+                .arg(1)
+                .arg(field)
+                .ignore();
+        }
+    }
+}
+
+/// Applies a field TTL change to the loaded field TTLs.
+fn apply_field_ttl(hash: &mut RedisHashValue, field: &SharedString, ttl: FieldTtl) {
+    match ttl {
+        FieldTtl::Keep => {}
+        FieldTtl::Persist => {
+            hash.field_ttls.remove(field);
+        }
+        FieldTtl::Expire(seconds) => {
+            hash.field_ttls.insert(field.clone(), seconds as i64);
+        }
+    }
+}
+
+/// Drops `field` from the loaded fields once `removed` fields were deleted.
+///
+/// The loaded size may be stale and smaller than `removed`, so it never goes below
+/// zero. `hlen` replaces it once the size was read again from the server.
+fn reconcile_removed_field(hash: &mut RedisHashValue, field: &SharedString, removed: usize, hlen: Option<usize>) {
+    hash.values.retain(|(f, _)| f != field);
+    hash.field_ttls.remove(field);
+    hash.size = hlen.unwrap_or_else(|| hash.size.saturating_sub(removed));
+}
+
+/// Shows a field written by HSET, `added` is its reply: 1 for a new field, 0 when it existed.
+///
+/// An existing field gets its value replaced, otherwise the field is appended, the
+/// next HSCAN pages skip the fields already loaded.
+fn apply_added_field(hash: &mut RedisHashValue, field: SharedString, value: SharedString, added: usize) {
+    hash.size += added;
+    match hash.values.iter_mut().find(|(f, _)| f == &field) {
+        Some((_, v)) => *v = value,
+        None => hash.values.push((field, value)),
+    }
 }
 
 /// Performs initial load of a Redis HASH value.
@@ -101,15 +198,26 @@ async fn get_redis_hash_value(
 /// # Arguments
 /// * `conn` - Redis async connection
 /// * `key` - The HASH key to load
+/// * `with_field_ttl` - Whether to load the field TTLs (Redis 7.4+)
+/// * `count` - COUNT hint of the first HSCAN
 ///
 /// # Returns
 /// A `RedisValue` containing HASH metadata and initial field-value pairs
-pub(crate) async fn first_load_hash_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
-    // Get total number of fields in the HASH
-    let size: usize = cmd("HLEN").arg(key).query_async(conn).await?;
-
-    // Load first batch of field-value pairs (up to 100)
-    let (cursor, values) = get_redis_hash_value(conn, key, None, 0, 100).await?;
+pub(crate) async fn first_load_hash_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    size: usize,
+    with_field_ttl: bool,
+    count: usize,
+) -> Result<RedisValue> {
+    // Load first batch of field-value pairs
+    let (cursor, values) = get_redis_hash_value(conn, key, None, 0, count).await?;
+    let field_ttls = if with_field_ttl {
+        let fields = values.iter().map(|(field, _)| field.clone()).collect::<Vec<_>>();
+        get_hash_field_ttls(conn, key, &fields).await
+    } else {
+        AHashMap::new()
+    };
 
     // If cursor is 0, all values have been loaded in one iteration
     let done = cursor == 0;
@@ -121,6 +229,7 @@ pub(crate) async fn first_load_hash_value(conn: &mut RedisAsyncConn, key: &str)
             size,
             values,
             done,
+            field_ttls,
             ..Default::default()
         }))),
         ..Default::default()
@@ -147,6 +256,8 @@ impl ZedisServerState {
         let key_str = key.to_string();
         value.status = RedisValueStatus::Updating;
 
+        // Restored if the edit fails
+        let snapshot = value.data.clone();
         // Step 1: Perform local optimistic update
         if let Some(RedisValueData::Hash(hash_data)) = value.data.as_mut() {
             optimistic_update(Arc::make_mut(hash_data));
@@ -171,7 +282,12 @@ impl ZedisServerState {
 
                 match result {
                     Ok(data) => on_success(this, data, cx),
-                    Err(e) => this.emit_error_notification(e.to_string().into(), cx),
+                    // The error is already reported by `spawn`, revert the optimistic update
+                    Err(_) => {
+                        if revert_failed_edit(this.value.as_mut(), this.key.as_ref(), &key, snapshot) {
+                            cx.emit(ServerEvent::ValueUpdated);
+                        }
+                    }
                 }
                 cx.notify();
             },
@@ -187,32 +303,32 @@ impl ZedisServerState {
     /// # Arguments
     /// * `field` - The field name to add
     /// * `value` - The value to set for the field
+    /// * `ttl` - TTL of the field, only supported by Redis 7.4+
     /// * `cx` - GPUI context for spawning async tasks and UI updates
-    pub fn add_hash_value(&mut self, field: SharedString, value: SharedString, cx: &mut Context<Self>) {
+    pub fn add_hash_value(&mut self, field: SharedString, value: SharedString, ttl: FieldTtl, cx: &mut Context<Self>) {
         let field_clone = field.clone();
         let value_clone = value.clone();
 
         self.exec_hash_op(
             ServerTask::AddHashField,
             cx,
-            |_| {}, // Wait for server confirmation to avoid duplicate UI entries during scan
+            |_| {}, // Wait for server confirmation, HSET may overwrite an existing field
             move |key, mut conn| async move {
-                let count: usize = cmd("HSET")
+                let mut pipe = redis::pipe();
+                pipe.atomic()
+                    .cmd("HSET")
                     .arg(&key)
                     .arg(field.as_str())
-                    .arg(value.as_str())
-                    .query_async(&mut conn)
-                    .await?;
+                    .arg(value.as_str());
+                add_field_ttl_cmd(&mut pipe, &key, field.as_str(), ttl);
+                let (count,): (usize,) = pipe.query_async(&mut conn).await?;
                 Ok(count)
             },
             move |this, count, cx| {
                 if let Some(RedisValueData::Hash(hash_data)) = this.value.as_mut().and_then(|v| v.data.as_mut()) {
                     let hash = Arc::make_mut(hash_data);
-                    hash.size += count;
-                    // Optimistically append if we are at the end of the scan
-                    if hash.done && !hash.values.iter().any(|(f, _)| f == &field_clone) {
-                        hash.values.push((field_clone, value_clone));
-                    }
+                    apply_field_ttl(hash, &field_clone, ttl);
+                    apply_added_field(hash, field_clone, value_clone, count);
                     if hash.size > SUCCESS_NOTIFY_THRESHOLD {
                         this.emit_success_notification(
                             i18n_hash_editor(cx, "add_value_success_tips"),
@@ -229,16 +345,21 @@ impl ZedisServerState {
     ///
     /// Uses HSET command to update the value of the specified field.
     ///
+    /// HSET recreates a field deleted by another client meanwhile, so the size is
+    /// reconciled from the number of fields added by HSET and removed by HDEL.
+    ///
     /// # Arguments
     /// * `old_field` - The old field name
     /// * `new_field` - The field name to update
     /// * `new_value` - The value to set for the field
+    /// * `ttl` - TTL change of the field, only supported by Redis 7.4+
     /// * `cx` - GPUI context for spawning async tasks and UI updates
     pub fn update_hash_value(
         &mut self,
         old_field: SharedString,
         new_field: SharedString,
         new_value: SharedString,
+        ttl: FieldTtl,
         cx: &mut Context<Self>,
     ) {
         let old_field_clone = old_field.clone();
@@ -252,34 +373,41 @@ impl ZedisServerState {
             move |hash| {
                 // Optimistic UI update: Replace old entry with new entry
                 if let Some(pos) = hash.values.iter().position(|(f, _)| f == &old_field_clone) {
+                    // The TTL of a renamed field is dropped by HDEL
+                    if is_rename {
+                        hash.field_ttls.remove(&old_field_clone);
+                    }
+                    apply_field_ttl(hash, &new_field_clone, ttl);
                     hash.values[pos] = (new_field_clone, new_value_clone);
                 }
             },
             move |key, mut conn| async move {
+                let mut pipe = redis::pipe();
+                pipe.atomic()
+                    .cmd("HSET")
+                    .arg(&key)
+                    .arg(new_field.as_str())
+                    .arg(new_value.as_str());
+                if is_rename {
+                    // Insert new field then delete old field
+                    pipe.cmd("HDEL").arg(&key).arg(old_field.as_str());
+                }
+                add_field_ttl_cmd(&mut pipe, &key, new_field.as_str(), ttl);
                 if is_rename {
-                    // Pipeline: Insert new field then delete old field
-                    let _: () = redis::pipe()
-                        .atomic()
-                        .cmd("HSET")
-                        .arg(&key)
-                        .arg(new_field.as_str())
-                        .arg(new_value.as_str())
-                        .cmd("HDEL")
-                        .arg(&key)
-                        .arg(old_field.as_str())
-                        .query_async(&mut conn)
-                        .await?;
+                    let (added, removed): (usize, usize) = pipe.query_async(&mut conn).await?;
+                    Ok((added, removed))
                 } else {
-                    let _: () = cmd("HSET")
-                        .arg(&key)
-                        .arg(new_field.as_str())
-                        .arg(new_value.as_str())
-                        .query_async(&mut conn)
-                        .await?;
+                    let (added,): (usize,) = pipe.query_async(&mut conn).await?;
+                    Ok((added, 0))
                 }
-                Ok(())
             },
-            |this, _, cx| {
+            |this, (added, removed), cx| {
+                if added != removed
+                    && let Some(RedisValueData::Hash(hash_data)) = this.value.as_mut().and_then(|v| v.data.as_mut())
+                {
+                    let hash = Arc::make_mut(hash_data);
+                    hash.size = (hash.size + added).saturating_sub(removed);
+                }
                 this.emit_info_notification(i18n_hash_editor(cx, "update_exist_field_value_success_tips"), cx);
                 cx.emit(ServerEvent::ValueUpdated);
             },
@@ -318,6 +446,9 @@ impl ZedisServerState {
     /// Uses HDEL command to delete the specified field and updates both the
     /// Redis field count and the local UI state.
     ///
+    /// The size loaded with the value may be stale, e.g. the field was already
+    /// deleted by another client, so it is read again with HLEN after HDEL.
+    ///
     /// # Arguments
     /// * `remove_field` - The field name to remove from the HASH
     /// * `cx` - GPUI context for spawning async tasks and UI updates
@@ -326,27 +457,102 @@ impl ZedisServerState {
         self.exec_hash_op(
             ServerTask::RemoveHashField,
             cx,
-            move |hash| {
-                hash.size = hash.size.saturating_sub(1);
-                hash.values.retain(|(f, _)| f != &remove_field_clone);
+            {
+                let remove_field = remove_field.clone();
+                move |hash| reconcile_removed_field(hash, &remove_field, 1, None)
             },
             move |key, mut conn| async move {
-                let count: usize = cmd("HDEL")
+                let (size,): (usize,) = redis::pipe()
+                    .atomic()
+                    .cmd("HDEL")
                     .arg(&key)
                     .arg(remove_field.as_str())
+                    .ignore()
+                    .cmd("HLEN")
+                    .arg(&key)
                     .query_async(&mut conn)
                     .await?;
-                Ok(count)
+                Ok(size)
             },
-            |_, _, cx| {
+            move |this, size, cx| {
+                if let Some(RedisValueData::Hash(hash_data)) = this.value.as_mut().and_then(|v| v.data.as_mut()) {
+                    reconcile_removed_field(Arc::make_mut(hash_data), &remove_field_clone, 1, Some(size));
+                }
                 cx.emit(ServerEvent::ValueUpdated);
             },
         );
     }
+    /// Replaces the loaded fields with a random sample of `count` fields.
+    ///
+    /// Uses `HRANDFIELD key count WITHVALUES` (Redis 6.2+), so huge HASHes can be
+    /// inspected without scanning them. Filtering starts a normal scan again.
+    ///
+    /// # Arguments
+    /// * `count` - Number of distinct fields to sample
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn sample_hash_value(&mut self, count: usize, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(size) = value.hash_value().map(|hash| hash.size) else {
+            return;
+        };
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        cx.emit(ServerEvent::ValuePaginationStarted);
+
+        self.spawn(
+            ServerTask::SampleValue,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                client.ensure(Capability::HRandField)?;
+                let mut conn = client.connection();
+                let raw_values: Vec<(Vec<u8>, Vec<u8>)> = cmd("HRANDFIELD")
+                    .arg(key.as_str())
+                    .arg(count)
+                    .arg("WITHVALUES")
+                    .query_async(&mut conn)
+                    .await?;
+                let values = into_field_values(raw_values);
+                let field_ttls = if client.supports(Capability::HashFieldTtl) {
+                    let fields = values.iter().map(|(field, _)| field.clone()).collect::<Vec<_>>();
+                    get_hash_field_ttls(&mut conn, &key, &fields).await
+                } else {
+                    AHashMap::new()
+                };
+                Ok((values, field_ttls))
+            },
+            move |this, result, cx| {
+                if let Ok((values, field_ttls)) = result
+                    && let Some(value) = this.value.as_mut()
+                {
+                    value.data = Some(RedisValueData::Hash(Arc::new(RedisHashValue {
+                        size,
+                        values,
+                        done: true,
+                        sample: true,
+                        field_ttls,
+                        ..Default::default()
+                    })));
+                }
+
+                cx.emit(ServerEvent::ValuePaginationFinished);
+
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
     /// Loads the next batch of HASH field-value pairs using cursor-based pagination.
     ///
     /// Uses HSCAN to incrementally load field-value pairs without blocking on large HASHes.
-    /// When filtering is active, uses larger batch sizes (1000) for better performance.
+    /// When filtering is active, uses larger batch sizes for better performance.
     ///
     /// # Arguments
     /// * `cx` - GPUI context for spawning async tasks and UI updates
@@ -367,36 +573,51 @@ impl ZedisServerState {
 
         let server_id = self.server_id.clone();
         let db = self.db;
+        // Use larger batch size when filtering to reduce round trips
+        let count = if keyword.is_some() {
+            self.filter_scan_count()
+        } else {
+            self.value_scan_count()
+        };
         cx.emit(ServerEvent::ValuePaginationStarted);
 
         self.spawn(
             ServerTask::LoadMoreValue,
             // Async operation: fetch next batch using HSCAN
             move || async move {
-                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.connection();
 
-                // Use larger batch size when filtering to reduce round trips
-                let count = if keyword.is_some() { 1000 } else { 100 };
-
-                get_redis_hash_value(&mut conn, &key, keyword, cursor, count).await
+                let (new_cursor, new_values) = get_redis_hash_value(&mut conn, &key, keyword, cursor, count).await?;
+                let field_ttls = if client.supports(Capability::HashFieldTtl) {
+                    let fields = new_values.iter().map(|(field, _)| field.clone()).collect::<Vec<_>>();
+                    get_hash_field_ttls(&mut conn, &key, &fields).await
+                } else {
+                    AHashMap::new()
+                };
+                Ok((new_cursor, new_values, field_ttls))
             },
             // UI callback: merge results into local state
             move |this, result, cx| {
                 let mut should_load_more = false;
-                if let Ok((new_cursor, new_values)) = result
+                if let Ok((new_cursor, new_values, field_ttls)) = result
                     && let Some(RedisValueData::Hash(hash_data)) = this.value.as_mut().and_then(|v| v.data.as_mut())
                 {
                     let hash = Arc::make_mut(hash_data);
                     hash.cursor = new_cursor;
+                    hash.field_ttls.extend(field_ttls);
 
                     // Mark as done when cursor returns to 0 (scan complete)
                     if new_cursor == 0 {
                         hash.done = true;
                     }
 
-                    // Append new field-value pairs to existing list
+                    // Append new field-value pairs to existing list, skipping the fields
+                    // returned again by HSCAN or added while scanning
                     if !new_values.is_empty() {
-                        hash.values.extend(new_values);
+                        let loaded: AHashSet<SharedString> = hash.values.iter().map(|(f, _)| f.clone()).collect();
+                        hash.values
+                            .extend(new_values.into_iter().filter(|(f, _)| !loaded.contains(f)));
                     }
                     if !hash.done && hash.values.len() < 50 {
                         should_load_more = true;
@@ -418,3 +639,73 @@ impl ZedisServerState {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_value(size: usize, fields: &[&str]) -> RedisHashValue {
+        RedisHashValue {
+            size,
+            values: fields
+                .iter()
+                .map(|field| (SharedString::from(field.to_string()), SharedString::from("value")))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_remove_field_with_stale_size() {
+        // The size was loaded before other fields were deleted by another client
+        let mut hash = hash_value(0, &["a", "b"]);
+        reconcile_removed_field(&mut hash, &"a".into(), 1, None);
+        assert_eq!(hash.size, 0);
+        assert_eq!(hash.values.len(), 1);
+        assert_eq!(hash.values[0].0, "b");
+
+        let mut hash = hash_value(1, &["a"]);
+        reconcile_removed_field(&mut hash, &"a".into(), 3, None);
+        assert_eq!(hash.size, 0);
+        assert!(hash.values.is_empty());
+    }
+
+    #[test]
+    fn test_remove_field_with_hlen() {
+        let mut hash = hash_value(2, &["a", "b"]);
+        hash.field_ttls.insert("a".into(), 60);
+        reconcile_removed_field(&mut hash, &"a".into(), 1, None);
+        assert_eq!(hash.size, 1);
+        assert!(hash.field_ttls.is_empty());
+
+        // HLEN wins over the local count
+        reconcile_removed_field(&mut hash, &"a".into(), 1, Some(5));
+        assert_eq!(hash.size, 5);
+        assert_eq!(hash.values.len(), 1);
+    }
+
+    #[test]
+    fn test_add_new_field() {
+        let mut hash = hash_value(1, &["a"]);
+        apply_added_field(&mut hash, "b".into(), "new".into(), 1);
+        assert_eq!(hash.size, 2);
+        assert_eq!(hash.values.len(), 2);
+        assert_eq!(hash.values[1], ("b".into(), "new".into()));
+    }
+
+    #[test]
+    fn test_add_existing_field() {
+        let mut hash = hash_value(2, &["a", "b"]);
+        apply_added_field(&mut hash, "a".into(), "new".into(), 0);
+        assert_eq!(hash.size, 2);
+        assert_eq!(hash.values.len(), 2);
+        assert_eq!(hash.values[0], ("a".into(), "new".into()));
+
+        // An existing field not scanned yet shows up once, without changing the size
+        let mut hash = hash_value(3, &["a"]);
+        apply_added_field(&mut hash, "c".into(), "new".into(), 0);
+        assert_eq!(hash.size, 3);
+        assert_eq!(hash.values.len(), 2);
+        assert_eq!(hash.values[1], ("c".into(), "new".into()));
+    }
+}
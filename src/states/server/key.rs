@@ -18,26 +18,136 @@ use super::{
     list::first_load_list_value,
     set::first_load_set_value,
     stream::first_load_stream_value,
-    string::get_redis_bytes_value,
-    value::{KeyType, RedisValue, RedisValueData, RedisValueStatus, SortOrder},
+    string::{first_load_json_value, get_redis_bytes_range, get_redis_bytes_value},
+    value::{
+        BulkOperation, BulkPreview, ColdKeyMetric, ColdKeysReport, CopyTarget, DuplicateGroup, DuplicateReport,
+        KeyMetadata, KeyType, RedisValue, RedisValueData, RedisValueStatus, SortOrder, read_import_file,
+    },
     zset::first_load_zset_value,
 };
-use crate::states::{QueryMode, ZedisGlobalStore};
+use crate::states::{GlobalEvent, QueryMode, ZedisGlobalStore, i18n_editor, i18n_key_tree};
 use crate::{
-    connection::get_connection_manager,
+    connection::{AccessMode, Capability, get_connection_manager},
     error::Error,
-    helpers::{parse_duration, unix_ts},
+    helpers::{ImportCommand, parse_duration, unix_ts},
 };
-use ahash::AHashSet;
+use ahash::{AHashMap, AHashSet, RandomState};
 use futures::{StreamExt, stream};
 use gpui::{SharedString, prelude::*};
-use redis::{cmd, pipe};
+use redis::{FromRedisValue, Value, cmd, pipe};
+use regex::Regex;
+use rust_i18n::t;
+use std::hash::BuildHasher;
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 use tracing::debug;
 use uuid::Uuid;
 
 const DEFAULT_SCAN_RESULT_MAX: usize = 1_000;
+/// Length command of each key type, sent after TYPE so the size of a value is known before loading it
+const LEN_COMMANDS: [(KeyType, &str); 6] = [
+    (KeyType::String, "STRLEN"),
+    (KeyType::List, "LLEN"),
+    (KeyType::Set, "SCARD"),
+    (KeyType::Zset, "ZCARD"),
+    (KeyType::Hash, "HLEN"),
+    (KeyType::Stream, "XLEN"),
+];
+/// Max keys sampled with MEMORY USAGE when estimating the memory of a folder
+const FOLDER_MEMORY_SAMPLES: usize = 100;
+/// Keys sent per pipeline when loading the memory usage of keys
+const KEY_MEMORY_BATCH_SIZE: usize = 500;
+/// Keys per pipeline of TTL lookups
+const KEY_TTL_BATCH_SIZE: usize = 500;
+/// Max keys checked by the duplicate detection, larger namespaces are sampled
+const DUPLICATE_SCAN_LIMIT: usize = 10_000;
+/// Keys read per pipeline by the duplicate detection
+const DUPLICATE_BATCH_SIZE: usize = 100;
+/// Max keys checked by the cold keys finder, larger namespaces are sampled
+const COLD_KEYS_SCAN_LIMIT: usize = 10_000;
+/// Coldest keys kept by the cold keys finder
+const COLD_KEYS_MAX: usize = 100;
+/// COUNT of the SCAN calls of bulk operations
+const BULK_SCAN_COUNT: u64 = 10_000;
+/// Max SCAN calls of bulk operations, to avoid blocking for too long
+const BULK_SCAN_ROUNDS: usize = 20;
+/// Keys per pipeline of the bulk TTL updates and import previews
+const BULK_EXISTS_BATCH_SIZE: usize = 500;
+/// Max TYPE lookups sent per batch when filling the key types of the tree
+const KEY_TYPE_BATCH_SIZE: usize = 200;
+/// Min interval between two batches of TYPE lookups
+const KEY_TYPE_BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Keeps the keys matching the regex, SCAN MATCH only supports globs
+fn filter_keys_by_regex(keys: Vec<SharedString>, regex: &str) -> Result<Vec<SharedString>, Error> {
+    let regex = Regex::new(regex).map_err(|e| Error::Invalid { message: e.to_string() })?;
+    Ok(keys.into_iter().filter(|key| regex.is_match(key)).collect())
+}
+
+/// Converts a SCAN MATCH glob to an anchored regex, for matching keys client-side.
+///
+/// Supports `*`, `?`, `[...]` classes (with `^` negation and ranges) and `\` escapes,
+/// an unclosed `[` is matched literally.
+fn glob_to_regex(glob: &str) -> String {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut regex = String::from("(?s)^");
+    let mut index = 0;
+    while index < chars.len() {
+        match chars[index] {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '\\' if index + 1 < chars.len() => {
+                index += 1;
+                regex.push_str(&regex::escape(&chars[index].to_string()));
+            }
+            '[' => {
+                // Members of the class up to the closing `]`, with their escapes resolved
+                let mut members = vec![];
+                let mut end = index + 1;
+                let negated = chars.get(end) == Some(&'^');
+                if negated {
+                    end += 1;
+                }
+                while end < chars.len() && chars[end] != ']' {
+                    if chars[end] == '\\' && end + 1 < chars.len() {
+                        end += 1;
+                        members.push((chars[end], true));
+                    } else {
+                        members.push((chars[end], false));
+                    }
+                    end += 1;
+                }
+                if end >= chars.len() {
+                    regex.push_str("\\[");
+                } else if members.is_empty() {
+                    // An empty class matches nothing, a negated one any character
+                    regex.push_str(if negated { "." } else { "[a&&b]" });
+                    index = end;
+                } else {
+                    regex.push('[');
+                    if negated {
+                        regex.push('^');
+                    }
+                    for (c, escaped) in members {
+                        // Unescaped `-` is a range, other characters are taken literally
+                        if c == '-' && !escaped {
+                            regex.push(c);
+                        } else {
+                            regex.push_str(&regex::escape(&c.to_string()));
+                        }
+                    }
+                    regex.push(']');
+                    index = end;
+                }
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        index += 1;
+    }
+    regex.push('$');
+    regex
+}
 
 impl ZedisServerState {
     /// Fills the type of keys that are currently loaded but have an unknown type.
@@ -49,15 +159,21 @@ impl ZedisServerState {
         let binding = prefix.unwrap_or_default();
         let prefix = binding.as_str();
         let count = self.keys.len();
-        let binding = cx.global::<ZedisGlobalStore>().value(cx);
-        let separator = binding.key_separator();
-        let mut keys = self
+        let key_separator = self.key_separator(cx);
+        let separator = key_separator.as_str();
+        // With a type filter, folders are only shown if they contain matching keys,
+        // so the types of all keys are needed rather than just the current level.
+        let type_filtered = self.key_type_filter.is_some();
+        let keys = self
             .keys
             .iter()
             .filter_map(|(key, value)| {
                 if *value != KeyType::Unknown {
                     return None;
                 }
+                if type_filtered {
+                    return Some(key.clone());
+                }
                 if prefix.is_empty() {
                     // if no prefix, only fill keys that are not in a subdirectory
                     // or if the count is less than 1000
@@ -73,19 +189,60 @@ impl ZedisServerState {
                 }
                 Some(key.clone())
             })
+            .filter(|key| !self.pending_key_types.contains(key))
             .take(2000)
             .collect::<Vec<SharedString>>();
         debug!(prefix, size = keys.len(), "fill key types");
+        // Coalesce with the lookups already queued by a previous expand or scan
+        self.pending_key_types.extend(keys);
+        self.drain_key_types(cx);
+    }
+    /// Queues the TYPE lookups of the given keys whose type is still unknown.
+    ///
+    /// Used by the flat key list, which shows keys of every folder at once.
+    pub fn load_key_types(&mut self, keys: Vec<SharedString>, cx: &mut Context<Self>) {
+        let keys: Vec<SharedString> = keys
+            .into_iter()
+            .filter(|key| self.keys.get(key) == Some(&KeyType::Unknown) && !self.pending_key_types.contains(key))
+            .collect();
         if keys.is_empty() {
             return;
         }
+        self.pending_key_types.extend(keys);
+        self.drain_key_types(cx);
+    }
+    /// Runs the next batch of queued TYPE lookups.
+    ///
+    /// At most `KEY_TYPE_BATCH_SIZE` commands are sent per `KEY_TYPE_BATCH_INTERVAL`,
+    /// and a batch waits while a value is being loaded so that opening a key is never
+    /// stuck behind the background lookups of a large keyspace.
+    fn drain_key_types(&mut self, cx: &mut Context<Self>) {
+        if self.key_types_running || self.pending_key_types.is_empty() {
+            return;
+        }
+        let mut keys: Vec<SharedString> = self
+            .pending_key_types
+            .iter()
+            .take(KEY_TYPE_BATCH_SIZE)
+            .cloned()
+            .collect();
+        for key in keys.iter() {
+            self.pending_key_types.remove(key);
+        }
+        keys.sort_unstable();
+        self.key_types_running = true;
         let server_id = self.server_id.clone();
         let db = self.db;
-        keys.sort_unstable();
+        let generation = self.scan_generation;
+        let value_loading = self.value_loading.clone();
         // Spawn a background task to fetch types concurrently
         self.spawn(
             ServerTask::FillKeyTypes,
             move || async move {
+                while value_loading.load(Ordering::Relaxed) > 0 {
+                    smol::Timer::after(KEY_TYPE_BATCH_INTERVAL).await;
+                }
+                let started_at = Instant::now();
                 let conn = get_connection_manager().get_connection(&server_id, db).await?;
                 // Use a stream to execute commands concurrently with backpressure
                 let types: Vec<(SharedString, String)> = stream::iter(keys.iter().cloned())
@@ -104,9 +261,17 @@ impl ZedisServerState {
                     .buffer_unordered(100) // Limit concurrency to 100
                     .collect::<Vec<_>>()
                     .await;
+                if let Some(rest) = KEY_TYPE_BATCH_INTERVAL.checked_sub(started_at.elapsed()) {
+                    smol::Timer::after(rest).await;
+                }
                 Ok(types)
             },
             move |this, result, cx| {
+                // The keys were reset (new scan or server) while the batch was running
+                if this.scan_generation != generation {
+                    return;
+                }
+                this.key_types_running = false;
                 if let Ok(types) = result {
                     // Update local state with fetched types
                     for (key, value) in types {
@@ -118,6 +283,7 @@ impl ZedisServerState {
                     this.key_tree_id = Uuid::now_v7().to_string().into();
                 }
                 cx.notify();
+                this.drain_key_types(cx);
             },
             cx,
         );
@@ -139,30 +305,43 @@ impl ZedisServerState {
         let processing_keyword = keyword.clone();
         let key_scan_count = cx.global::<ZedisGlobalStore>().read(cx).key_scan_count() as u64;
         let db = self.db;
+        let key_type_filter = self.key_type_filter;
+        let type_name = key_type_filter.and_then(|t| t.redis_type_name());
+        let query_mode = self.query_mode;
         self.spawn(
             ServerTask::ScanKeys,
             move || async move {
                 let client = get_connection_manager().get_client(&server_id, db).await?;
-                let pattern = if keyword.is_empty() {
+                // Older servers don't support SCAN TYPE, the tree filters keys client-side instead
+                let server_side = type_name.is_some() && client.supports_scan_type();
+                // Regex keywords are matched client-side, every key is scanned
+                let regex = query_mode == QueryMode::Regex && !keyword.is_empty();
+                let pattern = if keyword.is_empty() || regex {
                     "*".to_string()
+                } else if query_mode == QueryMode::Pattern {
+                    keyword.to_string()
                 } else {
                     format!("*{}*", keyword)
                 };
                 // Adjust count based on keyword specificity
-                let count = if keyword.is_empty() {
+                let count = if pattern == "*" {
                     (key_scan_count / 5).max(1000)
                 } else {
                     key_scan_count
                 };
-                if let Some(cursors) = cursors {
-                    client.scan(cursors, &pattern, count).await
+                let (cursors, mut keys) = if let Some(cursors) = cursors {
+                    client.scan_with_type(cursors, &pattern, count, type_name).await?
                 } else {
-                    client.first_scan(&pattern, count).await
+                    client.first_scan_with_type(&pattern, count, type_name).await?
+                };
+                if regex {
+                    keys = filter_keys_by_regex(keys, &keyword)?;
                 }
+                Ok((cursors, keys, server_side))
             },
             move |this, result, cx| {
                 match result {
-                    Ok((cursors, keys)) => {
+                    Ok((cursors, keys, server_side)) => {
                         debug!("cursors: {cursors:?}, keys count: {}", keys.len());
                         // Check if scan is complete (all cursors returned to 0)
                         if cursors.iter().sum::<u64>() == 0 {
@@ -172,7 +351,8 @@ impl ZedisServerState {
                         } else {
                             this.cursors = Some(cursors);
                         }
-                        this.extend_keys(keys);
+                        let key_type = key_type_filter.filter(|_| server_side).unwrap_or_default();
+                        this.extend_keys(keys, key_type);
                     }
                     Err(_) => {
                         this.cursors = None;
@@ -182,12 +362,15 @@ impl ZedisServerState {
                     cx.emit(ServerEvent::KeyScanPaged);
                 }
                 // Automatically load more if we haven't reached the limit and scan isn't done
-                if this.cursors.is_some() && this.keys.len() < max {
+                if this.cursors.is_some() && this.keys.len() < max && !this.scan_cancelled {
                     // run again
                     this.scan_keys(processing_server, processing_keyword, cx);
                     return cx.notify();
                 }
                 this.scanning = false;
+                if std::mem::take(&mut this.scan_cancelled) {
+                    this.emit_info_notification(i18n_key_tree(cx, "scan_cancelled"), cx);
+                }
                 cx.notify();
                 if this.keys.len() == 1
                     && let Some(key) = this.keys.keys().next()
@@ -211,20 +394,30 @@ impl ZedisServerState {
                 return;
             }
             QueryMode::Prefix => format!("{keyword}*"),
+            QueryMode::Pattern if !keyword.is_empty() => keyword.to_string(),
+            QueryMode::Regex => "*".to_string(),
             _ => format!("*{keyword}*"),
         };
+        let regex = (self.query_mode == QueryMode::Regex && !keyword.is_empty()).then(|| keyword.clone());
         let server_id = self.server_id.clone();
         let db = self.db;
         let count = self.keys.len().max(10_000);
+        let key_type_filter = self.key_type_filter;
+        let type_name = key_type_filter.and_then(|t| t.redis_type_name());
         self.spawn(
             ServerTask::AutoRefresh,
             move || async move {
                 let client = get_connection_manager().get_client(&server_id, db).await?;
+                let server_side = type_name.is_some() && client.supports_scan_type();
 
-                client.first_scan(&pattern, count as u64).await
+                let (cursors, mut keys) = client.first_scan_with_type(&pattern, count as u64, type_name).await?;
+                if let Some(regex) = regex {
+                    keys = filter_keys_by_regex(keys, &regex)?;
+                }
+                Ok((cursors, keys, server_side))
             },
             move |this, result, cx| {
-                if let Ok((_, keys)) = result {
+                if let Ok((_, keys, server_side)) = result {
                     let new_keys_set: AHashSet<SharedString> = keys.iter().cloned().collect();
 
                     let keys_to_remove: Vec<SharedString> = this
@@ -258,7 +451,8 @@ impl ZedisServerState {
                         if keys_to_add.is_empty() {
                             this.key_tree_id = Uuid::now_v7().to_string().into();
                         } else {
-                            this.extend_keys(keys_to_add);
+                            let key_type = key_type_filter.filter(|_| server_side).unwrap_or_default();
+                            this.extend_keys(keys_to_add, key_type);
                         }
                         this.fill_key_types(None, cx);
                         cx.notify();
@@ -332,11 +526,22 @@ impl ZedisServerState {
         let server_id = self.server_id.clone();
         let db = self.db;
         let pattern = format!("{}*", prefix);
+        // The keys of the folder must also match the active keyword, SCAN takes a single pattern
+        let keyword_regex = match self.query_mode {
+            _ if self.keyword.is_empty() => None,
+            QueryMode::All => Some(glob_to_regex(&format!("*{}*", self.keyword))),
+            QueryMode::Pattern => Some(glob_to_regex(&self.keyword)),
+            QueryMode::Regex => Some(self.keyword.to_string()),
+            QueryMode::Prefix | QueryMode::Exact => None,
+        };
         let key_scan_count = cx.global::<ZedisGlobalStore>().read(cx).key_scan_count() as u64;
+        let key_type_filter = self.key_type_filter;
+        let type_name = key_type_filter.and_then(|t| t.redis_type_name());
         self.spawn(
             ServerTask::ScanPrefix,
             move || async move {
                 let client = get_connection_manager().get_client(&server_id, db).await?;
+                let server_side = type_name.is_some() && client.supports_scan_type();
                 let mut cursors: Option<Vec<u64>> = None;
                 let mut result_keys = vec![];
                 let mut done = false;
@@ -344,9 +549,11 @@ impl ZedisServerState {
                 // to gather a sufficient amount without blocking for too long.
                 for _ in 0..20 {
                     let (new_cursor, keys) = if let Some(cursors) = cursors.clone() {
-                        client.scan(cursors, &pattern, key_scan_count).await?
+                        client
+                            .scan_with_type(cursors, &pattern, key_scan_count, type_name)
+                            .await?
                     } else {
-                        client.first_scan(&pattern, key_scan_count).await?
+                        client.first_scan_with_type(&pattern, key_scan_count, type_name).await?
                     };
                     result_keys.extend(keys);
                     // Break if scan cycle finishes
@@ -356,11 +563,14 @@ impl ZedisServerState {
                     }
                     cursors = Some(new_cursor);
                 }
+                if let Some(regex) = &keyword_regex {
+                    result_keys = filter_keys_by_regex(result_keys, regex)?;
+                }
 
-                Ok((result_keys, done))
+                Ok((result_keys, done, server_side))
             },
             move |this, result, cx| {
-                if let Ok((keys, done)) = result {
+                if let Ok((keys, done, server_side)) = result {
                     debug!(
                         prefix = prefix.as_str(),
                         count = keys.len(),
@@ -370,7 +580,8 @@ impl ZedisServerState {
                     if done {
                         this.loaded_prefixes.insert(prefix.clone());
                     }
-                    this.extend_keys(keys);
+                    let key_type = key_type_filter.filter(|_| server_side).unwrap_or_default();
+                    this.extend_keys(keys, key_type);
                 }
                 cx.notify();
                 // Resolve types for the keys under this prefix
@@ -388,6 +599,25 @@ impl ZedisServerState {
     }
 
     fn get_value(&mut self, key: SharedString, task: ServerTask, cx: &mut Context<Self>) {
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        let byte_limit = store.large_value_threshold();
+        let member_limit = store.large_collection_threshold();
+        self.get_value_with_limit(key, task, Some(byte_limit), Some(member_limit), cx);
+    }
+
+    /// Loads the value of a key, strings longer than `byte_limit` bytes are only
+    /// loaded partially (the first `byte_limit` bytes) to keep the UI responsive.
+    ///
+    /// Collections with more members than `member_limit` are held back until
+    /// the user asks for them.
+    fn get_value_with_limit(
+        &mut self,
+        key: SharedString,
+        task: ServerTask,
+        byte_limit: Option<usize>,
+        member_limit: Option<usize>,
+        cx: &mut Context<Self>,
+    ) {
         if key.is_empty() {
             return;
         }
@@ -395,6 +625,11 @@ impl ZedisServerState {
         let db = self.db;
         let current_key = key.clone();
         let max_truncate_length = cx.global::<ZedisGlobalStore>().read(cx).max_truncate_length();
+        let view_mode = cx.global::<ZedisGlobalStore>().read(cx).view_mode(&key);
+        let scan_count = self.value_scan_count();
+        // Background TYPE lookups wait until the value is loaded
+        let value_loading = self.value_loading.clone();
+        value_loading.fetch_add(1, Ordering::Relaxed);
 
         self.spawn(
             task,
@@ -421,21 +656,36 @@ impl ZedisServerState {
                 };
 
                 let key_type = KeyType::from(t.as_str());
+                let size = match LEN_COMMANDS.iter().find(|(len_type, _)| *len_type == key_type) {
+                    Some((_, len_cmd)) => cmd(len_cmd).arg(key.as_str()).query_async::<usize>(&mut conn).await?,
+                    None => 0,
+                };
+                let held_back = key_type != KeyType::String && member_limit.is_some_and(|limit| size > limit);
                 let mut redis_value = match key_type {
+                    _ if held_back => Ok(RedisValue {
+                        key_type,
+                        held_back_len: Some(size),
+                        ..Default::default()
+                    }),
                     KeyType::String => {
-                        let mut data = get_redis_bytes_value(&mut conn, &key).await?;
+                        let mut data = get_redis_bytes_value(&mut conn, &key, size, byte_limit).await?;
                         data.detect_and_update(server_id.as_str(), key.as_str(), max_truncate_length);
+                        data.view_mode = view_mode;
                         Ok(RedisValue {
                             key_type: KeyType::String,
                             data: Some(RedisValueData::Bytes(Arc::new(data))),
                             ..Default::default()
                         })
                     }
-                    KeyType::List => first_load_list_value(&mut conn, &key).await,
-                    KeyType::Set => first_load_set_value(&mut conn, &key).await,
-                    KeyType::Zset => first_load_zset_value(&mut conn, &key, SortOrder::Asc).await,
-                    KeyType::Hash => first_load_hash_value(&mut conn, &key).await,
-                    KeyType::Stream => first_load_stream_value(&mut conn, &key).await,
+                    KeyType::List => first_load_list_value(&mut conn, &key, size).await,
+                    KeyType::Set => first_load_set_value(&mut conn, &key, size, scan_count).await,
+                    KeyType::Zset => first_load_zset_value(&mut conn, &key, size, SortOrder::Asc, scan_count).await,
+                    KeyType::Hash => {
+                        let with_field_ttl = client.supports(Capability::HashFieldTtl);
+                        first_load_hash_value(&mut conn, &key, size, with_field_ttl, scan_count).await
+                    }
+                    KeyType::Stream => first_load_stream_value(&mut conn, &key, size).await,
+                    KeyType::Json => first_load_json_value(&mut conn, &key).await,
                     _ => Err(Error::Invalid {
                         message: "unsupported key type".to_string(),
                     }),
@@ -447,6 +697,7 @@ impl ZedisServerState {
                 Ok(redis_value)
             },
             move |this, result, cx| {
+                value_loading.fetch_sub(1, Ordering::Relaxed);
                 if this.key.as_ref() != Some(&current_key) {
                     return;
                 }
@@ -489,6 +740,69 @@ impl ZedisServerState {
     pub fn reload_value(&mut self, key: SharedString, cx: &mut Context<Self>) {
         self.get_value(key, ServerTask::ReloadValue, cx);
     }
+    /// Loads the whole value of the selected key, or the first page of a held back
+    /// collection, ignoring the large value threshold.
+    pub fn load_full_value(&mut self, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        if let Some(value) = self.value.as_mut() {
+            value.status = RedisValueStatus::Loading;
+        }
+        cx.notify();
+        self.get_value_with_limit(key, ServerTask::ReloadValue, None, None, cx);
+    }
+    /// Loads another window of a partially loaded string with GETRANGE.
+    ///
+    /// The window size is the large value threshold, `offset` is clamped to the value length.
+    pub fn load_string_range(&mut self, offset: usize, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let Some(bytes_value) = self.value.as_ref().and_then(|value| value.bytes_value()) else {
+            return;
+        };
+        if !bytes_value.is_partial() {
+            return;
+        }
+        let total_size = bytes_value.total_size;
+        let view_mode = bytes_value.view_mode.clone();
+        let window = cx.global::<ZedisGlobalStore>().read(cx).large_value_threshold();
+        let offset = offset.min(total_size.saturating_sub(1));
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let max_truncate_length = cx.global::<ZedisGlobalStore>().read(cx).max_truncate_length();
+        let current_key = key.clone();
+        if let Some(value) = self.value.as_mut() {
+            value.status = RedisValueStatus::Loading;
+        }
+        cx.notify();
+        self.spawn(
+            ServerTask::LoadStringRange,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut data = get_redis_bytes_range(&mut conn, &key, offset, window).await?;
+                data.total_size = total_size;
+                data.detect_and_update(server_id.as_str(), key.as_str(), max_truncate_length);
+                data.view_mode = view_mode;
+                Ok(data)
+            },
+            move |this, result, cx| {
+                if this.key.as_ref() != Some(&current_key) {
+                    return;
+                }
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok(data) = result {
+                        value.data = Some(RedisValueData::Bytes(Arc::new(data)));
+                    }
+                }
+                cx.emit(ServerEvent::ValueLoaded);
+                cx.notify();
+            },
+            cx,
+        );
+    }
     pub fn is_channel_mode(&self) -> bool {
         self.value.as_ref().is_some_and(|v| v.key_type == KeyType::Channel)
     }
@@ -501,6 +815,42 @@ impl ZedisServerState {
         self.key = None;
         cx.notify();
     }
+    pub fn is_monitor_mode(&self) -> bool {
+        self.value.as_ref().is_some_and(|v| v.key_type == KeyType::Monitor)
+    }
+    /// Shows the `MONITOR` stream of the current server in place of the value editor.
+    pub fn change_monitor_mode(&mut self, cx: &mut Context<Self>) {
+        self.value = Some(RedisValue {
+            key_type: KeyType::Monitor,
+            ..Default::default()
+        });
+        self.key = None;
+        cx.notify();
+    }
+    pub fn is_config_mode(&self) -> bool {
+        self.value.as_ref().is_some_and(|v| v.key_type == KeyType::Config)
+    }
+    /// Shows the runtime configuration of the current server in place of the value editor.
+    pub fn change_config_mode(&mut self, cx: &mut Context<Self>) {
+        self.value = Some(RedisValue {
+            key_type: KeyType::Config,
+            ..Default::default()
+        });
+        self.key = None;
+        cx.notify();
+    }
+    pub fn is_search_mode(&self) -> bool {
+        self.value.as_ref().is_some_and(|v| v.key_type == KeyType::Search)
+    }
+    /// Shows the advanced search of the current server in place of the value editor.
+    pub fn change_search_mode(&mut self, cx: &mut Context<Self>) {
+        self.value = Some(RedisValue {
+            key_type: KeyType::Search,
+            ..Default::default()
+        });
+        self.key = None;
+        cx.notify();
+    }
 
     /// Publishes a message to a Redis channel.
     pub fn publish_message(&mut self, channel: SharedString, message: SharedString, cx: &mut Context<Self>) {
@@ -527,6 +877,10 @@ impl ZedisServerState {
 
     /// Selects a key and fetches its details (Type, TTL, Value).
     pub fn select_key(&mut self, key: SharedString, cx: &mut Context<Self>) {
+        // The cursor of the previous value is no longer meaningful
+        if self.key.as_ref() != Some(&key) {
+            self.set_editor_cursor(None, cx);
+        }
         self.key = Some(key.clone());
         if key.is_empty() {
             return;
@@ -545,8 +899,628 @@ impl ZedisServerState {
         cx.emit(ServerEvent::KeySelected(key.clone()));
         cx.notify();
 
+        self.add_recent_key(key.clone(), cx);
         self.get_value(key, ServerTask::Selectkey, cx);
     }
+    /// Reveal a key in the tree and select it, even when it is outside of the scanned keys.
+    ///
+    /// The parent folder of the key is scanned so that the key shows up with its siblings.
+    pub fn reveal_key(&mut self, key: SharedString, cx: &mut Context<Self>) {
+        if key.is_empty() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let separator = self.key_separator(cx).to_string();
+        let check_key = key.clone();
+        self.spawn(
+            ServerTask::RevealKey,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let key_type: String = cmd("TYPE").arg(check_key.as_str()).query_async(&mut conn).await?;
+                Ok(key_type)
+            },
+            move |this, result, cx| {
+                let Ok(key_type) = result else {
+                    return;
+                };
+                if key_type == "none" {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!("key_tree.reveal_key_not_found", key = key, locale = locale).to_string();
+                    this.emit_warning_notification(message.into(), cx);
+                    return;
+                }
+                this.keys.insert(key.clone(), KeyType::from(key_type.as_str()));
+                this.key_tree_id = Uuid::now_v7().to_string().into();
+                if let Some(index) = key.rfind(separator.as_str()) {
+                    let prefix: SharedString = key[..index + separator.len()].to_string().into();
+                    this.scan_prefix(prefix, cx);
+                }
+                cx.emit(ServerEvent::KeyRevealed(key.clone()));
+                this.select_key(key, cx);
+            },
+            cx,
+        );
+    }
+    /// Loads TYPE, OBJECT ENCODING/IDLETIME/REFCOUNT and MEMORY USAGE of a key in one pipeline.
+    ///
+    /// Commands rejected by the server are omitted instead of failing the whole request.
+    pub fn load_key_metadata(&mut self, key: SharedString, cx: &mut Context<Self>) {
+        if key.is_empty() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.key_metadata = None;
+        let loading_key = key.clone();
+        self.spawn(
+            ServerTask::LoadKeyMetadata,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let values: Vec<Value> = pipe()
+                    .cmd("TYPE")
+                    .arg(key.as_str())
+                    .cmd("OBJECT")
+                    .arg("ENCODING")
+                    .arg(key.as_str())
+                    .cmd("OBJECT")
+                    .arg("IDLETIME")
+                    .arg(key.as_str())
+                    .cmd("OBJECT")
+                    .arg("REFCOUNT")
+                    .arg(key.as_str())
+                    .cmd("MEMORY")
+                    .arg("USAGE")
+                    .arg(key.as_str())
+                    .ignore_errors()
+                    .query_async(&mut conn)
+                    .await?;
+                let mut values = values.into_iter();
+                let mut next = || values.next().unwrap_or(Value::Nil);
+                // Server errors (NOPERM, unknown subcommand...) and nil replies become None
+                fn parse<T: FromRedisValue>(value: Value) -> Option<T> {
+                    if matches!(value, Value::ServerError(_) | Value::Nil) {
+                        return None;
+                    }
+                    T::from_redis_value(value).ok()
+                }
+                Ok(KeyMetadata {
+                    key_type: parse::<String>(next()).map(SharedString::from),
+                    encoding: parse::<String>(next()).map(SharedString::from),
+                    idle_time: parse(next()),
+                    refcount: parse(next()),
+                    memory_usage: parse(next()),
+                    key,
+                    error: None,
+                })
+            },
+            move |this, result, cx| {
+                // A failure is kept as well, so the popover doesn't stay loading
+                let metadata = result.unwrap_or_else(|e| KeyMetadata {
+                    key: loading_key,
+                    error: Some(e.to_string().into()),
+                    ..Default::default()
+                });
+                this.key_metadata = Some(metadata);
+                cx.emit(ServerEvent::KeyMetadataLoaded);
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Estimates the memory usage of key tree folders with MEMORY USAGE.
+    ///
+    /// Large folders are sampled (at most `FOLDER_MEMORY_SAMPLES` keys) and the
+    /// result is extrapolated to all loaded keys of the folder. Estimates are
+    /// cached until the keys are scanned again.
+    pub fn load_folder_memory(&mut self, folders: Vec<SharedString>, cx: &mut Context<Self>) {
+        let folders: Vec<SharedString> = folders
+            .into_iter()
+            .filter(|folder| !self.folder_memory.contains_key(folder))
+            .collect();
+        if folders.is_empty() {
+            // All cached, let the views pick the estimates up
+            cx.emit(ServerEvent::FolderMemoryLoaded);
+            return;
+        }
+        let separator = self.key_separator(cx).to_string();
+        let folder_set: AHashSet<&str> = folders.iter().map(|folder| folder.as_str()).collect();
+        // Group the loaded keys by the requested folders they belong to
+        let mut folder_keys: AHashMap<SharedString, Vec<SharedString>> = AHashMap::new();
+        for key in self.keys.keys() {
+            for (index, _) in key.match_indices(separator.as_str()) {
+                let prefix = &key[..index];
+                if folder_set.contains(prefix) {
+                    folder_keys
+                        .entry(prefix.to_string().into())
+                        .or_default()
+                        .push(key.clone());
+                }
+            }
+        }
+        // Marked as loading, so the folders are not requested twice
+        for folder in &folders {
+            self.folder_memory.insert(folder.clone(), None);
+        }
+        let generation = self.scan_generation;
+        let server_id = self.server_id.clone();
+        let loading_server_id = server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::LoadFolderMemory,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                client.ensure(Capability::MemoryUsage)?;
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut result = Vec::with_capacity(folder_keys.len());
+                for (folder, keys) in folder_keys {
+                    let step = keys.len().div_ceil(FOLDER_MEMORY_SAMPLES).max(1);
+                    let mut pipeline = pipe();
+                    for key in keys.iter().step_by(step) {
+                        pipeline.cmd("MEMORY").arg("USAGE").arg(key.as_str());
+                    }
+                    let values: Vec<Value> = pipeline.ignore_errors().query_async(&mut conn).await?;
+                    // Deleted keys and rejected commands are left out of the estimate
+                    let sizes: Vec<u64> = values
+                        .into_iter()
+                        .filter_map(|value| match value {
+                            Value::Nil | Value::ServerError(_) => None,
+                            value => u64::from_redis_value(value).ok(),
+                        })
+                        .collect();
+                    if sizes.is_empty() {
+                        continue;
+                    }
+                    let total: u64 = sizes.iter().sum();
+                    let estimate = total * keys.len() as u64 / sizes.len() as u64;
+                    result.push((folder, estimate));
+                }
+                Ok(result)
+            },
+            move |this, result, cx| {
+                // The keys were scanned again in the meantime
+                if this.scan_generation != generation || this.server_id != loading_server_id {
+                    return;
+                }
+                // Failed and empty folders are not cached, they are estimated again when asked for
+                for folder in &folders {
+                    if this.folder_memory.get(folder) == Some(&None) {
+                        this.folder_memory.remove(folder);
+                    }
+                }
+                if let Ok(result) = result {
+                    for (folder, estimate) in result {
+                        this.folder_memory.insert(folder, Some(estimate));
+                    }
+                }
+                cx.emit(ServerEvent::FolderMemoryLoaded);
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Loads the memory usage of keys with MEMORY USAGE, batched in pipelines.
+    ///
+    /// Keys rejected by the server are kept as failed instead of failing the
+    /// whole batch. Results are cached until the keys are scanned again.
+    pub fn load_key_memory(&mut self, keys: Vec<SharedString>, cx: &mut Context<Self>) {
+        let keys: Vec<SharedString> = keys
+            .into_iter()
+            .filter(|key| !self.key_memory.contains_key(key) && !self.key_memory_loading.contains(key))
+            .collect();
+        if keys.is_empty() {
+            // All cached, let the views pick the sizes up
+            cx.emit(ServerEvent::KeyMemoryLoaded);
+            return;
+        }
+        self.key_memory_loading.extend(keys.iter().cloned());
+        let loading_keys = keys.clone();
+        let generation = self.scan_generation;
+        let server_id = self.server_id.clone();
+        let loading_server_id = server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::LoadKeyMemory,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                client.ensure(Capability::MemoryUsage)?;
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut result = Vec::with_capacity(keys.len());
+                for chunk in keys.chunks(KEY_MEMORY_BATCH_SIZE) {
+                    let mut pipeline = pipe();
+                    for key in chunk {
+                        pipeline.cmd("MEMORY").arg("USAGE").arg(key.as_str());
+                    }
+                    let values: Vec<Value> = pipeline.ignore_errors().query_async(&mut conn).await?;
+                    // Deleted keys (nil) and rejected commands are shown as unknown
+                    for (key, value) in chunk.iter().zip(values) {
+                        let size = match value {
+                            Value::Nil | Value::ServerError(_) => None,
+                            value => u64::from_redis_value(value).ok(),
+                        };
+                        result.push((key.clone(), size));
+                    }
+                }
+                Ok(result)
+            },
+            move |this, result, cx| {
+                // Cleared first, a failed or stale load must not keep the keys loading forever
+                for key in &loading_keys {
+                    this.key_memory_loading.remove(key);
+                }
+                // The keys were scanned again in the meantime
+                if this.scan_generation != generation || this.server_id != loading_server_id {
+                    return;
+                }
+                if let Ok(result) = result {
+                    this.key_memory.extend(result);
+                }
+                cx.emit(ServerEvent::KeyMemoryLoaded);
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Loads the TTL of keys, batched in pipelines, for the expiry warnings of the key tree.
+    ///
+    /// The TTL is kept as an expiration time, so the remaining time stays right
+    /// without reloading. Results are cached until the keys are scanned again.
+    pub fn load_key_ttls(&mut self, keys: Vec<SharedString>, cx: &mut Context<Self>) {
+        let keys: Vec<SharedString> = keys
+            .into_iter()
+            .filter(|key| !self.key_expire_at.contains_key(key) && !self.key_expire_at_loading.contains(key))
+            .collect();
+        if keys.is_empty() {
+            cx.emit(ServerEvent::KeyTtlsLoaded);
+            return;
+        }
+        self.key_expire_at_loading.extend(keys.iter().cloned());
+        let loading_keys = keys.clone();
+        let generation = self.scan_generation;
+        let server_id = self.server_id.clone();
+        let loading_server_id = server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::LoadKeyTtls,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut result = Vec::with_capacity(keys.len());
+                for chunk in keys.chunks(KEY_TTL_BATCH_SIZE) {
+                    let mut pipeline = pipe();
+                    for key in chunk {
+                        pipeline.cmd("TTL").arg(key.as_str());
+                    }
+                    let values: Vec<Value> = pipeline.ignore_errors().query_async(&mut conn).await?;
+                    let now = unix_ts();
+                    for (key, value) in chunk.iter().zip(values) {
+                        // -1 without expiry, -2 once deleted, both are shown normally
+                        let ttl = i64::from_redis_value(value).unwrap_or(-1);
+                        let expire_at = if ttl >= 0 { now + ttl } else { -1 };
+                        result.push((key.clone(), expire_at));
+                    }
+                }
+                Ok(result)
+            },
+            move |this, result, cx| {
+                // Cleared first, a failed or stale load must not keep the keys loading forever
+                for key in &loading_keys {
+                    this.key_expire_at_loading.remove(key);
+                }
+                // The keys were scanned again in the meantime
+                if this.scan_generation != generation || this.server_id != loading_server_id {
+                    return;
+                }
+                if let Ok(result) = result {
+                    this.key_expire_at.extend(result);
+                }
+                cx.emit(ServerEvent::KeyTtlsLoaded);
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Groups the keys under `prefix` that hold identical values.
+    ///
+    /// The raw values are read with DUMP batch by batch while scanning and only
+    /// their ahash is kept. Keys larger than the large value threshold (by
+    /// MEMORY USAGE) are skipped, and the scan stops after `DUPLICATE_SCAN_LIMIT`
+    /// keys. Identical values stored with different encodings are not matched.
+    pub fn find_duplicates(&mut self, prefix: SharedString, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        let threshold = store.large_value_threshold() as u64;
+        let key_scan_count = store.key_scan_count() as u64;
+        let pattern = format!("{prefix}*");
+        self.spawn(
+            ServerTask::FindDuplicates,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                client.ensure(Capability::MemoryUsage)?;
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let hasher = RandomState::new();
+                let mut groups: AHashMap<u64, DuplicateGroup> = AHashMap::new();
+                let mut report = DuplicateReport {
+                    prefix,
+                    ..Default::default()
+                };
+                let (mut cursors, mut keys) = client.first_scan_with_type(&pattern, key_scan_count, None).await?;
+                loop {
+                    for batch in keys.chunks(DUPLICATE_BATCH_SIZE) {
+                        let mut pipeline = pipe();
+                        for key in batch {
+                            pipeline.cmd("MEMORY").arg("USAGE").arg(key.as_str());
+                        }
+                        let sizes: Vec<Option<u64>> = pipeline.query_async(&mut conn).await?;
+                        let mut candidates = Vec::with_capacity(batch.len());
+                        for (key, size) in batch.iter().zip(sizes) {
+                            match size {
+                                Some(size) if size > threshold => report.skipped += 1,
+                                Some(size) => candidates.push((key.clone(), size)),
+                                // Deleted since the scan
+                                None => {}
+                            }
+                        }
+                        if candidates.is_empty() {
+                            continue;
+                        }
+                        let mut pipeline = pipe();
+                        for (key, _) in &candidates {
+                            pipeline.cmd("DUMP").arg(key.as_str());
+                        }
+                        let dumps: Vec<Option<Vec<u8>>> = pipeline.query_async(&mut conn).await?;
+                        for ((key, size), dump) in candidates.into_iter().zip(dumps) {
+                            let Some(dump) = dump else {
+                                continue;
+                            };
+                            report.checked += 1;
+                            let group = groups.entry(hasher.hash_one(&dump)).or_default();
+                            group.keys.push(key);
+                            group.memory += size;
+                        }
+                    }
+                    if cursors.iter().sum::<u64>() == 0 {
+                        break;
+                    }
+                    if report.checked + report.skipped >= DUPLICATE_SCAN_LIMIT {
+                        report.sampled = true;
+                        break;
+                    }
+                    (cursors, keys) = client.scan(cursors, &pattern, key_scan_count).await?;
+                }
+                let mut groups: Vec<DuplicateGroup> = groups
+                    .into_values()
+                    .filter(|group| group.keys.len() > 1)
+                    .map(|mut group| {
+                        group.keys.sort_unstable();
+                        group
+                    })
+                    .collect();
+                groups.sort_unstable_by(|a, b| b.memory.cmp(&a.memory));
+                report.groups = groups;
+                Ok(report)
+            },
+            move |_this, result, cx| {
+                if let Ok(report) = result {
+                    cx.emit(ServerEvent::DuplicatesFound(Arc::new(report)));
+                }
+            },
+            cx,
+        );
+    }
+    /// Ranks the keys under `prefix` by coldness and keeps the `COLD_KEYS_MAX` coldest.
+    ///
+    /// LFU policies are ranked with `OBJECT FREQ`, the others with `OBJECT IDLETIME`
+    /// (which fails with LFU). The scan stops after `COLD_KEYS_SCAN_LIMIT` keys.
+    pub fn find_cold_keys(&mut self, prefix: SharedString, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_scan_count = cx.global::<ZedisGlobalStore>().read(cx).key_scan_count() as u64;
+        let pattern = format!("{prefix}*");
+        self.spawn(
+            ServerTask::FindColdKeys,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let config: AHashMap<String, String> = cmd("CONFIG")
+                    .arg("GET")
+                    .arg("maxmemory-policy")
+                    .query_async(&mut conn)
+                    .await
+                    .unwrap_or_default();
+                let mut policy = config.get("maxmemory-policy").cloned().unwrap_or_default();
+                // CONFIG is often disabled on managed servers, INFO reports the policy too
+                if policy.is_empty() {
+                    let info: String = cmd("INFO").arg("memory").query_async(&mut conn).await?;
+                    policy = info
+                        .lines()
+                        .find_map(|line| line.strip_prefix("maxmemory_policy:"))
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string();
+                }
+                let (metric, subcommand) = if policy.contains("lfu") {
+                    (ColdKeyMetric::Freq, "FREQ")
+                } else {
+                    (ColdKeyMetric::IdleTime, "IDLETIME")
+                };
+                let mut report = ColdKeysReport {
+                    prefix,
+                    policy: policy.into(),
+                    metric,
+                    ..Default::default()
+                };
+                let mut keys = vec![];
+                let (mut cursors, mut batch) = client.first_scan_with_type(&pattern, key_scan_count, None).await?;
+                loop {
+                    if !batch.is_empty() {
+                        let mut pipeline = pipe();
+                        for key in &batch {
+                            pipeline.cmd("OBJECT").arg(subcommand).arg(key.as_str());
+                        }
+                        let values: Vec<Value> = pipeline.ignore_errors().query_async(&mut conn).await?;
+                        for (key, value) in batch.into_iter().zip(values) {
+                            // Deleted keys and rejected commands are left out
+                            if let Value::Nil | Value::ServerError(_) = value {
+                                continue;
+                            }
+                            if let Ok(value) = u64::from_redis_value(value) {
+                                keys.push((key, value));
+                            }
+                        }
+                    }
+                    if cursors.iter().sum::<u64>() == 0 {
+                        break;
+                    }
+                    if keys.len() >= COLD_KEYS_SCAN_LIMIT {
+                        report.sampled = true;
+                        break;
+                    }
+                    (cursors, batch) = client.scan(cursors, &pattern, key_scan_count).await?;
+                }
+                report.checked = keys.len();
+                match metric {
+                    ColdKeyMetric::IdleTime => keys.sort_unstable_by(|a, b| b.1.cmp(&a.1)),
+                    ColdKeyMetric::Freq => keys.sort_unstable_by(|a, b| a.1.cmp(&b.1)),
+                }
+                keys.truncate(COLD_KEYS_MAX);
+                report.keys = keys;
+                Ok(report)
+            },
+            move |_this, result, cx| {
+                if let Ok(report) = result {
+                    cx.emit(ServerEvent::ColdKeysFound(Arc::new(report)));
+                }
+            },
+            cx,
+        );
+    }
+    /// Renames a key, RENAMENX is used unless `overwrite` so an existing key is never replaced by mistake.
+    pub fn rename_key(&mut self, old: SharedString, new: SharedString, overwrite: bool, cx: &mut Context<Self>) {
+        if old.is_empty() || new.is_empty() || old == new {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let old_key = old.clone();
+        let new_key = new.clone();
+        self.spawn(
+            ServerTask::RenameKey,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                if overwrite {
+                    let _: () = cmd("RENAME")
+                        .arg(old_key.as_str())
+                        .arg(new_key.as_str())
+                        .query_async(&mut conn)
+                        .await?;
+                    return Ok(());
+                }
+                let renamed: bool = cmd("RENAMENX")
+                    .arg(old_key.as_str())
+                    .arg(new_key.as_str())
+                    .query_async(&mut conn)
+                    .await?;
+                if !renamed {
+                    return Err(Error::Invalid {
+                        message: format!("Key {new_key} already exists"),
+                    });
+                }
+                Ok(())
+            },
+            move |this, result, cx| {
+                if let Ok(()) = result {
+                    let key_type = this.keys.remove(&old).unwrap_or_default();
+                    this.keys.insert(new.clone(), key_type);
+                    // Force refresh of the key tree view
+                    this.key_tree_id = Uuid::now_v7().to_string().into();
+                    cx.emit(ServerEvent::KeyRenamed {
+                        old: old.clone(),
+                        new: new.clone(),
+                    });
+                    // The editor follows the renamed key
+                    if this.key.as_ref() == Some(&old) {
+                        this.select_key(new, cx);
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Copies a key to another server (or db) with DUMP and RESTORE, keeping its TTL.
+    ///
+    /// Without `replace` an existing key on the target is left untouched and
+    /// `ServerEvent::KeyCopyConflict` is emitted, so the user can confirm the replacement.
+    pub fn copy_key_to_server(&mut self, key: SharedString, target: CopyTarget, replace: bool, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let copy_key = key.clone();
+        let copy_target = target.clone();
+        self.spawn(
+            ServerTask::CopyKeyToServer,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let (dump, ttl): (Option<Vec<u8>>, i64) = pipe()
+                    .cmd("DUMP")
+                    .arg(copy_key.as_str())
+                    .cmd("PTTL")
+                    .arg(copy_key.as_str())
+                    .query_async(&mut conn)
+                    .await?;
+                let Some(dump) = dump else {
+                    return Err(Error::Invalid {
+                        message: format!("Key {copy_key} does not exist"),
+                    });
+                };
+                let target_client = get_connection_manager()
+                    .get_client(&copy_target.server_id, copy_target.db)
+                    .await?;
+                if target_client.access_mode() != AccessMode::ReadWrite {
+                    return Err(Error::Invalid {
+                        message: format!("{} is read-only", copy_target.name),
+                    });
+                }
+                let mut target_conn = target_client.connection();
+                let mut command = cmd("RESTORE");
+                // 0 restores the key without expiry
+                command.arg(copy_key.as_str()).arg(ttl.max(0)).arg(dump);
+                if replace {
+                    command.arg("REPLACE");
+                }
+                match command.query_async::<()>(&mut target_conn).await {
+                    Ok(()) => Ok(true),
+                    Err(e) if e.code() == Some("BUSYKEY") => Ok(false),
+                    Err(e) => Err(e.into()),
+                }
+            },
+            move |this, result, cx| match result {
+                Ok(true) => {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!(
+                        "key_tree.copy_to_server_success",
+                        key = key,
+                        server = target.name,
+                        db = target.db,
+                        locale = locale
+                    )
+                    .to_string();
+                    this.emit_success_notification(message.into(), i18n_key_tree(cx, "copy_to_server"), cx);
+                    cx.global::<ZedisGlobalStore>().clone().update(cx, |_state, cx| {
+                        cx.emit(GlobalEvent::KeysAdded(target.server_id, target.db, vec![key]));
+                    });
+                }
+                Ok(false) => cx.emit(ServerEvent::KeyCopyConflict(key, target)),
+                Err(_) => {}
+            },
+            cx,
+        );
+    }
+    /// Adds keys written outside of the scan, e.g. copied from another server.
+    pub fn add_keys(&mut self, keys: Vec<SharedString>, cx: &mut Context<Self>) {
+        self.extend_keys(keys, KeyType::Unknown);
+        cx.notify();
+    }
     pub fn delete_key(&mut self, key: SharedString, cx: &mut Context<Self>) {
         let server_id = self.server_id.clone();
         let db = self.db;
@@ -575,42 +1549,123 @@ impl ZedisServerState {
         );
     }
 
-    pub fn delete_folder(&mut self, folder: SharedString, cx: &mut Context<Self>) {
+    /// Scans the keys a bulk operation would affect, without any write.
+    ///
+    /// The operation runs on the previewed keys once confirmed, so destructive
+    /// operations only touch what the user has seen.
+    pub fn preview_bulk_operation(&mut self, operation: BulkOperation, cx: &mut Context<Self>) {
         let server_id = self.server_id.clone();
         let db = self.db;
-        let separator = cx.global::<ZedisGlobalStore>().value(cx).key_separator().to_string();
-        let prefix = format!("{folder}{separator}");
-        let pattern = format!("{prefix}*");
+        let separator = self.key_separator(cx).to_string();
+        let pattern: SharedString = match &operation {
+            BulkOperation::DeleteFolder(folder) | BulkOperation::ExpireFolder(folder, _) => {
+                format!("{folder}{separator}*").into()
+            }
+            BulkOperation::ImportKeys(path, _) => path.to_string_lossy().to_string().into(),
+        };
         self.spawn(
-            ServerTask::DeleteKeys,
+            ServerTask::PreviewBulkOperation,
             move || async move {
                 let client = get_connection_manager().get_client(&server_id, db).await?;
-                let count = 10_000;
-                let mut cursors: Option<Vec<u64>> = None;
-                // Attempt to fetch keys in a loop (up to 20 iterations)
-                // to gather a sufficient amount without blocking for too long.
-                for _ in 0..20 {
-                    let (new_cursor, keys) = if let Some(cursors) = cursors.clone() {
-                        client.scan(cursors, &pattern, count).await?
-                    } else {
-                        client.first_scan(&pattern, count).await?
-                    };
-                    client.unlike_keys(keys).await?;
-
-                    // Break if scan cycle finishes
-                    if new_cursor.iter().sum::<u64>() == 0 {
-                        break;
+                if let BulkOperation::ImportKeys(path, _) = &operation {
+                    // The file is validated here, so a broken export never writes half of its keys
+                    let imported = read_import_file(path)?;
+                    if imported.iter().any(|(_, key_type, ..)| *key_type == KeyType::Json) {
+                        client.ensure(Capability::Json)?;
                     }
-                    cursors = Some(new_cursor);
+                    let mut conn = client.connection();
+                    let mut existing = 0;
+                    for batch in imported.chunks(BULK_EXISTS_BATCH_SIZE) {
+                        let mut pipeline = pipe();
+                        for (key, ..) in batch {
+                            pipeline.cmd("EXISTS").arg(key);
+                        }
+                        let exists: Vec<bool> = pipeline.query_async(&mut conn).await?;
+                        existing += exists.into_iter().filter(|exists| *exists).count();
+                    }
+                    return Ok(BulkPreview {
+                        operation,
+                        pattern,
+                        keys: imported.into_iter().map(|(key, ..)| key.into()).collect(),
+                        complete: true,
+                        existing,
+                    });
+                }
+                let (keys, complete) = client.scan_rounds(&pattern, BULK_SCAN_COUNT, BULK_SCAN_ROUNDS).await?;
+                Ok(BulkPreview {
+                    operation,
+                    pattern,
+                    keys,
+                    complete,
+                    existing: 0,
+                })
+            },
+            move |_this, result, cx| {
+                if let Ok(preview) = result {
+                    cx.emit(ServerEvent::BulkOperationPreviewed(Arc::new(preview)));
                 }
+            },
+            cx,
+        );
+    }
 
-                Ok(())
+    /// Sets the TTL of `keys`, a zero TTL removes it.
+    ///
+    /// Keys deleted since the preview are ignored, PEXPIRE on a missing key is a no-op.
+    pub fn expire_keys(&mut self, keys: Vec<SharedString>, ttl: Duration, cx: &mut Context<Self>) {
+        if keys.is_empty() || self.readonly() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let persist = ttl.is_zero();
+        let updated_keys = keys.clone();
+        self.spawn(
+            ServerTask::ExpireKeys,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut failed = 0;
+                for batch in keys.chunks(BULK_EXISTS_BATCH_SIZE) {
+                    let mut pipeline = pipe();
+                    for key in batch {
+                        if persist {
+                            pipeline.cmd("PERSIST").arg(key.as_str());
+                        } else {
+                            // A sub-second TTL would be EXPIRE 0, deleting the keys
+                            pipeline.cmd("PEXPIRE").arg(key.as_str()).arg(ttl.as_millis() as u64);
+                        }
+                    }
+                    let values: Vec<Value> = pipeline.ignore_errors().query_async(&mut conn).await?;
+                    failed += values
+                        .iter()
+                        .filter(|value| matches!(value, Value::ServerError(_)))
+                        .count();
+                }
+                Ok(failed)
             },
             move |this, result, cx| {
-                if let Ok(()) = result {
-                    this.keys.retain(|key, _| !key.starts_with(prefix.as_str()));
-                    // Force refresh of the key tree view
-                    this.key_tree_id = Uuid::now_v7().to_string().into();
+                let Ok(failed) = result else {
+                    return;
+                };
+                // The expiry warnings of the tree are reloaded for these keys
+                for key in &updated_keys {
+                    this.key_expire_at.remove(key);
+                }
+                cx.emit(ServerEvent::KeyTtlsLoaded);
+                if let Some(key) = this.key.clone().filter(|key| updated_keys.contains(key)) {
+                    this.reload_value(key, cx);
+                }
+                let message = if failed > 0 {
+                    i18n_key_tree(cx, "set_folder_ttl_failed")
+                } else if persist {
+                    i18n_editor(cx, "ttl_removed")
+                } else {
+                    i18n_editor(cx, "ttl_updated")
+                };
+                if failed > 0 {
+                    this.emit_warning_notification(message, cx);
+                } else {
+                    this.emit_info_notification(message, cx);
                 }
                 cx.notify();
             },
@@ -649,10 +1704,9 @@ impl ZedisServerState {
         self.delete_key(key, cx);
     }
     /// Updates the TTL (expiration) for a key.
+    ///
+    /// An empty or zero TTL removes the expiration with PERSIST, EXPIRE 0 would delete the key.
     pub fn update_key_ttl(&mut self, key: SharedString, ttl: SharedString, cx: &mut Context<Self>) {
-        if ttl.is_empty() {
-            return;
-        }
         let server_id = self.server_id.clone();
         let db = self.db;
         let Some(value) = self.value.as_mut() else {
@@ -663,17 +1717,25 @@ impl ZedisServerState {
 
         let mut new_ttl = Duration::ZERO;
         let mut parse_fail_error = "".to_string();
-        match parse_duration(&ttl) {
-            Ok(ttl) => new_ttl = ttl,
-            Err(err) => {
-                parse_fail_error = err.to_string();
+        if !ttl.trim().is_empty() {
+            match parse_duration(&ttl) {
+                Ok(ttl) => new_ttl = ttl,
+                Err(err) => {
+                    parse_fail_error = err.to_string();
+                }
             }
         }
+        let persist = new_ttl.is_zero();
+        // Rounded up, a sub-second TTL still shows the key as expiring
+        let expire_in = new_ttl.as_millis().div_ceil(1000) as i64;
 
-        if !new_ttl.is_zero() {
-            value.expire_at = Some(unix_ts() + new_ttl.as_secs() as i64);
+        if persist {
+            value.expire_at = Some(-1);
+        } else {
+            value.expire_at = Some(unix_ts() + expire_in);
         }
         cx.notify();
+        let updated_key = key.clone();
         self.spawn(
             ServerTask::UpdateKeyTtl,
             move || async move {
@@ -683,11 +1745,16 @@ impl ZedisServerState {
                     });
                 }
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
-                let _: () = cmd("EXPIRE")
-                    .arg(key.as_str())
-                    .arg(new_ttl.as_secs())
-                    .query_async(&mut conn)
-                    .await?;
+                if persist {
+                    let _: () = cmd("PERSIST").arg(key.as_str()).query_async(&mut conn).await?;
+                } else {
+                    // PEXPIRE keeps a sub-second TTL, EXPIRE would round it down to 0 and delete the key
+                    let _: () = cmd("PEXPIRE")
+                        .arg(key.as_str())
+                        .arg(new_ttl.as_millis() as u64)
+                        .query_async(&mut conn)
+                        .await?;
+                }
                 Ok(ttl)
             },
             move |this, result, cx| {
@@ -697,6 +1764,61 @@ impl ZedisServerState {
                     }
                     value.status = RedisValueStatus::Idle;
                 }
+                if result.is_ok() {
+                    // Keep the expiry warning of the key tree in sync
+                    if let Some(expire_at) = this.key_expire_at.get_mut(&updated_key) {
+                        *expire_at = if persist { -1 } else { unix_ts() + expire_in };
+                        cx.emit(ServerEvent::KeyTtlsLoaded);
+                    }
+                    let message = if persist {
+                        i18n_editor(cx, "ttl_removed")
+                    } else {
+                        i18n_editor(cx, "ttl_updated")
+                    };
+                    this.emit_info_notification(message, cx);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Confirms with EXISTS that the selected key is gone once its TTL elapsed.
+    ///
+    /// The local countdown may be ahead of the server (clock skew, TTL changed by
+    /// another client), so a key which still exists is reloaded instead.
+    pub fn check_value_expired(&mut self, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let current_key = key.clone();
+        self.spawn(
+            ServerTask::CheckValueExpired,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let exists: bool = cmd("EXISTS").arg(key.as_str()).query_async(&mut conn).await?;
+                Ok(exists)
+            },
+            move |this, result, cx| {
+                if this.key.as_ref() != Some(&current_key) {
+                    return;
+                }
+                match result {
+                    Ok(true) => this.reload_value(current_key, cx),
+                    Ok(false) => {
+                        if let Some(value) = this.value.as_mut() {
+                            value.expire_at = Some(-2);
+                            value.data = None;
+                        }
+                        this.keys.remove(&current_key);
+                        this.key_tree_id = Uuid::now_v7().to_string().into();
+                        this.emit_warning_notification(i18n_editor(cx, "key_expired"), cx);
+                        cx.emit(ServerEvent::ValueUpdated);
+                    }
+                    Err(_) => {}
+                }
                 cx.notify();
             },
             cx,
@@ -724,14 +1846,12 @@ impl ZedisServerState {
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 let exists: bool = cmd("EXISTS").arg(key.as_str()).query_async(&mut conn).await?;
-                let ttl_duration = if ttl.is_empty() {
-                    None
-                } else if let Ok(secs) = ttl.parse::<u64>() {
-                    Some(Duration::from_secs(secs))
-                } else {
-                    let ttl = humantime::parse_duration(&ttl).map_err(|e| Error::Invalid { message: e.to_string() })?;
-                    Some(ttl)
-                };
+                // Empty or zero means no TTL, PEXPIRE 0 would delete the new key
+                let ttl_duration = match ttl.trim() {
+                    "" => None,
+                    ttl => Some(parse_duration(ttl)?),
+                }
+                .filter(|ttl| !ttl.is_zero());
 
                 if exists {
                     return Err(Error::Invalid {
@@ -754,9 +1874,9 @@ impl ZedisServerState {
                 let _: () = c.query_async(&mut conn).await?;
 
                 if let Some(ttl_duration) = ttl_duration {
-                    let _: () = cmd("EXPIRE")
+                    let _: () = cmd("PEXPIRE")
                         .arg(key.as_str())
-                        .arg(ttl_duration.as_secs())
+                        .arg(ttl_duration.as_millis() as u64)
                         .query_async(&mut conn)
                         .await?;
                 }
@@ -774,4 +1894,119 @@ impl ZedisServerState {
             cx,
         );
     }
+    /// Executes write commands parsed from pasted text in order.
+    ///
+    /// Stops at the first failing command, the keys written so far are kept
+    /// and added to the key tree.
+    pub fn import_commands(&mut self, commands: Vec<ImportCommand>, cx: &mut Context<Self>) {
+        if commands.is_empty() || self.readonly() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::ImportCommands,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut count = 0;
+                let mut keys = vec![];
+                let mut error = None;
+                for command in &commands {
+                    let result: redis::RedisResult<Value> =
+                        cmd(&command.name).arg(&command.args).query_async(&mut conn).await;
+                    if let Err(e) = result {
+                        error = Some(format!("Line {}: {e}", command.line));
+                        break;
+                    }
+                    count += 1;
+                    keys.extend(
+                        command
+                            .keys()
+                            .into_iter()
+                            .map(|key| SharedString::from(key.to_string())),
+                    );
+                }
+                Ok((count, keys, error))
+            },
+            move |this, result, cx| {
+                let Ok((count, keys, error)) = result else {
+                    return;
+                };
+                if !keys.is_empty() {
+                    for key in keys {
+                        this.keys.entry(key).or_insert(KeyType::Unknown);
+                    }
+                    this.key_tree_id = Uuid::now_v7().to_string().into();
+                }
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale().to_string();
+                if let Some(error) = error {
+                    this.emit_error_notification(
+                        t!(
+                            "key_tree.import_commands_failed",
+                            count = count,
+                            error = error,
+                            locale = locale
+                        )
+                        .to_string()
+                        .into(),
+                        cx,
+                    );
+                } else {
+                    this.emit_success_notification(
+                        t!("key_tree.import_commands_success_tips", count = count, locale = locale)
+                            .to_string()
+                            .into(),
+                        i18n_key_tree(cx, "import_commands_success"),
+                        cx,
+                    );
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob_match(glob: &str, key: &str) -> bool {
+        Regex::new(&glob_to_regex(glob)).unwrap().is_match(key)
+    }
+
+    #[test]
+    fn test_glob_wildcards() {
+        assert!(glob_match("*user*", "app:user:1"));
+        assert!(!glob_match("*user*", "app:order:1"));
+        assert!(glob_match("user:?", "user:1"));
+        assert!(!glob_match("user:?", "user:10"));
+        assert!(glob_match("user:*", "user:\nline"));
+        // Regex metacharacters are literal in a glob
+        assert!(glob_match("a.b+(c)", "a.b+(c)"));
+        assert!(!glob_match("a.b", "axb"));
+    }
+
+    #[test]
+    fn test_glob_classes() {
+        assert!(glob_match("h[ae]llo", "hello"));
+        assert!(!glob_match("h[ae]llo", "hillo"));
+        assert!(glob_match("h[^e]llo", "hallo"));
+        assert!(!glob_match("h[^e]llo", "hello"));
+        assert!(glob_match("id:[0-9]", "id:7"));
+        assert!(!glob_match("id:[0-9]", "id:a"));
+        assert!(glob_match("id:[\\-x]", "id:-"));
+        assert!(glob_match("tag:[&~]", "tag:~"));
+        assert!(!glob_match("a[]", "a"));
+    }
+
+    #[test]
+    fn test_glob_escapes() {
+        assert!(glob_match("a\\*b", "a*b"));
+        assert!(!glob_match("a\\*b", "axb"));
+        assert!(glob_match("a\\?", "a?"));
+        // An unclosed class is matched literally
+        assert!(glob_match("a[b", "a[b"));
+        assert!(!glob_match("a[b", "ab"));
+    }
 }
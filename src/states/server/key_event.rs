@@ -0,0 +1,202 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ServerEvent, ServerTask, ZedisServerState};
+use crate::{
+    connection::get_connection_manager,
+    error::Error,
+    states::{KeyType, get_session_option, save_session_option},
+};
+use ahash::AHashMap;
+use futures::StreamExt;
+use gpui::{SharedString, prelude::*};
+use redis::cmd;
+use std::pin::pin;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Events read at most from the stream per update of the key tree
+const KEY_EVENT_BATCH_SIZE: usize = 256;
+/// Key-event notifications and the classes of the commands creating or removing keys,
+/// `t` for streams and `d` for module types such as JSON, all included in `A`
+const REQUIRED_KEYSPACE_EVENTS: &str = "Eg$lshztdxe";
+
+/// Events meaning the key doesn't exist anymore, any other one means it exists
+const KEY_REMOVED_EVENTS: &[&str] = &["del", "expired", "evicted", "rename_from", "move_from"];
+
+/// Whether `notify-keyspace-events` publishes the key events the watcher needs,
+/// `A` being the alias of every class
+fn keyspace_events_enabled(flags: &str) -> bool {
+    flags.contains('E')
+        && (flags.contains('A')
+            || REQUIRED_KEYSPACE_EVENTS
+                .chars()
+                .all(|flag| flag == 'E' || flags.contains(flag)))
+}
+
+impl ZedisServerState {
+    /// Whether the key tree follows the key events of the server
+    pub fn watching_key_events(&self) -> bool {
+        self.key_events_stop.is_some()
+    }
+    /// Watch the key events of the server, or stop watching them, and remember it for the server
+    pub fn toggle_watch_key_events(&mut self, cx: &mut Context<Self>) {
+        let watch = !self.watching_key_events();
+        if let Ok(mut option) = get_session_option(&self.server_id) {
+            option.watch_key_events = Some(watch);
+            save_session_option(&self.server_id, option, cx);
+        }
+        if watch {
+            self.watch_key_events(cx);
+        } else {
+            self.stop_watch_key_events();
+        }
+        cx.notify();
+    }
+    /// Stop watching, dropping the sender ends the loop and closes its connection
+    pub(super) fn stop_watch_key_events(&mut self) {
+        self.key_events_stop = None;
+    }
+    /// Subscribe to `__keyevent@<db>__:*` over a dedicated connection and apply
+    /// the events to the loaded keys, until `stop_watch_key_events`.
+    pub(super) fn watch_key_events(&mut self, cx: &mut Context<Self>) {
+        let (stop_tx, stop_rx) = smol::channel::bounded::<()>(1);
+        self.key_events_stop = Some(stop_tx);
+        let server_id = self.server_id.clone();
+        let db = self.db;
+
+        cx.spawn(async move |this, cx| {
+            let result: Result<_, Error> = cx
+                .background_spawn(async move {
+                    // CONFIG may be disabled, the events are then watched as they are
+                    let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                    let config: Option<AHashMap<String, String>> = cmd("CONFIG")
+                        .arg("GET")
+                        .arg("notify-keyspace-events")
+                        .query_async(&mut conn)
+                        .await
+                        .ok();
+                    let flags = config.and_then(|mut config| config.remove("notify-keyspace-events"));
+                    let mut pubsub = get_connection_manager().get_pubsub_connection(&server_id).await?;
+                    pubsub.psubscribe(format!("__keyevent@{db}__:*")).await?;
+                    Ok((pubsub, flags))
+                })
+                .await;
+            // Stopped, or replaced by the watcher of another server, while connecting
+            if stop_rx.is_closed() {
+                return;
+            }
+            let pubsub = match result {
+                Ok((pubsub, flags)) => {
+                    if let Some(flags) = flags.filter(|flags| !keyspace_events_enabled(flags)) {
+                        let _ = this.update(cx, |_this, cx| {
+                            cx.emit(ServerEvent::KeyspaceEventsDisabled(flags.into()));
+                        });
+                    }
+                    pubsub
+                }
+                Err(e) => {
+                    error!(error = %e, "watch key events fail");
+                    let _ = this.update(cx, |this, cx| {
+                        this.stop_watch_key_events();
+                        this.add_error_message("watch_key_events".to_string(), e.to_string(), cx);
+                        cx.notify();
+                    });
+                    return;
+                }
+            };
+            info!(db, "watching key events");
+
+            let mut stream = pin!(pubsub.into_on_message().ready_chunks(KEY_EVENT_BATCH_SIZE));
+            loop {
+                // The stop is polled first, so no event is applied once the watcher is stopped
+                let messages = smol::future::or(
+                    async {
+                        // Closed when the sender is dropped
+                        let _ = stop_rx.recv().await;
+                        None
+                    },
+                    stream.next(),
+                )
+                .await;
+                let Some(messages) = messages else {
+                    break;
+                };
+                let events: Vec<(String, SharedString)> = messages
+                    .into_iter()
+                    .filter_map(|msg| {
+                        let event = msg.get_channel_name().rsplit(':').next()?.to_string();
+                        let key: String = msg.get_payload().ok()?;
+                        Some((event, key.into()))
+                    })
+                    .collect();
+                let result = this.update(cx, |this, cx| {
+                    this.apply_key_events(events, cx);
+                });
+                if result.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+    /// Insert the written keys and remove the deleted or expired ones.
+    ///
+    /// New keys are only inserted while the tree lists every key, they may
+    /// not match the keyword or the type filter otherwise.
+    fn apply_key_events(&mut self, events: Vec<(String, SharedString)>, cx: &mut Context<Self>) {
+        let list_all = self.keyword.is_empty() && self.key_type_filter.is_none();
+        let mut added = Vec::new();
+        let mut removed = false;
+        for (event, key) in events {
+            if KEY_REMOVED_EVENTS.contains(&event.as_str()) {
+                removed |= self.keys.remove(&key).is_some();
+            } else if list_all && !self.keys.contains_key(&key) {
+                added.push(key);
+            }
+        }
+        if added.is_empty() && !removed {
+            return;
+        }
+        if removed {
+            self.key_tree_id = Uuid::now_v7().to_string().into();
+        }
+        self.extend_keys(added, KeyType::Unknown);
+        cx.notify();
+    }
+    /// Add every key-event class (`A`) to `notify-keyspace-events`, the watcher needs most of them
+    pub fn enable_keyspace_events(&mut self, flags: SharedString, cx: &mut Context<Self>) {
+        if self.readonly() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::EnableKeyspaceEvents,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let _: () = cmd("CONFIG")
+                    .arg("SET")
+                    .arg("notify-keyspace-events")
+                    // `A` instead of the required classes, `d` is rejected by servers before Redis 7
+                    .arg(format!("{flags}AE"))
+                    .query_async(&mut conn)
+                    .await?;
+                Ok(())
+            },
+            |_this, _result, _cx| {},
+            cx,
+        );
+    }
+}
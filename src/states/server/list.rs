@@ -51,8 +51,7 @@ async fn get_redis_list_value(
 
 /// Initial load for a List key.
 /// Fetches the total length (LLEN) and the first 100 items.
-pub(crate) async fn first_load_list_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
-    let size: usize = cmd("LLEN").arg(key).query_async(conn).await?;
+pub(crate) async fn first_load_list_value(conn: &mut RedisAsyncConn, key: &str, size: usize) -> Result<RedisValue> {
     let values = get_redis_list_value(conn, key, 0, 99).await?;
     Ok(RedisValue {
         key_type: KeyType::List,
@@ -0,0 +1,59 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ServerTask, ZedisServerState};
+use crate::db::get_recent_keys_manager;
+use gpui::{SharedString, prelude::*};
+
+impl ZedisServerState {
+    /// Recently viewed keys of the server, the last opened first
+    pub fn recent_keys(&self) -> &[SharedString] {
+        &self.recent_keys
+    }
+    /// Load the recently viewed keys of the selected server from the local database
+    pub(super) fn load_recent_keys(&mut self) {
+        self.recent_keys = get_recent_keys_manager()
+            .records(self.server_id.as_str())
+            .unwrap_or_default();
+    }
+    /// Move the opened key to the top of the recently viewed keys
+    pub(super) fn add_recent_key(&mut self, key: SharedString, cx: &mut Context<Self>) {
+        if self.recent_keys.first() == Some(&key) {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        self.spawn(
+            ServerTask::SaveRecentKey,
+            move || async move { get_recent_keys_manager().add_record(&server_id, &key) },
+            move |this, result, cx| {
+                if let Ok(recent_keys) = result {
+                    this.recent_keys = recent_keys;
+                    cx.notify();
+                }
+            },
+            cx,
+        );
+    }
+    pub fn clear_recent_keys(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        self.recent_keys.clear();
+        self.spawn(
+            ServerTask::SaveRecentKey,
+            move || async move { get_recent_keys_manager().clear_history(&server_id) },
+            |_this, _result, _cx| {},
+            cx,
+        );
+        cx.notify();
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ServerEvent, ServerTask, ZedisServerState, value::KeyType};
+use crate::{
+    connection::get_connection_manager,
+    states::{ZedisGlobalStore, i18n_search_editor},
+};
+use gpui::{SharedString, prelude::*};
+use redis::{FromRedisValue, Value, pipe};
+use rust_i18n::t;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Keys loaded at least per page of the advanced search
+const SEARCH_PAGE_SIZE: usize = 200;
+/// Max SCAN calls per page, so that a sparse pattern doesn't scan the whole db at once
+const SEARCH_MAX_ROUNDS: usize = 50;
+
+/// A key found by the advanced search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub key: SharedString,
+    pub key_type: KeyType,
+    /// TTL in seconds, -1 without expiry
+    pub ttl: i64,
+    /// `MEMORY USAGE` in bytes, `None` when the server rejects the command
+    pub size: Option<u64>,
+}
+
+/// Keys of one page of the advanced search, see `search_keys`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchPage {
+    pub pattern: SharedString,
+    pub key_type: Option<KeyType>,
+    pub results: Vec<SearchResult>,
+    /// Cursors of the next page, `None` once the scan cycle finished
+    pub cursors: Option<Vec<u64>>,
+}
+
+/// Quote a CSV field when it holds a separator, a quote or a line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl ZedisServerState {
+    /// Scan the keys matching `pattern`, with their type, TTL and memory usage.
+    ///
+    /// Pass the `cursors` of the previous page to load the next one. The type is
+    /// filtered with `SCAN ... TYPE`, or client-side on the servers without it.
+    pub fn search_keys(
+        &mut self,
+        pattern: SharedString,
+        key_type: Option<KeyType>,
+        cursors: Option<Vec<u64>>,
+        cx: &mut Context<Self>,
+    ) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_scan_count = cx.global::<ZedisGlobalStore>().read(cx).key_scan_count() as u64;
+        let type_name = key_type.and_then(|key_type| key_type.redis_type_name());
+        self.spawn(
+            ServerTask::SearchKeys,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let pattern_str = if pattern.is_empty() { "*" } else { pattern.as_str() };
+                let (mut cursors, mut keys) = match cursors {
+                    Some(cursors) => {
+                        client
+                            .scan_with_type(cursors, pattern_str, key_scan_count, type_name)
+                            .await?
+                    }
+                    None => {
+                        client
+                            .first_scan_with_type(pattern_str, key_scan_count, type_name)
+                            .await?
+                    }
+                };
+                for _ in 1..SEARCH_MAX_ROUNDS {
+                    if keys.len() >= SEARCH_PAGE_SIZE || cursors.iter().sum::<u64>() == 0 {
+                        break;
+                    }
+                    let (next_cursors, next_keys) = client
+                        .scan_with_type(cursors, pattern_str, key_scan_count, type_name)
+                        .await?;
+                    keys.extend(next_keys);
+                    cursors = next_cursors;
+                }
+
+                let mut results = Vec::with_capacity(keys.len());
+                for batch in keys.chunks(SEARCH_PAGE_SIZE) {
+                    let mut pipeline = pipe();
+                    for key in batch {
+                        pipeline.cmd("TYPE").arg(key.as_str());
+                        pipeline.cmd("TTL").arg(key.as_str());
+                        pipeline.cmd("MEMORY").arg("USAGE").arg(key.as_str());
+                    }
+                    let values: Vec<Value> = pipeline.ignore_errors().query_async(&mut conn).await?;
+                    let mut values = values.into_iter();
+                    for key in batch {
+                        let (Some(redis_type), Some(ttl), Some(size)) = (values.next(), values.next(), values.next())
+                        else {
+                            break;
+                        };
+                        let redis_type = String::from_redis_value(redis_type).unwrap_or_default();
+                        // Deleted since the scan
+                        if redis_type == "none" {
+                            continue;
+                        }
+                        let result_type = KeyType::from(redis_type.as_str());
+                        if key_type.is_some_and(|key_type| key_type != result_type) {
+                            continue;
+                        }
+                        results.push(SearchResult {
+                            key: key.clone(),
+                            key_type: result_type,
+                            ttl: i64::from_redis_value(ttl).unwrap_or(-1),
+                            size: u64::from_redis_value(size).ok(),
+                        });
+                    }
+                }
+                let cursors = (cursors.iter().sum::<u64>() != 0).then_some(cursors);
+                Ok(SearchPage {
+                    pattern,
+                    key_type,
+                    results,
+                    cursors,
+                })
+            },
+            move |_this, result, cx| match result {
+                Ok(page) => cx.emit(ServerEvent::SearchResultsLoaded(Arc::new(page))),
+                Err(_) => cx.emit(ServerEvent::SearchFailed),
+            },
+            cx,
+        );
+    }
+    /// Write the results of the advanced search to `path` as CSV: key, type, TTL and size.
+    pub fn export_search_results(&mut self, path: PathBuf, results: Arc<Vec<SearchResult>>, cx: &mut Context<Self>) {
+        let export_path = path.clone();
+        self.spawn(
+            ServerTask::ExportSearchResults,
+            move || async move {
+                let mut writer = BufWriter::new(File::create(&export_path)?);
+                writeln!(writer, "key,type,ttl,size")?;
+                for result in results.iter() {
+                    let key_type = result.key_type.redis_type_name().unwrap_or_default();
+                    let size = result.size.map(|size| size.to_string()).unwrap_or_default();
+                    writeln!(writer, "{},{key_type},{},{size}", csv_field(&result.key), result.ttl)?;
+                }
+                writer.flush()?;
+                Ok(results.len())
+            },
+            move |this, result, cx| {
+                if let Ok(count) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!(
+                        "search_editor.export_success",
+                        count = count,
+                        path = path.display().to_string(),
+                        locale = locale
+                    )
+                    .to_string();
+                    this.emit_success_notification(message.into(), i18n_search_editor(cx, "title"), cx);
+                }
+            },
+            cx,
+        );
+    }
+}
@@ -14,7 +14,7 @@
 
 use super::{
     KeyType, RedisValueData, ServerTask, ZedisServerState,
-    value::{RedisSetValue, RedisValue, RedisValueStatus},
+    value::{RedisSetValue, RedisValue, RedisValueStatus, revert_failed_edit},
 };
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
@@ -78,21 +78,24 @@ async fn get_redis_set_value(
 
 /// Performs initial load of a Redis SET value.
 ///
-/// Fetches the total cardinality (SCARD) and loads the first batch of members (up to 100).
+/// Fetches the total cardinality (SCARD) and loads the first batch of members.
 /// This is called when a SET key is first opened in the editor.
 ///
 /// # Arguments
 /// * `conn` - Redis async connection
 /// * `key` - The SET key to load
+/// * `count` - COUNT hint of the first SSCAN
 ///
 /// # Returns
 /// A `RedisValue` containing SET metadata and initial member values
-pub(crate) async fn first_load_set_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
-    // Get total number of members in the SET
-    let size: usize = cmd("SCARD").arg(key).query_async(conn).await?;
-
-    // Load first batch of values (up to 100 members)
-    let (cursor, values) = get_redis_set_value(conn, key, None, 0, 100).await?;
+pub(crate) async fn first_load_set_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    size: usize,
+    count: usize,
+) -> Result<RedisValue> {
+    // Load first batch of values
+    let (cursor, values) = get_redis_set_value(conn, key, None, 0, count).await?;
 
     // If cursor is 0, all values have been loaded in one iteration
     let done = cursor == 0;
@@ -131,6 +134,8 @@ impl ZedisServerState {
         let key_str = key.to_string();
         value.status = RedisValueStatus::Updating;
 
+        // Restored if the edit fails
+        let snapshot = value.data.clone();
         // Step 1: Perform local optimistic update
         if let Some(RedisValueData::Set(set_data)) = value.data.as_mut() {
             optimistic_update(Arc::make_mut(set_data));
@@ -155,9 +160,11 @@ impl ZedisServerState {
 
                 match result {
                     Ok(data) => on_success(this, data, cx),
-                    Err(e) => {
-                        // Handle error (e.g., show notification and potentially rollback if needed)
-                        this.emit_error_notification(e.to_string().into(), cx);
+                    // The error is already reported by `spawn`, revert the optimistic update
+                    Err(_) => {
+                        if revert_failed_edit(this.value.as_mut(), this.key.as_ref(), &key, snapshot) {
+                            cx.emit(ServerEvent::ValueUpdated);
+                        }
                     }
                 }
                 cx.notify();
@@ -267,10 +274,71 @@ impl ZedisServerState {
         // Trigger load with the new filter
         self.load_more_set_value(cx);
     }
+    /// Replaces the loaded members with a random sample of `count` members.
+    ///
+    /// Uses `SRANDMEMBER key count`, a positive count returns distinct members,
+    /// so huge SETs can be inspected without scanning them. Filtering starts a
+    /// normal scan again.
+    ///
+    /// # Arguments
+    /// * `count` - Number of distinct members to sample
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn sample_set_value(&mut self, count: usize, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(size) = value.set_value().map(|set| set.size) else {
+            return;
+        };
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        cx.emit(ServerEvent::ValuePaginationStarted);
+
+        self.spawn(
+            ServerTask::SampleValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let raw_values: Vec<Vec<u8>> = cmd("SRANDMEMBER")
+                    .arg(key.as_str())
+                    .arg(count)
+                    .query_async(&mut conn)
+                    .await?;
+                let values = raw_values
+                    .iter()
+                    .map(|v| SharedString::new(String::from_utf8_lossy(v)))
+                    .collect::<Vec<_>>();
+                Ok(values)
+            },
+            move |this, result, cx| {
+                if let Ok(values) = result
+                    && let Some(value) = this.value.as_mut()
+                {
+                    value.data = Some(RedisValueData::Set(Arc::new(RedisSetValue {
+                        size,
+                        values,
+                        done: true,
+                        sample: true,
+                        ..Default::default()
+                    })));
+                }
+
+                cx.emit(ServerEvent::ValuePaginationFinished);
+
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
     /// Loads the next batch of SET members using cursor-based pagination.
     ///
     /// Uses SSCAN to incrementally load members without blocking on large SETs.
-    /// When filtering is active, uses larger batch sizes (1000) and automatically
+    /// When filtering is active, uses larger batch sizes and automatically
     /// loads more batches until at least 50 items are collected or scan is complete.
     ///
     /// # Arguments
@@ -292,6 +360,12 @@ impl ZedisServerState {
 
         let server_id = self.server_id.clone();
         let db = self.db;
+        // Use larger batch size when filtering to reduce round trips
+        let count = if keyword.is_some() {
+            self.filter_scan_count()
+        } else {
+            self.value_scan_count()
+        };
         cx.emit(ServerEvent::ValuePaginationStarted);
 
         let keyword_clone = keyword.clone().unwrap_or_default();
@@ -302,9 +376,6 @@ impl ZedisServerState {
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
 
-                // Use larger batch size when filtering to reduce round trips
-                let count = if keyword.is_some() { 1000 } else { 100 };
-
                 get_redis_set_value(&mut conn, &key, keyword, cursor, count).await
             },
             // UI callback: merge results and handle auto-loading for filters
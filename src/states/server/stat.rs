@@ -14,7 +14,7 @@
 
 use crate::connection::get_connection_manager;
 use crate::helpers::{unix_ts, unix_ts_millis};
-use crate::states::{ServerEvent, ServerTask, ZedisServerState};
+use crate::states::{ConnectionStatus, ServerEvent, ServerTask, ZedisServerState, i18n_common};
 use gpui::prelude::*;
 use parking_lot::RwLock;
 use redis::cmd;
@@ -23,6 +23,11 @@ use std::sync::LazyLock;
 use std::time::Instant;
 use tracing::error;
 
+/// Failed heartbeats before the connection is reported as disconnected
+const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+/// Upper bound of the reconnect backoff in seconds
+const MAX_RECONNECT_BACKOFF_SECS: i64 = 60;
+
 #[derive(Debug, Default, Clone)]
 pub struct RedisKeySpaceStats {
     pub keys: u64,
@@ -114,6 +119,14 @@ pub fn get_metrics_cache() -> &'static MetricsCache {
     &METRICS_CACHE
 }
 
+/// Fields of an `INFO` section (`# Server`, `# Memory`...), in the order sent by the server
+#[derive(Debug, Default, Clone)]
+pub struct RedisInfoSection {
+    pub name: String,
+    /// `field:value` pairs, lines without a colon are kept with an empty value
+    pub fields: Vec<(String, String)>,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct RedisInfo {
     pub meta: RedisServerMeta,
@@ -121,6 +134,8 @@ pub struct RedisInfo {
     pub metrics: RedisMetrics,
     // --- Keyspace (db0, db1...) ---
     pub keyspace: HashMap<String, RedisKeySpaceStats>,
+    /// Raw sections, of the first node for a cluster
+    pub sections: Vec<RedisInfoSection>,
 }
 
 /// Aggregates metrics from multiple Redis Cluster nodes into a single global view.
@@ -128,7 +143,7 @@ pub struct RedisInfo {
 /// Strategies:
 /// - **Sum**: Capacity (Memory, Keys) and Throughput (QPS, Network)
 /// - **Max**: Health indicators where the worst node defines the cluster state (Fragmentation).
-/// - **Static**: Version, OS (taken from the first node).
+/// - **Static**: Version, OS and raw sections (taken from the first node).
 pub fn aggregate_redis_info(infos: Vec<RedisInfo>) -> RedisInfo {
     // Return default if no nodes are provided
     if infos.is_empty() {
@@ -213,10 +228,28 @@ impl RedisInfo {
 
         for line in info_str.lines() {
             let line = line.trim();
-            // ignore comment line
-            if line.is_empty() || line.starts_with('#') {
+            if line.is_empty() {
                 continue;
             }
+            // "# Server" starts a new section
+            if let Some(name) = line.strip_prefix('#') {
+                info.sections.push(RedisInfoSection {
+                    name: name.trim().to_string(),
+                    fields: vec![],
+                });
+                continue;
+            }
+            let field = line
+                .split_once(':')
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .unwrap_or_else(|| (line.to_string(), String::new()));
+            match info.sections.last_mut() {
+                Some(section) => section.fields.push(field),
+                None => info.sections.push(RedisInfoSection {
+                    name: String::new(),
+                    fields: vec![field],
+                }),
+            }
 
             if let Some((key, value)) = line.split_once(':') {
                 if key.starts_with("db") && value.contains("keys=") {
@@ -293,7 +326,8 @@ fn parse_keyspace_value(v: &str) -> Result<RedisKeySpaceStats, ()> {
 
 impl ZedisServerState {
     pub fn refresh_redis_info(&mut self, cx: &mut Context<Self>) {
-        if self.server_id.is_empty() {
+        // Skipped while waiting for the next reconnect attempt
+        if self.server_id.is_empty() || unix_ts() < self.next_ping_at {
             return;
         }
 
@@ -318,17 +352,36 @@ impl ZedisServerState {
                     None
                 };
 
-                let list: Vec<String> = client.query_async_masters(vec![cmd("INFO").arg("ALL").clone()]).await?;
-                let infos: Vec<RedisInfo> = list.iter().map(|info| RedisInfo::parse(info)).collect();
-                let mut info = aggregate_redis_info(infos);
-                info.metrics.timestamp_ms = unix_ts_millis();
-                info.metrics.latency_ms = latency.as_millis() as u64;
+                // The connection status only follows PING, a failing INFO (e.g. the command
+                // is disabled by ACL) doesn't mean the server is unreachable
+                let info = client
+                    .query_async_masters::<String>(vec![cmd("INFO").arg("ALL").clone()])
+                    .await
+                    .map(|list| {
+                        let infos: Vec<RedisInfo> = list.iter().map(|info| RedisInfo::parse(info)).collect();
+                        let mut info = aggregate_redis_info(infos);
+                        info.metrics.timestamp_ms = unix_ts_millis();
+                        info.metrics.latency_ms = latency.as_millis() as u64;
+                        info
+                    });
                 Ok((info, slow_logs))
             },
             move |this, result, cx| match result {
                 Ok((info, slow_logs)) => {
-                    METRICS_CACHE.add_metrics(&server_id_clone, info.metrics);
-                    this.redis_info = Some(info);
+                    if this.server_id == server_id_clone {
+                        this.ping_failures = 0;
+                        this.next_ping_at = 0;
+                        this.set_connection_status(ConnectionStatus::Connected, cx);
+                    }
+                    match info {
+                        Ok(info) => {
+                            METRICS_CACHE.add_metrics(&server_id_clone, info.metrics);
+                            this.redis_info = Some(info);
+                        }
+                        Err(e) => {
+                            error!(error = %e, "Get redis info failed");
+                        }
+                    }
                     if let Some(slow_logs) = slow_logs {
                         this.slow_logs = slow_logs;
                         this.last_slow_logs_checked_at = unix_ts();
@@ -339,9 +392,42 @@ impl ZedisServerState {
                     // Connection is invalid, remove cached client
                     get_connection_manager().remove_client(&server_id_clone, db);
                     error!(error = %e, "Ping failed, client connection removed");
+                    if this.server_id == server_id_clone {
+                        this.ping_failures += 1;
+                        let backoff = (1_i64 << this.ping_failures.min(6)).min(MAX_RECONNECT_BACKOFF_SECS);
+                        this.next_ping_at = unix_ts() + backoff;
+                        let status = if this.ping_failures < MAX_RECONNECT_ATTEMPTS {
+                            ConnectionStatus::Reconnecting
+                        } else {
+                            ConnectionStatus::Disconnected
+                        };
+                        this.set_connection_status(status, cx);
+                    }
                 }
             },
             cx,
         );
     }
+    /// Retry the connection right away instead of waiting for the backoff
+    pub fn reconnect(&mut self, cx: &mut Context<Self>) {
+        self.next_ping_at = 0;
+        self.refresh_redis_info(cx);
+    }
+    fn set_connection_status(&mut self, status: ConnectionStatus, cx: &mut Context<Self>) {
+        if self.connection_status == status {
+            return;
+        }
+        self.connection_status = status;
+        cx.emit(ServerEvent::ConnectionStatusChanged(status));
+        match status {
+            ConnectionStatus::Connected => self.emit_info_notification(i18n_common(cx, "connection_restored"), cx),
+            ConnectionStatus::Reconnecting => {
+                self.emit_warning_notification(i18n_common(cx, "connection_reconnecting"), cx)
+            }
+            ConnectionStatus::Disconnected => {
+                self.emit_error_notification(i18n_common(cx, "connection_disconnected"), cx)
+            }
+        }
+        cx.notify();
+    }
 }
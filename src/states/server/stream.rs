@@ -14,7 +14,7 @@
 
 use super::{
     KeyType, RedisValueData, ServerEvent, ServerTask, ZedisServerState,
-    value::{RedisStreamEntry, RedisStreamValue, RedisValue, RedisValueStatus},
+    value::{RedisStreamEntry, RedisStreamValue, RedisValue, RedisValueStatus, revert_failed_edit},
 };
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
@@ -28,6 +28,7 @@ type Result<T, E = Error> = std::result::Result<T, E>;
 
 type RawStreamData = Vec<(String, Vec<String>)>;
 
+/// Loads the entries older than `cursor` (exclusive), newest first.
 async fn get_redis_stream_value(
     conn: &mut RedisAsyncConn,
     key: &str,
@@ -37,12 +38,12 @@ async fn get_redis_stream_value(
     let cursor = if let Some(cursor) = cursor {
         format!("({cursor}")
     } else {
-        "-".to_string()
+        "+".to_string()
     };
-    let entries: RawStreamData = cmd("XRANGE")
+    let entries: RawStreamData = cmd("XREVRANGE")
         .arg(key)
         .arg(cursor)
-        .arg("+")
+        .arg("-")
         .arg("COUNT")
         .arg(count)
         .query_async(conn)
@@ -73,8 +74,7 @@ async fn get_redis_stream_value(
     Ok((cursor, values))
 }
 
-pub(crate) async fn first_load_stream_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
-    let size: usize = cmd("XLEN").arg(key).query_async(conn).await?;
+pub(crate) async fn first_load_stream_value(conn: &mut RedisAsyncConn, key: &str, size: usize) -> Result<RedisValue> {
     let (cursor, values) = get_redis_stream_value(conn, key, None, 100).await?;
     let done = cursor.is_empty();
 
@@ -109,6 +109,8 @@ impl ZedisServerState {
         };
         let key_str = key.to_string();
         value.status = RedisValueStatus::Updating;
+        // Restored if the edit fails
+        let snapshot = value.data.clone();
         if let Some(RedisValueData::Stream(stream_data)) = value.data.as_mut() {
             optimistic_update(Arc::make_mut(stream_data));
             cx.emit(ServerEvent::ValueUpdated);
@@ -130,7 +132,12 @@ impl ZedisServerState {
                 }
                 match result {
                     Ok(data) => on_success(this, data, cx),
-                    Err(e) => this.emit_error_notification(e.to_string().into(), cx),
+                    // The error is already reported by `spawn`, revert the optimistic update
+                    Err(_) => {
+                        if revert_failed_edit(this.value.as_mut(), this.key.as_ref(), &key, snapshot) {
+                            cx.emit(ServerEvent::ValueUpdated);
+                        }
+                    }
                 }
                 cx.notify();
             },
@@ -176,7 +183,7 @@ impl ZedisServerState {
 
         self.spawn(
             ServerTask::LoadMoreValue,
-            // Async operation: fetch next batch using HSCAN
+            // Async operation: fetch the next older batch using XREVRANGE
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 get_redis_stream_value(&mut conn, key.as_str(), Some(cursor), 100).await
@@ -212,7 +219,7 @@ impl ZedisServerState {
                 }
                 cx.notify();
                 if should_load_more {
-                    this.load_more_hash_value(cx);
+                    this.load_more_stream_value(cx);
                 }
             },
             cx,
@@ -245,9 +252,8 @@ impl ZedisServerState {
                 if let Some(RedisValueData::Stream(stream_data)) = this.value.as_mut().and_then(|v| v.data.as_mut()) {
                     let stream = Arc::make_mut(stream_data);
                     stream.size += 1;
-                    if stream.done {
-                        stream.values.push((id.into(), values_clone));
-                    }
+                    // XADD only accepts IDs greater than the last one, the entry is the newest
+                    stream.values.insert(0, (id.into(), values_clone));
                 }
                 cx.emit(ServerEvent::ValueUpdated);
             },
@@ -12,10 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::value::{DataFormat, RedisBytesValue, detect_format};
+use super::value::{DataFormat, KeyType, RedisBytesValue, RedisValue, RedisValueData, ViewMode, detect_format};
 use crate::db::ProtoManager;
 use crate::helpers::decompress_zstd;
 use crate::{connection::RedisAsyncConn, error::Error};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 use bytes::Bytes;
 use flate2::read::GzDecoder;
 use gpui::SharedString;
@@ -24,6 +25,7 @@ use redis::cmd;
 use serde_json::Value;
 use snap::read::FrameDecoder;
 use std::io::Read;
+use std::sync::Arc;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -54,11 +56,16 @@ fn truncate_long_strings(max_truncate_length: usize, v: &mut Value, truncated: &
     }
 }
 
+/// Cheap check on the delimiters, the content is only parsed when needed.
+pub fn looks_like_json(value: &str) -> bool {
+    let trimmed = value.trim();
+    (trimmed.starts_with('{') && trimmed.ends_with('}')) || (trimmed.starts_with('[') && trimmed.ends_with(']'))
+}
+
 /// Attempts to format a string as pretty-printed JSON.
 /// Returns None if the string is not valid JSON or doesn't look like JSON.
 fn pretty_json(value: &str, max_truncate_length: usize) -> Option<(SharedString, bool)> {
-    let trimmed = value.trim();
-    if !((trimmed.starts_with('{') && trimmed.ends_with('}')) || (trimmed.starts_with('[') && trimmed.ends_with(']'))) {
+    if !looks_like_json(value) {
         return None;
     }
     let mut json_value = serde_json::from_str::<Value>(value).ok()?;
@@ -130,6 +137,26 @@ pub fn detect_and_decode(data: &[u8], max_truncate_length: usize) -> (DataFormat
     }
 }
 
+/// Decodes the bytes for an explicit "View as" choice.
+///
+/// Returns `None` when the bytes can't be shown that way (e.g. invalid JSON),
+/// `Auto` and `Hex` are rendered by the editor itself.
+pub fn decode_as(data: &[u8], view_mode: &ViewMode) -> Option<SharedString> {
+    match view_mode {
+        ViewMode::Plain => Some(SharedString::new(String::from_utf8_lossy(data))),
+        ViewMode::Json => serde_json::from_slice::<Value>(data)
+            .ok()
+            .and_then(|v| serde_json::to_string_pretty(&v).ok())
+            .map(SharedString::from),
+        ViewMode::MessagePack => rmp_serde::from_slice::<Value>(data)
+            .ok()
+            .and_then(|v| serde_json::to_string_pretty(&v).ok())
+            .map(SharedString::from),
+        ViewMode::Base64 => Some(BASE64.encode(data).into()),
+        ViewMode::Auto | ViewMode::Hex => None,
+    }
+}
+
 impl RedisBytesValue {
     pub fn detect_and_update(&mut self, server_id: &str, key: &str, max_truncate_length: usize) {
         let data = self.bytes.as_ref();
@@ -190,11 +217,68 @@ impl RedisBytesValue {
     }
 }
 
-pub(crate) async fn get_redis_bytes_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisBytesValue> {
+/// Gets a string value of `size` bytes, if it is longer than `limit` bytes only the first `limit` bytes are loaded.
+pub(crate) async fn get_redis_bytes_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    size: usize,
+    limit: Option<usize>,
+) -> Result<RedisBytesValue> {
+    if let Some(limit) = limit
+        && size > limit
+    {
+        let mut value = get_redis_bytes_range(conn, key, 0, limit).await?;
+        value.total_size = size;
+        return Ok(value);
+    }
     let value_bytes: Vec<u8> = cmd("GET").arg(key).query_async(conn).await?;
+    Ok(RedisBytesValue {
+        format: DataFormat::Text,
+        total_size: value_bytes.len(),
+        bytes: Bytes::from(value_bytes),
+        ..Default::default()
+    })
+}
+
+/// Gets the root of a RedisJSON document, `JSON.GET key $` wraps it in an array.
+pub(crate) async fn get_json_document(conn: &mut RedisAsyncConn, key: &str) -> Result<Value> {
+    let text: String = cmd("JSON.GET").arg(key).arg("$").query_async(conn).await?;
+    match serde_json::from_str::<Value>(&text)? {
+        Value::Array(mut values) if values.len() == 1 => Ok(values.remove(0)),
+        value => Ok(value),
+    }
+}
+
+/// Loads a RedisJSON document as pretty-printed text for the string editor.
+pub(crate) async fn first_load_json_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
+    let document = get_json_document(conn, key).await?;
+    let text = serde_json::to_string_pretty(&document)?;
+    Ok(RedisValue {
+        key_type: KeyType::Json,
+        data: Some(RedisValueData::Json(Arc::new(RedisBytesValue {
+            format: DataFormat::Json,
+            total_size: text.len(),
+            bytes: Bytes::from(text.clone().into_bytes()),
+            text: Some(text.into()),
+            ..Default::default()
+        }))),
+        ..Default::default()
+    })
+}
+
+/// Gets `size` bytes of a string value starting at `offset` with GETRANGE.
+pub(crate) async fn get_redis_bytes_range(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    offset: usize,
+    size: usize,
+) -> Result<RedisBytesValue> {
+    let end = offset + size.max(1) - 1;
+    let value_bytes: Vec<u8> = cmd("GETRANGE").arg(key).arg(offset).arg(end).query_async(conn).await?;
     Ok(RedisBytesValue {
         format: DataFormat::Text,
         bytes: Bytes::from(value_bytes),
+        offset,
         ..Default::default()
     })
 }
@@ -12,19 +12,62 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{ServerEvent, ServerTask, ZedisServerState};
-use crate::connection::get_connection_manager;
+use super::{
+    ServerEvent, ServerTask, ZedisServerState,
+    string::{decode_as, get_json_document},
+};
+use crate::connection::{Capability, RedisAsyncConn, get_connection_manager};
+use crate::error::Error;
+use crate::states::{ZedisGlobalStore, i18n_common, i18n_editor, i18n_key_tree, update_app_state_and_save};
+use ahash::{AHashMap, AHashSet};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 use bytes::Bytes;
 use chrono::Local;
-use gpui::{Hsla, SharedString, prelude::*};
-use redis::cmd;
+use gpui::{ClipboardItem, Hsla, SharedString, prelude::*};
+use redis::{Value, cmd, pipe};
+use rust_i18n::t;
 use serde::Deserialize;
 use std::collections::HashSet;
-use std::io::Cursor;
+use std::fs::File;
+use std::io::{BufWriter, Cursor, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+use tracing::debug;
+use uuid::Uuid;
 
 pub(crate) const SUCCESS_NOTIFY_THRESHOLD: usize = 10;
 
+/// Length of the values shown in notifications
+const PREVIEW_VALUE_LENGTH: usize = 200;
+/// Keys read per pipeline when exporting a prefix
+const EXPORT_BATCH_SIZE: usize = 100;
+/// Interval to refresh the progress of a prefix export
+const EXPORT_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+/// Keys written per pipeline when importing an export
+const IMPORT_BATCH_SIZE: usize = 100;
+
+/// Lossy text of `bytes` cut to `PREVIEW_VALUE_LENGTH` characters for notifications.
+fn preview_bytes(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    if text.chars().count() <= PREVIEW_VALUE_LENGTH {
+        return text.to_string();
+    }
+    let mut preview: String = text.chars().take(PREVIEW_VALUE_LENGTH).collect();
+    preview.push_str("...");
+    preview
+}
+
+/// Result of saving a string value
+enum SaveOutcome {
+    /// Saved, with the new memory usage if available and the value it replaced
+    /// when it was read again while writing
+    Saved(Option<u64>, Option<Vec<u8>>),
+    /// The key was changed by another client since it was loaded
+    Conflict,
+}
+
 #[derive(Debug, PartialEq, Clone, Copy, Default)]
 pub enum DataFormat {
     #[default]
@@ -165,6 +208,8 @@ pub enum RedisValueData {
     Zset(Arc<RedisZsetValue>),
     Hash(Arc<RedisHashValue>),
     Stream(Arc<RedisStreamValue>),
+    /// RedisJSON document, edited as pretty-printed text like strings
+    Json(Arc<RedisBytesValue>),
 }
 
 /// Redis Set value structure with pagination support
@@ -175,6 +220,8 @@ pub struct RedisSetValue {
     pub size: usize,
     pub values: Vec<SharedString>,
     pub done: bool,
+    /// Whether `values` is a random sample instead of the scanned members
+    pub sample: bool,
 }
 
 /// Sort order for sorted sets
@@ -182,6 +229,7 @@ pub struct RedisSetValue {
 pub enum SortOrder {
     #[default]
     Asc, // Ascending order (default)
+    Desc, // Descending order
 }
 
 /// Redis Sorted Set value structure with pagination and sorting support
@@ -203,6 +251,41 @@ pub struct RedisHashValue {
     pub size: usize,
     pub done: bool,
     pub values: Vec<(SharedString, SharedString)>,
+    /// Whether `values` is a random sample instead of the scanned fields
+    pub sample: bool,
+    /// TTL in seconds of the loaded fields with a TTL when they were loaded (Redis 7.4+)
+    pub field_ttls: AHashMap<SharedString, i64>,
+}
+
+/// Change of a hash field TTL submitted with the field value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldTtl {
+    /// Leave the TTL as it is
+    Keep,
+    /// Remove the TTL (HPERSIST)
+    Persist,
+    /// Expire the field in the given seconds (HEXPIRE)
+    Expire(u64),
+}
+
+/// How the value of the selected key is copied to the clipboard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CopyFormat {
+    /// The exact bytes, only for UTF-8 values as the clipboard holds text
+    Raw,
+    Base64,
+    Hex,
+    /// The JSON (or MessagePack) string value, or the whole structure of other types
+    Json,
+}
+
+/// How a string value is written to a file, the other types are written as JSON
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    /// UTF-8 text, binary values are rejected
+    Text,
+    /// The exact bytes
+    Binary,
 }
 
 /// Redis List value structure
@@ -221,8 +304,8 @@ pub struct RedisStreamValue {
     /// Optional keyword filter for searching stream entries.
     pub keyword: Option<SharedString>,
 
-    /// The ID of the last entry loaded, used for pagination (e.g., "1700000000000-0").
-    /// Stream uses IDs as cursors for XREVRANGE/XRANGE.
+    /// The ID of the oldest entry loaded, used for pagination (e.g., "1700000000000-0").
+    /// Entries are loaded newest first with XREVRANGE, using the ID as cursor.
     pub cursor: String,
 
     /// Total count of items in the stream (XLEN).
@@ -231,7 +314,7 @@ pub struct RedisStreamValue {
     /// Whether we have reached the end of the stream (or loaded all requested).
     pub done: bool,
 
-    /// The stream entries.
+    /// The stream entries, newest first.
     pub values: Vec<RedisStreamEntry>,
 }
 
@@ -263,28 +346,47 @@ impl RedisStreamValue {
     }
 }
 
+/// How a string value is shown in the editor, `Auto` uses the detected format.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum ViewMode {
     #[default]
     Auto,
     Plain,
+    Json,
+    MessagePack,
+    Base64,
     Hex,
 }
 
 impl ViewMode {
+    /// All modes, in the order of the "View as" select
+    pub const ALL: [ViewMode; 6] = [
+        ViewMode::Auto,
+        ViewMode::Plain,
+        ViewMode::Json,
+        ViewMode::MessagePack,
+        ViewMode::Base64,
+        ViewMode::Hex,
+    ];
     pub fn as_str(&self) -> &'static str {
         match self {
             ViewMode::Auto => "Auto",
             ViewMode::Plain => "Plain",
+            ViewMode::Json => "JSON",
+            ViewMode::MessagePack => "MessagePack",
+            ViewMode::Base64 => "Base64",
             ViewMode::Hex => "Hex",
         }
     }
     pub fn from_str(s: &str) -> Self {
-        match s {
-            "Plain" => ViewMode::Plain,
-            "Hex" => ViewMode::Hex,
-            _ => ViewMode::Auto,
-        }
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str() == s)
+            .unwrap_or_default()
+    }
+    /// Whether the editor shows a decoded representation which can't be saved back as is
+    pub fn is_decoded(&self) -> bool {
+        matches!(self, ViewMode::MessagePack | ViewMode::Base64)
     }
 }
 
@@ -295,6 +397,10 @@ pub struct RedisBytesValue {
     pub mime: Option<SharedString>,
     pub text: Option<SharedString>,
     pub view_mode: ViewMode,
+    /// Start of `bytes` in the whole value, non-zero only for partially loaded values
+    pub offset: usize,
+    /// Length of the whole value in Redis
+    pub total_size: usize,
 }
 
 impl RedisBytesValue {
@@ -307,6 +413,10 @@ impl RedisBytesValue {
     pub fn is_utf8_text(&self) -> bool {
         matches!(self.format, DataFormat::Text | DataFormat::Json)
     }
+    /// Whether only a window of the value was loaded because it is too large
+    pub fn is_partial(&self) -> bool {
+        self.offset > 0 || self.total_size > self.bytes.len()
+    }
 }
 
 impl RedisValue {
@@ -372,7 +482,15 @@ pub enum KeyType {
     Hash,
     Stream,
     Vectorset,
+    /// RedisJSON document (`ReJSON-RL`)
+    Json,
     Channel,
+    /// Live `MONITOR` stream of the server, not a key type
+    Monitor,
+    /// Runtime configuration of the server, not a key type
+    Config,
+    /// Advanced search of the keys in a table, not a key type
+    Search,
 }
 impl KeyType {
     /// Returns the abbreviated string representation of the key type
@@ -385,11 +503,30 @@ impl KeyType {
             KeyType::Zset => "ZSET",
             KeyType::Stream => "STRM",
             KeyType::Vectorset => "VEC",
+            KeyType::Json => "JSON",
             KeyType::Channel => "CHANNEL",
+            KeyType::Monitor => "MONITOR",
+            KeyType::Config => "CONFIG",
+            KeyType::Search => "SEARCH",
             KeyType::Unknown => "",
         }
     }
 
+    /// Returns the type name reported by `TYPE` and accepted by `SCAN ... TYPE`.
+    pub fn redis_type_name(&self) -> Option<&'static str> {
+        match self {
+            KeyType::String => Some("string"),
+            KeyType::List => Some("list"),
+            KeyType::Hash => Some("hash"),
+            KeyType::Set => Some("set"),
+            KeyType::Zset => Some("zset"),
+            KeyType::Stream => Some("stream"),
+            KeyType::Vectorset => Some("vectorset"),
+            KeyType::Json => Some("ReJSON-RL"),
+            _ => None,
+        }
+    }
+
     /// Returns the Redis command used to create a key of this type.
     pub fn create_command(&self) -> &'static str {
         match self {
@@ -426,6 +563,7 @@ impl KeyType {
             KeyType::Zset => gpui::hsla(0.0, 0.6, 0.55, 1.0),     // Red
             KeyType::Stream => gpui::hsla(0.3, 0.5, 0.4, 1.0),    // Green
             KeyType::Vectorset => gpui::hsla(0.9, 0.5, 0.5, 1.0), // Pink
+            KeyType::Json => gpui::hsla(0.15, 0.7, 0.45, 1.0),    // Yellow
             _ => gpui::hsla(0.0, 0.0, 0.4, 1.0),                  // Gray
         }
     }
@@ -448,6 +586,8 @@ pub struct RedisValue {
     pub(crate) data: Option<RedisValueData>,
     pub(crate) expire_at: Option<i64>,
     pub(crate) size: u64,
+    /// Length of a collection too large to be loaded, its first page waits for the user
+    pub(crate) held_back_len: Option<usize>,
 }
 
 impl RedisValue {
@@ -461,9 +601,9 @@ impl RedisValue {
         matches!(self.status, RedisValueStatus::Loading)
     }
 
-    /// Returns the bytes value if the data is a Bytes type
+    /// Returns the bytes value if the data is a Bytes type, or the text of a JSON document
     pub fn bytes_value(&self) -> Option<Arc<RedisBytesValue>> {
-        if let Some(RedisValueData::Bytes(value)) = self.data.as_ref() {
+        if let Some(RedisValueData::Bytes(value) | RedisValueData::Json(value)) = self.data.as_ref() {
             return Some(value.clone());
         }
         None
@@ -474,6 +614,11 @@ impl RedisValue {
         self.size
     }
 
+    /// Returns the length of a collection that was not loaded because it is too large
+    pub fn held_back_len(&self) -> Option<usize> {
+        self.held_back_len
+    }
+
     /// Returns the time-to-live duration for this key
     ///
     /// Returns None if no expiration is set.
@@ -510,6 +655,109 @@ impl RedisValue {
     }
 }
 
+/// Internal details of a key, loaded on demand for the metadata popover.
+///
+/// Rows that the server refuses (e.g. `OBJECT` on restricted servers,
+/// or `IDLETIME` with an LFU eviction policy) are left as `None`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyMetadata {
+    pub key: SharedString,
+    pub key_type: Option<SharedString>,
+    pub encoding: Option<SharedString>,
+    pub idle_time: Option<u64>,
+    pub refcount: Option<i64>,
+    pub memory_usage: Option<u64>,
+    /// Why the metadata could not be loaded at all, e.g. the connection failed
+    pub error: Option<SharedString>,
+}
+
+/// Keys of a namespace holding byte-identical values.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DuplicateGroup {
+    pub keys: Vec<SharedString>,
+    /// Combined `MEMORY USAGE` of the keys
+    pub memory: u64,
+}
+
+/// Result of the duplicate detection of a namespace, see `find_duplicates`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DuplicateReport {
+    pub prefix: SharedString,
+    /// Keys whose values were compared
+    pub checked: usize,
+    /// Keys skipped because they are larger than the large value threshold
+    pub skipped: usize,
+    /// Whether the scan stopped at `DUPLICATE_SCAN_LIMIT` keys
+    pub sampled: bool,
+    /// Groups sorted by combined memory, largest first
+    pub groups: Vec<DuplicateGroup>,
+}
+
+/// Metric ranking the keys by coldness, chosen from the `maxmemory-policy`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ColdKeyMetric {
+    /// `OBJECT IDLETIME` in seconds, higher is colder
+    #[default]
+    IdleTime,
+    /// `OBJECT FREQ` logarithmic access counter, lower is colder
+    Freq,
+}
+
+/// Result of the cold keys finder of a namespace, see `find_cold_keys`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColdKeysReport {
+    pub prefix: SharedString,
+    /// `maxmemory-policy` of the server, empty when it can't be read
+    pub policy: SharedString,
+    pub metric: ColdKeyMetric,
+    /// Keys whose metric was read
+    pub checked: usize,
+    /// Whether the scan stopped at `COLD_KEYS_SCAN_LIMIT` keys
+    pub sampled: bool,
+    /// Coldest keys first, with their metric value
+    pub keys: Vec<(SharedString, u64)>,
+}
+
+impl ColdKeysReport {
+    /// Whether the eviction policy uses the metric, idle times are still
+    /// tracked with the other policies but don't drive the evictions.
+    pub fn is_policy_relevant(&self) -> bool {
+        self.policy.contains("lru") || self.policy.contains("lfu")
+    }
+}
+
+/// Bulk operation previewed before it runs, see `preview_bulk_operation`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkOperation {
+    /// Delete all keys of a key tree folder
+    DeleteFolder(SharedString),
+    /// Set the TTL of all keys of a key tree folder, zero removes it
+    ExpireFolder(SharedString, Duration),
+    /// Import a key export, replacing the existing keys when set
+    ImportKeys(PathBuf, bool),
+}
+
+/// Keys affected by a bulk operation, found with the scan the operation uses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkPreview {
+    pub operation: BulkOperation,
+    pub pattern: SharedString,
+    pub keys: Vec<SharedString>,
+    /// Whether the scan cycle finished, otherwise more keys may match
+    pub complete: bool,
+    /// Keys which already exist, only counted for imports
+    pub existing: usize,
+}
+
+/// Server and db a key is copied to, see `copy_key_to_server`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyTarget {
+    pub server_id: SharedString,
+    /// Name of the server, shown in messages
+    pub name: SharedString,
+    pub db: usize,
+}
+
 /// Converts a string representation to a KeyType
 impl From<&str> for KeyType {
     fn from(value: &str) -> Self {
@@ -520,6 +768,7 @@ impl From<&str> for KeyType {
             "hash" => KeyType::Hash,
             "stream" => KeyType::Stream,
             "vectorset" => KeyType::Vectorset,
+            "ReJSON-RL" => KeyType::Json,
             "string" => KeyType::String,
             _ => KeyType::Unknown,
         }
@@ -532,7 +781,22 @@ impl ZedisServerState {
     /// This method updates the UI immediately with the new value and then
     /// asynchronously persists it to Redis. If the save fails, the original
     /// value is restored.
+    ///
+    /// When optimistic save is enabled, the key is watched and the value is only
+    /// written if it still matches the loaded one. On conflict the edit is copied
+    /// to the clipboard and the editor asks whether to overwrite the new value.
+    ///
+    /// JSON documents are written with `JSON.SET`, which validates them and keeps
+    /// the TTL. They are shown pretty-printed, so they are not compared first.
     pub fn save_value(&mut self, key: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
+        let optimistic = cx.global::<ZedisGlobalStore>().read(cx).optimistic_save();
+        self.write_value(key, new_value, optimistic, cx);
+    }
+    /// Saves the value without comparing it first, once a save conflict is confirmed
+    pub fn overwrite_value(&mut self, key: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
+        self.write_value(key, new_value, false, cx);
+    }
+    fn write_value(&mut self, key: SharedString, new_value: SharedString, optimistic: bool, cx: &mut Context<Self>) {
         let server_id = self.server_id.clone();
         let db = self.db;
         let Some(value) = self.value.as_mut() else {
@@ -542,17 +806,33 @@ impl ZedisServerState {
         let Some(original_bytes_value) = value.bytes_value() else {
             return;
         };
+        // Saving a window of a large value would truncate it
+        if original_bytes_value.is_partial() {
+            return;
+        }
         let format = original_bytes_value.format;
         let original_size = value.size;
+        let original_bytes = original_bytes_value.bytes.clone();
+        let is_json = value.key_type == KeyType::Json;
+        let to_data = move |data: Arc<RedisBytesValue>| {
+            if is_json {
+                RedisValueData::Json(data)
+            } else {
+                RedisValueData::Bytes(data)
+            }
+        };
 
         value.status = RedisValueStatus::Updating;
-        value.data = Some(RedisValueData::Bytes(Arc::new(RedisBytesValue {
+        value.data = Some(to_data(Arc::new(RedisBytesValue {
             bytes: Bytes::from(new_value.clone().to_string().into_bytes()),
             text: Some(new_value.clone()),
             format,
             ..Default::default()
         })));
         let ttl = value.ttl().map(|ttl| ttl.num_milliseconds()).unwrap_or_default();
+        let saved_key = key.clone();
+        let edited_value = new_value.clone();
+        let expected = original_bytes.clone();
 
         cx.notify();
         self.spawn(
@@ -560,17 +840,76 @@ impl ZedisServerState {
             move || async move {
                 let client = get_connection_manager().get_client(&server_id, db).await?;
                 let mut conn = client.connection();
-                let mut binding = cmd("SET");
-                let mut new_cmd = binding.arg(key.as_str()).arg(new_value.as_str());
-                // keep ttl if the version is at least 6.0.0
-                new_cmd = if client.is_at_least_version("6.0.0") {
-                    new_cmd.arg("KEEPTTL")
-                } else if ttl > 0 {
-                    new_cmd.arg("PX").arg(ttl)
+                if is_json {
+                    client.ensure(Capability::Json)?;
+                }
+                let mut new_cmd = if is_json { cmd("JSON.SET") } else { cmd("SET") };
+                new_cmd.arg(key.as_str());
+                if is_json {
+                    new_cmd.arg("$").arg(new_value.as_str());
                 } else {
-                    new_cmd
-                };
-                let _: () = new_cmd.query_async(&mut conn).await?;
+                    new_cmd.arg(new_value.as_str());
+                    // keep ttl if the version is at least 6.0.0
+                    if client.supports(Capability::KeepTtl) {
+                        new_cmd.arg("KEEPTTL");
+                    } else if ttl > 0 {
+                        new_cmd.arg("PX").arg(ttl);
+                    }
+                }
+                // Value replaced by the save, when it may differ from the loaded one
+                let mut previous = None;
+                if is_json {
+                    let _: () = new_cmd.query_async(&mut conn).await?;
+                } else if !optimistic {
+                    // The overwrite replaces whatever another client wrote, so the undo
+                    // restores that value instead of the one loaded before the conflict
+                    if client.is_cluster() {
+                        previous = cmd("GET").arg(key.as_str()).query_async(&mut conn).await?;
+                        let _: () = new_cmd.query_async(&mut conn).await?;
+                    } else {
+                        let (current, _): (Option<Vec<u8>>, Value) = pipe()
+                            .atomic()
+                            .cmd("GET")
+                            .arg(key.as_str())
+                            .add_command(new_cmd)
+                            .query_async(&mut conn)
+                            .await?;
+                        previous = current;
+                    }
+                } else if client.is_cluster() {
+                    // cluster connections can not hold a transaction, compare right before writing
+                    let current: Option<Vec<u8>> = cmd("GET").arg(key.as_str()).query_async(&mut conn).await?;
+                    if current.unwrap_or_default() != expected.as_ref() {
+                        return Ok(SaveOutcome::Conflict);
+                    }
+                    let _: () = new_cmd.query_async(&mut conn).await?;
+                } else {
+                    // The WATCH only isolates the transaction on a connection no other task
+                    // writes to, it is closed when dropped, which also discards the WATCH
+                    let mut watch_conn = get_connection_manager()
+                        .get_dedicated_connection(&server_id, db)
+                        .await?;
+                    let (current,): (Option<Vec<u8>>,) = pipe()
+                        .cmd("WATCH")
+                        .arg(key.as_str())
+                        .ignore()
+                        .cmd("GET")
+                        .arg(key.as_str())
+                        .query_async(&mut watch_conn)
+                        .await?;
+                    if current.unwrap_or_default() != expected.as_ref() {
+                        return Ok(SaveOutcome::Conflict);
+                    }
+                    // EXEC replies nil if the watched key was modified in between
+                    let result: Option<(Value,)> = pipe()
+                        .atomic()
+                        .add_command(new_cmd)
+                        .query_async(&mut watch_conn)
+                        .await?;
+                    if result.is_none() {
+                        return Ok(SaveOutcome::Conflict);
+                    }
+                }
 
                 let mut size = None;
                 if let Ok(memory_usage) = cmd("MEMORY")
@@ -582,22 +921,659 @@ impl ZedisServerState {
                     size = Some(memory_usage);
                 }
 
-                Ok(size)
+                Ok(SaveOutcome::Saved(size, previous))
             },
             move |this, result, cx| {
+                let saved = matches!(result, Ok(SaveOutcome::Saved(..)));
                 if let Some(value) = this.value.as_mut() {
                     value.status = RedisValueStatus::Idle;
-                    if let Ok(result_size) = result {
-                        if let Some(size) = result_size {
-                            value.size = size;
-                        }
-                    } else {
-                        // Recover original value if save failed
+                    if let Ok(SaveOutcome::Saved(Some(size), _)) = &result {
+                        value.size = *size;
+                    } else if !saved {
+                        // Recover original value if save failed or conflicted
                         value.size = original_size;
-                        value.data = Some(RedisValueData::Bytes(original_bytes_value.clone()));
+                        value.data = Some(to_data(original_bytes_value.clone()));
                     }
                     cx.emit(ServerEvent::ValueUpdated);
                 }
+                // The undo restores with SET, which would replace a JSON document by a string
+                if let Ok(SaveOutcome::Saved(_, previous)) = result
+                    && !is_json
+                {
+                    let previous = previous.map(Bytes::from).unwrap_or(original_bytes);
+                    this.last_saved = Some((saved_key, previous));
+                } else if let Ok(SaveOutcome::Conflict) = result {
+                    // The editor asks whether to overwrite, the edit is kept to merge it otherwise
+                    cx.write_to_clipboard(ClipboardItem::new_string(edited_value.to_string()));
+                    cx.emit(ServerEvent::SaveConflict(saved_key, edited_value));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Restores the value that was overwritten by the last save of the selected key.
+    ///
+    /// The undo is one-shot: it is cleared once used or when another key is saved.
+    pub fn undo_last_save(&mut self, cx: &mut Context<Self>) {
+        if !self.can_undo_save() {
+            return;
+        }
+        let Some((key, previous)) = self.last_saved.take() else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let reload_key = key.clone();
+        self.spawn(
+            ServerTask::SaveValue,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.connection();
+                let mut restore_cmd = cmd("SET");
+                restore_cmd.arg(key.as_str()).arg(previous.as_ref());
+                if client.supports(Capability::KeepTtl) {
+                    restore_cmd.arg("KEEPTTL");
+                } else {
+                    // SET discards the TTL before 6.0, it is read first and restored with PX
+                    let ttl: i64 = cmd("PTTL").arg(key.as_str()).query_async(&mut conn).await?;
+                    if ttl > 0 {
+                        restore_cmd.arg("PX").arg(ttl);
+                    }
+                }
+                let _: () = restore_cmd.query_async(&mut conn).await?;
+                Ok(())
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    this.reload_value(reload_key, cx);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Replaces the string value and shows the previous one in a notification.
+    ///
+    /// Uses `SET .. KEEPTTL GET` when available, otherwise `GETSET` followed by
+    /// restoring the TTL, as `GETSET` discards it.
+    pub fn getset_value(&mut self, key: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let Some(value) = self.value.as_mut() else {
+            return;
+        };
+        // Replacing a window of a large value would truncate it
+        if value.key_type != KeyType::String || value.bytes_value().is_none_or(|bytes_value| bytes_value.is_partial()) {
+            return;
+        }
+        let ttl = value.ttl().map(|ttl| ttl.num_milliseconds()).unwrap_or_default();
+        value.status = RedisValueStatus::Updating;
+        let reload_key = key.clone();
+        cx.notify();
+        self.spawn(
+            ServerTask::GetSetValue,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = client.connection();
+                let previous: Option<Vec<u8>> = if client.supports(Capability::SetGet) {
+                    cmd("SET")
+                        .arg(key.as_str())
+                        .arg(new_value.as_str())
+                        .arg("KEEPTTL")
+                        .arg("GET")
+                        .query_async(&mut conn)
+                        .await?
+                } else {
+                    let previous = cmd("GETSET")
+                        .arg(key.as_str())
+                        .arg(new_value.as_str())
+                        .query_async(&mut conn)
+                        .await?;
+                    if ttl > 0 {
+                        let _: () = cmd("PEXPIRE").arg(key.as_str()).arg(ttl).query_async(&mut conn).await?;
+                    }
+                    previous
+                };
+                Ok(previous.unwrap_or_default())
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                }
+                if let Ok(previous) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!(
+                        "editor.getset_value_success",
+                        value = preview_bytes(&previous),
+                        locale = locale
+                    )
+                    .to_string();
+                    this.emit_success_notification(message.into(), i18n_editor(cx, "getset_value"), cx);
+                    this.last_saved = Some((reload_key.clone(), Bytes::from(previous)));
+                    this.reload_value(reload_key, cx);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Reads and deletes the string value with GETDEL.
+    ///
+    /// The final value is shown in a notification and copied to the clipboard,
+    /// then the key is removed from the tree and the editor.
+    pub fn getdel_value(&mut self, key: SharedString, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let Some(value) = self.value.as_mut() else {
+            return;
+        };
+        value.status = RedisValueStatus::Updating;
+        let remove_key = key.clone();
+        cx.notify();
+        self.spawn(
+            ServerTask::GetDelValue,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                client.ensure(Capability::GetDel)?;
+                let mut conn = client.connection();
+                let value: Option<Vec<u8>> = cmd("GETDEL").arg(key.as_str()).query_async(&mut conn).await?;
+                Ok(value.unwrap_or_default())
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                }
+                if let Ok(final_value) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!(
+                        "editor.getdel_value_success",
+                        value = preview_bytes(&final_value),
+                        locale = locale
+                    )
+                    .to_string();
+                    cx.write_to_clipboard(ClipboardItem::new_string(
+                        String::from_utf8_lossy(&final_value).to_string(),
+                    ));
+                    this.emit_success_notification(message.into(), i18n_editor(cx, "getdel_value"), cx);
+                    this.keys.remove(&remove_key);
+                    // Force refresh of the key tree view
+                    this.key_tree_id = Uuid::now_v7().to_string().into();
+                    if this.key == Some(remove_key) {
+                        this.key = None;
+                        this.value = None;
+                    }
+                    cx.emit(ServerEvent::ValueUpdated);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Copies the whole value of the selected key to the clipboard.
+    ///
+    /// The value is read again as bytes, so the copy neither depends on the lossy
+    /// UTF-8 text of the loaded value nor on how much of it was loaded.
+    pub fn copy_value(&mut self, format: CopyFormat, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let Some(key_type) = self.value.as_ref().map(|value| value.key_type) else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let count = self.value_scan_count();
+        self.spawn(
+            ServerTask::CopyValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                if key_type != KeyType::String {
+                    let (value, _) = get_structure_json(&mut conn, &key, key_type, count).await?;
+                    return Ok(serde_json::to_string_pretty(&value)?);
+                }
+                let bytes: Vec<u8> = cmd("GET").arg(key.as_str()).query_async(&mut conn).await?;
+                let content = match format {
+                    CopyFormat::Raw => String::from_utf8(bytes).map_err(|_| Error::Invalid {
+                        message: "The value is not valid UTF-8, copy it as base64 or hex".to_string(),
+                    })?,
+                    CopyFormat::Base64 => BASE64.encode(&bytes),
+                    CopyFormat::Hex => bytes.iter().map(|b| format!("{b:02x}")).collect(),
+                    CopyFormat::Json => decode_as(&bytes, &ViewMode::Json)
+                        .or_else(|| decode_as(&bytes, &ViewMode::MessagePack))
+                        .ok_or_else(|| Error::Invalid {
+                            message: "The value is neither JSON nor MessagePack".to_string(),
+                        })?
+                        .to_string(),
+                };
+                Ok(content)
+            },
+            move |this, result, cx| {
+                if let Ok(content) = result {
+                    cx.write_to_clipboard(ClipboardItem::new_string(content));
+                    this.emit_info_notification(i18n_common(cx, "copied_to_clipboard"), cx);
+                }
+            },
+            cx,
+        );
+    }
+
+    /// Writes the whole value of the selected key to `path`.
+    ///
+    /// Like `copy_value` the value is read again, so large values are written
+    /// completely and not only the loaded page.
+    pub fn export_value(&mut self, path: PathBuf, format: ExportFormat, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let Some(key_type) = self.value.as_ref().map(|value| value.key_type) else {
+            return;
+        };
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let count = self.value_scan_count();
+        let export_path = path.clone();
+        self.spawn(
+            ServerTask::ExportValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let content = if key_type == KeyType::String {
+                    let bytes: Vec<u8> = cmd("GET").arg(key.as_str()).query_async(&mut conn).await?;
+                    if format == ExportFormat::Text && std::str::from_utf8(&bytes).is_err() {
+                        return Err(Error::Invalid {
+                            message: "The value is not valid UTF-8, export it as binary".to_string(),
+                        });
+                    }
+                    bytes
+                } else {
+                    let (value, _) = get_structure_json(&mut conn, &key, key_type, count).await?;
+                    serde_json::to_vec_pretty(&value)?
+                };
+                std::fs::write(&export_path, content)?;
+                Ok(())
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!(
+                        "editor.export_value_success",
+                        path = path.display().to_string(),
+                        locale = locale
+                    )
+                    .to_string();
+                    this.emit_success_notification(message.into(), i18n_editor(cx, "export_value"), cx);
+                }
+            },
+            cx,
+        );
+    }
+
+    /// Writes every key under `prefix` to `path` as a JSON object keyed by key name.
+    ///
+    /// Each entry holds the Redis type, the TTL in milliseconds (-1 without expiry)
+    /// and the value, values that are not valid UTF-8 are stored as base64 with
+    /// `"encoding": "base64"`, for every element of a collection. Keys are read in batches and written as they come, so
+    /// the export never holds the whole prefix in memory. Cancelling removes the file.
+    pub fn export_prefix(&mut self, prefix: SharedString, path: PathBuf, cx: &mut Context<Self>) {
+        if self.prefix_export.is_some() {
+            self.emit_warning_notification(i18n_key_tree(cx, "export_prefix_running"), cx);
+            return;
+        }
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let exported = Arc::new(AtomicUsize::new(0));
+        self.prefix_export = Some((cancelled.clone(), exported.clone()));
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let count = self.value_scan_count();
+        let key_scan_count = cx.global::<ZedisGlobalStore>().read(cx).key_scan_count() as u64;
+        let pattern = format!("{prefix}*");
+        let export_path = path.clone();
+        let task_cancelled = cancelled.clone();
+        self.spawn(
+            ServerTask::ExportPrefix,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let mut writer = BufWriter::new(File::create(&export_path)?);
+                let mut seen = AHashSet::new();
+                let mut skipped = 0;
+                writer.write_all(b"{")?;
+                let (mut cursors, mut keys) = client.first_scan_with_type(&pattern, key_scan_count, None).await?;
+                loop {
+                    // SCAN may return a key more than once
+                    keys.retain(|key| seen.insert(key.clone()));
+                    for batch in keys.chunks(EXPORT_BATCH_SIZE) {
+                        if task_cancelled.load(Ordering::Relaxed) {
+                            drop(writer);
+                            std::fs::remove_file(&export_path)?;
+                            return Ok(None);
+                        }
+                        let mut pipeline = pipe();
+                        for key in batch {
+                            pipeline.cmd("TYPE").arg(key.as_str());
+                            pipeline.cmd("PTTL").arg(key.as_str());
+                        }
+                        let types: Vec<(String, i64)> = pipeline.query_async(&mut conn).await?;
+                        let string_keys: Vec<&SharedString> = batch
+                            .iter()
+                            .zip(&types)
+                            .filter(|(_, (key_type, _))| key_type == "string")
+                            .map(|(key, _)| key)
+                            .collect();
+                        let mut strings: Vec<Option<Vec<u8>>> = vec![];
+                        if !string_keys.is_empty() {
+                            let mut pipeline = pipe();
+                            for key in string_keys {
+                                pipeline.cmd("GET").arg(key.as_str());
+                            }
+                            strings = pipeline.query_async(&mut conn).await?;
+                        }
+                        let mut strings = strings.into_iter();
+                        for (key, (redis_type, ttl)) in batch.iter().zip(types) {
+                            let key_type = KeyType::from(redis_type.as_str());
+                            let mut entry = serde_json::json!({ "type": redis_type, "ttl": ttl });
+                            match key_type {
+                                KeyType::String => {
+                                    // Deleted since the scan
+                                    let Some(bytes) = strings.next().flatten() else {
+                                        continue;
+                                    };
+                                    match String::from_utf8(bytes) {
+                                        Ok(text) => entry["value"] = text.into(),
+                                        Err(e) => {
+                                            entry["value"] = BASE64.encode(e.as_bytes()).into();
+                                            entry["encoding"] = "base64".into();
+                                        }
+                                    }
+                                }
+                                KeyType::List
+                                | KeyType::Set
+                                | KeyType::Zset
+                                | KeyType::Hash
+                                | KeyType::Stream
+                                | KeyType::Json => {
+                                    let (value, encoding) = get_structure_json(&mut conn, key, key_type, count).await?;
+                                    entry["value"] = value;
+                                    if let Some(encoding) = encoding {
+                                        entry["encoding"] = encoding.into();
+                                    }
+                                }
+                                // Deleted since the scan
+                                _ if redis_type == "none" => continue,
+                                _ => {
+                                    skipped += 1;
+                                    continue;
+                                }
+                            }
+                            let written = exported.fetch_add(1, Ordering::Relaxed);
+                            if written > 0 {
+                                writer.write_all(b",")?;
+                            }
+                            writer.write_all(b"\n  ")?;
+                            serde_json::to_writer(&mut writer, key.as_str())?;
+                            writer.write_all(b": ")?;
+                            serde_json::to_writer(&mut writer, &entry)?;
+                        }
+                    }
+                    if cursors.iter().sum::<u64>() == 0 {
+                        break;
+                    }
+                    (cursors, keys) = client.scan(cursors, &pattern, key_scan_count).await?;
+                }
+                writer.write_all(b"\n}\n")?;
+                writer.flush()?;
+                Ok(Some((exported.load(Ordering::Relaxed), skipped)))
+            },
+            move |this, result, cx| {
+                if this
+                    .prefix_export
+                    .as_ref()
+                    .is_some_and(|(flag, _)| Arc::ptr_eq(flag, &cancelled))
+                {
+                    this.prefix_export = None;
+                }
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                match result {
+                    Ok(Some((exported, skipped))) => {
+                        let message = t!(
+                            "key_tree.export_prefix_success",
+                            count = exported,
+                            skipped = skipped,
+                            path = path.display().to_string(),
+                            locale = locale
+                        )
+                        .to_string();
+                        this.emit_success_notification(message.into(), i18n_key_tree(cx, "export_prefix"), cx);
+                    }
+                    Ok(None) => {
+                        this.emit_warning_notification(i18n_key_tree(cx, "export_prefix_cancelled"), cx);
+                    }
+                    // The error is already reported, don't leave a truncated file
+                    Err(_) => {
+                        let _ = std::fs::remove_file(&path);
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+        self.emit_info_notification(i18n_key_tree(cx, "export_prefix_started"), cx);
+        // The export runs in the background, refresh the written count until it ends
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor().timer(EXPORT_PROGRESS_INTERVAL).await;
+                let running = this.update(cx, |this, cx| {
+                    cx.notify();
+                    this.prefix_export.is_some()
+                });
+                if !matches!(running, Ok(true)) {
+                    break;
+                }
+            }
+        })
+        .detach();
+        cx.notify();
+    }
+
+    /// Recreates the keys of a file written by `export_prefix`.
+    ///
+    /// The whole file is validated before the first write. Existing keys are
+    /// skipped, or deleted first when `overwrite` is set so collections are not
+    /// merged with the imported members.
+    pub fn import_keys(&mut self, path: PathBuf, overwrite: bool, cx: &mut Context<Self>) {
+        if self.readonly() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::ImportKeys,
+            move || async move {
+                let keys = read_import_file(&path)?;
+
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                if keys.iter().any(|(_, key_type, ..)| *key_type == KeyType::Json) {
+                    client.ensure(Capability::Json)?;
+                }
+                let mut conn = client.connection();
+                let mut imported = vec![];
+                let mut skipped = 0;
+                let mut failed = 0;
+                for batch in keys.chunks(IMPORT_BATCH_SIZE) {
+                    let mut pipeline = pipe();
+                    for (key, ..) in batch {
+                        pipeline.cmd("EXISTS").arg(key);
+                    }
+                    let exists: Vec<bool> = pipeline.query_async(&mut conn).await?;
+                    let mut pipeline = pipe();
+                    // Imported key and number of commands written for it
+                    let mut writes = vec![];
+                    for ((key, key_type, ttl, commands), exists) in batch.iter().zip(exists) {
+                        if commands.is_empty() || (exists && !overwrite) {
+                            skipped += 1;
+                            continue;
+                        }
+                        // A replaced key is deleted and written in one transaction, so a
+                        // failing write never leaves it deleted or half written
+                        if exists {
+                            pipeline.cmd("MULTI");
+                            pipeline.cmd("DEL").arg(key);
+                        }
+                        for command in commands {
+                            pipeline.add_command(command.clone());
+                        }
+                        if let Some(ttl) = ttl {
+                            pipeline.cmd("PEXPIRE").arg(key).arg(ttl);
+                        }
+                        if exists {
+                            pipeline.cmd("EXEC");
+                        }
+                        writes.push((
+                            key,
+                            *key_type,
+                            3 * exists as usize + commands.len() + ttl.is_some() as usize,
+                        ));
+                    }
+                    if writes.is_empty() {
+                        continue;
+                    }
+                    let values: Vec<Value> = pipeline.ignore_errors().query_async(&mut conn).await?;
+                    let mut values = values.into_iter();
+                    for (key, key_type, count) in writes {
+                        // EXEC replies with the results of the queued commands, or EXECABORT
+                        // when one of them was rejected while queued
+                        let errors = values
+                            .by_ref()
+                            .take(count)
+                            .filter(|value| match value {
+                                Value::ServerError(_) => true,
+                                Value::Array(values) => {
+                                    values.iter().any(|value| matches!(value, Value::ServerError(_)))
+                                }
+                                _ => false,
+                            })
+                            .count();
+                        if errors > 0 {
+                            failed += 1;
+                        } else {
+                            imported.push((SharedString::from(key.clone()), key_type));
+                        }
+                    }
+                }
+                Ok((imported, skipped, failed))
+            },
+            move |this, result, cx| {
+                let Ok((imported, skipped, failed)) = result else {
+                    return;
+                };
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                let message: SharedString = t!(
+                    "key_tree.import_keys_result",
+                    imported = imported.len(),
+                    skipped = skipped,
+                    failed = failed,
+                    locale = locale
+                )
+                .to_string()
+                .into();
+                if !imported.is_empty() {
+                    for (key, key_type) in imported {
+                        this.keys.insert(key, key_type);
+                    }
+                    this.key_tree_id = Uuid::now_v7().to_string().into();
+                }
+                if failed > 0 {
+                    this.emit_warning_notification(message, cx);
+                } else {
+                    this.emit_success_notification(message, i18n_key_tree(cx, "import_keys"), cx);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+
+    /// Copies `key` as `new_key` with DUMP and RESTORE, keeping its type and TTL.
+    ///
+    /// Some managed services disable DUMP, the key is then recreated with type-specific
+    /// commands. Without `overwrite` an existing `new_key` is never replaced.
+    pub fn duplicate_key(
+        &mut self,
+        key: SharedString,
+        new_key: SharedString,
+        overwrite: bool,
+        select: bool,
+        cx: &mut Context<Self>,
+    ) {
+        if key == new_key || new_key.is_empty() || self.readonly() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let count = self.value_scan_count();
+        let source = key.clone();
+        let target = new_key.clone();
+        self.spawn(
+            ServerTask::DuplicateKey,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let result: redis::RedisResult<(Option<Vec<u8>>, i64)> = pipe()
+                    .cmd("DUMP")
+                    .arg(source.as_str())
+                    .cmd("PTTL")
+                    .arg(source.as_str())
+                    .query_async(&mut conn)
+                    .await;
+                let (dump, ttl) = match result {
+                    Ok(value) => value,
+                    Err(e) if is_command_unavailable(&e) => {
+                        debug!(error = %e, "DUMP is not available, duplicate the key with commands");
+                        return duplicate_with_commands(&mut conn, &source, &target, overwrite, count).await;
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+                let Some(dump) = dump else {
+                    return Err(Error::Invalid {
+                        message: format!("Key {source} does not exist"),
+                    });
+                };
+                let mut command = cmd("RESTORE");
+                command.arg(target.as_str()).arg(ttl.max(0)).arg(dump);
+                if overwrite {
+                    command.arg("REPLACE");
+                }
+                match command.query_async::<()>(&mut conn).await {
+                    Ok(()) => Ok(()),
+                    Err(e) if e.code() == Some("BUSYKEY") => Err(Error::Invalid {
+                        message: format!("Key {target} already exists"),
+                    }),
+                    Err(e) => Err(e.into()),
+                }
+            },
+            move |this, result, cx| {
+                if result.is_err() {
+                    return;
+                }
+                let key_type = this.keys.get(&key).copied().unwrap_or_default();
+                this.keys.insert(new_key.clone(), key_type);
+                // Force refresh of the key tree view
+                this.key_tree_id = Uuid::now_v7().to_string().into();
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                let message = t!(
+                    "key_tree.duplicate_key_success",
+                    key = key,
+                    new_key = new_key,
+                    locale = locale
+                )
+                .to_string();
+                this.emit_success_notification(message.into(), i18n_key_tree(cx, "duplicate_key"), cx);
+                if select {
+                    this.select_key(new_key, cx);
+                }
                 cx.notify();
             },
             cx,
@@ -605,16 +1581,550 @@ impl ZedisServerState {
     }
 
     pub fn update_bytes_value_view_mode(&mut self, view_mode: SharedString, cx: &mut Context<Self>) {
+        let separator = self.key_separator(cx);
         let Some(value) = self.value.as_mut() else {
             return;
         };
         let view_mode = ViewMode::from_str(view_mode.as_str());
         // Directly modify the data in place
-        if let Some(RedisValueData::Bytes(bytes_value)) = &mut value.data {
+        if let Some(RedisValueData::Bytes(bytes_value) | RedisValueData::Json(bytes_value)) = &mut value.data {
             let bytes_value = Arc::make_mut(bytes_value);
-            bytes_value.view_mode = view_mode;
+            bytes_value.view_mode = view_mode.clone();
+            // Remember the choice for the keys sharing the same prefix
+            if let Some(key) = self.key.clone() {
+                update_app_state_and_save(cx, "save_view_mode", move |state, _cx| {
+                    state.set_view_mode(&key, &separator, view_mode.clone());
+                });
+            }
             cx.emit(ServerEvent::ValueModeViewUpdated);
             cx.notify();
         }
     }
 }
+
+/// Entry of a file written by `export_prefix`.
+#[derive(Deserialize)]
+struct ExportedKey {
+    /// Redis type, as returned by TYPE
+    #[serde(rename = "type")]
+    key_type: String,
+    /// Milliseconds, -1 without expiry
+    #[serde(default)]
+    ttl: Option<i64>,
+    value: serde_json::Value,
+    /// `base64` for values that are not valid UTF-8, every element of such a collection is encoded
+    #[serde(default)]
+    encoding: Option<String>,
+}
+
+/// Key read from an export: name, type, TTL in milliseconds and the commands writing it
+pub(crate) type ImportedKey = (String, KeyType, Option<i64>, Vec<redis::Cmd>);
+
+/// Reads and validates a key export, see `export_prefix`.
+pub(crate) fn read_import_file(path: &Path) -> Result<Vec<ImportedKey>, Error> {
+    let content = std::fs::read_to_string(path)?;
+    let entries: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&content).map_err(|e| Error::Invalid {
+            message: format!("The file is not a key export: {e}"),
+        })?;
+    let mut keys = Vec::with_capacity(entries.len());
+    for (key, value) in entries {
+        let invalid = |message: String| Error::Invalid {
+            message: format!("Invalid entry {key}: {message}"),
+        };
+        let entry: ExportedKey = serde_json::from_value(value).map_err(|e| invalid(e.to_string()))?;
+        let key_type = KeyType::from(entry.key_type.as_str());
+        let ttl = entry.ttl.filter(|ttl| *ttl > 0);
+        let commands = import_key_commands(&key, entry).map_err(invalid)?;
+        keys.push((key, key_type, ttl, commands));
+    }
+    Ok(keys)
+}
+
+/// Commands recreating an exported key, without its TTL.
+///
+/// Returns no command for an empty collection, which Redis can't store.
+fn import_key_commands(key: &str, entry: ExportedKey) -> Result<Vec<redis::Cmd>, String> {
+    let value = entry.value;
+    let base64 = match entry.encoding.as_deref() {
+        None => false,
+        Some("base64") => true,
+        Some(encoding) => return Err(format!("unknown encoding {encoding}")),
+    };
+    let decode = |text: &str| -> Result<Vec<u8>, String> {
+        if base64 {
+            BASE64.decode(text).map_err(|e| e.to_string())
+        } else {
+            Ok(text.as_bytes().to_vec())
+        }
+    };
+    let text = |value: &serde_json::Value| -> Result<Vec<u8>, String> {
+        decode(value.as_str().ok_or("the values must be strings")?)
+    };
+    let mut commands = vec![];
+    match KeyType::from(entry.key_type.as_str()) {
+        KeyType::String => {
+            let mut command = cmd("SET");
+            command.arg(key).arg(text(&value)?);
+            commands.push(command);
+        }
+        key_type @ (KeyType::List | KeyType::Set) => {
+            let items = value
+                .as_array()
+                .ok_or("the value must be an array of strings")?
+                .iter()
+                .map(text)
+                .collect::<Result<Vec<_>, _>>()?;
+            if !items.is_empty() {
+                let mut command = cmd(if key_type == KeyType::List { "RPUSH" } else { "SADD" });
+                command.arg(key).arg(items);
+                commands.push(command);
+            }
+        }
+        KeyType::Zset => {
+            let items = value.as_array().ok_or("the value must be an array")?;
+            if !items.is_empty() {
+                let mut command = cmd("ZADD");
+                command.arg(key);
+                for item in items {
+                    let member = item.get("member");
+                    let score = item.get("score").and_then(parse_json_score);
+                    let (Some(member), Some(score)) = (member, score) else {
+                        return Err("each item must have a member and a score".to_string());
+                    };
+                    command.arg(score).arg(text(member)?);
+                }
+                commands.push(command);
+            }
+        }
+        KeyType::Hash => {
+            let fields = value.as_object().ok_or("the value must be an object")?;
+            if !fields.is_empty() {
+                let mut command = cmd("HSET");
+                command.arg(key);
+                for (field, value) in fields {
+                    command.arg(decode(field)?).arg(text(value)?);
+                }
+                commands.push(command);
+            }
+        }
+        KeyType::Stream => {
+            let entries = value.as_array().ok_or("the value must be an array")?;
+            for entry in entries {
+                let id = entry.get("id").and_then(|id| id.as_str());
+                let fields = entry.get("fields").and_then(|fields| fields.as_object());
+                let (Some(id), Some(fields)) = (id, fields) else {
+                    return Err("each entry must have an id and fields".to_string());
+                };
+                let mut command = cmd("XADD");
+                command.arg(key).arg(id);
+                for (field, value) in fields {
+                    command.arg(decode(field)?).arg(text(value)?);
+                }
+                commands.push(command);
+            }
+        }
+        KeyType::Json => {
+            let mut command = cmd("JSON.SET");
+            command.arg(key).arg("$").arg(value.to_string());
+            commands.push(command);
+        }
+        _ => return Err(format!("unsupported type {}", entry.key_type)),
+    }
+    Ok(commands)
+}
+
+/// Recreates `key` as `new_key` with type-specific commands, for servers without DUMP.
+async fn duplicate_with_commands(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    new_key: &str,
+    overwrite: bool,
+    count: usize,
+) -> Result<(), Error> {
+    let (redis_type, ttl, exists): (String, i64, bool) = pipe()
+        .cmd("TYPE")
+        .arg(key)
+        .cmd("PTTL")
+        .arg(key)
+        .cmd("EXISTS")
+        .arg(new_key)
+        .query_async(conn)
+        .await?;
+    if redis_type == "none" {
+        return Err(Error::Invalid {
+            message: format!("Key {key} does not exist"),
+        });
+    }
+    if exists && !overwrite {
+        return Err(Error::Invalid {
+            message: format!("Key {new_key} already exists"),
+        });
+    }
+    // Elements are copied as bytes, binary members are kept as they are
+    let mut commands = vec![];
+    match KeyType::from(redis_type.as_str()) {
+        KeyType::String => {
+            let bytes: Vec<u8> = cmd("GET").arg(key).query_async(conn).await?;
+            let mut command = cmd("SET");
+            command.arg(new_key).arg(bytes);
+            commands.push(command);
+        }
+        key_type @ (KeyType::List | KeyType::Set) => {
+            let values: Vec<Vec<u8>> = if key_type == KeyType::List {
+                range_all(conn, "LRANGE", key, &[], count).await?
+            } else {
+                scan_all(conn, "SSCAN", key, count).await?
+            };
+            if !values.is_empty() {
+                let mut command = cmd(if key_type == KeyType::List { "RPUSH" } else { "SADD" });
+                command.arg(new_key).arg(values);
+                commands.push(command);
+            }
+        }
+        KeyType::Zset => {
+            let values: Vec<(Vec<u8>, f64)> = range_all(conn, "ZRANGE", key, &["WITHSCORES"], count).await?;
+            if !values.is_empty() {
+                let mut command = cmd("ZADD");
+                command.arg(new_key);
+                for (member, score) in values {
+                    command.arg(score).arg(member);
+                }
+                commands.push(command);
+            }
+        }
+        KeyType::Hash => {
+            let values: Vec<(Vec<u8>, Vec<u8>)> = scan_all(conn, "HSCAN", key, count).await?;
+            if !values.is_empty() {
+                let mut command = cmd("HSET");
+                command.arg(new_key);
+                for (field, value) in values {
+                    command.arg(field).arg(value);
+                }
+                commands.push(command);
+            }
+        }
+        KeyType::Stream => {
+            for (id, fields) in xrange_all(conn, key, count).await? {
+                let mut command = cmd("XADD");
+                command.arg(new_key).arg(id).arg(fields);
+                commands.push(command);
+            }
+        }
+        KeyType::Json => {
+            let document = get_json_document(conn, key).await?;
+            let mut command = cmd("JSON.SET");
+            command.arg(new_key).arg("$").arg(document.to_string());
+            commands.push(command);
+        }
+        key_type => {
+            return Err(Error::Invalid {
+                message: format!("Copying {} values is not supported", key_type.as_str()),
+            });
+        }
+    }
+    // Emptied since the TYPE lookup
+    if commands.is_empty() {
+        return Err(Error::Invalid {
+            message: format!("Key {key} does not exist"),
+        });
+    }
+    let mut pipeline = pipe();
+    if exists {
+        pipeline.cmd("DEL").arg(new_key);
+    }
+    for command in commands {
+        pipeline.add_command(command);
+    }
+    if ttl > 0 {
+        pipeline.cmd("PEXPIRE").arg(new_key).arg(ttl);
+    }
+    pipeline.query_async::<()>(conn).await?;
+    Ok(())
+}
+
+/// Reverts the optimistic update of a failed edit of `key`, restoring `snapshot`,
+/// the data taken before the update.
+///
+/// Returns false and leaves the value untouched when another key was selected
+/// meanwhile, the loaded value is not the edited one anymore.
+pub(crate) fn revert_failed_edit(
+    value: Option<&mut RedisValue>,
+    selected: Option<&SharedString>,
+    key: &SharedString,
+    snapshot: Option<RedisValueData>,
+) -> bool {
+    let Some(value) = value.filter(|_| selected == Some(key)) else {
+        return false;
+    };
+    value.data = snapshot;
+    true
+}
+
+/// Whether DUMP failed because the server doesn't offer it, renamed or denied by ACL,
+/// rather than because of the key.
+fn is_command_unavailable(e: &redis::RedisError) -> bool {
+    if e.code() == Some("NOPERM") {
+        return true;
+    }
+    let message = e.to_string().to_lowercase();
+    message.contains("unknown command") || message.contains("not allowed") || message.contains("disabled")
+}
+
+/// Text of a member copied as JSON, base64 when the collection holds binary members.
+fn json_text(bytes: Vec<u8>, binary: bool) -> serde_json::Value {
+    json_key(bytes, binary).into()
+}
+
+/// Same as `json_text`, for hash fields which are written as object keys.
+fn json_key(bytes: Vec<u8>, binary: bool) -> String {
+    if binary {
+        return BASE64.encode(bytes);
+    }
+    String::from_utf8(bytes).unwrap_or_else(|e| BASE64.encode(e.as_bytes()))
+}
+
+/// Score of a sorted set member, `inf` and `-inf` are written as strings as JSON has no infinity.
+fn json_score(score: f64) -> serde_json::Value {
+    if score.is_finite() {
+        score.into()
+    } else if score > 0.0 {
+        "inf".into()
+    } else {
+        "-inf".into()
+    }
+}
+
+/// Reads a score written by `json_score`.
+fn parse_json_score(value: &serde_json::Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|score| score.parse::<f64>().ok()))
+        .filter(|score| !score.is_nan())
+}
+
+/// Reads every element of a hash or a set with HSCAN or SSCAN, `count` elements per call.
+///
+/// Unlike HGETALL and SMEMBERS this doesn't block the server on large keys.
+async fn scan_all<T: redis::FromRedisValue>(
+    conn: &mut RedisAsyncConn,
+    command: &str,
+    key: &str,
+    count: usize,
+) -> Result<Vec<T>, Error> {
+    let mut cursor = 0_u64;
+    let mut items = vec![];
+    loop {
+        let (next_cursor, batch): (u64, Vec<T>) = cmd(command)
+            .arg(key)
+            .arg(cursor)
+            .arg("COUNT")
+            .arg(count)
+            .query_async(conn)
+            .await?;
+        items.extend(batch);
+        if next_cursor == 0 {
+            return Ok(items);
+        }
+        cursor = next_cursor;
+    }
+}
+
+/// Reads a whole list or sorted set with LRANGE / ZRANGE by pages of `count` items,
+/// so that a large value is not requested in a single reply.
+async fn range_all<T: redis::FromRedisValue>(
+    conn: &mut RedisAsyncConn,
+    command: &str,
+    key: &str,
+    options: &[&str],
+    count: usize,
+) -> Result<Vec<T>, Error> {
+    let count = count.max(1);
+    let mut start = 0;
+    let mut items = vec![];
+    loop {
+        let batch: Vec<T> = cmd(command)
+            .arg(key)
+            .arg(start)
+            .arg(start + count - 1)
+            .arg(options)
+            .query_async(conn)
+            .await?;
+        let done = batch.len() < count;
+        items.extend(batch);
+        if done {
+            return Ok(items);
+        }
+        start += count;
+    }
+}
+
+/// Smallest stream ID after `id`, the start of the next XRANGE page (`(id` needs Redis 6.2).
+fn next_stream_id(id: &str) -> String {
+    let (ms, seq) = id.split_once('-').unwrap_or((id, "0"));
+    match seq.parse::<u64>() {
+        Ok(seq) if seq < u64::MAX => format!("{ms}-{}", seq + 1),
+        _ => format!("{}-0", ms.parse::<u64>().unwrap_or_default().saturating_add(1)),
+    }
+}
+
+/// Reads a whole stream with XRANGE by pages of `count` entries.
+async fn xrange_all(conn: &mut RedisAsyncConn, key: &str, count: usize) -> Result<Vec<(String, Vec<Vec<u8>>)>, Error> {
+    let count = count.max(1);
+    let mut start = "-".to_string();
+    let mut entries = vec![];
+    loop {
+        let batch: Vec<(String, Vec<Vec<u8>>)> = cmd("XRANGE")
+            .arg(key)
+            .arg(&start)
+            .arg("+")
+            .arg("COUNT")
+            .arg(count)
+            .query_async(conn)
+            .await?;
+        let Some((last_id, _)) = batch.last() else {
+            return Ok(entries);
+        };
+        start = next_stream_id(last_id);
+        let done = batch.len() < count;
+        entries.extend(batch);
+        if done {
+            return Ok(entries);
+        }
+    }
+}
+
+/// Reads the whole structure of a non string key as JSON, large values are read by pages.
+///
+/// When an element is not valid UTF-8 every element is written as base64 and the
+/// returned encoding is `base64`, so members that merely look like base64 are kept.
+async fn get_structure_json(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    key_type: KeyType,
+    count: usize,
+) -> Result<(serde_json::Value, Option<&'static str>), Error> {
+    let is_binary = |bytes: &[u8]| std::str::from_utf8(bytes).is_err();
+    let (value, binary): (serde_json::Value, bool) = match key_type {
+        KeyType::List | KeyType::Set => {
+            let values: Vec<Vec<u8>> = if key_type == KeyType::List {
+                range_all(conn, "LRANGE", key, &[], count).await?
+            } else {
+                let mut values: Vec<Vec<u8>> = scan_all(conn, "SSCAN", key, count).await?;
+                // SSCAN may return a member more than once
+                values.sort();
+                values.dedup();
+                values
+            };
+            let binary = values.iter().any(|value| is_binary(value));
+            let values = values.into_iter().map(|value| json_text(value, binary)).collect();
+            (values, binary)
+        }
+        KeyType::Zset => {
+            let values: Vec<(Vec<u8>, f64)> = range_all(conn, "ZRANGE", key, &["WITHSCORES"], count).await?;
+            let binary = values.iter().any(|(member, _)| is_binary(member));
+            let values = values
+                .into_iter()
+                .map(|(member, score)| serde_json::json!({ "member": json_text(member, binary), "score": json_score(score) }))
+                .collect();
+            (values, binary)
+        }
+        KeyType::Hash => {
+            let values: Vec<(Vec<u8>, Vec<u8>)> = scan_all(conn, "HSCAN", key, count).await?;
+            let binary = values.iter().any(|(field, value)| is_binary(field) || is_binary(value));
+            let mut object = serde_json::Map::new();
+            for (field, value) in values {
+                object.insert(json_key(field, binary), json_text(value, binary));
+            }
+            (serde_json::Value::Object(object), binary)
+        }
+        KeyType::Stream => {
+            let entries = xrange_all(conn, key, count).await?;
+            let binary = entries
+                .iter()
+                .any(|(_, fields)| fields.iter().any(|field| is_binary(field)));
+            let entries = entries
+                .into_iter()
+                .map(|(id, fields)| {
+                    let mut object = serde_json::Map::new();
+                    let mut iter = fields.into_iter();
+                    while let (Some(field), Some(value)) = (iter.next(), iter.next()) {
+                        object.insert(json_key(field, binary), json_text(value, binary));
+                    }
+                    serde_json::json!({ "id": id, "fields": object })
+                })
+                .collect();
+            (entries, binary)
+        }
+        KeyType::Json => (get_json_document(conn, key).await?, false),
+        _ => {
+            return Err(Error::Invalid {
+                message: format!("Copying {} values is not supported", key_type.as_str()),
+            });
+        }
+    };
+    Ok((value, binary.then_some("base64")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_value(fields: &[&str]) -> RedisValue {
+        RedisValue {
+            key_type: KeyType::Hash,
+            data: Some(RedisValueData::Hash(Arc::new(RedisHashValue {
+                size: fields.len(),
+                values: fields
+                    .iter()
+                    .map(|field| (SharedString::from(field.to_string()), SharedString::from("value")))
+                    .collect(),
+                ..Default::default()
+            }))),
+            ..Default::default()
+        }
+    }
+
+    /// Applies an optimistic update like the edit helpers, returning the snapshot taken before it
+    fn remove_first_field(value: &mut RedisValue) -> Option<RedisValueData> {
+        let snapshot = value.data.clone();
+        if let Some(RedisValueData::Hash(hash)) = value.data.as_mut() {
+            let hash = Arc::make_mut(hash);
+            hash.values.remove(0);
+            hash.size -= 1;
+        }
+        snapshot
+    }
+
+    #[test]
+    fn test_revert_failed_edit_restores_value() {
+        let key = SharedString::from("user:1");
+        let original = hash_value(&["name", "age"]);
+        let mut value = original.clone();
+        let snapshot = remove_first_field(&mut value);
+        assert_ne!(value, original);
+
+        assert!(revert_failed_edit(Some(&mut value), Some(&key), &key, snapshot));
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn test_revert_failed_edit_keeps_other_key() {
+        let key = SharedString::from("user:1");
+        let other = SharedString::from("user:2");
+        let mut value = hash_value(&["name", "age"]);
+        let snapshot = remove_first_field(&mut value);
+        // The value of the newly selected key must not be replaced by the old snapshot
+        let loaded = hash_value(&["title"]);
+        let mut other_value = loaded.clone();
+        assert!(!revert_failed_edit(
+            Some(&mut other_value),
+            Some(&other),
+            &key,
+            snapshot.clone()
+        ));
+        assert_eq!(other_value, loaded);
+
+        assert!(!revert_failed_edit(None, None, &key, snapshot));
+    }
+}
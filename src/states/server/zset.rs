@@ -24,7 +24,7 @@
 
 use super::{
     KeyType, RedisValueData, ServerTask, ZedisServerState,
-    value::{RedisValue, RedisValueStatus, RedisZsetValue, SortOrder},
+    value::{RedisValue, RedisValueStatus, RedisZsetValue, SortOrder, revert_failed_edit},
 };
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
@@ -148,26 +148,26 @@ async fn search_redis_zset_value(
 
 /// Performs initial load of a Redis ZSET value.
 ///
-/// Fetches the total cardinality (ZCARD) and loads the first batch of members (up to 100).
+/// Fetches the total cardinality (ZCARD) and loads the first batch of members.
 /// This is called when a ZSET key is first opened in the editor.
 ///
 /// # Arguments
 /// * `conn` - Redis async connection
 /// * `key` - The ZSET key to load
 /// * `sort_order` - Initial sort order (Ascending or Descending)
+/// * `count` - Number of members of the first batch
 ///
 /// # Returns
 /// A `RedisValue` containing ZSET metadata and initial member/score pairs
 pub(crate) async fn first_load_zset_value(
     conn: &mut RedisAsyncConn,
     key: &str,
+    size: usize,
     sort_order: SortOrder,
+    count: usize,
 ) -> Result<RedisValue> {
-    // Get total number of members in the ZSET
-    let size: usize = cmd("ZCARD").arg(key).query_async(conn).await?;
-
-    // Load first batch (ranks 0-99, i.e., 100 members)
-    let values = get_redis_zset_value(conn, key, sort_order, 0, 99).await?;
+    // Load first batch (ranks 0 to count - 1)
+    let values = get_redis_zset_value(conn, key, sort_order, 0, count - 1).await?;
 
     Ok(RedisValue {
         key_type: KeyType::Zset,
@@ -203,6 +203,8 @@ impl ZedisServerState {
         let key_str = key.to_string();
         value.status = RedisValueStatus::Updating;
 
+        // Restored if the edit fails
+        let snapshot = value.data.clone();
         // Perform optimistic update on local state
         if let Some(RedisValueData::Zset(zset_data)) = value.data.as_mut() {
             optimistic_update(Arc::make_mut(zset_data));
@@ -225,7 +227,12 @@ impl ZedisServerState {
                 }
                 match result {
                     Ok(data) => on_success(this, data, cx),
-                    Err(e) => this.emit_error_notification(e.to_string().into(), cx),
+                    // The error is already reported by `spawn`, revert the optimistic update
+                    Err(_) => {
+                        if revert_failed_edit(this.value.as_mut(), this.key.as_ref(), &key, snapshot) {
+                            cx.emit(ServerEvent::ValueUpdated);
+                        }
+                    }
                 }
                 cx.notify();
             },
@@ -361,6 +368,7 @@ impl ZedisServerState {
         let new_zset = RedisZsetValue {
             keyword,
             size: zset.size,
+            sort_order: zset.sort_order,
             ..Default::default()
         };
         value.data = Some(RedisValueData::Zset(Arc::new(new_zset)));
@@ -368,6 +376,25 @@ impl ZedisServerState {
         // Trigger load with the new filter
         self.load_more_zset_value(cx);
     }
+    /// Reloads the ZSET members ordered by score in the given direction.
+    ///
+    /// The keyword filter is kept, ZSCAN results are not ordered by score though.
+    pub fn sort_zset_value(&mut self, sort_order: SortOrder, cx: &mut Context<Self>) {
+        let Some((_, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(zset) = value.zset_value() else {
+            return;
+        };
+        let new_zset = RedisZsetValue {
+            keyword: zset.keyword.clone(),
+            size: zset.size,
+            sort_order,
+            ..Default::default()
+        };
+        value.data = Some(RedisValueData::Zset(Arc::new(new_zset)));
+        self.load_more_zset_value(cx);
+    }
     /// Loads the next batch of ZSET members using appropriate pagination strategy.
     ///
     /// Uses two different strategies based on whether filtering is active:
@@ -400,9 +427,11 @@ impl ZedisServerState {
         let server_id = self.server_id.clone();
         let db = self.db;
 
-        // Calculate range for pagination (load 100 items)
+        // Calculate range for pagination (load one batch of scan count items)
+        let scan_count = self.value_scan_count();
+        let filter_scan_count = self.filter_scan_count() as u64;
         let start = current_len;
-        let stop = start + 99;
+        let stop = start + scan_count - 1;
 
         cx.emit(ServerEvent::ValuePaginationStarted);
         let keyword_clone = keyword.clone();
@@ -420,7 +449,7 @@ impl ZedisServerState {
                 } else {
                     // With filter: use scan-based pagination with pattern matching
                     let pattern = format!("*{keyword}*");
-                    let result = search_redis_zset_value(&mut conn, &key, cursor, &pattern, 1000).await?;
+                    let result = search_redis_zset_value(&mut conn, &key, cursor, &pattern, filter_scan_count).await?;
                     Ok(result)
                 }
             },
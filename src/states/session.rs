@@ -43,6 +43,10 @@ pub enum QueryMode {
     All,
     Prefix,
     Exact,
+    /// Glob passed as is to `SCAN MATCH`, wildcards can be anywhere in the key
+    Pattern,
+    /// Regex matched client-side, SCAN only supports globs so the whole keyspace is scanned
+    Regex,
 }
 
 impl fmt::Display for QueryMode {
@@ -50,12 +54,27 @@ impl fmt::Display for QueryMode {
         let s = match self {
             QueryMode::Prefix => "^",
             QueryMode::Exact => "=",
+            QueryMode::Pattern => "~",
+            QueryMode::Regex => "/",
             _ => "*",
         };
         write!(f, "{}", s)
     }
 }
 
+impl QueryMode {
+    /// The mode after this one in the query mode menu, wrapping around
+    pub fn next(self) -> Self {
+        match self {
+            QueryMode::All => QueryMode::Prefix,
+            QueryMode::Prefix => QueryMode::Exact,
+            QueryMode::Exact => QueryMode::Pattern,
+            QueryMode::Pattern => QueryMode::Regex,
+            QueryMode::Regex => QueryMode::All,
+        }
+    }
+}
+
 impl FromStr for QueryMode {
     type Err = std::convert::Infallible;
 
@@ -63,17 +82,45 @@ impl FromStr for QueryMode {
         match s {
             "^" => Ok(QueryMode::Prefix),
             "=" => Ok(QueryMode::Exact),
+            "~" => Ok(QueryMode::Pattern),
+            "/" => Ok(QueryMode::Regex),
             _ => Ok(QueryMode::All),
         }
     }
 }
 
+/// Order of the siblings in the key tree, folders are always listed before keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum KeySortMode {
+    #[default]
+    NameAsc,
+    NameDesc,
+    /// By key type, then by name
+    Type,
+    /// By the loaded memory usage, the largest first
+    Memory,
+}
+
 #[derive(Debug, Default, Deserialize, Clone, Serialize)]
 pub struct SessionOption {
     pub id: String,
     pub soft_wrap: Option<bool>,
+    pub indent_guides: Option<bool>,
     pub query_mode: Option<String>,
     pub refresh_interval_sec: Option<u32>,
+    pub show_folder_memory: Option<bool>,
+    pub show_key_memory: Option<bool>,
+    pub key_sort_mode: Option<KeySortMode>,
+    /// Whether the key tree lists the full key names without folders
+    pub flat_view: Option<bool>,
+    /// Whether the key tree filters while typing, without pressing Enter
+    pub live_search: Option<bool>,
+    /// Separator of the key namespaces, the global setting when unset
+    pub key_separator: Option<String>,
+    /// Expanded folders of the key tree, restored when the server is opened again
+    pub expanded_items: Option<Vec<String>>,
+    /// Whether the key tree follows the keyspace notifications of the server, opt-in for the overhead
+    pub watch_key_events: Option<bool>,
 }
 
 #[derive(Debug, Default, Deserialize, Clone, Serialize)]
@@ -14,18 +14,25 @@
 
 mod about;
 mod bytes_editor;
+mod command_palette;
+mod config_editor;
 mod content;
 mod editor;
 mod hash_editor;
 mod key_tree;
 mod kv_table;
 mod list_editor;
+mod log_viewer;
 mod metrics;
+mod monitor_editor;
+mod notification_center;
 mod proto_editor;
 mod pubsub_editor;
+mod search_editor;
 mod servers;
 mod set_editor;
 mod setting_editor;
+mod shortcuts;
 mod sidebar;
 mod status_bar;
 mod stream_editor;
@@ -34,18 +41,25 @@ mod zset_editor;
 
 pub use about::open_about_window;
 pub use bytes_editor::ZedisBytesEditor;
+pub use command_palette::open_command_palette;
+pub use config_editor::ZedisConfigEditor;
 pub use content::ZedisContent;
 pub use editor::ZedisEditor;
 pub use hash_editor::ZedisHashEditor;
 pub use key_tree::ZedisKeyTree;
 pub use kv_table::ZedisKvTable;
 pub use list_editor::ZedisListEditor;
+pub use log_viewer::ZedisLogViewer;
 pub use metrics::ZedisMetrics;
+pub use monitor_editor::ZedisMonitorEditor;
+pub use notification_center::open_notification_center;
 pub use proto_editor::ZedisProtoEditor;
 pub use pubsub_editor::ZedisPubsubEditor;
+pub use search_editor::ZedisSearchEditor;
 pub use servers::ZedisServers;
 pub use set_editor::ZedisSetEditor;
 pub use setting_editor::ZedisSettingEditor;
+pub use shortcuts::open_shortcuts_dialog;
 pub use sidebar::ZedisSidebar;
 pub use status_bar::ZedisStatusBar;
 pub use stream_editor::ZedisStreamEditor;
@@ -13,16 +13,22 @@
 // limitations under the License.
 
 use crate::helpers::get_font_family;
-use crate::states::{DataFormat, RedisBytesValue, ServerEvent, ViewMode, ZedisGlobalStore, ZedisServerState};
+use crate::states::{
+    DataFormat, RedisBytesValue, ServerEvent, ViewMode, ZedisGlobalStore, ZedisServerState, decode_as, i18n_editor,
+    looks_like_json,
+};
 use gpui::{App, Entity, Image, ObjectFit, SharedString, Subscription, Window, img, px};
 use gpui::{div, hsla, prelude::*};
+use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::highlighter::Language;
-use gpui_component::input::{Input, InputEvent, InputState, TabSize};
+use gpui_component::input::{Input, InputEvent, InputState, Position, TabSize};
 use gpui_component::label::Label;
 use gpui_component::list::{List, ListDelegate, ListItem, ListState};
-use gpui_component::{ActiveTheme, IndexPath, h_flex};
+use gpui_component::{ActiveTheme, Disableable, IndexPath, Sizable, h_flex, v_flex};
+use humansize::{DECIMAL, format_size};
 use pretty_hex::HexConfig;
 use pretty_hex::config_hex;
+use rust_i18n::t;
 use std::sync::Arc;
 use tracing::info;
 
@@ -60,18 +66,33 @@ pub struct ZedisBytesEditor {
     /// Whether to soft wrap the editor
     soft_wrap: bool,
 
+    /// Whether to show indent guides in the editor
+    indent_guides: bool,
+
     /// Whether the editor is readonly
     readonly: bool,
 
+    /// Whether the text looks like JSON, it is only parsed when formatted
+    json: bool,
+
     /// Whether to update the editor
     should_update_editor: bool,
 
     /// Whether the soft wrap has been changed
     soft_wrap_changed: bool,
 
+    /// Whether the indent guides have been changed
+    indent_guides_changed: bool,
+
+    /// Line (1-based) to move the cursor to on the next render
+    go_to_line: Option<usize>,
+
     /// The data to display in the editor
     data: ByteEditorData,
 
+    /// (offset, length, total size) of the loaded window if the value is too large to load at once
+    partial: Option<(usize, usize, usize)>,
+
     /// Event subscriptions for reactive updates
     _subscriptions: Vec<Subscription>,
 }
@@ -134,12 +155,15 @@ fn format_byte_editor_data(value: &Arc<RedisBytesValue>, cx: &App) -> ByteEditor
     match value.view_mode {
         ViewMode::Hex => create_hex_view(),
 
-        ViewMode::Plain => {
-            let text = SharedString::new(String::from_utf8_lossy(&value.bytes));
-            ByteEditorData::Text(text)
+        ViewMode::Plain | ViewMode::Json | ViewMode::MessagePack | ViewMode::Base64 => {
+            // Bytes which can't be decoded as requested fall back to hex
+            match decode_as(&value.bytes, &value.view_mode) {
+                Some(text) => ByteEditorData::Text(text),
+                None => create_hex_view(),
+            }
         }
 
-        _ => {
+        ViewMode::Auto => {
             if value.is_image() {
                 let format = match value.format {
                     DataFormat::Png => gpui::ImageFormat::Png,
@@ -250,11 +274,20 @@ impl ZedisBytesEditor {
                     this.soft_wrap_changed = true;
                     this.soft_wrap = *soft_wrap;
                 }
+                ServerEvent::IndentGuidesToggled(indent_guides) => {
+                    this.indent_guides_changed = true;
+                    this.indent_guides = *indent_guides;
+                }
+                ServerEvent::GoToLineRequested(line) => {
+                    this.go_to_line = Some(*line);
+                    cx.notify();
+                }
                 _ => {}
             }),
         );
 
         let soft_wrap = server_state.read(cx).soft_wrap();
+        let indent_guides = server_state.read(cx).indent_guides();
 
         // Configure code editor with JSON syntax highlighting
         let default_language = Language::from_str(DEFAULT_LANGUAGE);
@@ -262,7 +295,7 @@ impl ZedisBytesEditor {
             InputState::new(window, cx)
                 .code_editor(default_language.name())
                 .line_number(true)
-                .indent_guides(true)
+                .indent_guides(indent_guides)
                 .tab_size(TabSize {
                     tab_size: DEFAULT_TAB_SIZE,
                     hard_tabs: false,
@@ -271,6 +304,24 @@ impl ZedisBytesEditor {
                 .soft_wrap(soft_wrap)
         });
 
+        // Share the cursor with the status bar, the editor notifies on cursor moves
+        subscriptions.push(cx.observe(&editor, |this, editor, cx| {
+            let position = editor.read(cx).cursor_position();
+            this.server_state.update(cx, |state, cx| {
+                state.set_editor_cursor(Some((position.line, position.character)), cx);
+            });
+        }));
+        // Keys of other types don't use this editor, the status bar hides the cursor once it is dropped
+        cx.on_release({
+            let server_state = server_state.clone();
+            move |_, cx| {
+                server_state.update(cx, |state, cx| {
+                    state.set_editor_cursor(None, cx);
+                });
+            }
+        })
+        .detach();
+
         // Subscribe to editor changes to track modification state
         subscriptions.push(cx.subscribe(&editor, |this, _, event, cx| {
             if let InputEvent::Change = &event {
@@ -291,12 +342,17 @@ impl ZedisBytesEditor {
             value_modified: false,
             soft_wrap,
             soft_wrap_changed: false,
+            indent_guides,
+            indent_guides_changed: false,
+            go_to_line: None,
             data: ByteEditorData::Text(SharedString::default()),
+            partial: None,
             hex_viewer_state: None,
             editor,
             should_update_editor: true,
             server_state,
             readonly,
+            json: false,
             _subscriptions: subscriptions,
         };
         this.update_editor_data(cx);
@@ -321,22 +377,87 @@ impl ZedisBytesEditor {
 
         let redis_bytes_value = value.and_then(|v| v.bytes_value());
         if let Some(redis_bytes_value) = &redis_bytes_value {
-            self.readonly = readonly || !redis_bytes_value.is_utf8_text();
+            let is_partial = redis_bytes_value.is_partial();
+            self.readonly =
+                readonly || is_partial || !redis_bytes_value.is_utf8_text() || redis_bytes_value.view_mode.is_decoded();
+            self.partial = is_partial.then(|| {
+                (
+                    redis_bytes_value.offset,
+                    redis_bytes_value.bytes.len(),
+                    redis_bytes_value.total_size,
+                )
+            });
             self.data = format_byte_editor_data(redis_bytes_value, cx);
         } else {
+            self.partial = None;
             self.data = ByteEditorData::Text(SharedString::default());
         }
-
-        if !matches!(self.data, ByteEditorData::Hex(_)) {
-            self.hex_viewer_state = None;
-        }
+        self.json = self.data.to_string().is_some_and(|value| looks_like_json(&value));
+        // Hex rows are rebuilt from the new window
+        self.hex_viewer_state = None;
     }
 
     /// Check if the current editor value differs from the original Redis value
+    /// Moves the cursor to the start of `line` (1-based), warns when the line is out of range
+    fn handle_go_to_line(&mut self, line: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let lines = self.editor.read(cx).value().lines().count().max(1);
+        if line == 0 || line > lines {
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!(
+                "editor.go_to_line_out_of_range",
+                line = line,
+                lines = lines,
+                locale = locale
+            )
+            .to_string();
+            self.server_state.update(cx, |state, cx| {
+                state.emit_warning_notification(message.into(), cx);
+            });
+            return;
+        }
+        self.editor.update(cx, |state, cx| {
+            state.set_cursor_position(Position::new(line as u32 - 1, 0), window, cx);
+            state.focus(window, cx);
+        });
+    }
     pub fn is_value_modified(&self) -> bool {
         self.value_modified
     }
 
+    /// Whether the editable text looks like JSON and can be formatted
+    pub fn is_json(&self) -> bool {
+        self.json && !self.readonly
+    }
+
+    /// Pretty-print (2 spaces) or minify the JSON in the editor.
+    ///
+    /// Only the editor content changes, the value is saved as shown on demand.
+    pub fn format_json(&mut self, minify: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.is_json() {
+            return;
+        }
+        let value = self.editor.read(cx).value();
+        let formatted = serde_json::from_str::<serde_json::Value>(&value).ok().and_then(|json| {
+            if minify {
+                serde_json::to_string(&json).ok()
+            } else {
+                serde_json::to_string_pretty(&json).ok()
+            }
+        });
+        let Some(formatted) = formatted else {
+            self.server_state.update(cx, |state, cx| {
+                state.emit_warning_notification(i18n_editor(cx, "invalid_json"), cx);
+            });
+            return;
+        };
+        self.editor.update(cx, |state, cx| {
+            state.set_value(formatted.clone(), window, cx);
+        });
+        let original = self.data.to_string().unwrap_or_default();
+        self.value_modified = original != formatted.as_str();
+        cx.notify();
+    }
+
     /// Check if the editor is readonly
     pub fn is_readonly(&self) -> bool {
         self.readonly
@@ -346,6 +467,73 @@ impl ZedisBytesEditor {
     pub fn value(&self, cx: &mut Context<Self>) -> SharedString {
         self.editor.read(cx).value()
     }
+
+    /// Render the notice bar of a partially loaded value with window navigation
+    fn render_partial_bar(&self, offset: usize, len: usize, total: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let notice = t!(
+            "editor.large_value_notice",
+            start = format_size(offset as u64, DECIMAL),
+            end = format_size((offset + len) as u64, DECIMAL),
+            total = format_size(total as u64, DECIMAL),
+            locale = locale
+        )
+        .to_string();
+        let has_previous = offset > 0;
+        let has_next = offset + len < total;
+        let previous_offset = offset.saturating_sub(len);
+        let next_offset = offset + len;
+
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().warning.opacity(0.1))
+            .child(
+                Label::new(notice)
+                    .flex_1()
+                    .text_sm()
+                    .text_color(cx.theme().warning_foreground),
+            )
+            .child(
+                Button::new("bytes-editor-previous-chunk")
+                    .small()
+                    .outline()
+                    .label(i18n_editor(cx, "previous_chunk"))
+                    .disabled(!has_previous)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.load_string_range(previous_offset, cx);
+                        });
+                    })),
+            )
+            .child(
+                Button::new("bytes-editor-next-chunk")
+                    .small()
+                    .outline()
+                    .label(i18n_editor(cx, "next_chunk"))
+                    .disabled(!has_next)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.load_string_range(next_offset, cx);
+                        });
+                    })),
+            )
+            .child(
+                Button::new("bytes-editor-load-full")
+                    .small()
+                    .primary()
+                    .label(i18n_editor(cx, "load_full_value"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.load_full_value(cx);
+                        });
+                    })),
+            )
+    }
 }
 
 impl Render for ZedisBytesEditor {
@@ -362,7 +550,16 @@ impl Render for ZedisBytesEditor {
             });
             self.soft_wrap_changed = false;
         }
-        match &self.data {
+        if self.indent_guides_changed {
+            self.editor.update(cx, |this, cx| {
+                this.set_indent_guides(self.indent_guides, window, cx);
+            });
+            self.indent_guides_changed = false;
+        }
+        if let Some(line) = self.go_to_line.take() {
+            self.handle_go_to_line(line, window, cx);
+        }
+        let content = match &self.data {
             ByteEditorData::Image(value) => div()
                 .size_full()
                 .flex()
@@ -398,6 +595,14 @@ impl Render for ZedisBytesEditor {
                     .focus_bordered(false)
                     .into_any_element()
             }
-        }
+        };
+        let Some((offset, len, total)) = self.partial else {
+            return content;
+        };
+        v_flex()
+            .size_full()
+            .child(self.render_partial_bar(offset, len, total, cx))
+            .child(div().flex_1().w_full().overflow_hidden().child(content))
+            .into_any_element()
     }
 }
@@ -0,0 +1,330 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    connection::get_servers,
+    helpers::{EditorAction, MemuAction, ShortcutAction, get_shortcuts, humanize_keystroke},
+    states::{
+        FontSizeAction, LocaleAction, Route, SettingsAction, ThemeAction, ZedisGlobalStore, i18n_command_palette,
+        i18n_shortcuts, i18n_sidebar,
+    },
+};
+use gpui::{Action, App, Entity, ScrollHandle, SharedString, Subscription, Window, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, IconName, WindowExt, h_flex,
+    input::{Input, InputEvent, InputState, MoveDown, MoveUp},
+    label::Label,
+    v_flex,
+};
+use rust_i18n::t;
+use std::rc::Rc;
+use zedis_ui::ZedisDialog;
+
+const LIST_MAX_HEIGHT: f32 = 360.0;
+
+type CommandHandler = Rc<dyn Fn(&mut Window, &mut App)>;
+
+struct PaletteCommand {
+    label: SharedString,
+    /// Humanized keystrokes of the bound shortcut
+    keystrokes: Option<SharedString>,
+    handler: CommandHandler,
+}
+
+/// Handler dispatching the action, as the menus and the key bindings do.
+fn dispatch(action: impl Action) -> CommandHandler {
+    Rc::new(move |window, cx| window.dispatch_action(action.boxed_clone(), cx))
+}
+
+/// Score of `label` for the lowercase `query`, `None` when the characters of the
+/// query are not all found in order. Lower is better, so consecutive characters
+/// and matches near the start rank first.
+fn fuzzy_score(label: &str, query: &str) -> Option<usize> {
+    let label = label.to_lowercase();
+    let mut chars = label.chars().enumerate();
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    for ch in query.chars() {
+        let (index, _) = chars.find(|(_, c)| *c == ch)?;
+        score += match previous {
+            Some(previous) => index - previous - 1,
+            None => index,
+        };
+        previous = Some(index);
+    }
+    Some(score)
+}
+
+/// Every command of the palette, the editor commands only when a server is opened.
+fn new_commands(window: &Window, cx: &App) -> Vec<PaletteCommand> {
+    let shortcuts = get_shortcuts();
+    let command = |label: SharedString, shortcut: Option<&str>, handler: CommandHandler| {
+        let keystrokes = shortcut
+            .and_then(|name| shortcuts.iter().find(|item| item.name == name))
+            .map(|item| {
+                item.keystrokes
+                    .split_whitespace()
+                    .map(humanize_keystroke)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .into()
+            });
+        PaletteCommand {
+            label,
+            keystrokes,
+            handler,
+        }
+    };
+    let window_id = window.window_handle().window_id();
+    let store = cx.global::<ZedisGlobalStore>().read(cx);
+    let locale = store.locale();
+    let mut commands = vec![];
+
+    if store.route(window_id) == Route::Editor && store.selected_server(window_id).is_some() {
+        for (name, action) in [
+            ("add_key", EditorAction::Create),
+            ("delete_key", EditorAction::DeleteKey),
+            ("refresh_keys", EditorAction::RefreshKeys),
+            ("focus_filter", EditorAction::Search),
+            ("quick_open", EditorAction::QuickOpen),
+            ("cycle_query_mode", EditorAction::CycleQueryMode),
+            ("reload", EditorAction::Reload),
+            ("save", EditorAction::Save),
+            ("update_ttl", EditorAction::UpdateTtl),
+            ("toggle_terminal", EditorAction::Cmd),
+        ] {
+            commands.push(command(i18n_shortcuts(cx, name), Some(name), dispatch(action)));
+        }
+        commands.push(command(
+            i18n_command_palette(cx, "rename_key"),
+            None,
+            dispatch(EditorAction::RenameKey),
+        ));
+        commands.push(command(
+            i18n_command_palette(cx, "toggle_soft_wrap"),
+            None,
+            dispatch(EditorAction::ToggleSoftWrap),
+        ));
+    }
+
+    for server in get_servers().unwrap_or_default() {
+        let label = t!("command_palette.switch_server", name = server.name, locale = locale).to_string();
+        let server_id = server.id;
+        let db = server.db();
+        let handler: CommandHandler = Rc::new(move |window, cx| {
+            let window_id = window.window_handle().window_id();
+            let server_id = server_id.clone();
+            cx.global::<ZedisGlobalStore>().clone().update(cx, |state, cx| {
+                state.go_to(window_id, Route::Editor, cx);
+                state.set_selected_server(window_id, (server_id, db), cx);
+            });
+        });
+        commands.push(command(label.into(), None, handler));
+    }
+    for (name, action) in [
+        ("next_server", ShortcutAction::NextServer),
+        ("previous_server", ShortcutAction::PreviousServer),
+        ("show_shortcuts", ShortcutAction::ShowShortcuts),
+    ] {
+        commands.push(command(i18n_shortcuts(cx, name), Some(name), dispatch(action)));
+    }
+
+    for (name, action) in [
+        ("open_settings", SettingsAction::Editor),
+        ("open_protos", SettingsAction::Protos),
+        ("open_logs", SettingsAction::Logs),
+    ] {
+        commands.push(command(i18n_command_palette(cx, name), None, dispatch(action)));
+    }
+    let theme = i18n_sidebar(cx, "theme");
+    for (name, action) in [
+        ("light", ThemeAction::Light),
+        ("dark", ThemeAction::Dark),
+        ("system", ThemeAction::System),
+    ] {
+        let label = format!("{theme}: {}", i18n_sidebar(cx, name));
+        commands.push(command(label.into(), None, dispatch(action)));
+    }
+    for (name, action) in [
+        ("zoom_in", FontSizeAction::ZoomIn),
+        ("zoom_out", FontSizeAction::ZoomOut),
+        ("reset_zoom", FontSizeAction::Medium),
+    ] {
+        commands.push(command(i18n_shortcuts(cx, name), Some(name), dispatch(action)));
+    }
+    let lang = i18n_sidebar(cx, "lang");
+    for (name, action) in [("中文", LocaleAction::Zh), ("English", LocaleAction::En)] {
+        commands.push(command(format!("{lang}: {name}").into(), None, dispatch(action)));
+    }
+
+    commands.push(command(
+        i18n_shortcuts(cx, "new_window"),
+        Some("new_window"),
+        dispatch(MemuAction::NewWindow),
+    ));
+    commands.push(command(i18n_sidebar(cx, "about"), None, dispatch(MemuAction::About)));
+    commands.push(command(
+        i18n_shortcuts(cx, "quit"),
+        Some("quit"),
+        dispatch(MemuAction::Quit),
+    ));
+    commands
+}
+
+/// Fuzzy finder over the commands, the selected one runs on Enter or click.
+struct ZedisCommandPalette {
+    keyword_state: Entity<InputState>,
+    commands: Vec<PaletteCommand>,
+    /// Indices of the commands matching the keyword, best match first
+    matches: Vec<usize>,
+    /// Position of the selected command in `matches`
+    selected_index: usize,
+    should_focus: bool,
+    scroll_handle: ScrollHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisCommandPalette {
+    fn new(commands: Vec<PaletteCommand>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let keyword_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_command_palette(cx, "placeholder")));
+        let subscriptions = vec![
+            cx.subscribe_in(&keyword_state, window, |this, _, event, window, cx| match event {
+                InputEvent::Change => this.filter(cx),
+                InputEvent::PressEnter { .. } => this.run(this.selected_index, window, cx),
+                _ => {}
+            }),
+        ];
+        let matches = (0..commands.len()).collect();
+        Self {
+            keyword_state,
+            commands,
+            matches,
+            selected_index: 0,
+            should_focus: true,
+            scroll_handle: ScrollHandle::new(),
+            _subscriptions: subscriptions,
+        }
+    }
+    fn filter(&mut self, cx: &mut Context<Self>) {
+        let query = self.keyword_state.read(cx).value().trim().to_lowercase();
+        let mut scores: Vec<(usize, usize)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| Some((fuzzy_score(&command.label, &query)?, index)))
+            .collect();
+        // Stable, the commands with the same score keep their order
+        scores.sort_by_key(|(score, _)| *score);
+        self.matches = scores.into_iter().map(|(_, index)| index).collect();
+        self.selected_index = 0;
+        self.scroll_handle.scroll_to_item(0);
+        cx.notify();
+    }
+    fn move_selection(&mut self, is_up: bool, cx: &mut Context<Self>) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let max = self.matches.len() - 1;
+        self.selected_index = match (is_up, self.selected_index) {
+            (true, 0) => max,
+            (true, index) => index - 1,
+            (false, index) if index >= max => 0,
+            (false, index) => index + 1,
+        };
+        self.scroll_handle.scroll_to_item(self.selected_index);
+        cx.notify();
+    }
+    fn run(&mut self, position: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(command) = self.matches.get(position).and_then(|index| self.commands.get(*index)) else {
+            return;
+        };
+        let handler = command.handler.clone();
+        // Closing first gives the focus back to the view the palette was opened from
+        window.close_dialog(cx);
+        window.defer(cx, move |window, cx| handler(window, cx));
+    }
+}
+
+impl Render for ZedisCommandPalette {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if std::mem::take(&mut self.should_focus) {
+            self.keyword_state.update(cx, |state, cx| state.focus(window, cx));
+        }
+        let selected_index = self.selected_index;
+
+        v_flex()
+            .gap_2()
+            .w_full()
+            .capture_action(cx.listener(|this, _: &MoveUp, _window, cx| {
+                this.move_selection(true, cx);
+                cx.stop_propagation();
+            }))
+            .capture_action(cx.listener(|this, _: &MoveDown, _window, cx| {
+                this.move_selection(false, cx);
+                cx.stop_propagation();
+            }))
+            .child(Input::new(&self.keyword_state).cleanable(true))
+            .when(self.matches.is_empty(), |this| {
+                this.child(
+                    Label::new(i18n_command_palette(cx, "no_commands"))
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground),
+                )
+            })
+            .child(
+                v_flex()
+                    .id("command-palette-list")
+                    .w_full()
+                    .max_h(px(LIST_MAX_HEIGHT))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .children(self.matches.iter().enumerate().filter_map(|(position, index)| {
+                        let command = self.commands.get(*index)?;
+                        Some(
+                            h_flex()
+                                .id(("command-palette-item", position))
+                                .w_full()
+                                .px_2()
+                                .py_1()
+                                .gap_4()
+                                .justify_between()
+                                .rounded_sm()
+                                .cursor_pointer()
+                                .when(position == selected_index, |this| this.bg(cx.theme().selection))
+                                .hover(|this| this.bg(cx.theme().secondary_hover))
+                                .child(Label::new(command.label.clone()).text_sm())
+                                .when_some(command.keystrokes.clone(), |this, keystrokes| {
+                                    this.child(Label::new(keystrokes).text_xs().text_color(cx.theme().muted_foreground))
+                                })
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.run(position, window, cx);
+                                })),
+                        )
+                    })),
+            )
+    }
+}
+
+/// Open the command palette listing every action, whatever the current route.
+pub fn open_command_palette(window: &mut Window, cx: &mut App) {
+    let commands = new_commands(window, cx);
+    let palette = cx.new(|cx| ZedisCommandPalette::new(commands, window, cx));
+
+    ZedisDialog::new(i18n_command_palette(cx, "title"))
+        .alert()
+        .icon(IconName::Search)
+        .child(move || palette.clone())
+        .open(window, cx);
+}
@@ -0,0 +1,371 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    assets::CustomIconName,
+    helpers::get_font_family,
+    states::{ServerConfig, ServerEvent, ZedisGlobalStore, ZedisServerState, dialog_button_props, i18n_config_editor},
+};
+use gpui::{Entity, SharedString, Subscription, Window, div, prelude::*, px, uniform_list};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    notification::Notification,
+    v_flex,
+};
+use rust_i18n::t;
+use std::sync::Arc;
+use tracing::info;
+use zedis_ui::ZedisDialog;
+
+/// Parameters whose change can lose data or lock the clients out, applied after a confirmation
+const DANGEROUS_PARAMETERS: &[&str] = &[
+    "maxmemory",
+    "maxmemory-policy",
+    "save",
+    "appendonly",
+    "appendfsync",
+    "dir",
+    "dbfilename",
+    "requirepass",
+    "masterauth",
+    "masteruser",
+    "replicaof",
+    "slaveof",
+    "bind",
+    "port",
+    "protected-mode",
+];
+/// Units accepted by the memory parameters, the longest first
+const MEMORY_UNITS: &[&str] = &["kb", "mb", "gb", "k", "m", "g", "b"];
+const NAME_COLUMN_WIDTH: f32 = 280.0;
+
+/// Expected kind of the new value, from the current one: yes/no for the flags and an
+/// integer for the numbers. `None` when the value can't be checked, e.g. a list.
+fn expected_value_kind(current: &str) -> Option<&'static str> {
+    if matches!(current, "yes" | "no") {
+        Some("kind_boolean")
+    } else if current.parse::<i64>().is_ok() {
+        Some("kind_integer")
+    } else {
+        None
+    }
+}
+
+fn is_valid_value(kind: &str, value: &str) -> bool {
+    let value = value.to_lowercase();
+    match kind {
+        "kind_boolean" => matches!(value.as_str(), "yes" | "no"),
+        "kind_integer" => MEMORY_UNITS
+            .iter()
+            .find_map(|unit| value.strip_suffix(unit))
+            .unwrap_or(&value)
+            .parse::<i64>()
+            .is_ok(),
+        _ => true,
+    }
+}
+
+/// Runtime configuration of the server from `CONFIG GET *`, searchable by name or value.
+///
+/// A parameter is edited by selecting its row, the value is applied with `CONFIG SET`
+/// and read back, so the table shows the value normalized by the server.
+pub struct ZedisConfigEditor {
+    server_state: Entity<ZedisServerState>,
+    keyword_state: Entity<InputState>,
+    value_state: Entity<InputState>,
+    config: ServerConfig,
+    /// Name of the parameter being edited
+    selected: Option<SharedString>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisConfigEditor {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let keyword_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .clean_on_escape()
+                .placeholder(i18n_config_editor(cx, "search_placeholder"))
+        });
+        let value_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder(i18n_config_editor(cx, "value_placeholder")));
+
+        let subscriptions = vec![
+            cx.subscribe(&server_state, |this, _server_state, event, cx| match event {
+                ServerEvent::ServerConfigLoaded(config) => {
+                    this.config = config.clone();
+                    cx.notify();
+                }
+                ServerEvent::ServerConfigUpdated(name, value) => {
+                    let mut config = (*this.config).clone();
+                    if let Some(entry) = config.iter_mut().find(|(item, _)| item == name) {
+                        entry.1 = value.clone();
+                    }
+                    this.config = Arc::new(config);
+                    cx.notify();
+                }
+                _ => {}
+            }),
+            cx.subscribe(&keyword_state, |_this, _state, event: &InputEvent, cx| {
+                if let InputEvent::Change = event {
+                    cx.notify();
+                }
+            }),
+            cx.subscribe_in(&value_state, window, |this, _state, event, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    this.handle_apply(window, cx);
+                }
+            }),
+        ];
+
+        server_state.update(cx, |state, cx| {
+            state.load_server_config(cx);
+        });
+        info!("Creating new config editor");
+
+        Self {
+            server_state,
+            keyword_state,
+            value_state,
+            config: Arc::new(vec![]),
+            selected: None,
+            _subscriptions: subscriptions,
+        }
+    }
+    fn select(&mut self, name: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let value = self
+            .config
+            .iter()
+            .find(|(item, _)| *item == name)
+            .map(|(_, value)| value.clone())
+            .unwrap_or_default();
+        self.value_state.update(cx, |state, cx| {
+            state.set_value(value, window, cx);
+            state.focus(window, cx);
+        });
+        self.selected = Some(name);
+        cx.notify();
+    }
+    /// Check the type of the value, and ask for confirmation for the dangerous parameters
+    fn handle_apply(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.server_state.read(cx).readonly() {
+            return;
+        }
+        let Some(name) = self.selected.clone() else {
+            return;
+        };
+        let Some(current) = self
+            .config
+            .iter()
+            .find(|(item, _)| *item == name)
+            .map(|(_, value)| value)
+        else {
+            return;
+        };
+        let value: SharedString = self.value_state.read(cx).value().trim().to_string().into();
+        if value == *current {
+            return;
+        }
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        if let Some(kind) = expected_value_kind(current)
+            && !is_valid_value(kind, &value)
+        {
+            let message = t!(
+                "config_editor.invalid_value",
+                name = name,
+                kind = i18n_config_editor(cx, kind),
+                locale = locale
+            )
+            .to_string();
+            window.push_notification(Notification::error(message), cx);
+            return;
+        }
+
+        let server_state = self.server_state.clone();
+        if !DANGEROUS_PARAMETERS.contains(&name.as_str()) {
+            server_state.update(cx, |state, cx| {
+                state.set_server_config(name, value, cx);
+            });
+            return;
+        }
+        let message = t!(
+            "config_editor.apply_prompt",
+            name = name,
+            value = value,
+            locale = locale
+        )
+        .to_string();
+        ZedisDialog::new_alert(i18n_config_editor(cx, "apply_title"), message)
+            .button_props(dialog_button_props(cx))
+            .on_ok(move |_, _, cx| {
+                server_state.update(cx, |state, cx| {
+                    state.set_server_config(name.clone(), value.clone(), cx);
+                });
+                true
+            })
+            .open(window, cx);
+    }
+    /// Renders the toolbar: the search input, a reload button and the rewrite button.
+    fn render_toolbar(&self, readonly: bool, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .items_center()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(Input::new(&self.keyword_state).w_full().flex_1().cleanable(true))
+            .child(
+                Button::new("config-editor-reload-btn")
+                    .outline()
+                    .icon(CustomIconName::RotateCw)
+                    .tooltip(i18n_config_editor(cx, "reload_tooltip"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.load_server_config(cx);
+                        });
+                    })),
+            )
+            .child(
+                Button::new("config-editor-rewrite-btn")
+                    .outline()
+                    .label(i18n_config_editor(cx, "rewrite"))
+                    .tooltip(i18n_config_editor(cx, "rewrite_tooltip"))
+                    .disabled(readonly)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.rewrite_server_config(cx);
+                        });
+                    })),
+            )
+    }
+    /// Renders the edit bar of the selected parameter.
+    fn render_edit_bar(&self, name: SharedString, readonly: bool, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .items_center()
+            .border_t_1()
+            .border_color(cx.theme().border)
+            .child(
+                Label::new(name)
+                    .flex_none()
+                    .w(px(NAME_COLUMN_WIDTH))
+                    .text_sm()
+                    .text_ellipsis(),
+            )
+            .child(Input::new(&self.value_state).w_full().flex_1().disabled(readonly))
+            .child(
+                Button::new("config-editor-apply-btn")
+                    .outline()
+                    .label(i18n_config_editor(cx, "apply"))
+                    .disabled(readonly)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_apply(window, cx);
+                    })),
+            )
+    }
+}
+
+impl Render for ZedisConfigEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let readonly = self.server_state.read(cx).readonly();
+        let keyword = self.keyword_state.read(cx).value().trim().to_lowercase();
+        let entries: Arc<Vec<(SharedString, SharedString)>> = Arc::new(
+            self.config
+                .iter()
+                .filter(|(name, value)| {
+                    keyword.is_empty()
+                        || name.to_lowercase().contains(&keyword)
+                        || value.to_lowercase().contains(&keyword)
+                })
+                .cloned()
+                .collect(),
+        );
+        let is_empty = entries.is_empty();
+        let selected = self.selected.clone();
+        let selected_color = cx.theme().selection;
+        let hover_color = cx.theme().secondary_hover;
+        let muted_color = cx.theme().muted_foreground;
+        let font_family = get_font_family();
+        let entity = cx.entity();
+
+        v_flex()
+            .size_full()
+            .overflow_hidden()
+            .child(self.render_toolbar(readonly, cx))
+            .when(is_empty, |this| {
+                this.child(
+                    div().flex_1().w_full().flex().items_center().justify_center().child(
+                        Label::new(i18n_config_editor(cx, "no_parameters")).text_color(cx.theme().muted_foreground),
+                    ),
+                )
+            })
+            .when(!is_empty, |this| {
+                this.child(
+                    uniform_list("config-editor-parameters", entries.len(), move |range, _window, _cx| {
+                        range
+                            .filter_map(|index| {
+                                let (name, value) = entries.get(index).cloned()?;
+                                let is_selected = selected.as_ref() == Some(&name);
+                                let entity = entity.clone();
+                                Some(
+                                    h_flex()
+                                        .id(("config-editor-parameter", index))
+                                        .w_full()
+                                        .px_3()
+                                        .py_1()
+                                        .gap_2()
+                                        .text_sm()
+                                        .cursor_pointer()
+                                        .when(is_selected, |this| this.bg(selected_color))
+                                        .hover(|this| this.bg(hover_color))
+                                        .child(
+                                            Label::new(name.clone())
+                                                .flex_none()
+                                                .w(px(NAME_COLUMN_WIDTH))
+                                                .text_ellipsis()
+                                                .text_color(muted_color),
+                                        )
+                                        .child(
+                                            Label::new(value)
+                                                .flex_1()
+                                                .font_family(font_family.clone())
+                                                .text_ellipsis()
+                                                .whitespace_nowrap(),
+                                        )
+                                        .on_click(move |_, window, cx| {
+                                            entity.update(cx, |this, cx| {
+                                                this.select(name.clone(), window, cx);
+                                            });
+                                        }),
+                                )
+                            })
+                            .collect()
+                    })
+                    .flex_1()
+                    .w_full(),
+                )
+            })
+            .when_some(self.selected.clone(), |this, name| {
+                this.child(self.render_edit_bar(name, readonly, cx))
+            })
+    }
+}
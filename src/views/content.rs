@@ -13,28 +13,42 @@
 // limitations under the License.
 
 use crate::{
-    connection::{get_command_description, get_connection_manager, list_commands},
+    assets::CustomIconName,
+    connection::{
+        check_command_version, get_command_description, get_command_key, get_connection_manager, is_dangerous_command,
+        is_write_command, list_commands,
+    },
     db::get_cmd_history_manager,
     error::Error,
     helpers::{
         EditorAction, get_font_family, get_key_tree_widths, redis_value_to_string, starts_with_ignore_ascii_case,
     },
-    states::{GlobalEvent, Route, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, save_app_state},
+    states::{
+        ConnectionStatus, GlobalEvent, Route, ServerEvent, ZedisGlobalStore, ZedisServerState, dialog_button_props,
+        i18n_common, save_app_state,
+    },
     views::{
-        ZedisEditor, ZedisKeyTree, ZedisMetrics, ZedisProtoEditor, ZedisServers, ZedisSettingEditor, ZedisStatusBar,
+        ZedisEditor, ZedisKeyTree, ZedisLogViewer, ZedisMetrics, ZedisProtoEditor, ZedisServers, ZedisSettingEditor,
+        ZedisStatusBar,
     },
 };
-use gpui::{Entity, FocusHandle, Pixels, ScrollHandle, SharedString, Subscription, Window, div, prelude::*, px};
+use ahash::AHashMap;
+use gpui::{
+    Entity, FocusHandle, Pixels, ScrollHandle, SharedString, Subscription, Window, WindowId, div, prelude::*, px,
+};
 use gpui_component::{
-    ActiveTheme,
+    ActiveTheme, Icon, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
     input::{Input, InputEvent, InputState, MoveDown, MoveUp, Position},
     label::Label,
     resizable::{ResizableState, h_resizable, resizable_panel},
     v_flex,
 };
 use redis::cmd;
+use rust_i18n::t;
 use tracing::{debug, error, info};
-use zedis_ui::ZedisSkeletonLoading;
+use zedis_ui::{ZedisDialog, ZedisSkeletonLoading};
 type Result<T, E = Error> = std::result::Result<T, E>;
 
 // Constants for UI dimensions
@@ -67,6 +81,7 @@ pub struct ZedisContent {
     servers: Option<Entity<ZedisServers>>,
     setting_editor: Option<Entity<ZedisSettingEditor>>,
     proto_editor: Option<Entity<ZedisProtoEditor>>,
+    log_viewer: Option<Entity<ZedisLogViewer>>,
     value_editor: Option<Entity<ZedisEditor>>,
     metrics: Option<Entity<ZedisMetrics>>,
     key_tree: Option<Entity<ZedisKeyTree>>,
@@ -74,6 +89,8 @@ pub struct ZedisContent {
     cmd_output_scroll_handle: ScrollHandle,
     cmd_input_state: Entity<InputState>,
     cmd_outputs: Vec<SharedString>,
+    /// Keys referenced by the commands, by index of the command line in `cmd_outputs`
+    cmd_output_keys: AHashMap<usize, SharedString>,
     redis_commands: Vec<SharedString>,
     cmd_suggestions: Vec<String>,
     cmd_suggestion_index: Option<usize>,
@@ -81,6 +98,9 @@ pub struct ZedisContent {
     /// Persisted width of the key tree panel (resizable by user)
     key_tree_width: Pixels,
 
+    /// Window hosting this content, routes and server selection are per window
+    window_id: WindowId,
+
     /// Cached current route to avoid unnecessary updates
     current_route: Route,
     should_focus: bool,
@@ -107,6 +127,9 @@ impl ZedisContent {
         if route != Route::Protos {
             self.proto_editor.take();
         }
+        if route != Route::Logs {
+            self.log_viewer.take();
+        }
     }
     /// Create a new content view with route-aware view management
     ///
@@ -117,21 +140,36 @@ impl ZedisContent {
         let focus_handle = cx.focus_handle();
         focus_handle.focus(window, cx);
         let global_state = cx.global::<ZedisGlobalStore>().state();
+        let window_id = window.window_handle().window_id();
         let server_state = cx.new(|_cx| ZedisServerState::new());
         let status_bar = cx.new(|cx| ZedisStatusBar::new(server_state.clone(), window, cx));
 
         subscriptions.push(
             cx.subscribe(&global_state, |this, _global_state, event, cx| match event {
-                GlobalEvent::RouteChanged(route) => {
+                GlobalEvent::RouteChanged(window_id, route) if *window_id == this.window_id => {
                     this.current_route = *route;
                     this.clear_views();
                     cx.notify();
                 }
-                GlobalEvent::ServerSelected(server_id, db) => {
+                GlobalEvent::ServerSelected(window_id, server_id, db) if *window_id == this.window_id => {
                     this.server_state.update(cx, |state, cx| {
                         state.select(server_id.clone(), *db, cx);
                     });
                 }
+                GlobalEvent::ServerUpdated(server_id) => {
+                    this.server_state.update(cx, |state, cx| {
+                        if state.server_id() == server_id.as_str() {
+                            state.reselect(cx);
+                        }
+                    });
+                }
+                GlobalEvent::KeysAdded(server_id, db, keys) => {
+                    this.server_state.update(cx, |state, cx| {
+                        if state.server_id() == server_id.as_str() && state.db() == *db {
+                            state.add_keys(keys.clone(), cx);
+                        }
+                    });
+                }
                 _ => {}
             }),
         );
@@ -149,6 +187,9 @@ impl ZedisContent {
                 ServerEvent::ServerSelected(_) => {
                     this.reset_cmd_state(cx);
                 }
+                ServerEvent::ConnectionStatusChanged(_) => {
+                    cx.notify();
+                }
                 _ => {}
             }),
         );
@@ -156,7 +197,7 @@ impl ZedisContent {
         // Restore persisted key tree width from global state
         let global_store = cx.global::<ZedisGlobalStore>().read(cx);
         let key_tree_width = global_store.key_tree_width();
-        let route = global_store.route();
+        let route = global_store.route(window_id);
         let cmd_input_state = cx.new(|cx| InputState::new(window, cx).auto_grow(1, 3));
         subscriptions.push(
             cx.subscribe_in(&cmd_input_state, window, |this, state, event, window, cx| match event {
@@ -180,7 +221,7 @@ impl ZedisContent {
                     });
                     this.cmd_suggestions.clear();
                     this.cmd_suggestion_index = None;
-                    this.execute_command(cmd, cx);
+                    this.execute_command(cmd, window, cx);
                 }
                 InputEvent::Change => {
                     if this.cmd_history_index.is_some() {
@@ -209,6 +250,7 @@ impl ZedisContent {
         Self {
             server_state,
             status_bar,
+            window_id,
             current_route: route,
             servers: None,
             value_editor: None,
@@ -216,6 +258,7 @@ impl ZedisContent {
             metrics: None,
             key_tree: None,
             cmd_outputs: Vec::with_capacity(5),
+            cmd_output_keys: AHashMap::new(),
             redis_commands: Vec::new(),
             key_tree_width,
             cmd_input_state,
@@ -227,11 +270,13 @@ impl ZedisContent {
             cmd_history_index: None,
             focus_handle,
             proto_editor: None,
+            log_viewer: None,
             _subscriptions: subscriptions,
         }
     }
     fn reset_cmd_state(&mut self, _cx: &mut Context<Self>) {
         self.cmd_outputs.clear();
+        self.cmd_output_keys.clear();
         self.cmd_outputs.extend(
             ZEDIS_LOGO
                 .replace("{VERSION}", VERSION)
@@ -309,7 +354,8 @@ impl ZedisContent {
             cx.notify();
         }
     }
-    fn execute_command(&mut self, command: SharedString, cx: &mut Context<Self>) {
+    /// Run the typed commands, asking first for the dangerous ones unless allowed in the settings
+    fn execute_command(&mut self, command: SharedString, window: &mut Window, cx: &mut Context<Self>) {
         if command.is_empty() {
             return;
         }
@@ -317,35 +363,81 @@ impl ZedisContent {
             self.reset_cmd_state(cx);
             return;
         }
+        let dangerous = command
+            .lines()
+            .filter_map(|line| shlex::split(line)?.into_iter().next())
+            .find(|name| is_dangerous_command(name));
+        if let Some(name) = dangerous
+            && !cx.global::<ZedisGlobalStore>().read(cx).allow_dangerous_commands()
+        {
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!(
+                "common.dangerous_command_prompt",
+                command = name.to_uppercase(),
+                locale = locale
+            )
+            .to_string();
+            let entity = cx.entity();
+            ZedisDialog::new_alert(i18n_common(cx, "dangerous_command_title"), message)
+                .button_props(dialog_button_props(cx))
+                .on_ok(move |_, window, cx| {
+                    let command = command.clone();
+                    entity.update(cx, |this, cx| {
+                        this.run_command(command, cx);
+                    });
+                    window.close_dialog(cx);
+                    true
+                })
+                .open(window, cx);
+            return;
+        }
+        self.run_command(command, cx);
+    }
+    fn run_command(&mut self, command: SharedString, cx: &mut Context<Self>) {
         let server_state = self.server_state.read(cx);
         let server_id = server_state.server_id().to_string();
         let db = server_state.db();
+        let readonly = server_state.readonly();
+        // The command docs list the Redis version of each command, also reported by Valkey
+        let version = server_state.redis_version().to_string();
         cx.spawn(async move |handle, cx| {
             for command in command.lines() {
                 let command = command.trim().to_string();
                 let command_clone = command.clone();
+                let command_key = shlex::split(&command).and_then(|parts| get_command_key(&parts));
                 let server_id = server_id.clone();
+                let version = version.clone();
                 let task = cx.background_spawn(async move {
                     let Some(parts) = shlex::split(&command) else {
                         return Ok(SharedString::default());
                     };
-                    if parts.len() < 2 {
+                    if parts.is_empty() {
                         return Ok(SharedString::default());
                     }
+                    check_command_version(&parts, &version).map_err(|message| Error::Invalid { message })?;
                     let cmd_name = parts[0].clone();
                     let args = parts[1..].to_vec();
                     let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                    if readonly && is_write_command(&mut conn, &server_id, &cmd_name).await {
+                        return Err(Error::Invalid {
+                            message: format!("{cmd_name} is rejected in read-only mode"),
+                        });
+                    }
                     let data: redis::Value = cmd(&cmd_name).arg(&args).query_async(&mut conn).await?;
                     let _ = get_cmd_history_manager().add_record(server_id.as_str(), command.as_str());
                     Ok(redis_value_to_string(&data).into())
                 });
                 let result: Result<SharedString> = task.await;
+                let command_key = command_key.filter(|_| result.is_ok());
                 let content: SharedString = match result {
                     Ok(result) => result,
                     Err(e) => e.to_string().into(),
                 };
 
                 let _ = handle.update(cx, |this, cx| {
+                    if let Some(key) = command_key {
+                        this.cmd_output_keys.insert(this.cmd_outputs.len(), key);
+                    }
                     this.cmd_outputs.extend(vec![
                         format!("{CMD_LABEL} {command_clone}").into(),
                         content,
@@ -397,6 +489,16 @@ impl ZedisContent {
             .clone();
         div().size_full().child(proto_editor)
     }
+    fn render_log_viewer(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let log_viewer = self
+            .log_viewer
+            .get_or_insert_with(|| {
+                debug!("Creating new log viewer view");
+                cx.new(|cx| ZedisLogViewer::new(window, cx))
+            })
+            .clone();
+        div().size_full().child(log_viewer)
+    }
     fn render_metrics(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let metrics = self
             .metrics
@@ -418,6 +520,38 @@ impl ZedisContent {
                 .child(ZedisSkeletonLoading::new().text(i18n_common(cx, "loading"))),
         )
     }
+    /// Banner shown instead of the key tree and the value editor while the connection is lost
+    fn render_disconnected(&mut self, status: ConnectionStatus, cx: &mut Context<Self>) -> impl IntoElement {
+        let text = if status == ConnectionStatus::Reconnecting {
+            i18n_common(cx, "connection_reconnecting")
+        } else {
+            i18n_common(cx, "connection_disconnected")
+        };
+        v_flex()
+            .w_full()
+            .h_full()
+            .items_center()
+            .justify_center()
+            .gap_3()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(Icon::new(CustomIconName::Unplug).text_color(cx.theme().danger))
+                    .child(Label::new(text)),
+            )
+            .child(
+                Button::new("content-reconnect")
+                    .outline()
+                    .icon(CustomIconName::RotateCw)
+                    .label(i18n_common(cx, "reconnect"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.reconnect(cx);
+                        });
+                    })),
+            )
+    }
     /// Handle command history navigation
     ///
     /// This function is called when the user presses the up or down arrow keys
@@ -537,13 +671,26 @@ impl ZedisContent {
                         .flex_1()
                         .w_full()
                         .overflow_y_scroll()
-                        .child(
-                            v_flex().p_2().gap_1().children(
-                                self.cmd_outputs
-                                    .iter()
-                                    .map(|line| div().child(Label::new(line.clone()).font_family(font_family.clone()))),
-                            ),
-                        ),
+                        .child(v_flex().p_2().gap_1().children(self.cmd_outputs.iter().enumerate().map(
+                            |(index, line)| {
+                                let label = Label::new(line.clone()).font_family(font_family.clone());
+                                let Some(key) = self.cmd_output_keys.get(&index).cloned() else {
+                                    return div().child(label);
+                                };
+                                let server_state = server_state.clone();
+                                h_flex().gap_2().child(label).child(
+                                    Button::new(("cmd-reveal-key", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .label(i18n_common(cx, "reveal_in_tree"))
+                                        .on_click(move |_, _window, cx| {
+                                            server_state.update(cx, |state, cx| {
+                                                state.reveal_key(key.clone(), cx);
+                                            });
+                                        }),
+                                )
+                            },
+                        ))),
                 )
                 .child(
                     v_flex()
@@ -655,7 +802,7 @@ impl Render for ZedisContent {
     /// 2. If server is busy (connecting/loading) -> show loading skeleton
     /// 3. Otherwise -> show editor interface (key tree + value editor)
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let route = cx.global::<ZedisGlobalStore>().read(cx).route();
+        let route = cx.global::<ZedisGlobalStore>().read(cx).route(self.window_id);
         if std::mem::take(&mut self.should_focus) {
             self.focus_handle.focus(window, cx);
         }
@@ -670,10 +817,14 @@ impl Render for ZedisContent {
             Route::Home => base.child(self.render_servers(window, cx)).into_any_element(),
             Route::Settings => base.child(self.render_settings(window, cx)).into_any_element(),
             Route::Protos => base.child(self.render_proto_editor(window, cx)).into_any_element(),
+            Route::Logs => base.child(self.render_log_viewer(window, cx)).into_any_element(),
             _ => {
                 // Route 2: Loading state (show skeleton while connecting/loading)
                 let is_busy = self.server_state.read(cx).is_busy();
                 let is_metrics = route == Route::Metrics;
+                // Stale keys and values are hidden until the heartbeat reconnects
+                let connection_status = self.server_state.read(cx).connection_status();
+                let connected = connection_status == ConnectionStatus::Connected;
 
                 // Route 3: Main editor interface
                 base.when(is_busy, |this| this.child(self.render_loading(window, cx)))
@@ -686,13 +837,26 @@ impl Render for ZedisContent {
                                     .size_full()
                                     .overflow_hidden()
                                     .when(is_metrics, |this| this.child(self.render_metrics(window, cx)))
-                                    .when(!is_metrics, |this| this.child(self.render_editor(window, cx))),
+                                    .when(!is_metrics && connected, |this| {
+                                        this.child(self.render_editor(window, cx))
+                                    })
+                                    .when(!is_metrics && !connected, |this| {
+                                        this.child(self.render_disconnected(connection_status, cx))
+                                    }),
                             ),
                         )
                     })
                     .child(self.status_bar.clone())
                     .on_action(cx.listener(move |this, event: &EditorAction, _window, cx| match event {
-                        EditorAction::UpdateTtl | EditorAction::Reload | EditorAction::Create => {
+                        EditorAction::UpdateTtl
+                        | EditorAction::Reload
+                        | EditorAction::Create
+                        | EditorAction::RefreshKeys
+                        | EditorAction::DeleteKey
+                        | EditorAction::RenameKey
+                        | EditorAction::CycleQueryMode
+                        | EditorAction::QuickOpen
+                        | EditorAction::ToggleSoftWrap => {
                             this.server_state.update(cx, move |state, cx| {
                                 state.emit_editor_action(*event, cx);
                             });
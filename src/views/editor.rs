@@ -14,23 +14,28 @@
 
 use crate::{
     assets::CustomIconName,
+    connection::Capability,
     constants::EDITOR_KEY_BAR_HEIGHT,
-    db::get_favorites_manager,
-    helpers::{EditorAction, format_duration, humanize_keystroke, validate_ttl},
-    states::{KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, dialog_button_props, i18n_common, i18n_editor},
+    helpers::{EditorAction, format_duration, get_home_dir, humanize_keystroke, to_file_name, validate_ttl},
+    states::{
+        CopyFormat, DataFormat, ExportFormat, KeyType, ServerEvent, ViewMode, ZedisGlobalStore, ZedisServerState,
+        dialog_button_props, i18n_common, i18n_editor,
+    },
     views::{
-        ZedisBytesEditor, ZedisHashEditor, ZedisListEditor, ZedisPubsubEditor, ZedisSetEditor, ZedisStreamEditor,
-        ZedisZsetEditor,
+        ZedisBytesEditor, ZedisConfigEditor, ZedisHashEditor, ZedisListEditor, ZedisMonitorEditor, ZedisPubsubEditor,
+        ZedisSearchEditor, ZedisSetEditor, ZedisStreamEditor, ZedisZsetEditor,
     },
 };
 use gpui::{ClipboardItem, Entity, SharedString, Subscription, Task, Window, div, prelude::*, px};
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, WindowExt,
-    button::{Button, DropdownButton},
+    ActiveTheme, Disableable, Icon, IconName, Sizable, WindowExt,
+    button::{Button, ButtonVariants, DropdownButton},
+    dialog::DialogButtonProps,
     h_flex,
     input::{Input, InputEvent, InputState},
     label::Label,
     notification::Notification,
+    popover::Popover,
     v_flex,
 };
 use humansize::{DECIMAL, format_size};
@@ -57,6 +62,10 @@ pub struct ZedisEditor {
     hash_editor: Option<Entity<ZedisHashEditor>>,
     stream_editor: Option<Entity<ZedisStreamEditor>>,
     pubsub_editor: Option<Entity<ZedisPubsubEditor>>,
+    monitor_editor: Option<Entity<ZedisMonitorEditor>>,
+    config_editor: Option<Entity<ZedisConfigEditor>>,
+    /// Kept while keys are opened from the results, dropped when the server changes
+    search_editor: Option<Entity<ZedisSearchEditor>>,
 
     /// TTL editing state
     should_enter_ttl_edit_mode: Option<bool>,
@@ -71,6 +80,9 @@ pub struct ZedisEditor {
     auto_refresh_task: Option<Task<()>>,
     auto_refresh_interval_sec: u64,
 
+    /// Ticks while the value has a TTL, see `watch_expiry`
+    expiry_task: Option<Task<()>>,
+
     /// Event subscriptions for reactive updates
     _subscriptions: Vec<Subscription>,
 }
@@ -118,13 +130,26 @@ impl ZedisEditor {
                     this.selected_key_at = Some(Instant::now());
                     this.start_auto_refresh(None, cx);
                 }
+                ServerEvent::ServerSelected(_) => {
+                    // Stop polling the value of the previous server
+                    this.start_auto_refresh(None, cx);
+                    // The search results belong to the previous server
+                    this.search_editor.take();
+                }
                 ServerEvent::ValueLoaded => {
                     // stream editor is different of each key, so we need to destroy it
                     this.stream_editor.take();
+                    this.watch_expiry(cx);
+                }
+                ServerEvent::ValueUpdated => {
+                    this.watch_expiry(cx);
                 }
                 ServerEvent::ServerInfoUpdated => {
                     this.readonly = server_state.read(cx).readonly();
                 }
+                ServerEvent::FavoritesUpdated => {
+                    cx.notify();
+                }
                 ServerEvent::EditionActionTriggered(action) => match action {
                     EditorAction::UpdateTtl => {
                         this.should_enter_ttl_edit_mode = Some(true);
@@ -139,6 +164,15 @@ impl ZedisEditor {
             }),
         );
 
+        // Ask whether to overwrite the value changed by another client
+        subscriptions.push(
+            cx.subscribe_in(&server_state, window, |this, _server_state, event, window, cx| {
+                if let ServerEvent::SaveConflict(key, value) = event {
+                    this.handle_save_conflict(key.clone(), value.clone(), window, cx);
+                }
+            }),
+        );
+
         // Subscribe to TTL input events for Enter key and blur
         subscriptions.push(cx.subscribe_in(
             &ttl_input_state,
@@ -160,6 +194,7 @@ impl ZedisEditor {
 
         Self {
             auto_refresh_task: None,
+            expiry_task: None,
             auto_refresh_interval_sec: 0,
             server_state,
             list_editor: None,
@@ -169,6 +204,9 @@ impl ZedisEditor {
             hash_editor: None,
             stream_editor: None,
             pubsub_editor: None,
+            monitor_editor: None,
+            config_editor: None,
+            search_editor: None,
             readonly,
             ttl_edit_mode: false,
             ttl_input_state,
@@ -202,6 +240,43 @@ impl ZedisEditor {
         }));
     }
 
+    /// Ticks once a second while the selected value has a TTL.
+    ///
+    /// The countdown is computed from the value's expire time, so each tick only
+    /// re-renders it. Once it elapses the expiry is confirmed by the server state.
+    fn watch_expiry(&mut self, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        self.expiry_task = Some(cx.spawn(async move |this, cx| {
+            loop {
+                let Ok(watching) = this.update(cx, |_this, cx| {
+                    let seconds = match server_state.read(cx).value() {
+                        Some(value) if !value.is_expired() => value.ttl().map(|ttl| ttl.num_seconds()),
+                        _ => None,
+                    };
+                    match seconds {
+                        Some(-2) => {
+                            server_state.update(cx, |state, cx| {
+                                state.check_value_expired(cx);
+                            });
+                            false
+                        }
+                        Some(seconds) if seconds >= 0 => {
+                            cx.notify();
+                            true
+                        }
+                        _ => false,
+                    }
+                }) else {
+                    break;
+                };
+                if !watching {
+                    break;
+                }
+                cx.background_executor().timer(Duration::from_secs(1)).await;
+            }
+        }));
+    }
+
     /// Check if a key was selected recently (within threshold)
     /// Used to prevent showing loading indicator immediately after selection
     fn is_selected_key_recently(&self) -> bool {
@@ -222,6 +297,7 @@ impl ZedisEditor {
         self.server_state.update(cx, move |state, cx| {
             state.update_key_ttl(key, ttl.into(), cx);
         });
+        self.watch_expiry(cx);
         cx.notify();
     }
 
@@ -247,6 +323,30 @@ impl ZedisEditor {
             })
             .open(window, cx);
     }
+    /// Overwrite the value changed by another client once confirmed, the edit stays in the clipboard otherwise
+    fn handle_save_conflict(
+        &mut self,
+        key: SharedString,
+        value: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let server_state = self.server_state.clone();
+        let button_props = DialogButtonProps::default()
+            .cancel_text(i18n_common(cx, "cancel"))
+            .ok_text(i18n_editor(cx, "overwrite"));
+        ZedisDialog::new_alert(i18n_editor(cx, "save_conflict_title"), i18n_editor(cx, "save_conflict"))
+            .button_props(button_props)
+            .on_ok(move |_, _, cx| {
+                let key = key.clone();
+                let value = value.clone();
+                server_state.update(cx, move |state, cx| {
+                    state.overwrite_value(key, value, cx);
+                });
+                true
+            })
+            .open(window, cx);
+    }
     fn reload(&mut self, cx: &mut Context<Self>) {
         let Some(key) = self.server_state.read(cx).key() else {
             return;
@@ -274,6 +374,93 @@ impl ZedisEditor {
             });
         });
     }
+    /// Pretty-print or minify the JSON shown in the string editor, nothing is saved
+    fn format_json(&mut self, minify: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(editor) = self.bytes_editor.as_ref() else {
+            return;
+        };
+        editor.update(cx, |state, cx| {
+            state.format_json(minify, window, cx);
+        });
+    }
+    /// Replace the value with the edited one, the previous value is shown in a notification
+    fn getset(&mut self, cx: &mut Context<Self>) {
+        let server_state = self.server_state.read(cx);
+        if self.readonly || server_state.value().is_none_or(|v| v.is_busy()) {
+            return;
+        }
+        let Some(key) = server_state.key() else {
+            return;
+        };
+        let Some(editor) = self.bytes_editor.as_ref() else {
+            return;
+        };
+        if editor.read(cx).is_readonly() {
+            return;
+        }
+        editor.clone().update(cx, move |state, cx| {
+            let value = state.value(cx);
+            self.server_state.update(cx, move |state, cx| {
+                state.getset_value(key, value, cx);
+            });
+        });
+    }
+    /// Read and delete the value with confirmation dialog
+    fn getdel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(key) = self.server_state.read(cx).key() else {
+            return;
+        };
+        if self.readonly {
+            return;
+        }
+
+        let server_state = self.server_state.clone();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let message = t!("editor.getdel_value_prompt", key = key, locale = locale).to_string();
+
+        ZedisDialog::new_alert(i18n_editor(cx, "getdel_value"), message)
+            .button_props(dialog_button_props(cx))
+            .on_ok(move |_, window, cx| {
+                let key = key.clone();
+                server_state.update(cx, move |state, cx| {
+                    state.getdel_value(key, cx);
+                });
+                window.close_dialog(cx);
+                true
+            })
+            .open(window, cx);
+    }
+    /// Copy the whole value of the selected key in the given format
+    fn copy_value(&mut self, format: CopyFormat, cx: &mut Context<Self>) {
+        self.server_state.update(cx, move |state, cx| {
+            state.copy_value(format, cx);
+        });
+    }
+    /// Pick the file, then write the whole value of the selected key to it
+    fn export_value(&mut self, format: ExportFormat, cx: &mut Context<Self>) {
+        let server_state = self.server_state.read(cx);
+        let (Some(key), Some(value)) = (server_state.key(), server_state.value()) else {
+            return;
+        };
+        let extension = match (value.key_type(), format) {
+            (KeyType::String, ExportFormat::Binary) => "bin",
+            (KeyType::String, ExportFormat::Text) => "txt",
+            _ => "json",
+        };
+        let name = to_file_name(key);
+        let dir = get_home_dir().unwrap_or_default();
+        let path = cx.prompt_for_new_path(&dir, Some(&format!("{name}.{extension}")));
+        let server_state = self.server_state.clone();
+        cx.spawn(async move |_, cx| {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            server_state.update(cx, |state, cx| {
+                state.export_value(path, format, cx);
+            });
+        })
+        .detach();
+    }
     fn enter_ttl_edit_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let server_state = self.server_state.read(cx);
         let Some(value) = server_state.value() else {
@@ -297,6 +484,70 @@ impl ZedisEditor {
         });
         cx.notify();
     }
+    /// Render the info button that shows the key's internal details in a popover
+    fn render_key_metadata(&self, key: SharedString, cx: &mut Context<Self>) -> impl IntoElement {
+        let server_state = self.server_state.read(cx);
+        let rows: Vec<(SharedString, SharedString)> = if let Some(metadata) = server_state.key_metadata() {
+            let mut rows = vec![];
+            if let Some(error) = &metadata.error {
+                rows.push((i18n_editor(cx, "metadata_unavailable"), error.clone()));
+            }
+            if let Some(key_type) = &metadata.key_type {
+                rows.push((i18n_editor(cx, "metadata_type"), key_type.clone()));
+            }
+            if let Some(encoding) = &metadata.encoding {
+                rows.push((i18n_editor(cx, "metadata_encoding"), encoding.clone()));
+            }
+            if let Some(idle_time) = metadata.idle_time {
+                rows.push((
+                    i18n_editor(cx, "metadata_idle_time"),
+                    format_duration(Duration::from_secs(idle_time)).into(),
+                ));
+            }
+            if let Some(refcount) = metadata.refcount {
+                rows.push((i18n_editor(cx, "metadata_refcount"), refcount.to_string().into()));
+            }
+            if let Some(memory_usage) = metadata.memory_usage {
+                rows.push((
+                    i18n_editor(cx, "metadata_memory_usage"),
+                    format_size(memory_usage, DECIMAL).into(),
+                ));
+            }
+            // Every command was refused by the server
+            if rows.is_empty() {
+                rows.push((i18n_editor(cx, "metadata_unavailable"), SharedString::default()));
+            }
+            rows
+        } else {
+            vec![(i18n_common(cx, "loading"), SharedString::default())]
+        };
+
+        Popover::new("zedis-editor-key-metadata")
+            .trigger(
+                Button::new("zedis-editor-key-metadata-btn")
+                    .outline()
+                    .tooltip(i18n_editor(cx, "key_metadata_tooltip"))
+                    .icon(IconName::Info)
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        let key = key.clone();
+                        this.server_state.update(cx, move |state, cx| {
+                            state.load_key_metadata(key, cx);
+                        });
+                    })),
+            )
+            .child(
+                v_flex()
+                    .gap_1()
+                    .text_sm()
+                    .children(rows.into_iter().map(|(name, value)| {
+                        h_flex()
+                            .gap_4()
+                            .justify_between()
+                            .child(Label::new(name).text_color(cx.theme().muted_foreground))
+                            .child(Label::new(value))
+                    })),
+            )
+    }
     /// Render the key information bar with actions (copy, save, TTL, delete)
     fn render_select_key(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state = self.server_state.read(cx);
@@ -342,11 +593,14 @@ impl ZedisEditor {
             );
         }
 
+        btns.push(self.render_key_metadata(key.clone(), cx).into_any_element());
+
         // Add save button for string editor if value is modified
         if let Some(bytes_editor) = &self.bytes_editor {
             let state = bytes_editor.read(cx);
             let value_modified = state.is_value_modified();
             let readonly = state.is_readonly();
+            let is_json = state.is_json();
             let tooltip = if self.readonly {
                 i18n_common(cx, "disable_in_readonly")
             } else if readonly {
@@ -360,18 +614,81 @@ impl ZedisEditor {
                 .into()
             };
 
+            // GETSET and GETDEL only work on strings, not on JSON documents
+            let is_string = server_state
+                .value()
+                .is_some_and(|value| value.key_type() == KeyType::String);
+            let can_write = !self.readonly && !readonly && !should_show_loading && is_string;
+            let supports_getdel = server_state.supports(Capability::GetDel);
             btns.push(
-                Button::new("zedis-editor-save-key")
-                    .disabled(self.readonly || !value_modified || should_show_loading)
-                    .outline()
-                    .label(i18n_common(cx, "save"))
-                    .tooltip(tooltip)
-                    .icon(CustomIconName::FileCheckCorner)
-                    .on_click(cx.listener(move |this, _event, window, cx| {
-                        this.save(window, cx);
-                    }))
+                DropdownButton::new("zedis-editor-save-key")
+                    .button(
+                        Button::new("zedis-editor-save-now")
+                            .disabled(self.readonly || !value_modified || should_show_loading)
+                            .outline()
+                            .label(i18n_common(cx, "save"))
+                            .tooltip(tooltip)
+                            .icon(CustomIconName::FileCheckCorner)
+                            .on_click(cx.listener(move |this, _event, window, cx| {
+                                this.save(window, cx);
+                            })),
+                    )
+                    .when(can_write, |this| {
+                        this.dropdown_menu(move |menu, _, _cx| {
+                            let menu = menu.menu_element_with_icon(
+                                CustomIconName::FilePenLine,
+                                Box::new(EditorAction::GetSet),
+                                move |_, cx| Label::new(i18n_editor(cx, "getset_value")),
+                            );
+                            // GETDEL is only available since Redis 6.2
+                            if supports_getdel {
+                                menu.menu_element_with_icon(
+                                    CustomIconName::FileXCorner,
+                                    Box::new(EditorAction::GetDel),
+                                    move |_, cx| Label::new(i18n_editor(cx, "getdel_value")),
+                                )
+                            } else {
+                                menu
+                            }
+                        })
+                    })
                     .into_any_element(),
             );
+            if server_state.can_undo_save() {
+                btns.push(
+                    Button::new("zedis-editor-undo-save")
+                        .disabled(self.readonly || should_show_loading)
+                        .outline()
+                        .tooltip(i18n_editor(cx, "undo_save_tooltip"))
+                        .icon(CustomIconName::Undo2)
+                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                            this.server_state.update(cx, |state, cx| {
+                                state.undo_last_save(cx);
+                            });
+                        }))
+                        .into_any_element(),
+                );
+            }
+            if is_json {
+                btns.push(
+                    DropdownButton::new("zedis-editor-format-json")
+                        .button(
+                            Button::new("zedis-editor-format-json-now")
+                                .outline()
+                                .label(i18n_editor(cx, "format_json"))
+                                .tooltip(i18n_editor(cx, "format_json_tooltip"))
+                                .on_click(cx.listener(move |this, _event, window, cx| {
+                                    this.format_json(false, window, cx);
+                                })),
+                        )
+                        .dropdown_menu(move |menu, _, _cx| {
+                            menu.menu_element(Box::new(EditorAction::MinifyJson), move |_, cx| {
+                                Label::new(i18n_editor(cx, "minify_json"))
+                            })
+                        })
+                        .into_any_element(),
+                );
+            }
         }
 
         // Add TTL button (or input field when in edit mode)
@@ -457,6 +774,31 @@ impl ZedisEditor {
                 .into_any_element(),
         );
 
+        // Export, strings can also be written as raw bytes
+        let is_string = server_state
+            .value()
+            .is_some_and(|value| value.key_type() == KeyType::String);
+        let export_btn = Button::new("zedis-editor-export-value-now")
+            .outline()
+            .disabled(should_show_loading)
+            .tooltip(i18n_editor(cx, "export_value_tooltip"))
+            .icon(CustomIconName::Download)
+            .on_click(cx.listener(move |this, _event, _window, cx| {
+                this.export_value(ExportFormat::Text, cx);
+            }));
+        btns.push(if is_string {
+            DropdownButton::new("zedis-editor-export-value")
+                .button(export_btn)
+                .dropdown_menu(move |menu, _, _cx| {
+                    menu.menu_element(Box::new(EditorAction::ExportBinary), move |_, cx| {
+                        Label::new(i18n_editor(cx, "export_value_binary"))
+                    })
+                })
+                .into_any_element()
+        } else {
+            export_btn.into_any_element()
+        });
+
         // Add delete button
         btns.push(
             Button::new("zedis-editor-delete-key")
@@ -477,13 +819,22 @@ impl ZedisEditor {
                 .into_any_element(),
         );
 
+        // (is string, can copy as JSON) of the value, strings follow the view mode for JSON
+        let copy_formats = server_state.value().and_then(|value| match value.key_type() {
+            KeyType::String => {
+                let json = value.bytes_value().is_some_and(|bytes_value| {
+                    bytes_value.format == DataFormat::Json
+                        || matches!(bytes_value.view_mode, ViewMode::Json | ViewMode::MessagePack)
+                });
+                Some((true, json))
+            }
+            KeyType::List | KeyType::Set | KeyType::Zset | KeyType::Hash | KeyType::Stream | KeyType::Json => {
+                Some((false, true))
+            }
+            _ => None,
+        });
         let content = key.clone();
-        let server_id = server_state.server_id().to_string();
-        let is_favorited = get_favorites_manager()
-            .records(&server_id)
-            .unwrap_or_default()
-            .iter()
-            .any(|k| k.as_ref() == key.as_ref());
+        let is_favorited = server_state.is_favorite(&key);
         let favorite_icon = if is_favorited {
             IconName::StarFill
         } else {
@@ -497,47 +848,64 @@ impl ZedisEditor {
         let favorite_key = key.clone();
         h_flex()
             .px_2()
-            .h(EDITOR_KEY_BAR_HEIGHT)
+            .min_h(EDITOR_KEY_BAR_HEIGHT)
             .border_b_1()
             .border_color(cx.theme().border)
             .items_center()
             .gap_2()
             .w_full()
             .child(
-                // Copy key button
-                Button::new("zedis-editor-copy-key")
-                    .outline()
-                    .tooltip(i18n_editor(cx, "copy_key_tooltip"))
-                    .loading(should_show_loading)
-                    .icon(IconName::Copy)
-                    .on_click(cx.listener(move |_this, _event, window, cx| {
-                        cx.write_to_clipboard(ClipboardItem::new_string(content.to_string()));
-                        window.push_notification(Notification::info(i18n_editor(cx, "copied_key_to_clipboard")), cx);
-                    })),
+                // Copy key button, the menu copies the value
+                DropdownButton::new("zedis-editor-copy-key")
+                    .button(
+                        Button::new("zedis-editor-copy-key-name")
+                            .outline()
+                            .tooltip(i18n_editor(cx, "copy_key_tooltip"))
+                            .loading(should_show_loading)
+                            .icon(IconName::Copy)
+                            .on_click(cx.listener(move |_this, _event, window, cx| {
+                                cx.write_to_clipboard(ClipboardItem::new_string(content.to_string()));
+                                window.push_notification(
+                                    Notification::info(i18n_editor(cx, "copied_key_to_clipboard")),
+                                    cx,
+                                );
+                            })),
+                    )
+                    .when(copy_formats.is_some(), |this| {
+                        this.dropdown_menu(move |menu, _, _cx| {
+                            let Some((is_string, json)) = copy_formats else {
+                                return menu;
+                            };
+                            let mut menu = menu;
+                            if is_string {
+                                for (action, name) in [
+                                    (EditorAction::CopyRaw, "copy_value_raw"),
+                                    (EditorAction::CopyBase64, "copy_value_base64"),
+                                    (EditorAction::CopyHex, "copy_value_hex"),
+                                ] {
+                                    menu = menu
+                                        .menu_element(Box::new(action), move |_, cx| Label::new(i18n_editor(cx, name)));
+                                }
+                            }
+                            if json {
+                                menu = menu.menu_element(Box::new(EditorAction::CopyJson), move |_, cx| {
+                                    Label::new(i18n_editor(cx, "copy_value_json"))
+                                });
+                            }
+                            menu
+                        })
+                    }),
             )
             .child(
                 Button::new("zedis-editor-favorite-key")
                     .outline()
                     .tooltip(favorite_tooltip)
                     .icon(favorite_icon)
-                    .on_click(cx.listener(move |_this, _event, _window, cx| {
-                        let server_id = _this.server_state.read(cx).server_id().to_string();
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
                         let key = favorite_key.clone();
-                        let is_favorited = is_favorited;
-                        cx.spawn(async move |_, cx| {
-                            let _ = cx
-                                .background_spawn(async move {
-                                    let manager = get_favorites_manager();
-                                    if is_favorited {
-                                        let _ = manager.remove_record(&server_id, key.as_ref());
-                                    } else {
-                                        let _ = manager.add_record(&server_id, key.as_ref());
-                                    }
-                                })
-                                .await;
-                        })
-                        .detach();
-                        cx.notify();
+                        this.server_state.update(cx, |state, cx| {
+                            state.toggle_favorite(key, cx);
+                        });
                     })),
             )
             .child(
@@ -573,6 +941,12 @@ impl ZedisEditor {
         if key_type != KeyType::Channel {
             let _ = self.pubsub_editor.take();
         }
+        if key_type != KeyType::Monitor {
+            let _ = self.monitor_editor.take();
+        }
+        if key_type != KeyType::Config {
+            let _ = self.config_editor.take();
+        }
     }
 
     /// Render the appropriate editor based on the key type
@@ -586,6 +960,46 @@ impl ZedisEditor {
         if value.key_type == KeyType::Unknown && value.is_busy() {
             return div().into_any_element();
         }
+        if value.is_expired() {
+            self.reset_editors(KeyType::Unknown);
+            return h_flex()
+                .size_full()
+                .justify_center()
+                .gap_2()
+                .text_color(cx.theme().muted_foreground)
+                .child(Icon::new(IconName::Info).text_sm())
+                .child(Label::new(i18n_editor(cx, "key_expired")).text_sm())
+                .into_any_element();
+        }
+        if let Some(len) = value.held_back_len() {
+            self.reset_editors(KeyType::Unknown);
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let notice = t!("editor.large_collection_notice", total = len, locale = locale).to_string();
+            return v_flex()
+                .size_full()
+                .items_center()
+                .justify_center()
+                .gap_2()
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .text_color(cx.theme().warning_foreground)
+                        .child(Icon::new(IconName::TriangleAlert).text_sm())
+                        .child(Label::new(notice).text_sm()),
+                )
+                .child(
+                    Button::new("editor-load-first-page")
+                        .small()
+                        .primary()
+                        .label(i18n_editor(cx, "load_first_page"))
+                        .on_click(cx.listener(|this, _, _window, cx| {
+                            this.server_state.update(cx, |state, cx| {
+                                state.load_full_value(cx);
+                            });
+                        })),
+                )
+                .into_any_element();
+        }
 
         match value.key_type() {
             KeyType::List => {
@@ -636,6 +1050,30 @@ impl ZedisEditor {
                 });
                 editor.clone().into_any_element()
             }
+            KeyType::Monitor => {
+                self.reset_editors(KeyType::Monitor);
+                let editor = self.monitor_editor.get_or_insert_with(|| {
+                    debug!("Creating new monitor editor");
+                    cx.new(|cx| ZedisMonitorEditor::new(self.server_state.clone(), window, cx))
+                });
+                editor.clone().into_any_element()
+            }
+            KeyType::Config => {
+                self.reset_editors(KeyType::Config);
+                let editor = self.config_editor.get_or_insert_with(|| {
+                    debug!("Creating new config editor");
+                    cx.new(|cx| ZedisConfigEditor::new(self.server_state.clone(), window, cx))
+                });
+                editor.clone().into_any_element()
+            }
+            KeyType::Search => {
+                self.reset_editors(KeyType::Search);
+                let editor = self.search_editor.get_or_insert_with(|| {
+                    debug!("Creating new search editor");
+                    cx.new(|cx| ZedisSearchEditor::new(self.server_state.clone(), window, cx))
+                });
+                editor.clone().into_any_element()
+            }
             _ => {
                 // Default to bytes editor for String type and other types
                 self.reset_editors(KeyType::String);
@@ -654,7 +1092,11 @@ impl Render for ZedisEditor {
     /// Main render method - displays key info bar and appropriate editor
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state = self.server_state.read(cx);
-        let is_channel_mode = server_state.is_channel_mode();
+        // The pub/sub, monitor, config and search panels are not bound to a key
+        let is_channel_mode = server_state.is_channel_mode()
+            || server_state.is_monitor_mode()
+            || server_state.is_config_mode()
+            || server_state.is_search_mode();
 
         // Don't render anything if no key is selected
         if !is_channel_mode && server_state.key().is_none() {
@@ -673,6 +1115,33 @@ impl Render for ZedisEditor {
                 EditorAction::Save => {
                     this.save(window, cx);
                 }
+                EditorAction::GetSet => {
+                    this.getset(cx);
+                }
+                EditorAction::GetDel => {
+                    this.getdel(window, cx);
+                }
+                EditorAction::CopyRaw => {
+                    this.copy_value(CopyFormat::Raw, cx);
+                }
+                EditorAction::CopyBase64 => {
+                    this.copy_value(CopyFormat::Base64, cx);
+                }
+                EditorAction::CopyHex => {
+                    this.copy_value(CopyFormat::Hex, cx);
+                }
+                EditorAction::CopyJson => {
+                    this.copy_value(CopyFormat::Json, cx);
+                }
+                EditorAction::ExportBinary => {
+                    this.export_value(ExportFormat::Binary, cx);
+                }
+                EditorAction::FormatJson => {
+                    this.format_json(false, window, cx);
+                }
+                EditorAction::MinifyJson => {
+                    this.format_json(true, window, cx);
+                }
                 EditorAction::AutoRefresh(interval) => {
                     this.start_auto_refresh(Some(*interval as u64), cx);
                 }
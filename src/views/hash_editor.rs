@@ -22,16 +22,32 @@
 //! - Removing field-value pairs
 //! - Filtering fields with pattern matching
 //! - Incremental loading of large HASHes with pagination
+//! - Random sampling of huge HASHes
+//! - Viewing and setting field TTLs on Redis 7.4+
 
 use crate::{
     components::KvTableColumn,
     components::ZedisKvFetcher,
-    states::{KeyType, RedisValue, ZedisServerState},
+    connection::Capability,
+    error::Error,
+    helpers::parse_duration,
+    states::{FieldTtl, KeyType, RedisValue, ZedisServerState},
     views::{ZedisKvTable, kv_table::define_kv_editor},
 };
 use gpui::{App, Entity, SharedString, Window, prelude::*};
+use gpui_component::{WindowExt, notification::Notification};
+use std::time::Duration;
 use zedis_ui::ZedisFormFieldType;
 
+/// Column index of the field TTL, after the index, field and value columns
+const TTL_COLUMN_INDEX: usize = 3;
+
+/// Width of the field TTL column in pixels
+const TTL_COLUMN_WIDTH: f32 = 140.0;
+
+/// Fields expiring within this many seconds are flagged
+const EXPIRING_SOON_SECONDS: i64 = 60;
+
 /// Data adapter for Redis HASH values to work with the KV table component.
 ///
 /// This struct implements the `ZedisKvFetcher` trait to provide data access
@@ -58,16 +74,38 @@ impl ZedisKvFetcher for ZedisHashValues {
     /// Column layout:
     /// - Column 1: Field name
     /// - Column 2: Field value
+    /// - Column 3: Field TTL, only on Redis 7.4+
     fn get(&self, row_ix: usize, col_ix: usize) -> Option<SharedString> {
         let hash = self.value.hash_value()?;
         let (field, value) = hash.values.get(row_ix)?;
 
-        // Column 2 is the value, others show the field name
-        if col_ix == 2 {
-            Some(value.clone())
-        } else {
-            Some(field.clone())
+        // Column 2 is the value, column 3 the TTL, others show the field name
+        match col_ix {
+            2 => Some(value.clone()),
+            TTL_COLUMN_INDEX => {
+                let ttl = *hash.field_ttls.get(field)?;
+                Some(
+                    humantime::format_duration(Duration::from_secs(ttl as u64))
+                        .to_string()
+                        .into(),
+                )
+            }
+            _ => Some(field.clone()),
+        }
+    }
+
+    /// Flags the TTL of fields expiring soon.
+    fn is_warning(&self, row_ix: usize, col_ix: usize) -> bool {
+        if col_ix != TTL_COLUMN_INDEX {
+            return false;
         }
+        let Some(hash) = self.value.hash_value() else {
+            return false;
+        };
+        hash.values
+            .get(row_ix)
+            .and_then(|(field, _)| hash.field_ttls.get(field))
+            .is_some_and(|ttl| *ttl < EXPIRING_SOON_SECONDS)
     }
 
     /// Returns the total number of fields in the HASH (from Redis HLEN).
@@ -98,6 +136,22 @@ impl ZedisKvFetcher for ZedisHashValues {
         });
     }
 
+    /// HRANDFIELD requires Redis 6.2
+    fn can_sample(&self, cx: &App) -> bool {
+        self.server_state.read(cx).supports(Capability::HRandField)
+    }
+
+    /// Loads a random sample of fields via HRANDFIELD.
+    fn sample(&self, count: usize, cx: &mut App) {
+        self.server_state.update(cx, |this, cx| {
+            this.sample_hash_value(count, cx);
+        });
+    }
+
+    fn is_sample(&self) -> bool {
+        self.value.hash_value().is_some_and(|v| v.sample)
+    }
+
     /// Removes a field-value pair from the HASH at the given index.
     ///
     /// Executes Redis HDEL command to delete the field.
@@ -116,6 +170,22 @@ impl ZedisKvFetcher for ZedisHashValues {
         });
     }
 
+    /// The field and its value, the TTL of the field is not restored.
+    fn restore_values(&self, index: usize) -> Option<Vec<SharedString>> {
+        let (field, value) = self.value.hash_value()?.values.get(index)?;
+        Some(vec![field.clone(), value.clone()])
+    }
+
+    /// Sets the removed field again with HSET.
+    fn restore(&self, values: Vec<SharedString>, cx: &mut App) {
+        let Ok([field, value]) = <[SharedString; 2]>::try_from(values) else {
+            return;
+        };
+        self.server_state.update(cx, |this, cx| {
+            this.add_hash_value(field, value, FieldTtl::Keep, cx);
+        });
+    }
+
     /// Applies a filter to HASH fields by pattern matching.
     ///
     /// Resets the scan and loads fields matching the keyword pattern.
@@ -125,11 +195,19 @@ impl ZedisKvFetcher for ZedisHashValues {
         });
     }
 
+    /// The HSCAN MATCH keyword, highlighted in both field names and values.
+    fn keyword(&self) -> Option<SharedString> {
+        self.value
+            .hash_value()
+            .and_then(|hash| hash.keyword.clone())
+            .filter(|keyword| !keyword.is_empty())
+    }
+
     /// Handles inline editing of a HASH field's value.
     ///
     /// Called when the user edits the value column directly in the table.
     /// Updates the value for the existing field using Redis HSET.
-    fn handle_update_value(&self, row_ix: usize, values: Vec<SharedString>, _window: &mut Window, cx: &mut App) {
+    fn handle_update_value(&self, row_ix: usize, values: Vec<SharedString>, window: &mut Window, cx: &mut App) {
         // Extract field name and new value from values
         let Some(field) = values.first() else {
             return;
@@ -144,27 +222,65 @@ impl ZedisKvFetcher for ZedisHashValues {
         else {
             return;
         };
+        let original_ttl = self.get(row_ix, TTL_COLUMN_INDEX).unwrap_or_default();
+        let ttl = match parse_field_ttl(values.get(2), &original_ttl) {
+            Ok(ttl) => ttl,
+            Err(e) => {
+                window.push_notification(Notification::error(e.to_string()), cx);
+                return;
+            }
+        };
 
         // Execute update operation
         self.server_state.update(cx, |this, cx| {
-            this.update_hash_value(old_field, field.clone(), value.clone(), cx);
+            this.update_hash_value(old_field, field.clone(), value.clone(), ttl, cx);
         });
     }
 
     /// Adds a new field-value pair to the HASH.
-    fn handle_add_value(&self, values: Vec<SharedString>, _window: &mut Window, cx: &mut App) {
-        // Validate that both field and value were provided
-        if values.len() != 2 {
+    fn handle_add_value(&self, values: Vec<SharedString>, window: &mut Window, cx: &mut App) {
+        // Validate that both field and value were provided, the TTL is optional
+        if values.len() < 2 {
             return;
         }
+        let ttl = match parse_field_ttl(values.get(2), "") {
+            Ok(ttl) => ttl,
+            Err(e) => {
+                window.push_notification(Notification::error(e.to_string()), cx);
+                return;
+            }
+        };
 
         let server_state = self.server_state.clone();
         // Execute the add operation on server state
         server_state.update(cx, |this, cx| {
-            this.add_hash_value(values[0].clone(), values[1].clone(), cx);
+            this.add_hash_value(values[0].clone(), values[1].clone(), ttl, cx);
         });
     }
 }
+/// Converts the TTL input of the edit form into a TTL change.
+///
+/// An input equal to the displayed TTL keeps it, an empty input removes it,
+/// otherwise seconds or a duration like `30m` are expected.
+fn parse_field_ttl(input: Option<&SharedString>, original: &str) -> Result<FieldTtl, Error> {
+    let Some(input) = input.map(|input| input.trim()) else {
+        return Ok(FieldTtl::Keep);
+    };
+    if input == original {
+        return Ok(FieldTtl::Keep);
+    }
+    if input.is_empty() {
+        return Ok(FieldTtl::Persist);
+    }
+    let seconds = parse_duration(input)?.as_secs();
+    if seconds == 0 {
+        return Err(Error::Invalid {
+            message: "TTL must be at least 1 second".to_string(),
+        });
+    }
+    Ok(FieldTtl::Expire(seconds))
+}
+
 define_kv_editor!(ZedisHashEditor, ZedisHashValues);
 
 impl ZedisHashEditor {
@@ -178,17 +294,16 @@ impl ZedisHashEditor {
             0.4
         };
 
-        let table_state = cx.new(|cx| {
-            ZedisKvTable::<ZedisHashValues>::new(
-                vec![
-                    KvTableColumn::new("Field", Some(field_width)),
-                    KvTableColumn::new_flex("Value").field_type(ZedisFormFieldType::Editor),
-                ],
-                server_state,
-                window,
-                cx,
-            )
-        });
+        let mut columns = vec![
+            KvTableColumn::new("Field", Some(field_width)),
+            KvTableColumn::new_flex("Value").field_type(ZedisFormFieldType::Editor),
+        ];
+        // Field TTLs are only available since Redis 7.4, the column is omitted on older servers
+        if server_state.read(cx).supports(Capability::HashFieldTtl) {
+            columns.push(KvTableColumn::new("TTL", Some(TTL_COLUMN_WIDTH)).optional());
+        }
+
+        let table_state = cx.new(|cx| ZedisKvTable::<ZedisHashValues>::new(columns, server_state, window, cx));
 
         Self { table_state }
     }
@@ -14,37 +14,45 @@
 
 use crate::{
     assets::CustomIconName,
-    constants::KEY_TREE_KEYWORD_INPUT_HEIGHT,
-    db::{get_favorites_manager, get_search_history_manager},
-    helpers::{EditorAction, get_font_family, humanize_keystroke, validate_long_string, validate_ttl},
+    connection::{Capability, get_servers},
+    constants::{DEFAULT_REM_SIZE, KEY_TREE_KEYWORD_INPUT_HEIGHT},
+    db::get_search_history_manager,
+    helpers::{
+        EditorAction, TTL_UNITS, format_count, format_duration, get_font_family, get_home_dir, humanize_keystroke,
+        parse_duration, parse_import_commands, to_file_name, unix_ts, validate_long_string, validate_ttl,
+    },
     states::{
-        KeyType, QueryMode, ServerEvent, ZedisGlobalStore, ZedisServerState, dialog_button_props, get_session_option,
+        BulkOperation, BulkPreview, ColdKeyMetric, ColdKeysReport, CopyTarget, DuplicateReport, KeySortMode, KeyType,
+        QueryMode, ServerEvent, ZedisGlobalStore, ZedisServerState, dialog_button_props, get_session_option,
         i18n_common, i18n_key_tree, save_session_option,
     },
 };
 use ahash::{AHashMap, AHashSet};
 use gpui::{
-    Action, App, AppContext, Corner, Entity, FocusHandle, Focusable, Hsla, ScrollStrategy, SharedString, Subscription,
-    Task, Window, div, prelude::*, px,
+    Action, App, AppContext, ClipboardItem, Corner, Entity, FocusHandle, Focusable, Hsla, KeyDownEvent,
+    PathPromptOptions, ScrollStrategy, SharedString, Subscription, Task, Window, div, prelude::*, px, relative,
 };
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, IndexPath, StyledExt,
+    ActiveTheme, Disableable, Icon, IconName, IndexPath, Sizable, StyledExt, WindowExt,
     button::{Button, ButtonVariants, DropdownButton},
+    dialog::DialogButtonProps,
     h_flex,
     input::{Input, InputEvent, InputState},
     label::Label,
     menu::ContextMenuExt,
+    notification::Notification,
     v_flex,
 };
 use gpui_component::{
     list::{List, ListDelegate, ListEvent, ListItem, ListState},
     menu::DropdownMenu,
 };
+use humansize::{DECIMAL, format_size};
+use regex::Regex;
 use rust_i18n::t;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::{str::FromStr, time::Duration};
+use std::{cmp::Ordering, str::FromStr, sync::Arc, time::Duration};
 use tracing::info;
 use zedis_ui::{ZedisDialog, ZedisFormField, ZedisFormFieldType, ZedisFormOptions, ZedisSkeletonLoading};
 
@@ -52,24 +60,57 @@ use zedis_ui::{ZedisDialog, ZedisFormField, ZedisFormFieldType, ZedisFormOptions
 const TREE_INDENT_BASE: f32 = 16.0; // Base indentation per level in pixels
 const TREE_INDENT_OFFSET: f32 = 8.0; // Additional offset for all items
 const EXPANDED_ITEMS_INITIAL_CAPACITY: usize = 10;
+const MAX_SAVED_EXPANDED_ITEMS: usize = 1_000; // Max expanded folders remembered per server
+const IMPORT_PREVIEW_MAX_COMMANDS: usize = 20; // Max commands listed in the import confirmation
+const BULK_PREVIEW_MAX_KEYS: usize = 20; // Max keys listed in the bulk operation confirmation
+const DUPLICATE_GROUPS_MAX: usize = 100; // Max groups listed in the duplicate report
+const REPORT_LIST_MAX_HEIGHT: f32 = 400.0; // Max height of the key lists in the report dialogs
 const KEY_TYPE_FADE_ALPHA: f32 = 0.8; // Background transparency for key type badges
 const KEY_TYPE_BORDER_FADE_ALPHA: f32 = 0.5; // Border transparency for key type badges
 const STRIPE_BACKGROUND_ALPHA_DARK: f32 = 0.1; // Odd row background alpha for dark theme
 const STRIPE_BACKGROUND_ALPHA_LIGHT: f32 = 0.03; // Odd row background alpha for light theme
+const FAVORITES_MAX_HEIGHT: f32 = 160.0; // Max height of the favorites section above the tree
+const KEY_TREE_ROW_GROUP: &str = "key-tree-row"; // Hover group showing the pin button of key rows
+const LIVE_SEARCH_DEBOUNCE: Duration = Duration::from_millis(300); // Typing pause before the live search scans
+const KEY_SEPARATORS: [&str; 5] = [":", "/", ".", "|", "_"]; // Separators offered in the key tree menu
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Action)]
 enum KeyTreeAction {
     Search(SharedString),
     Clear,
+    SelectRecentKey(SharedString),
+    ClearRecentKeys,
     DeleteMultipleKeys,
     DeleteKey(SharedString),
     DeleteFolder(SharedString),
+    ExpireFolder(SharedString),
+    RenameKey(SharedString),
+    DuplicateKey(SharedString),
     CollapseAllKeys,
     ToggleMultiSelectMode,
     ChangeChannelMode,
+    ChangeMonitorMode,
+    ChangeConfigMode,
+    ChangeSearchMode,
+    ToggleWatchKeyEvents,
     AutoRefresh(u32),
-    SelectFavoriteKey(SharedString),
-    ClearFavorites,
+    FilterKeyType(SharedString),
+    CopyKey(SharedString),
+    CopyPrefix(SharedString),
+    CopyToServer(SharedString),
+    ScanPrefix(SharedString),
+    FindDuplicates(SharedString),
+    FindColdKeys(SharedString),
+    ExportPrefix(SharedString),
+    ImportKeys,
+    ToggleFolderMemory,
+    ToggleKeyMemory,
+    SortKeys(KeySortMode),
+    /// An empty separator follows the global setting
+    SetKeySeparator(SharedString),
+    ToggleFlatView,
+    ToggleLiveSearch,
+    ImportCommands,
 }
 
 #[derive(Default)]
@@ -87,10 +128,26 @@ struct KeyTreeState {
     error: Option<SharedString>,
     /// Set of expanded folder paths (persisted during tree rebuilds)
     expanded_items: AHashSet<SharedString>,
+    /// Whether `expanded_items` was restored from the session and waits for the first scan
+    expanded_items_restored: bool,
     /// Index path to scroll to when the tree is updated
     scroll_to_index: Option<IndexPath>,
+    /// Revealed key to scroll to once it shows up in the rebuilt tree
+    reveal_key: Option<SharedString>,
     /// Refresh interval in seconds
     refresh_interval_sec: u32,
+    /// Whether folders show their estimated memory usage
+    show_folder_memory: bool,
+    /// Whether keys show their MEMORY USAGE
+    show_key_memory: bool,
+    /// Order of the siblings in the tree
+    sort_mode: KeySortMode,
+    /// Whether the keys are listed with their full names instead of grouped in folders
+    flat_view: bool,
+    /// Whether typing in the keyword input filters without pressing Enter
+    live_search: bool,
+    /// Whether the favorites section is collapsed to its header
+    favorites_collapsed: bool,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -100,18 +157,117 @@ struct KeyTreeItem {
     depth: usize,
     key_type: KeyType,
     expanded: bool,
-    children_count: usize,
+    /// Number of keys beneath the folder, including nested folders
+    key_count: usize,
     is_folder: bool,
 }
 
+/// Loaded memory usage of the tree items, used to sort them by size
+struct TreeMemory {
+    folders: AHashMap<SharedString, u64>,
+    keys: AHashMap<SharedString, u64>,
+}
+
+impl TreeMemory {
+    /// Memory usage of the item, items not loaded yet come last
+    fn size(&self, item: &KeyTreeItem) -> u64 {
+        let sizes = if item.is_folder { &self.folders } else { &self.keys };
+        sizes.get(&item.id).copied().unwrap_or_default()
+    }
+}
+
+/// Order of the siblings, `memory` is only set when sorting by memory usage
+struct TreeSort {
+    mode: KeySortMode,
+    memory: Option<TreeMemory>,
+}
+
+impl TreeSort {
+    fn compare(&self, a: &KeyTreeItem, b: &KeyTreeItem) -> Ordering {
+        let order = match self.mode {
+            KeySortMode::NameDesc => b.label.cmp(&a.label),
+            // The type of a folder is the one of its first key, so folders stay sorted by name
+            KeySortMode::Type if !a.is_folder && !b.is_folder => a
+                .key_type
+                .as_str()
+                .cmp(b.key_type.as_str())
+                .then_with(|| a.label.cmp(&b.label)),
+            // Without the memory usage loaded, fall back to the name
+            KeySortMode::Memory => self
+                .memory
+                .as_ref()
+                .map(|memory| memory.size(b).cmp(&memory.size(a)))
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.label.cmp(&b.label)),
+            _ => a.label.cmp(&b.label),
+        };
+        b.is_folder.cmp(&a.is_folder).then(order)
+    }
+}
+
+/// Drops the rows beneath the row of `folder`, whose keys start with `prefix`.
+///
+/// Returns false when the folder has no row.
+fn collapse_folder_rows(items: &mut Vec<KeyTreeItem>, folder: &str, prefix: &str) -> bool {
+    let Some(index) = items.iter().position(|item| item.is_folder && item.id == folder) else {
+        return false;
+    };
+    items[index].expanded = false;
+    let end = items[index + 1..]
+        .iter()
+        .position(|item| !item.id.starts_with(prefix))
+        .map_or(items.len(), |count| index + 1 + count);
+    items.drain(index + 1..end);
+    true
+}
+
+/// Inserts `rows` after the row of `folder`, the other rows are moved but not rebuilt.
+///
+/// Returns false when the folder has no row or is already expanded.
+fn expand_folder_rows(items: &mut Vec<KeyTreeItem>, folder: &str, rows: Vec<KeyTreeItem>) -> bool {
+    let Some(index) = items.iter().position(|item| item.is_folder && item.id == folder) else {
+        return false;
+    };
+    if items[index].expanded {
+        return false;
+    }
+    items[index].expanded = true;
+    items.splice(index + 1..index + 1, rows);
+    true
+}
+
+/// One item per matching key with its full name, for the flat list view
+fn new_flat_key_items(
+    keys: Vec<(SharedString, KeyType)>,
+    keyword: SharedString,
+    key_type_filter: Option<KeyType>,
+    sort: TreeSort,
+) -> Vec<KeyTreeItem> {
+    let mut items: Vec<KeyTreeItem> = keys
+        .into_iter()
+        .filter(|(key, _)| keyword.is_empty() || key.contains(keyword.as_str()))
+        .filter(|(_, key_type)| key_type_filter.is_none_or(|filter| *key_type == filter))
+        .map(|(key, key_type)| KeyTreeItem {
+            id: key.clone(),
+            label: key,
+            key_type,
+            ..Default::default()
+        })
+        .collect();
+    items.sort_unstable_by(|a, b| sort.compare(a, b));
+    items
+}
+
 fn new_key_tree_items(
     mut keys: Vec<(SharedString, KeyType)>,
     keyword: SharedString,
+    key_type_filter: Option<KeyType>,
     expanded_items: AHashSet<SharedString>,
     separator: &str,
     max_key_tree_depth: usize,
+    sort: TreeSort,
 ) -> Vec<KeyTreeItem> {
-    keys.sort_unstable_by_key(|(k, _)| k.clone());
+    keys.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
     let expanded_items_set = expanded_items.iter().map(|s| s.as_str()).collect::<AHashSet<&str>>();
     let mut items: AHashMap<SharedString, KeyTreeItem> = AHashMap::with_capacity(100);
     // Tracks standalone keys whose HashMap slot was taken over by a folder
@@ -119,11 +275,19 @@ fn new_key_tree_items(
     // These are re-inserted as **siblings** of the folder so both remain
     // visible at the same tree level.
     let mut promoted_leaves: Vec<(SharedString, KeyType, SharedString, usize)> = Vec::new();
+    // Path of the current segment, shared by all the keys
+    let mut dir = String::with_capacity(50);
 
     for (key, key_type) in keys {
         if !keyword.is_empty() && !key.contains(keyword.as_str()) {
             continue;
         }
+        // Folders are built from matching leaves only, so empty folders never show up
+        if let Some(filter) = key_type_filter
+            && key_type != filter
+        {
+            continue;
+        }
         if !key.contains(separator) {
             items.insert(
                 key.clone(),
@@ -137,97 +301,120 @@ fn new_key_tree_items(
             continue;
         }
 
-        let mut dir = String::with_capacity(50);
-        let mut key_tree_item: Option<KeyTreeItem> = None;
+        dir.clear();
+        // Depth, start of the label in `dir` and expanded state of the last segment,
+        // it becomes a folder when another segment follows
+        let mut pending: Option<(usize, usize, bool)> = None;
         for (index, k) in key.splitn(max_key_tree_depth, separator).enumerate() {
             let expanded = index == 0 || expanded_items_set.contains(dir.as_str());
-            if let Some(pending) = key_tree_item.take() {
-                match items.entry(pending.id.clone()) {
-                    Occupied(mut e) => {
-                        let existing = e.get_mut();
-                        if !existing.is_folder {
-                            promoted_leaves.push((
-                                existing.id.clone(),
-                                existing.key_type,
-                                existing.label.clone(),
-                                existing.depth,
-                            ));
-                        }
-                        existing.is_folder = true;
-                        existing.children_count += 1;
-                        existing.expanded = expanded;
-                    }
-                    Vacant(e) => {
-                        let mut item = pending;
-                        item.is_folder = true;
-                        item.children_count = 1;
-                        item.expanded = expanded;
-                        e.insert(item);
+            if let Some((depth, label_start, _)) = pending.take() {
+                // Only the first key of a folder allocates its id and label
+                if let Some(existing) = items.get_mut(dir.as_str()) {
+                    if !existing.is_folder {
+                        promoted_leaves.push((
+                            existing.id.clone(),
+                            existing.key_type,
+                            existing.label.clone(),
+                            existing.depth,
+                        ));
                     }
+                    existing.is_folder = true;
+                    existing.key_count += 1;
+                    existing.expanded = expanded;
+                } else {
+                    let id: SharedString = dir.clone().into();
+                    items.insert(
+                        id.clone(),
+                        KeyTreeItem {
+                            id,
+                            label: dir[label_start..].to_string().into(),
+                            key_type,
+                            depth,
+                            expanded,
+                            key_count: 1,
+                            is_folder: true,
+                        },
+                    );
                 }
             }
 
             if !expanded {
                 break;
             }
-            let name: SharedString = k.to_string().into();
             if index != 0 {
                 dir.push_str(separator);
             };
+            pending = Some((index, dir.len(), expanded));
             dir.push_str(k);
-
-            key_tree_item = Some(KeyTreeItem {
-                id: dir.clone().into(),
-                label: name.clone(),
-                key_type,
-                depth: index,
-                expanded,
-                ..Default::default()
-            });
         }
-        if let Some(key_tree_item) = key_tree_item.take() {
-            items.insert(key_tree_item.id.clone(), key_tree_item);
+        // All the segments were walked, so the path is the key itself
+        if let Some((depth, label_start, expanded)) = pending {
+            items.insert(
+                key.clone(),
+                KeyTreeItem {
+                    id: key.clone(),
+                    label: key[label_start..].to_string().into(),
+                    key_type,
+                    depth,
+                    expanded,
+                    ..Default::default()
+                },
+            );
         }
     }
 
     let mut children_map: AHashMap<String, Vec<KeyTreeItem>> = AHashMap::new();
+    // Folders have many children, so the parent id is only allocated once per folder
+    let mut push_child = |parent_id: &str, item: KeyTreeItem| {
+        if let Some(children) = children_map.get_mut(parent_id) {
+            children.push(item);
+        } else {
+            children_map.insert(parent_id.to_string(), vec![item]);
+        }
+    };
+    let parent_len = |id: &str, label: &str| (id.len() - label.len()).saturating_sub(separator.len());
 
     for item in items.into_values() {
-        let size = item.id.len() - item.label.len();
-        let parent_id = if size == 0 { "" } else { &item.id[..(size - 1)] };
-        children_map.entry(parent_id.to_string()).or_default().push(item);
+        let len = parent_len(&item.id, &item.label);
+        let id = item.id.clone();
+        push_child(&id[..len], item);
     }
 
     for (key_id, key_type, label, depth) in promoted_leaves {
-        let size = key_id.len() - label.len();
-        let parent_id = if size == 0 { "" } else { &key_id[..(size - 1)] };
-        children_map
-            .entry(parent_id.to_string())
-            .or_default()
-            .push(KeyTreeItem {
+        let len = parent_len(&key_id, &label);
+        let id = key_id.clone();
+        push_child(
+            &id[..len],
+            KeyTreeItem {
                 id: key_id,
                 label,
                 depth,
                 key_type,
                 ..Default::default()
-            });
+            },
+        );
     }
 
     let mut result = Vec::with_capacity(children_map.values().map(|v| v.len()).sum());
 
-    fn build_sorted_list(parent_id: &str, map: &mut AHashMap<String, Vec<KeyTreeItem>>, result: &mut Vec<KeyTreeItem>) {
+    fn build_sorted_list(
+        parent_id: &str,
+        map: &mut AHashMap<String, Vec<KeyTreeItem>>,
+        sort: &TreeSort,
+        result: &mut Vec<KeyTreeItem>,
+    ) {
         if let Some(mut children) = map.remove(parent_id) {
-            children.sort_unstable_by(|a, b| b.is_folder.cmp(&a.is_folder).then_with(|| a.label.cmp(&b.label)));
+            children.sort_unstable_by(|a, b| sort.compare(a, b));
 
             for child in children {
-                let child_id = child.id.to_string();
+                let child_id = child.id.clone();
                 result.push(child);
-                build_sorted_list(&child_id, map, result);
+                build_sorted_list(&child_id, map, sort, result);
             }
         }
     }
 
-    build_sorted_list("", &mut children_map, &mut result);
+    build_sorted_list("", &mut children_map, &sort, &mut result);
 
     result
 }
@@ -238,6 +425,17 @@ struct KeyTreeDelegate {
     enabled_multiple_selection: bool,
     selected_items: AHashSet<SharedString>,
     readonly: bool,
+    show_folder_memory: bool,
+    /// Estimated memory usage of the folders in `items`
+    folder_memory: AHashMap<SharedString, u64>,
+    show_key_memory: bool,
+    /// Memory usage of the keys in `items`, `None` when it could not be read
+    key_memory: AHashMap<SharedString, Option<u64>>,
+    /// Expiration of the keys in `items` as unix seconds, -1 without expiry
+    key_expire_at: AHashMap<SharedString, i64>,
+    /// Pinned keys, shown with a filled star
+    favorites: AHashSet<SharedString>,
+    server_state: Entity<ZedisServerState>,
 }
 
 impl KeyTreeDelegate {
@@ -265,6 +463,22 @@ impl KeyTreeDelegate {
             .border_color(border)
             .into_any_element()
     }
+    /// Ids of the folders currently in the tree
+    fn folder_ids(&self) -> Vec<SharedString> {
+        self.items
+            .iter()
+            .filter(|item| item.is_folder)
+            .map(|item| item.id.clone())
+            .collect()
+    }
+    /// Ids of the keys currently in the tree
+    fn key_ids(&self) -> Vec<SharedString> {
+        self.items
+            .iter()
+            .filter(|item| !item.is_folder)
+            .map(|item| item.id.clone())
+            .collect()
+    }
     fn toggle_multiple_selection(&mut self, cx: &mut Context<ListState<Self>>) {
         self.enabled_multiple_selection = !self.enabled_multiple_selection;
         if self.enabled_multiple_selection {
@@ -284,7 +498,7 @@ impl ListDelegate for KeyTreeDelegate {
     fn render_item(
         &mut self,
         ix: IndexPath,
-        _window: &mut Window,
+        window: &mut Window,
         cx: &mut Context<ListState<Self>>,
     ) -> Option<Self::Item> {
         let yellow = cx.theme().colors.yellow;
@@ -317,17 +531,30 @@ impl ListDelegate for KeyTreeDelegate {
             cx.theme().foreground
         };
 
-        let bg = if ix.row.is_multiple_of(2) { even_bg } else { odd_bg };
         let show_check_icon = self.enabled_multiple_selection && !is_folder;
-        let selected = if show_check_icon && let Some(item) = self.items.get(ix.row) {
-            let id = &item.id;
-            self.selected_items.contains(id)
+        let selected = !is_folder && self.selected_items.contains(&entry.id);
+        // Keys without expiry (-1) render normally
+        let ttl_warning_threshold = cx.global::<ZedisGlobalStore>().read(cx).ttl_warning_threshold() as i64;
+        let expires_soon = !is_folder
+            && ttl_warning_threshold > 0
+            && self
+                .key_expire_at
+                .get(&entry.id)
+                .is_some_and(|expire_at| *expire_at >= 0 && *expire_at - unix_ts() < ttl_warning_threshold);
+        let bg = if selected {
+            cx.theme().selection
+        } else if expires_soon {
+            cx.theme().warning.opacity(0.15)
+        } else if ix.row.is_multiple_of(2) {
+            even_bg
         } else {
-            false
+            odd_bg
         };
         let selected_items_count = self.selected_items.len();
         let id = entry.id.clone();
         let readonly = self.readonly;
+        // The indentation follows the zoom like the rem based sizes
+        let indent_scale = window.rem_size() / px(DEFAULT_REM_SIZE);
         Some(
             ListItem::new(ix)
                 .font_family(get_font_family())
@@ -336,14 +563,53 @@ impl ListDelegate for KeyTreeDelegate {
                 .py_2()
                 .px_2()
                 .mb_1()
-                .pl(px(TREE_INDENT_BASE) * entry.depth + px(TREE_INDENT_OFFSET))
+                .pl((px(TREE_INDENT_BASE) * entry.depth + px(TREE_INDENT_OFFSET)) * indent_scale)
                 .child(
                     div()
                         .context_menu(move |mut menu, _window, cx| {
+                            let id = id.clone();
+                            menu = if is_folder {
+                                menu.menu_element_with_icon(
+                                    IconName::Copy,
+                                    Box::new(KeyTreeAction::CopyPrefix(id.clone())),
+                                    move |_, cx| Label::new(i18n_key_tree(cx, "copy_prefix")),
+                                )
+                                .menu_element_with_icon(
+                                    IconName::Search,
+                                    Box::new(KeyTreeAction::ScanPrefix(id.clone())),
+                                    move |_, cx| Label::new(i18n_key_tree(cx, "scan_prefix")),
+                                )
+                                .menu_element_with_icon(
+                                    CustomIconName::Equal,
+                                    Box::new(KeyTreeAction::FindDuplicates(id.clone())),
+                                    move |_, cx| Label::new(i18n_key_tree(cx, "find_duplicates")),
+                                )
+                                .menu_element_with_icon(
+                                    CustomIconName::Snail,
+                                    Box::new(KeyTreeAction::FindColdKeys(id.clone())),
+                                    move |_, cx| Label::new(i18n_key_tree(cx, "find_cold_keys")),
+                                )
+                                .menu_element_with_icon(
+                                    CustomIconName::Download,
+                                    Box::new(KeyTreeAction::ExportPrefix(id.clone())),
+                                    move |_, cx| Label::new(i18n_key_tree(cx, "export_prefix")),
+                                )
+                            } else {
+                                menu.menu_element_with_icon(
+                                    IconName::Copy,
+                                    Box::new(KeyTreeAction::CopyKey(id.clone())),
+                                    move |_, cx| Label::new(i18n_key_tree(cx, "copy_key_name")),
+                                )
+                                .menu_element_with_icon(
+                                    IconName::ArrowRight,
+                                    Box::new(KeyTreeAction::CopyToServer(id.clone())),
+                                    move |_, cx| Label::new(i18n_key_tree(cx, "copy_to_server")),
+                                )
+                            };
                             if readonly {
                                 return menu;
                             }
-                            let id = id.clone();
+                            menu = menu.separator();
                             if selected && selected_items_count > 1 {
                                 let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
                                 let text = t!(
@@ -359,12 +625,27 @@ impl ListDelegate for KeyTreeDelegate {
                             } else {
                                 menu = if is_folder {
                                     menu.menu_element_with_icon(
+                                        CustomIconName::Clock3,
+                                        Box::new(KeyTreeAction::ExpireFolder(id.clone())),
+                                        move |_, cx| Label::new(i18n_key_tree(cx, "set_folder_ttl")),
+                                    )
+                                    .menu_element_with_icon(
                                         CustomIconName::X,
                                         Box::new(KeyTreeAction::DeleteFolder(id)),
                                         move |_, cx| Label::new(i18n_key_tree(cx, "delete_folder_tooltip")),
                                     )
                                 } else {
                                     menu.menu_element_with_icon(
+                                        CustomIconName::FilePenLine,
+                                        Box::new(KeyTreeAction::RenameKey(id.clone())),
+                                        move |_, cx| Label::new(i18n_key_tree(cx, "rename_key")),
+                                    )
+                                    .menu_element_with_icon(
+                                        IconName::Copy,
+                                        Box::new(KeyTreeAction::DuplicateKey(id.clone())),
+                                        move |_, cx| Label::new(i18n_key_tree(cx, "duplicate_key")),
+                                    )
+                                    .menu_element_with_icon(
                                         CustomIconName::X,
                                         Box::new(KeyTreeAction::DeleteKey(id)),
                                         move |_, cx| Label::new(i18n_key_tree(cx, "delete_key_tooltip")),
@@ -376,6 +657,7 @@ impl ListDelegate for KeyTreeDelegate {
                         .child(
                             div()
                                 .h_flex()
+                                .group(KEY_TREE_ROW_GROUP)
                                 .gap_2()
                                 .flex_1()
                                 .min_w_0()
@@ -386,6 +668,53 @@ impl ListDelegate for KeyTreeDelegate {
                                         .min_w_0()
                                         .child(Label::new(entry.label.clone()).text_color(label_color).text_ellipsis()),
                                 )
+                                .when(expires_soon, |this| {
+                                    this.child(
+                                        Icon::new(CustomIconName::Clock3)
+                                            .xsmall()
+                                            .text_color(cx.theme().warning),
+                                    )
+                                })
+                                .when(!is_folder && self.show_key_memory, |this| {
+                                    let memory = self.key_memory.get(&entry.id).map(|memory| match memory {
+                                        Some(memory) => format_size(*memory, DECIMAL),
+                                        None => "?".to_string(),
+                                    });
+                                    this.when_some(memory, |this, memory| {
+                                        this.child(Label::new(memory).text_xs().text_color(cx.theme().muted_foreground))
+                                    })
+                                })
+                                .when(!is_folder, |this| {
+                                    let is_favorite = self.favorites.contains(&entry.id);
+                                    let key = entry.id.clone();
+                                    let server_state = self.server_state.clone();
+                                    // The pin button of the other keys only shows up on hover
+                                    this.child(
+                                        div()
+                                            .when(!is_favorite, |this| {
+                                                this.invisible()
+                                                    .group_hover(KEY_TREE_ROW_GROUP, |style| style.visible())
+                                            })
+                                            .child(
+                                                Button::new(("key-tree-favorite", ix.row))
+                                                    .ghost()
+                                                    .xsmall()
+                                                    .when_else(
+                                                        is_favorite,
+                                                        |this| {
+                                                            this.icon(Icon::new(IconName::StarFill).text_color(yellow))
+                                                        },
+                                                        |this| this.icon(IconName::Star),
+                                                    )
+                                                    .on_click(move |_, _window, cx| {
+                                                        cx.stop_propagation();
+                                                        server_state.update(cx, |state, cx| {
+                                                            state.toggle_favorite(key.clone(), cx);
+                                                        });
+                                                    }),
+                                            ),
+                                    )
+                                })
                                 .when(show_check_icon, |this| {
                                     let check_icon = if selected {
                                         CustomIconName::SquareCheck
@@ -395,8 +724,19 @@ impl ListDelegate for KeyTreeDelegate {
                                     this.child(Icon::new(check_icon))
                                 })
                                 .when(entry.is_folder, |this| {
-                                    this.child(
-                                        Label::new(entry.children_count.to_string())
+                                    let memory = self
+                                        .show_folder_memory
+                                        .then(|| self.folder_memory.get(&entry.id))
+                                        .flatten();
+                                    this.when_some(memory, |this, memory| {
+                                        this.child(
+                                            Label::new(format!("~{}", format_size(*memory, DECIMAL)))
+                                                .text_xs()
+                                                .text_color(cx.theme().muted_foreground),
+                                        )
+                                    })
+                                    .child(
+                                        Label::new(format_count(entry.key_count))
                                             .text_sm()
                                             .text_color(cx.theme().muted_foreground),
                                     )
@@ -406,16 +746,40 @@ impl ListDelegate for KeyTreeDelegate {
         )
     }
 
-    fn set_selected_index(&mut self, ix: Option<IndexPath>, _window: &mut Window, _cx: &mut Context<ListState<Self>>) {
-        if self.enabled_multiple_selection
-            && let Some(ix) = ix
-            && let Some(item) = self.items.get(ix.row)
+    /// Ctrl/Cmd-click toggles a key in the selection, Shift-click selects the keys
+    /// from the previous row, a plain click clears the selection
+    fn set_selected_index(&mut self, ix: Option<IndexPath>, window: &mut Window, _cx: &mut Context<ListState<Self>>) {
+        let modifiers = window.modifiers();
+        if let Some(ix) = ix
+            && let Some(item) = self.items.get(ix.row).filter(|item| !item.is_folder)
         {
-            let id = &item.id;
-            if self.selected_items.contains(id) {
-                self.selected_items.remove(id);
+            let id = item.id.clone();
+            if self.enabled_multiple_selection || modifiers.secondary() {
+                // The opened key is part of the selection started with Ctrl/Cmd
+                if self.selected_items.is_empty()
+                    && let Some(previous) = self
+                        .selected_index
+                        .and_then(|previous| self.items.get(previous.row))
+                        .filter(|previous| !previous.is_folder && previous.id != id)
+                {
+                    self.selected_items.insert(previous.id.clone());
+                }
+                if !self.selected_items.remove(&id) {
+                    self.selected_items.insert(id);
+                }
+            } else if modifiers.shift
+                && let Some(anchor) = self.selected_index
+            {
+                let start = anchor.row.min(ix.row);
+                let end = anchor.row.max(ix.row).min(self.items.len() - 1);
+                self.selected_items.extend(
+                    self.items[start..=end]
+                        .iter()
+                        .filter(|item| !item.is_folder)
+                        .map(|item| item.id.clone()),
+                );
             } else {
-                self.selected_items.insert(id.clone());
+                self.selected_items.clear();
             }
         }
         self.selected_index = ix;
@@ -436,6 +800,9 @@ pub struct ZedisKeyTree {
 
     auto_refresh_task: Option<Task<()>>,
 
+    /// Pending live search, replaced (and so cancelled) by every keystroke
+    live_search_task: Option<Task<()>>,
+
     state: KeyTreeState,
 
     current_keyword: Entity<SharedString>,
@@ -459,6 +826,26 @@ pub struct ZedisKeyTree {
     _subscriptions: Vec<Subscription>,
 }
 
+/// Placeholder of the keyword input, the pattern mode shows a glob example
+/// Key name of a pasted text, taken as is whatever its separator, without the
+/// surrounding blanks and the quotes added by `redis-cli`.
+fn normalize_pasted_key(text: &str) -> String {
+    let text = text.trim();
+    text.strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .or_else(|| text.strip_prefix('\'').and_then(|text| text.strip_suffix('\'')))
+        .unwrap_or(text)
+        .to_string()
+}
+
+fn keyword_placeholder(query_mode: QueryMode, cx: &App) -> SharedString {
+    match query_mode {
+        QueryMode::Pattern => i18n_key_tree(cx, "pattern_placeholder"),
+        QueryMode::Regex => i18n_key_tree(cx, "regex_placeholder"),
+        _ => i18n_common(cx, "filter_placeholder"),
+    }
+}
+
 impl ZedisKeyTree {
     /// Create a new key tree view with event subscriptions
     ///
@@ -476,12 +863,71 @@ impl ZedisKeyTree {
         }));
         subscriptions.push(
             cx.subscribe(&server_state, |this, server_state, event, cx| match event {
+                ServerEvent::FolderMemoryLoaded => {
+                    let folders = this.key_tree_list_state.read(cx).delegate().folder_ids();
+                    let server_state = server_state.read(cx);
+                    let folder_memory: AHashMap<SharedString, u64> = folders
+                        .into_iter()
+                        .filter_map(|folder| server_state.folder_memory(&folder).map(|memory| (folder, memory)))
+                        .collect();
+                    let changed = this.key_tree_list_state.update(cx, |state, cx| {
+                        let changed = state.delegate().folder_memory != folder_memory;
+                        state.delegate_mut().folder_memory = folder_memory;
+                        cx.notify();
+                        changed
+                    });
+                    // Only rebuild for new sizes, cached ones are emitted again after each rebuild
+                    if changed && this.state.sort_mode == KeySortMode::Memory {
+                        this.update_key_tree(true, cx);
+                    }
+                }
+                ServerEvent::KeyMemoryLoaded => {
+                    let keys = this.key_tree_list_state.read(cx).delegate().key_ids();
+                    let server_state = server_state.read(cx);
+                    let key_memory: AHashMap<SharedString, Option<u64>> = keys
+                        .into_iter()
+                        .filter_map(|key| server_state.key_memory(&key).map(|memory| (key, memory)))
+                        .collect();
+                    let changed = this.key_tree_list_state.update(cx, |state, cx| {
+                        let changed = state.delegate().key_memory != key_memory;
+                        state.delegate_mut().key_memory = key_memory;
+                        cx.notify();
+                        changed
+                    });
+                    if changed && this.state.sort_mode == KeySortMode::Memory {
+                        this.update_key_tree(true, cx);
+                    }
+                }
+                ServerEvent::KeyTtlsLoaded => {
+                    let keys = this.key_tree_list_state.read(cx).delegate().key_ids();
+                    let server_state = server_state.read(cx);
+                    let key_expire_at: AHashMap<SharedString, i64> = keys
+                        .into_iter()
+                        .filter_map(|key| server_state.key_expire_at(&key).map(|expire_at| (key, expire_at)))
+                        .collect();
+                    this.key_tree_list_state.update(cx, |state, cx| {
+                        state.delegate_mut().key_expire_at = key_expire_at;
+                        cx.notify();
+                    });
+                }
+                ServerEvent::KeySeparatorChanged => {
+                    // The expanded folders belong to the previous separator
+                    this.state.expanded_items.clear();
+                    this.save_expanded_items(cx);
+                    this.update_key_tree(true, cx);
+                }
                 ServerEvent::KeyCollapseAll => {
                     this.state.expanded_items.clear();
+                    this.save_expanded_items(cx);
                     this.update_key_tree(true, cx);
                 }
-                ServerEvent::ServerSelected(_) => {
+                ServerEvent::ServerSelected(server_id) => {
                     this.reset(cx);
+                    this.restore_expanded_items(server_id);
+                    this.sync_favorites(cx);
+                }
+                ServerEvent::FavoritesUpdated => {
+                    this.sync_favorites(cx);
                 }
                 ServerEvent::ServerInfoUpdated => {
                     let readonly = server_state.read(cx).readonly();
@@ -493,18 +939,35 @@ impl ZedisKeyTree {
                     if action == &EditorAction::Create {
                         this.should_enter_add_key_mode = Some(true);
                         cx.notify();
+                    } else if action == &EditorAction::RefreshKeys {
+                        this.handle_filter(cx);
                     }
                 }
                 ServerEvent::KeySelected(key) => {
                     this.update_expand(key.clone(), cx);
                 }
+                ServerEvent::KeyRenamed { old, new } => {
+                    this.key_tree_list_state.update(cx, |state, _cx| {
+                        let selected_items = &mut state.delegate_mut().selected_items;
+                        if selected_items.remove(old) {
+                            selected_items.insert(new.clone());
+                        }
+                    });
+                    this.update_expand(new.clone(), cx);
+                }
                 ServerEvent::KeyScanFinished => {
-                    let keys = server_state.read(cx).keys();
-                    let global_state = cx.global::<ZedisGlobalStore>().read(cx);
-                    if keys.len() > global_state.auto_expand_threshold() {
+                    // Restored folders win over the auto expansion
+                    if this.state.expanded_items_restored {
+                        this.prune_expanded_items(cx);
+                        return;
+                    }
+                    let server_state = server_state.read(cx);
+                    let keys = server_state.keys();
+                    if keys.len() > cx.global::<ZedisGlobalStore>().read(cx).auto_expand_threshold() {
                         return;
                     }
-                    let key_separator = global_state.key_separator();
+                    let key_separator = server_state.key_separator(cx);
+                    let key_separator = key_separator.as_str();
                     let mut expanded_items: AHashSet<SharedString> = AHashSet::new();
                     keys.iter().for_each(|(key, _)| {
                         if !key.contains(key_separator) {
@@ -529,6 +992,57 @@ impl ZedisKeyTree {
                 .clean_on_escape()
                 .placeholder(i18n_common(cx, "filter_placeholder"))
         });
+        subscriptions.push(
+            cx.subscribe_in(&server_state, window, |this, _, event, window, cx| match event {
+                ServerEvent::KeyRevealed(key) => {
+                    // Revealed keys must not be hidden by the keyword filter
+                    if !key.contains(this.state.keyword.as_str()) {
+                        this.state.keyword = SharedString::default();
+                        this.keyword_state.update(cx, |state, cx| {
+                            state.set_value(SharedString::default(), window, cx);
+                        });
+                    }
+                    this.state.reveal_key = Some(key.clone());
+                    this.update_expand(key.clone(), cx);
+                }
+                ServerEvent::DuplicatesFound(report) => {
+                    this.open_duplicates_dialog(report.clone(), window, cx);
+                }
+                ServerEvent::ColdKeysFound(report) => {
+                    this.open_cold_keys_dialog(report.clone(), window, cx);
+                }
+                ServerEvent::BulkOperationPreviewed(preview) => {
+                    this.open_bulk_preview_dialog(preview.clone(), window, cx);
+                }
+                ServerEvent::KeyCopyConflict(key, target) => {
+                    this.open_copy_conflict_dialog(key.clone(), target.clone(), window, cx);
+                }
+                ServerEvent::KeyspaceEventsDisabled(flags) => {
+                    this.open_keyspace_events_dialog(flags.clone(), window, cx);
+                }
+                ServerEvent::EditionActionTriggered(EditorAction::DeleteKey) => {
+                    // Typing in the filter keeps its own cmd-backspace
+                    if this.keyword_state.focus_handle(cx).is_focused(window) {
+                        return;
+                    }
+                    if let Some(key) = this.server_state.read(cx).key() {
+                        this.handle_delete_key(key, window, cx);
+                    }
+                }
+                ServerEvent::EditionActionTriggered(EditorAction::RenameKey) => {
+                    if let Some(key) = this.server_state.read(cx).key() {
+                        this.handle_rename_key(key, window, cx);
+                    }
+                }
+                ServerEvent::EditionActionTriggered(EditorAction::CycleQueryMode) => {
+                    this.handle_query_mode(this.state.query_mode.next(), window, cx);
+                }
+                ServerEvent::EditionActionTriggered(EditorAction::QuickOpen) => {
+                    this.handle_quick_open(window, cx);
+                }
+                _ => {}
+            }),
+        );
         // initial focus
         keyword_state.update(cx, |state, cx| {
             state.focus(window, cx);
@@ -538,6 +1052,12 @@ impl ZedisKeyTree {
         let server_id = server_state_value.server_id().to_string();
         let mut query_mode = QueryMode::All;
         let mut refresh_interval_sec = 0;
+        let mut show_folder_memory = false;
+        let mut show_key_memory = false;
+        let mut sort_mode = KeySortMode::default();
+        let mut flat_view = false;
+        let mut live_search = false;
+        let mut expanded_items = AHashSet::with_capacity(EXPANDED_ITEMS_INITIAL_CAPACITY);
         if let Ok(option) = get_session_option(&server_id) {
             query_mode = option
                 .query_mode
@@ -545,15 +1065,39 @@ impl ZedisKeyTree {
                 .and_then(|s| QueryMode::from_str(s).ok())
                 .unwrap_or(QueryMode::All);
             refresh_interval_sec = option.refresh_interval_sec.unwrap_or_default();
+            show_folder_memory = option.show_folder_memory.unwrap_or_default();
+            show_key_memory = option.show_key_memory.unwrap_or_default();
+            sort_mode = option.key_sort_mode.unwrap_or_default();
+            flat_view = option.flat_view.unwrap_or_default();
+            live_search = option.live_search.unwrap_or_default();
+            expanded_items.extend(
+                option
+                    .expanded_items
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(SharedString::from),
+            );
         }
         let readonly = server_state_value.readonly();
+        let favorites = server_state_value.favorites().iter().cloned().collect();
+        let placeholder = keyword_placeholder(query_mode, cx);
+        keyword_state.update(cx, |state, cx| {
+            state.set_placeholder(placeholder, window, cx);
+        });
 
-        // Subscribe to search input events (Enter key triggers filter)
-        subscriptions.push(cx.subscribe_in(&keyword_state, window, |view, _, event, _, cx| {
-            if let InputEvent::PressEnter { .. } = &event {
-                view.handle_filter(cx);
-            }
-        }));
+        // Subscribe to search input events (Enter key triggers filter, typing too in live search)
+        subscriptions.push(
+            cx.subscribe_in(&keyword_state, window, |view, _, event, _, cx| match event {
+                InputEvent::PressEnter { .. } => {
+                    view.live_search_task = None;
+                    view.handle_filter(cx);
+                }
+                InputEvent::Change if view.state.live_search => {
+                    view.schedule_live_search(cx);
+                }
+                _ => {}
+            }),
+        );
 
         info!(server_id, "Creating new key tree view");
 
@@ -563,6 +1107,13 @@ impl ZedisKeyTree {
             selected_index: None,
             selected_items: AHashSet::with_capacity(5),
             readonly,
+            show_folder_memory,
+            folder_memory: AHashMap::new(),
+            show_key_memory,
+            key_memory: AHashMap::new(),
+            key_expire_at: AHashMap::new(),
+            favorites,
+            server_state: server_state.clone(),
         };
         let key_tree_list_state = cx.new(|cx| ListState::new(delegate, window, cx));
         subscriptions.push(cx.subscribe(&key_tree_list_state, |view, _, event, cx| match event {
@@ -581,7 +1132,13 @@ impl ZedisKeyTree {
                 query_mode,
                 server_id: server_id.into(),
                 refresh_interval_sec,
-                expanded_items: AHashSet::with_capacity(EXPANDED_ITEMS_INITIAL_CAPACITY),
+                show_folder_memory,
+                show_key_memory,
+                sort_mode,
+                flat_view,
+                live_search,
+                expanded_items_restored: !expanded_items.is_empty(),
+                expanded_items,
                 ..Default::default()
             },
             current_keyword: cx.new(|_cx| SharedString::default()),
@@ -590,6 +1147,7 @@ impl ZedisKeyTree {
             server_state,
             should_enter_add_key_mode: None,
             auto_refresh_task: None,
+            live_search_task: None,
             _subscriptions: subscriptions,
         };
 
@@ -625,18 +1183,73 @@ impl ZedisKeyTree {
     fn reset(&mut self, _cx: &mut Context<Self>) {
         self.state = KeyTreeState::default();
     }
+    /// Copy the pinned keys of the server state to the tree rows
+    fn sync_favorites(&mut self, cx: &mut Context<Self>) {
+        let favorites = self.server_state.read(cx).favorites().iter().cloned().collect();
+        self.key_tree_list_state.update(cx, |state, cx| {
+            state.delegate_mut().favorites = favorites;
+            cx.notify();
+        });
+        cx.notify();
+    }
+    /// Restore the expanded folders saved in the session options of the server
+    fn restore_expanded_items(&mut self, server_id: &str) {
+        let items = get_session_option(server_id)
+            .ok()
+            .and_then(|option| option.expanded_items)
+            .unwrap_or_default();
+        self.state.expanded_items_restored = !items.is_empty();
+        self.state.expanded_items = items.into_iter().map(SharedString::from).collect();
+    }
+    /// Remember the expanded folders in the session options of the server
+    fn save_expanded_items(&self, cx: &mut Context<Self>) {
+        let server_id = self.server_state.read(cx).server_id();
+        if let Ok(mut option) = get_session_option(server_id) {
+            let mut items: Vec<String> = self.state.expanded_items.iter().map(|item| item.to_string()).collect();
+            items.sort_unstable();
+            items.truncate(MAX_SAVED_EXPANDED_ITEMS);
+            option.expanded_items = Some(items);
+            save_session_option(server_id, option, cx);
+        }
+    }
+    /// Drop the restored folders that no longer exist once the first full scan is done
+    fn prune_expanded_items(&mut self, cx: &mut Context<Self>) {
+        // Prefix scans also finish with `KeyScanFinished`, only a complete scan of every key
+        // lists every folder. Filtered scans don't either, the folders are kept until one runs
+        let server_state = self.server_state.read(cx);
+        if !server_state.scan_completed() || server_state.key_type_filter().is_some() || !self.state.keyword.is_empty()
+        {
+            return;
+        }
+        self.state.expanded_items_restored = false;
+        let separator = self.server_state.read(cx).key_separator(cx).to_string();
+        let mut folders: AHashSet<&str> = AHashSet::new();
+        for key in self.server_state.read(cx).keys().keys() {
+            let mut end = 0;
+            while let Some(index) = key[end..].find(separator.as_str()) {
+                end += index;
+                folders.insert(&key[..end]);
+                end += separator.len();
+            }
+        }
+        let count = self.state.expanded_items.len();
+        self.state.expanded_items.retain(|item| folders.contains(item.as_str()));
+        if self.state.expanded_items.len() != count {
+            self.save_expanded_items(cx);
+            self.update_key_tree(true, cx);
+        }
+    }
     fn reset_expand(&mut self, _cx: &mut Context<Self>) {
+        // Keep the restored folders until the first scan has loaded the keys
+        if self.state.expanded_items_restored {
+            return;
+        }
         self.state.expanded_items.clear();
         self.state.scroll_to_index = Some(IndexPath::new(0));
     }
     fn update_expand(&mut self, selected_key: SharedString, cx: &mut Context<Self>) {
-        let (separator, max_depth) = {
-            let global_state = cx.global::<ZedisGlobalStore>().read(cx);
-            (
-                global_state.key_separator().to_string(),
-                global_state.max_key_tree_depth(),
-            )
-        };
+        let separator = self.server_state.read(cx).key_separator(cx);
+        let max_depth = cx.global::<ZedisGlobalStore>().read(cx).max_key_tree_depth();
         if !selected_key.contains(separator.as_str()) {
             return;
         }
@@ -663,6 +1276,7 @@ impl ZedisKeyTree {
         );
 
         self.state.query_mode = server_state.query_mode();
+        let key_type_filter = server_state.key_type_filter();
 
         // Skip rebuild if tree ID hasn't changed (same keys)
         if !force_update && self.state.key_tree_id == key_tree_id {
@@ -673,21 +1287,43 @@ impl ZedisKeyTree {
         // Auto-expand all folders if key count is small
         let keys_snapshot: Vec<(SharedString, KeyType)> =
             server_state.keys().iter().map(|(k, v)| (k.clone(), *v)).collect();
+        let separator = server_state.key_separator(cx).to_string();
         let readonly = server_state.readonly();
         let expanded_items = self.state.expanded_items.clone();
 
         let view_handle = cx.entity().downgrade();
-        let keyword = self.state.keyword.clone();
+        // Pattern and regex keywords are already matched while scanning
+        let keyword = match self.state.query_mode {
+            QueryMode::Pattern | QueryMode::Regex => SharedString::default(),
+            _ => self.state.keyword.clone(),
+        };
+        let show_folder_memory = self.state.show_folder_memory;
+        let show_key_memory = self.state.show_key_memory;
+        let flat_view = self.state.flat_view;
+        let sort = self.new_tree_sort(cx);
+        let server_state_handle = self.server_state.clone();
+        let reveal_key = self.state.reveal_key.clone();
 
         self.key_tree_list_state.update(cx, move |_state, cx| {
-            let app_state = cx.global::<ZedisGlobalStore>().value(cx);
-            let separator = app_state.key_separator().to_string();
-            let max_key_tree_depth = app_state.max_key_tree_depth();
+            let max_key_tree_depth = cx.global::<ZedisGlobalStore>().read(cx).max_key_tree_depth();
+            let show_ttl_warning = cx.global::<ZedisGlobalStore>().read(cx).ttl_warning_threshold() > 0;
             cx.spawn(async move |handle, cx| {
                 let task = cx.background_spawn(async move {
                     let start = std::time::Instant::now();
-                    let items =
-                        new_key_tree_items(keys_snapshot, keyword, expanded_items, &separator, max_key_tree_depth);
+                    // The flat list has no folders, so the expanded ones don't matter
+                    let items = if flat_view {
+                        new_flat_key_items(keys_snapshot, keyword, key_type_filter, sort)
+                    } else {
+                        new_key_tree_items(
+                            keys_snapshot,
+                            keyword,
+                            key_type_filter,
+                            expanded_items,
+                            &separator,
+                            max_key_tree_depth,
+                            sort,
+                        )
+                    };
                     tracing::debug!("Key tree build time: {:?}", start.elapsed());
                     items
                 });
@@ -697,11 +1333,156 @@ impl ZedisKeyTree {
                     let _ = view_handle.update(cx, |view: &mut ZedisKeyTree, cx| {
                         view.reset_expand(cx);
                     });
+                } else if let Some(reveal_key) = reveal_key
+                    && let Some(index) = result.iter().position(|item| item.id == reveal_key)
+                {
+                    let _ = view_handle.update(cx, |view: &mut ZedisKeyTree, cx| {
+                        view.state.reveal_key = None;
+                        view.state.scroll_to_index = Some(IndexPath::new(index));
+                        cx.notify();
+                    });
                 }
                 handle.update(cx, |this, cx| {
                     this.delegate_mut().selected_items.clear();
                     this.delegate_mut().items = result;
                     this.delegate_mut().readonly = readonly;
+                    if show_folder_memory {
+                        let folders = this.delegate().folder_ids();
+                        server_state_handle.update(cx, |state, cx| {
+                            state.load_folder_memory(folders, cx);
+                        });
+                    }
+                    if show_key_memory {
+                        let keys = this.delegate().key_ids();
+                        server_state_handle.update(cx, |state, cx| {
+                            state.load_key_memory(keys, cx);
+                        });
+                    }
+                    if show_ttl_warning {
+                        let keys = this.delegate().key_ids();
+                        server_state_handle.update(cx, |state, cx| {
+                            state.load_key_ttls(keys, cx);
+                        });
+                    }
+                    // Folders fill the types of their keys when expanded, the flat list shows them all
+                    if flat_view {
+                        let keys: Vec<SharedString> = this
+                            .delegate()
+                            .items
+                            .iter()
+                            .filter(|item| item.key_type == KeyType::Unknown)
+                            .map(|item| item.id.clone())
+                            .collect();
+                        server_state_handle.update(cx, |state, cx| {
+                            state.load_key_types(keys, cx);
+                        });
+                    }
+                    cx.notify();
+                })
+            })
+            .detach();
+        });
+    }
+
+    /// Sort of the tree, with a snapshot of the loaded sizes when sorting by memory usage
+    fn new_tree_sort(&self, cx: &App) -> TreeSort {
+        let mode = self.state.sort_mode;
+        let memory = (self.state.show_key_memory && mode == KeySortMode::Memory).then(|| {
+            let delegate = self.key_tree_list_state.read(cx).delegate();
+            TreeMemory {
+                folders: delegate.folder_memory.clone(),
+                keys: delegate
+                    .key_memory
+                    .iter()
+                    .filter_map(|(key, memory)| memory.map(|memory| (key.clone(), memory)))
+                    .collect(),
+            }
+        });
+        TreeSort { mode, memory }
+    }
+
+    /// Show or hide the rows beneath a folder without rebuilding the whole tree
+    ///
+    /// Collapsing drops the rows of the folder. Expanding builds the rows from the
+    /// keys under the folder only and splices them after the folder row, the other
+    /// rows keep their order and allocations. Keys loaded afterwards by the scan of
+    /// the folder still go through `update_key_tree`.
+    fn update_folder_rows(&mut self, folder: SharedString, cx: &mut Context<Self>) {
+        let server_state = self.server_state.read(cx);
+        let separator = server_state.key_separator(cx).to_string();
+        let prefix = format!("{folder}{separator}");
+        if !self.state.expanded_items.contains(&folder) {
+            self.key_tree_list_state.update(cx, |state, cx| {
+                collapse_folder_rows(&mut state.delegate_mut().items, &folder, &prefix);
+                cx.notify();
+            });
+            return;
+        }
+
+        let keys: Vec<(SharedString, KeyType)> = server_state
+            .keys()
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix.as_str()))
+            .map(|(key, key_type)| (key.clone(), *key_type))
+            .collect();
+        let key_type_filter = server_state.key_type_filter();
+        let keyword = match self.state.query_mode {
+            QueryMode::Pattern | QueryMode::Regex => SharedString::default(),
+            _ => self.state.keyword.clone(),
+        };
+        let expanded_items = self.state.expanded_items.clone();
+        let max_key_tree_depth = cx.global::<ZedisGlobalStore>().read(cx).max_key_tree_depth();
+        let sort = self.new_tree_sort(cx);
+        let show_folder_memory = self.state.show_folder_memory;
+        let show_key_memory = self.state.show_key_memory;
+        let show_ttl_warning = cx.global::<ZedisGlobalStore>().read(cx).ttl_warning_threshold() > 0;
+        let server_state_handle = self.server_state.clone();
+
+        self.key_tree_list_state.update(cx, move |_state, cx| {
+            cx.spawn(async move |handle, cx| {
+                let rows = cx
+                    .background_spawn(async move {
+                        // The ancestors of the folder are built as well, only its descendants are kept
+                        new_key_tree_items(
+                            keys,
+                            keyword,
+                            key_type_filter,
+                            expanded_items,
+                            &separator,
+                            max_key_tree_depth,
+                            sort,
+                        )
+                        .into_iter()
+                        .filter(|item| item.id.starts_with(prefix.as_str()))
+                        .collect::<Vec<_>>()
+                    })
+                    .await;
+                handle.update(cx, |this, cx| {
+                    let folders: Vec<SharedString> = rows
+                        .iter()
+                        .filter(|item| item.is_folder)
+                        .map(|item| item.id.clone())
+                        .collect();
+                    let keys: Vec<SharedString> = rows
+                        .iter()
+                        .filter(|item| !item.is_folder)
+                        .map(|item| item.id.clone())
+                        .collect();
+                    // The tree may have been rebuilt while the rows were built
+                    if !expand_folder_rows(&mut this.delegate_mut().items, &folder, rows) {
+                        return;
+                    }
+                    server_state_handle.update(cx, |state, cx| {
+                        if show_folder_memory {
+                            state.load_folder_memory(folders, cx);
+                        }
+                        if show_key_memory {
+                            state.load_key_memory(keys.clone(), cx);
+                        }
+                        if show_ttl_warning {
+                            state.load_key_ttls(keys, cx);
+                        }
+                    });
                     cx.notify();
                 })
             })
@@ -714,6 +1495,35 @@ impl ZedisKeyTree {
     /// Delegates to server state to perform the actual filtering based on
     /// current query mode. Ignores if a scan is already in progress.
     fn handle_filter(&mut self, cx: &mut Context<Self>) {
+        self.filter_keys(true, cx);
+    }
+    /// Filter once the typing pauses for `LIVE_SEARCH_DEBOUNCE`
+    ///
+    /// Each keystroke replaces the pending task, so rapid typing only scans once.
+    /// A running scan is not interrupted, the search waits until it is done.
+    fn schedule_live_search(&mut self, cx: &mut Context<Self>) {
+        self.live_search_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(LIVE_SEARCH_DEBOUNCE).await;
+            loop {
+                let Ok(scanning) = this.update(cx, |this, cx| this.server_state.read(cx).scanning()) else {
+                    return;
+                };
+                if !scanning {
+                    break;
+                }
+                cx.background_executor().timer(LIVE_SEARCH_DEBOUNCE).await;
+            }
+            let _ = this.update(cx, |this, cx| {
+                let keyword = this.keyword_state.read(cx).value();
+                // Unchanged, e.g. typed and erased again
+                if keyword != this.state.keyword {
+                    // Only the searches submitted with Enter are remembered
+                    this.filter_keys(false, cx);
+                }
+            });
+        }));
+    }
+    fn filter_keys(&mut self, save_history: bool, cx: &mut Context<Self>) {
         // Don't trigger filter while already scanning
         let server_state_clone = self.server_state.clone();
         let server_state = self.server_state.read(cx);
@@ -722,56 +1532,192 @@ impl ZedisKeyTree {
         }
 
         let keyword = self.keyword_state.read(cx).value();
+        if self.state.query_mode == QueryMode::Regex
+            && let Err(e) = Regex::new(&keyword)
+        {
+            self.state.error = Some(format!("{}: {e}", i18n_key_tree(cx, "invalid_regex")).into());
+            cx.notify();
+            return;
+        }
+        self.state.error = None;
         self.state.keyword = keyword.clone();
 
         let server_id_clone = server_state.server_id().to_string();
         let keyword_clone = keyword.clone();
         self.current_keyword
             .update(cx, |state, _cx| *state = keyword_clone.clone());
-        cx.spawn(async move |_, cx| {
-            let result = cx
-                .background_spawn(async move {
-                    let search_history_manager = get_search_history_manager();
-                    search_history_manager.add_record(server_id_clone.as_str(), keyword_clone.as_str())
-                })
-                .await;
-            if let Ok(history) = result {
-                server_state_clone.update(cx, |state, _cx| {
-                    state.set_search_history(history);
-                });
-            }
-        })
-        .detach();
+        if save_history {
+            cx.spawn(async move |_, cx| {
+                let result = cx
+                    .background_spawn(async move {
+                        let search_history_manager = get_search_history_manager();
+                        search_history_manager.add_record(server_id_clone.as_str(), keyword_clone.as_str())
+                    })
+                    .await;
+                if let Ok(history) = result {
+                    server_state_clone.update(cx, |state, _cx| {
+                        state.set_search_history(history);
+                    });
+                }
+            })
+            .detach();
+        }
         self.server_state.update(cx, move |handle, cx| {
             handle.handle_filter(keyword, cx);
         });
     }
-    fn handle_clear_history(&mut self, cx: &mut Context<Self>) {
-        let server_state = self.server_state.read(cx);
-        let server_id = server_state.server_id().to_string();
+    /// Switch the query mode and remember it in the session options
+    fn set_query_mode(&mut self, new_mode: QueryMode, cx: &mut Context<Self>) {
+        let server_id = self.server_state.read(cx).server_id();
+        if let Ok(mut option) = get_session_option(server_id) {
+            option.query_mode = Some(new_mode.to_string());
+            save_session_option(server_id, option, cx);
+        }
+
+        // Step 1: Update server state with new query mode
         self.server_state.update(cx, |state, cx| {
-            state.clear_search_history(cx);
+            state.set_query_mode(new_mode, cx);
         });
-        cx.spawn(async move |_, cx| {
-            let _ = cx
-                .background_spawn(async move {
-                    let search_history_manager = get_search_history_manager();
-                    let _ = search_history_manager.clear_history(server_id.as_str());
-                })
-                .await;
-        })
-        .detach();
+
+        // Step 2: Update local UI state
+        self.state.query_mode = new_mode;
+        self.state.error = None;
+    }
+    /// Switch the query mode from the menu or the shortcut, updating the filter placeholder
+    fn handle_query_mode(&mut self, mode: QueryMode, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_query_mode(mode, cx);
+        let placeholder = keyword_placeholder(mode, cx);
+        self.keyword_state.update(cx, |state, cx| {
+            state.set_placeholder(placeholder, window, cx);
+        });
+        if mode == QueryMode::Regex {
+            window.push_notification(Notification::warning(i18n_key_tree(cx, "regex_full_scan_tips")), cx);
+        }
     }
+    /// Ask for the exact name of a key and open it, without a scan of the whole keyspace
+    fn handle_quick_open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let fields = vec![
+            ZedisFormField::new("key", i18n_key_tree(cx, "quick_open_key"))
+                .placeholder(i18n_key_tree(cx, "quick_open_placeholder"))
+                .required()
+                .focus(),
+        ];
+        let key_tree = cx.entity();
 
-    fn handle_add_key(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let prefix: Option<SharedString> = if let Some(key) = self.server_state.read(cx).key()
-            && let Some((prefix, _)) = key.rsplit_once(":")
-        {
-            Some(format!("{prefix}:").into())
+        ZedisFormOptions::new(fields)
+            .title(i18n_key_tree(cx, "quick_open_title"))
+            .confirm_label(i18n_common(cx, "confirm"))
+            .cancel_label(i18n_common(cx, "cancel"))
+            .on_dialog_submit(move |values, _window, cx| {
+                let key = normalize_pasted_key(values.get("key").map(|value| value.as_str()).unwrap_or_default());
+                if key.is_empty() {
+                    return false;
+                }
+                key_tree.update(cx, |this, cx| {
+                    this.open_exact_key(key.into(), cx);
+                });
+                true
+            })
+            .open_dialog(window, cx);
+    }
+    /// Reveal the key in the tree and select it, the query mode and the scanned keys are kept
+    fn open_exact_key(&mut self, key: SharedString, cx: &mut Context<Self>) {
+        self.server_state.update(cx, |state, cx| {
+            state.reveal_key(key, cx);
+        });
+    }
+    /// Ask for confirmation before deleting the selected keys in one batch
+    fn handle_delete_selected_keys(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let mut keys: Vec<SharedString> = self
+            .key_tree_list_state
+            .read(cx)
+            .delegate()
+            .selected_items
+            .iter()
+            .cloned()
+            .collect();
+        if keys.is_empty() {
+            return;
+        }
+        keys.sort_unstable();
+        let server_state = self.server_state.clone();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let text = t!(
+            "key_tree.delete_keys_prompt",
+            count = keys.len(),
+            keys = keys.join(", "),
+            locale = locale
+        )
+        .to_string();
+
+        ZedisDialog::new_alert(i18n_key_tree(cx, "delete_keys_title"), text)
+            .button_props(dialog_button_props(cx))
+            .on_ok(move |_, _, cx| {
+                server_state.update(cx, |state, cx| {
+                    state.unlink_key(keys.clone(), cx);
+                });
+                true
+            })
+            .open(window, cx);
+    }
+    /// Clear the selected keys, returns whether there was a selection
+    fn clear_selected_keys(&mut self, cx: &mut Context<Self>) -> bool {
+        self.key_tree_list_state.update(cx, |state, cx| {
+            let selected_items = &mut state.delegate_mut().selected_items;
+            if selected_items.is_empty() {
+                return false;
+            }
+            selected_items.clear();
+            cx.notify();
+            true
+        })
+    }
+    /// Ask for confirmation before deleting the key
+    fn handle_delete_key(&mut self, id: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let text = t!("key_tree.delete_key_prompt", key = id.clone(), locale = locale).to_string();
+
+        ZedisDialog::new_alert(i18n_key_tree(cx, "delete_key_title"), text)
+            .button_props(dialog_button_props(cx))
+            .on_ok(move |_, _, cx| {
+                server_state.update(cx, |state, cx| {
+                    state.delete_key(id.clone(), cx);
+                });
+                true
+            })
+            .open(window, cx);
+    }
+    fn handle_clear_history(&mut self, cx: &mut Context<Self>) {
+        let server_state = self.server_state.read(cx);
+        let server_id = server_state.server_id().to_string();
+        self.server_state.update(cx, |state, cx| {
+            state.clear_search_history(cx);
+        });
+        cx.spawn(async move |_, cx| {
+            let _ = cx
+                .background_spawn(async move {
+                    let search_history_manager = get_search_history_manager();
+                    let _ = search_history_manager.clear_history(server_id.as_str());
+                })
+                .await;
+        })
+        .detach();
+    }
+
+    fn handle_add_key(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let prefix: Option<SharedString> = if let Some(key) = self.server_state.read(cx).key()
+            && let Some((prefix, _)) = key.rsplit_once(":")
+        {
+            Some(format!("{prefix}:").into())
         } else {
             None
         };
         let category_list = ["String", "List", "Set", "Zset", "Hash", "Stream"];
+        // TTL unit indices follow TTL_UNITS, the last one means no expiry
+        let no_expiry_index = TTL_UNITS.len();
+        let mut ttl_unit_options: Vec<SharedString> = TTL_UNITS.iter().map(|(name, _)| i18n_common(cx, name)).collect();
+        ttl_unit_options.push(i18n_common(cx, "no_expiry"));
         // Category indices: String=0, List=1, Set=2, Zset=3, Hash=4, Stream=5
         let fields = vec![
             ZedisFormField::new("category", i18n_key_tree(cx, "category"))
@@ -797,7 +1743,11 @@ impl ZedisKeyTree {
                     } else {
                         Some("Invalid TTL".into())
                     }
-                }),
+                })
+                .visible_on("ttl_unit", &(0..no_expiry_index).collect::<Vec<_>>()),
+            ZedisFormField::new("ttl_unit", i18n_common(cx, "ttl_unit"))
+                .field_type(ZedisFormFieldType::RadioGroup)
+                .options(ttl_unit_options),
             // Value field for String, List, Set
             ZedisFormField::new("value", i18n_common(cx, "value"))
                 .placeholder(i18n_common(cx, "value_placeholder"))
@@ -834,14 +1784,35 @@ impl ZedisKeyTree {
             .support_add_fields_on("category", &[5])
             .add_field_placeholder(i18n_common(cx, "field_placeholder"))
             .add_value_placeholder(i18n_common(cx, "value_placeholder"))
-            .on_dialog_submit(move |values, _window, cx| {
+            .on_dialog_submit(move |values, window, cx| {
                 let category_index = values
                     .get("category")
                     .and_then(|v| v.parse::<usize>().ok())
                     .unwrap_or(0);
                 let category = category_list.get(category_index).cloned().unwrap_or_default();
                 let key = values.get("key").cloned().unwrap_or_default();
-                let ttl = values.get("ttl").cloned().unwrap_or_default();
+                let ttl_unit = values
+                    .get("ttl_unit")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(0);
+                // Normalize the TTL to seconds, plain numbers are counted in the selected unit
+                let ttl_secs = match (values.get("ttl").map(|ttl| ttl.trim()), TTL_UNITS.get(ttl_unit)) {
+                    (Some(ttl), Some((_, unit_secs))) if !ttl.is_empty() => match parse_duration(ttl) {
+                        Ok(duration) if ttl.parse::<u64>().is_ok() => duration.as_secs().saturating_mul(*unit_secs),
+                        Ok(duration) => duration.as_secs(),
+                        Err(e) => {
+                            window.push_notification(Notification::error(e.to_string()), cx);
+                            return false;
+                        }
+                    },
+                    _ => 0,
+                };
+                // Hidden (no expiry), empty or zero means no TTL, EXPIRE 0 would delete the new key
+                let ttl: SharedString = if ttl_secs == 0 {
+                    SharedString::default()
+                } else {
+                    ttl_secs.to_string().into()
+                };
 
                 let key_type = KeyType::from(category.to_lowercase().as_str());
                 let seed = key_type.seed_args();
@@ -872,6 +1843,7 @@ impl ZedisKeyTree {
                             "category",
                             "key",
                             "ttl",
+                            "ttl_unit",
                             "value",
                             "score",
                             "member",
@@ -910,6 +1882,626 @@ impl ZedisKeyTree {
         });
     }
 
+    /// Opens a dialog to rename a key, an existing key is only replaced when overwrite is checked.
+    fn handle_rename_key(&mut self, key: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let fields = vec![
+            ZedisFormField::new("key", i18n_key_tree(cx, "new_key_name"))
+                .default_value(key.clone())
+                .required()
+                .focus()
+                .validate(move |s| {
+                    if validate_long_string(s) {
+                        None
+                    } else {
+                        Some("Too long".into())
+                    }
+                }),
+            ZedisFormField::new("overwrite", i18n_key_tree(cx, "rename_overwrite"))
+                .placeholder(i18n_key_tree(cx, "rename_overwrite_label"))
+                .field_type(ZedisFormFieldType::Checkbox),
+        ];
+        let server_state = self.server_state.clone();
+
+        ZedisFormOptions::new(fields)
+            .title(i18n_key_tree(cx, "rename_key_title"))
+            .confirm_label(i18n_common(cx, "confirm"))
+            .cancel_label(i18n_common(cx, "cancel"))
+            .on_dialog_submit(move |values, _window, cx| {
+                let new_key: SharedString = values.get("key").cloned().unwrap_or_default().into();
+                let overwrite = values.get("overwrite").is_some_and(|v| v == "true");
+                let old_key = key.clone();
+                server_state.update(cx, |state, cx| {
+                    state.rename_key(old_key, new_key, overwrite, cx);
+                });
+                true
+            })
+            .open_dialog(window, cx);
+    }
+
+    /// Ask for the name of the copy, whether an existing key is replaced and whether the copy is opened
+    fn handle_duplicate_key(&mut self, key: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let fields = vec![
+            ZedisFormField::new("key", i18n_key_tree(cx, "new_key_name"))
+                .default_value(format!("{key}_copy"))
+                .required()
+                .focus()
+                .validate(move |s| {
+                    if validate_long_string(s) {
+                        None
+                    } else {
+                        Some("Too long".into())
+                    }
+                }),
+            ZedisFormField::new("overwrite", i18n_key_tree(cx, "rename_overwrite"))
+                .placeholder(i18n_key_tree(cx, "rename_overwrite_label"))
+                .field_type(ZedisFormFieldType::Checkbox),
+            ZedisFormField::new("select", i18n_key_tree(cx, "duplicate_key_select"))
+                .placeholder(i18n_key_tree(cx, "duplicate_key_select_label"))
+                .default_value("true")
+                .field_type(ZedisFormFieldType::Checkbox),
+        ];
+        let server_state = self.server_state.clone();
+
+        ZedisFormOptions::new(fields)
+            .title(i18n_key_tree(cx, "duplicate_key"))
+            .confirm_label(i18n_common(cx, "confirm"))
+            .cancel_label(i18n_common(cx, "cancel"))
+            .on_dialog_submit(move |values, _window, cx| {
+                let new_key: SharedString = values.get("key").cloned().unwrap_or_default().into();
+                let overwrite = values.get("overwrite").is_some_and(|v| v == "true");
+                let select = values.get("select").is_some_and(|v| v == "true");
+                let key = key.clone();
+                server_state.update(cx, |state, cx| {
+                    state.duplicate_key(key, new_key, overwrite, select, cx);
+                });
+                true
+            })
+            .open_dialog(window, cx);
+    }
+    /// Ask for the server and db to copy the key to
+    fn handle_copy_to_server(&mut self, key: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let servers = get_servers().unwrap_or_default();
+        if servers.is_empty() {
+            return;
+        }
+        let fields = vec![
+            ZedisFormField::new("server", i18n_key_tree(cx, "copy_to_server_target"))
+                .field_type(ZedisFormFieldType::RadioGroup)
+                .options(servers.iter().map(|server| server.name.clone().into()).collect()),
+            ZedisFormField::new("db", i18n_key_tree(cx, "copy_to_server_db"))
+                .field_type(ZedisFormFieldType::InputNumber)
+                .default_value("0")
+                .required(),
+        ];
+        let server_state = self.server_state.clone();
+
+        ZedisFormOptions::new(fields)
+            .title(i18n_key_tree(cx, "copy_to_server"))
+            .confirm_label(i18n_common(cx, "confirm"))
+            .cancel_label(i18n_common(cx, "cancel"))
+            .on_dialog_submit(move |values, _window, cx| {
+                let index = values
+                    .get("server")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or_default();
+                let Some(server) = servers.get(index) else {
+                    return false;
+                };
+                let Some(db) = values.get("db").and_then(|v| v.parse::<usize>().ok()) else {
+                    return false;
+                };
+                let target = CopyTarget {
+                    server_id: server.id.clone().into(),
+                    name: server.name.clone().into(),
+                    db,
+                };
+                let key = key.clone();
+                server_state.update(cx, |state, cx| {
+                    state.copy_key_to_server(key, target, false, cx);
+                });
+                true
+            })
+            .open_dialog(window, cx);
+    }
+    /// Ask whether the existing key on the target server is replaced
+    fn open_copy_conflict_dialog(
+        &mut self,
+        key: SharedString,
+        target: CopyTarget,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let server_state = self.server_state.clone();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let text = t!(
+            "key_tree.copy_to_server_conflict",
+            key = key,
+            server = target.name,
+            db = target.db,
+            locale = locale
+        )
+        .to_string();
+
+        ZedisDialog::new_alert(i18n_key_tree(cx, "copy_to_server"), text)
+            .button_props(dialog_button_props(cx))
+            .on_ok(move |_, _, cx| {
+                let key = key.clone();
+                let target = target.clone();
+                server_state.update(cx, |state, cx| {
+                    state.copy_key_to_server(key, target, true, cx);
+                });
+                true
+            })
+            .open(window, cx);
+    }
+    /// Offer to enable the key events, the watcher receives nothing without them
+    fn open_keyspace_events_dialog(&mut self, flags: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        if self.server_state.read(cx).readonly() {
+            window.push_notification(
+                Notification::warning(i18n_key_tree(cx, "keyspace_events_disabled_readonly")),
+                cx,
+            );
+            return;
+        }
+        let server_state = self.server_state.clone();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let current = if flags.is_empty() { "\"\"" } else { flags.as_str() };
+        let text = t!("key_tree.keyspace_events_prompt", flags = current, locale = locale).to_string();
+
+        ZedisDialog::new_alert(i18n_key_tree(cx, "watch_key_events"), text)
+            .button_props(dialog_button_props(cx))
+            .on_ok(move |_, _, cx| {
+                let flags = flags.clone();
+                server_state.update(cx, |state, cx| {
+                    state.enable_keyspace_events(flags, cx);
+                });
+                true
+            })
+            .open(window, cx);
+    }
+    /// Ask where to save the keys of the folder `id`, then export them
+    fn handle_export_prefix(&mut self, id: SharedString, cx: &mut Context<Self>) {
+        let separator = self.server_state.read(cx).key_separator(cx).to_string();
+        let prefix: SharedString = format!("{id}{separator}").into();
+        let dir = get_home_dir().unwrap_or_default();
+        let path = cx.prompt_for_new_path(&dir, Some(&format!("{}.json", to_file_name(&id))));
+        let server_state = self.server_state.clone();
+        cx.spawn(async move |_, cx| {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            server_state.update(cx, |state, cx| {
+                state.export_prefix(prefix, path, cx);
+            });
+        })
+        .detach();
+    }
+    /// Ask for the TTL of the keys of the folder `id`, the keys are listed before it is set
+    fn handle_expire_folder(&mut self, id: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let fields = vec![
+            ZedisFormField::new("ttl", i18n_common(cx, "ttl"))
+                .placeholder(i18n_common(cx, "ttl_placeholder"))
+                .focus()
+                .validate(move |s| {
+                    if validate_ttl(s) {
+                        None
+                    } else {
+                        Some("Invalid TTL".into())
+                    }
+                }),
+        ];
+        let server_state = self.server_state.clone();
+
+        ZedisFormOptions::new(fields)
+            .title(i18n_key_tree(cx, "set_folder_ttl"))
+            .description(i18n_key_tree(cx, "set_folder_ttl_tips"))
+            .confirm_label(i18n_common(cx, "confirm"))
+            .cancel_label(i18n_common(cx, "cancel"))
+            .on_dialog_submit(move |values, window, cx| {
+                // Plain numbers are seconds, empty or zero removes the TTL
+                let ttl = match values.get("ttl").map(|ttl| ttl.trim()) {
+                    Some(ttl) if !ttl.is_empty() => match parse_duration(ttl) {
+                        Ok(ttl) => ttl,
+                        Err(e) => {
+                            window.push_notification(Notification::error(e.to_string()), cx);
+                            return false;
+                        }
+                    },
+                    _ => Duration::ZERO,
+                };
+                let operation = BulkOperation::ExpireFolder(id.clone(), ttl);
+                server_state.update(cx, |state, cx| {
+                    state.preview_bulk_operation(operation, cx);
+                });
+                true
+            })
+            .open_dialog(window, cx);
+    }
+    /// Ask whether existing keys are skipped or overwritten, then pick the export file to import
+    fn handle_import_keys(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let fields = vec![
+            ZedisFormField::new("mode", i18n_key_tree(cx, "import_keys_mode"))
+                .field_type(ZedisFormFieldType::RadioGroup)
+                .options(vec![
+                    i18n_key_tree(cx, "import_keys_skip"),
+                    i18n_key_tree(cx, "import_keys_overwrite"),
+                ]),
+        ];
+        let server_state = self.server_state.clone();
+
+        ZedisFormOptions::new(fields)
+            .title(i18n_key_tree(cx, "import_keys"))
+            .description(i18n_key_tree(cx, "import_keys_tips"))
+            .confirm_label(i18n_common(cx, "confirm"))
+            .cancel_label(i18n_common(cx, "cancel"))
+            .on_dialog_submit(move |values, _window, cx| {
+                let overwrite = values.get("mode").is_some_and(|v| v == "1");
+                let paths = cx.prompt_for_paths(PathPromptOptions {
+                    files: true,
+                    directories: false,
+                    multiple: false,
+                    prompt: None,
+                });
+                let server_state = server_state.clone();
+                cx.spawn(async move |cx| {
+                    let Ok(Ok(Some(paths))) = paths.await else {
+                        return;
+                    };
+                    let Some(path) = paths.into_iter().next() else {
+                        return;
+                    };
+                    // The confirmation lists the keys of the file and how many already exist
+                    server_state.update(cx, |state, cx| {
+                        state.preview_bulk_operation(BulkOperation::ImportKeys(path, overwrite), cx);
+                    });
+                })
+                .detach();
+                true
+            })
+            .open_dialog(window, cx);
+    }
+    /// Opens a dialog to create keys from pasted write commands (SET, HSET, ...).
+    ///
+    /// The commands are parsed and validated first, then listed in a confirmation
+    /// dialog before being executed.
+    fn handle_import_commands(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let fields = vec![
+            ZedisFormField::new("commands", i18n_key_tree(cx, "import_commands"))
+                .placeholder(i18n_key_tree(cx, "import_commands_placeholder"))
+                .field_type(ZedisFormFieldType::AutoGrow(6, 20))
+                .required()
+                .focus()
+                .validate(|s| parse_import_commands(s).err().map(SharedString::from)),
+        ];
+        let server_state = self.server_state.clone();
+
+        ZedisFormOptions::new(fields)
+            .title(i18n_key_tree(cx, "import_commands_title"))
+            .confirm_label(i18n_common(cx, "confirm"))
+            .cancel_label(i18n_common(cx, "cancel"))
+            .on_dialog_submit(move |values, window, cx| {
+                let text = values.get("commands").cloned().unwrap_or_default();
+                let Ok(commands) = parse_import_commands(&text) else {
+                    return false;
+                };
+                let mut preview: Vec<String> = commands
+                    .iter()
+                    .take(IMPORT_PREVIEW_MAX_COMMANDS)
+                    .map(|command| command.to_string())
+                    .collect();
+                if commands.len() > IMPORT_PREVIEW_MAX_COMMANDS {
+                    preview.push(format!("... (+{})", commands.len() - IMPORT_PREVIEW_MAX_COMMANDS));
+                }
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                let message = t!(
+                    "key_tree.import_commands_prompt",
+                    count = commands.len(),
+                    commands = preview.join("\n"),
+                    locale = locale
+                )
+                .to_string();
+                let title = i18n_key_tree(cx, "import_commands_title");
+                let button_props = DialogButtonProps::default()
+                    .cancel_text(i18n_common(cx, "cancel"))
+                    .ok_text(i18n_common(cx, "confirm"));
+                let server_state = server_state.clone();
+                // Open the confirmation after the form dialog is closed
+                window.defer(cx, move |window, cx| {
+                    ZedisDialog::new_alert(title, message)
+                        .button_props(button_props)
+                        .on_ok(move |_, _, cx| {
+                            let commands = commands.clone();
+                            server_state.update(cx, |state, cx| {
+                                state.import_commands(commands, cx);
+                            });
+                            true
+                        })
+                        .open(window, cx);
+                });
+                true
+            })
+            .open_dialog(window, cx);
+    }
+
+    /// Lists the groups of keys holding identical values, clicking a key reveals it in the tree.
+    fn open_duplicates_dialog(&mut self, report: Arc<DuplicateReport>, window: &mut Window, cx: &mut Context<Self>) {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let mut notes: Vec<SharedString> = vec![
+            t!(
+                "key_tree.duplicates_summary",
+                checked = report.checked,
+                prefix = report.prefix,
+                groups = report.groups.len(),
+                locale = locale
+            )
+            .into(),
+        ];
+        if report.sampled {
+            notes.push(
+                t!(
+                    "key_tree.duplicates_sampled",
+                    count = report.checked + report.skipped,
+                    locale = locale
+                )
+                .into(),
+            );
+        }
+        if report.skipped > 0 {
+            notes.push(t!("key_tree.duplicates_skipped", count = report.skipped, locale = locale).into());
+        }
+        if report.groups.is_empty() {
+            notes.push(i18n_key_tree(cx, "no_duplicates"));
+        }
+        let groups: Vec<(SharedString, Vec<SharedString>)> = report
+            .groups
+            .iter()
+            .take(DUPLICATE_GROUPS_MAX)
+            .map(|group| {
+                let title = t!(
+                    "key_tree.duplicates_group",
+                    count = group.keys.len(),
+                    memory = format_size(group.memory, DECIMAL),
+                    locale = locale
+                );
+                (title.into(), group.keys.clone())
+            })
+            .collect();
+        let more: Option<SharedString> = (report.groups.len() > DUPLICATE_GROUPS_MAX).then(|| {
+            t!(
+                "key_tree.duplicates_more",
+                count = report.groups.len() - DUPLICATE_GROUPS_MAX,
+                locale = locale
+            )
+            .into()
+        });
+        let server_state = self.server_state.clone();
+
+        ZedisDialog::new(i18n_key_tree(cx, "duplicates_title"))
+            .alert()
+            .icon(CustomIconName::Equal)
+            .child(move || {
+                v_flex()
+                    .gap_2()
+                    .children(notes.iter().map(|note| Label::new(note.clone()).text_sm()))
+                    .child(
+                        v_flex()
+                            .id("key-tree-duplicates")
+                            .max_h(px(REPORT_LIST_MAX_HEIGHT))
+                            .overflow_y_scroll()
+                            .gap_2()
+                            .children(groups.iter().enumerate().map(|(group_index, (title, keys))| {
+                                v_flex().child(Label::new(title.clone()).text_xs()).children(
+                                    keys.iter().enumerate().map(|(key_index, key)| {
+                                        let key = key.clone();
+                                        let server_state = server_state.clone();
+                                        Button::new(("key-tree-duplicate-key", group_index * 10_000 + key_index))
+                                            .ghost()
+                                            .xsmall()
+                                            .label(key.clone())
+                                            .on_click(move |_, window, cx| {
+                                                server_state.update(cx, |state, cx| {
+                                                    state.reveal_key(key.clone(), cx);
+                                                });
+                                                window.close_dialog(cx);
+                                            })
+                                    }),
+                                )
+                            }))
+                            .when_some(more.clone(), |this, more| this.child(Label::new(more).text_xs())),
+                    )
+            })
+            .open(window, cx);
+    }
+
+    /// Lists the coldest keys of a namespace, with a bulk delete of the listed keys.
+    fn open_cold_keys_dialog(&mut self, report: Arc<ColdKeysReport>, window: &mut Window, cx: &mut Context<Self>) {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let metric = match report.metric {
+            ColdKeyMetric::IdleTime => i18n_key_tree(cx, "cold_keys_idle_time"),
+            ColdKeyMetric::Freq => i18n_key_tree(cx, "cold_keys_freq"),
+        };
+        let policy = if report.policy.is_empty() {
+            i18n_key_tree(cx, "cold_keys_unknown_policy")
+        } else {
+            report.policy.clone()
+        };
+        let mut notes: Vec<SharedString> = vec![
+            t!(
+                "key_tree.cold_keys_summary",
+                checked = report.checked,
+                prefix = report.prefix,
+                metric = metric,
+                policy = policy,
+                locale = locale
+            )
+            .into(),
+        ];
+        if report.sampled {
+            notes.push(t!("key_tree.cold_keys_sampled", count = report.checked, locale = locale).into());
+        }
+        if !report.is_policy_relevant() {
+            notes.push(t!("key_tree.cold_keys_policy_warning", policy = policy, locale = locale).into());
+        }
+        if report.keys.is_empty() {
+            notes.push(i18n_key_tree(cx, "no_cold_keys"));
+        }
+        let rows: Vec<(SharedString, SharedString)> = report
+            .keys
+            .iter()
+            .map(|(key, value)| {
+                let value = match report.metric {
+                    ColdKeyMetric::IdleTime => format_duration(Duration::from_secs(*value)),
+                    ColdKeyMetric::Freq => value.to_string(),
+                };
+                (key.clone(), value.into())
+            })
+            .collect();
+        let keys: Vec<SharedString> = report.keys.iter().map(|(key, _)| key.clone()).collect();
+        let readonly = self.server_state.read(cx).readonly();
+        let server_state = self.server_state.clone();
+        let delete_text = t!("key_tree.delete_cold_keys", count = keys.len(), locale = locale).to_string();
+        let button_props = DialogButtonProps::default()
+            .cancel_text(i18n_common(cx, "cancel"))
+            .ok_text(delete_text);
+
+        let dialog_server_state = server_state.clone();
+        ZedisDialog::new(i18n_key_tree(cx, "cold_keys_title"))
+            .alert()
+            .icon(CustomIconName::Snail)
+            .child(move || {
+                v_flex()
+                    .gap_2()
+                    .children(notes.iter().map(|note| Label::new(note.clone()).text_sm()))
+                    .child(
+                        v_flex()
+                            .id("key-tree-cold-keys")
+                            .max_h(px(REPORT_LIST_MAX_HEIGHT))
+                            .overflow_y_scroll()
+                            .children(rows.iter().enumerate().map(|(index, (key, value))| {
+                                let key = key.clone();
+                                let server_state = dialog_server_state.clone();
+                                h_flex()
+                                    .justify_between()
+                                    .gap_4()
+                                    .child(
+                                        Button::new(("key-tree-cold-key", index))
+                                            .ghost()
+                                            .xsmall()
+                                            .label(key.clone())
+                                            .on_click(move |_, window, cx| {
+                                                server_state.update(cx, |state, cx| {
+                                                    state.reveal_key(key.clone(), cx);
+                                                });
+                                                window.close_dialog(cx);
+                                            }),
+                                    )
+                                    .child(Label::new(value.clone()).text_xs())
+                            })),
+                    )
+            })
+            .when(!readonly && !keys.is_empty(), |this| {
+                this.button_props(button_props).on_ok(move |_, _, cx| {
+                    server_state.update(cx, |state, cx| {
+                        state.unlink_key(keys.clone(), cx);
+                    });
+                    true
+                })
+            })
+            .open(window, cx);
+    }
+
+    /// Asks to confirm a bulk operation, listing a sample of the keys it affects.
+    ///
+    /// Nothing is asked when no key matches.
+    fn open_bulk_preview_dialog(&mut self, preview: Arc<BulkPreview>, window: &mut Window, cx: &mut Context<Self>) {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        if preview.keys.is_empty() {
+            let message = t!(
+                "key_tree.bulk_preview_no_keys",
+                pattern = preview.pattern,
+                locale = locale
+            )
+            .to_string();
+            window.push_notification(Notification::info(message), cx);
+            return;
+        }
+        let mut sample: Vec<&str> = preview
+            .keys
+            .iter()
+            .take(BULK_PREVIEW_MAX_KEYS)
+            .map(|key| key.as_str())
+            .collect();
+        let more = format!("... (+{})", preview.keys.len().saturating_sub(BULK_PREVIEW_MAX_KEYS));
+        if preview.keys.len() > BULK_PREVIEW_MAX_KEYS {
+            sample.push(&more);
+        }
+        let (title, mut message) = match &preview.operation {
+            BulkOperation::DeleteFolder(folder) => (
+                i18n_key_tree(cx, "delete_folder_title"),
+                t!(
+                    "key_tree.delete_folder_prompt",
+                    folder = folder,
+                    count = preview.keys.len(),
+                    pattern = preview.pattern,
+                    keys = sample.join("\n"),
+                    locale = locale
+                )
+                .to_string(),
+            ),
+            BulkOperation::ExpireFolder(folder, ttl) => (
+                i18n_key_tree(cx, "set_folder_ttl_title"),
+                t!(
+                    "key_tree.set_folder_ttl_prompt",
+                    folder = folder,
+                    count = preview.keys.len(),
+                    pattern = preview.pattern,
+                    ttl = if ttl.is_zero() {
+                        i18n_common(cx, "no_expiry").to_string()
+                    } else {
+                        format_duration(*ttl)
+                    },
+                    keys = sample.join("\n"),
+                    locale = locale
+                )
+                .to_string(),
+            ),
+            BulkOperation::ImportKeys(_, overwrite) => (
+                i18n_key_tree(cx, "import_keys"),
+                t!(
+                    if *overwrite {
+                        "key_tree.import_keys_prompt_overwrite"
+                    } else {
+                        "key_tree.import_keys_prompt_skip"
+                    },
+                    count = preview.keys.len(),
+                    existing = preview.existing,
+                    file = preview.pattern,
+                    keys = sample.join("\n"),
+                    locale = locale
+                )
+                .to_string(),
+            ),
+        };
+        if !preview.complete {
+            message.push_str("\n\n");
+            message.push_str(&i18n_key_tree(cx, "bulk_preview_incomplete"));
+        }
+        let server_state = self.server_state.clone();
+
+        ZedisDialog::new_alert(title, message)
+            .button_props(dialog_button_props(cx))
+            .on_ok(move |_, _, cx| {
+                let keys = preview.keys.clone();
+                server_state.update(cx, |state, cx| match &preview.operation {
+                    BulkOperation::DeleteFolder(_) => state.unlink_key(keys, cx),
+                    BulkOperation::ExpireFolder(_, ttl) => state.expire_keys(keys, *ttl, cx),
+                    BulkOperation::ImportKeys(path, overwrite) => state.import_keys(path.clone(), *overwrite, cx),
+                });
+                true
+            })
+            .open(window, cx);
+    }
+
     fn get_tree_status_view(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
         let server_state = self.server_state.read(cx);
         // if scanning, return None
@@ -986,14 +2578,17 @@ impl ZedisKeyTree {
                 }
                 // User clicked an expanded folder -> collapse it
                 self.state.expanded_items.remove(&item_id);
+                self.save_expanded_items(cx);
             } else {
                 // User clicked a collapsed folder -> expand it and load data
                 self.state.expanded_items.insert(item_id.clone());
+                self.save_expanded_items(cx);
                 self.server_state.update(cx, |state, cx| {
-                    state.scan_prefix(format!("{}:", item_id.as_str()).into(), cx);
+                    let separator = state.key_separator(cx);
+                    state.scan_prefix(format!("{item_id}{separator}").into(), cx);
                 });
             }
-            self.update_key_tree(true, cx);
+            self.update_folder_rows(item_id, cx);
         } else {
             let is_selected = self.server_state.read(cx).key().as_ref() == Some(&item_id);
             // Select Key
@@ -1004,63 +2599,339 @@ impl ZedisKeyTree {
             }
         }
     }
-
-    /// Render the tree view or empty state message
-    ///
-    /// Displays:
-    /// - Tree structure with keys and folders (normal state)
-    /// - "Key not exists" message (Exact mode with expired key)
-    /// - Error or "no keys found" message (empty state)
-    fn render_tree(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
-        if let Some(status_view) = self.get_tree_status_view(cx) {
-            return status_view.into_any_element();
+
+    /// Render the tree view or empty state message
+    ///
+    /// Displays:
+    /// - Tree structure with keys and folders (normal state)
+    /// - "Key not exists" message (Exact mode with expired key)
+    /// - Error or "no keys found" message (empty state)
+    fn render_tree(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        if let Some(status_view) = self.get_tree_status_view(cx) {
+            return status_view.into_any_element();
+        }
+
+        div()
+            .p_1()
+            .bg(cx.theme().sidebar)
+            .text_color(cx.theme().sidebar_foreground)
+            .h_full()
+            .child(List::new(&self.key_tree_list_state))
+            .into_any_element()
+    }
+    /// Render the progress of the running scan against `DBSIZE`
+    ///
+    /// SCAN may return a key more than once and the keyspace changes while
+    /// scanning, so the ratio is only an estimate. It is meaningless when the
+    /// scan is filtered, only the number of loaded keys is shown then.
+    /// Number of selected keys with the bulk delete, shown while keys are selected
+    fn render_selection_bar(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let count = self.key_tree_list_state.read(cx).delegate().selected_items.len();
+        if count == 0 {
+            return None;
+        }
+        let readonly = self.server_state.read(cx).readonly();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+
+        Some(
+            h_flex()
+                .id("key-tree-selection-bar")
+                .flex_shrink_0()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .w_full()
+                .items_center()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .child(
+                    Label::new(t!("key_tree.selected_keys", count = count, locale = locale).to_string())
+                        .flex_1()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .when(!readonly, |this| {
+                    this.child(
+                        Button::new("key-tree-delete-selected")
+                            .ghost()
+                            .xsmall()
+                            .icon(CustomIconName::ListX)
+                            .tooltip(t!("key_tree.delete_keys_tooltip", count = count, locale = locale).to_string())
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.handle_delete_selected_keys(window, cx);
+                            })),
+                    )
+                })
+                .child(
+                    Button::new("key-tree-clear-selection")
+                        .ghost()
+                        .xsmall()
+                        .icon(CustomIconName::X)
+                        .tooltip(i18n_key_tree(cx, "clear_selection_tooltip"))
+                        .on_click(cx.listener(|this, _, _window, cx| {
+                            this.clear_selected_keys(cx);
+                        })),
+                ),
+        )
+    }
+    fn render_scan_progress(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let server_state = self.server_state.read(cx);
+        if !server_state.scanning() {
+            return None;
+        }
+        let scan_count = server_state.scan_count();
+        let dbsize = server_state.dbsize().filter(|dbsize| *dbsize > 0);
+        let filtered = !server_state.keyword().is_empty() || server_state.key_type_filter().is_some();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let (ratio, text) = match dbsize {
+            Some(dbsize) if !filtered => {
+                let ratio = (scan_count as f32 / dbsize as f32).min(1.0);
+                let text = t!(
+                    "key_tree.scan_progress",
+                    percent = (ratio * 100.0).round() as u32,
+                    count = scan_count,
+                    total = dbsize,
+                    locale = locale
+                );
+                (Some(ratio), text.to_string())
+            }
+            _ => (
+                None,
+                t!("key_tree.scan_loaded", count = scan_count, locale = locale).to_string(),
+            ),
+        };
+
+        Some(
+            h_flex()
+                .id("key-tree-scan-progress")
+                .flex_shrink_0()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .w_full()
+                .items_center()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .when_some(ratio, |this, ratio| {
+                    this.child(
+                        div()
+                            .flex_1()
+                            .h(px(4.))
+                            .rounded_full()
+                            .bg(cx.theme().border)
+                            .child(div().h_full().w(relative(ratio)).rounded_full().bg(cx.theme().primary)),
+                    )
+                })
+                .child(
+                    Label::new(text)
+                        .flex_shrink_0()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground),
+                ),
+        )
+    }
+    /// Render the number of keys written by the running prefix export, with a cancel button
+    fn render_export_progress(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let count = self.server_state.read(cx).prefix_export_count()?;
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+
+        Some(
+            h_flex()
+                .id("key-tree-export-progress")
+                .flex_shrink_0()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .w_full()
+                .items_center()
+                .justify_between()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .child(
+                    Label::new(t!("key_tree.export_prefix_progress", count = count, locale = locale).to_string())
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .child(
+                    Button::new("key-tree-export-cancel")
+                        .ghost()
+                        .xsmall()
+                        .label(i18n_common(cx, "cancel"))
+                        .on_click(cx.listener(|this, _, _window, cx| {
+                            this.server_state.update(cx, |state, _cx| {
+                                state.cancel_prefix_export();
+                            });
+                        })),
+                ),
+        )
+    }
+    /// Render the pinned keys above the tree, a missing key is struck through
+    fn render_favorites(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let server_state = self.server_state.read(cx);
+        let favorites = server_state.favorites();
+        if favorites.is_empty() {
+            return None;
         }
+        let collapsed = self.state.favorites_collapsed;
+        let yellow = cx.theme().colors.yellow;
+        let header = h_flex()
+            .id("key-tree-favorites-header")
+            .px_2()
+            .py_1()
+            .gap_2()
+            .w_full()
+            .items_center()
+            .cursor_pointer()
+            .child(Icon::new(if collapsed {
+                IconName::ChevronRight
+            } else {
+                IconName::ChevronDown
+            }))
+            .child(Icon::new(IconName::StarFill).text_color(yellow))
+            .child(
+                Label::new(format!("{} ({})", i18n_key_tree(cx, "favorite_keys"), favorites.len()))
+                    .flex_1()
+                    .text_sm(),
+            )
+            .child(
+                Button::new("key-tree-favorites-clear")
+                    .ghost()
+                    .xsmall()
+                    .icon(CustomIconName::Eraser)
+                    .tooltip(i18n_key_tree(cx, "clear_favorites"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        cx.stop_propagation();
+                        this.server_state.update(cx, |state, cx| {
+                            state.clear_favorites(cx);
+                        });
+                    })),
+            )
+            .on_click(cx.listener(|this, _, _window, cx| {
+                this.state.favorites_collapsed = !this.state.favorites_collapsed;
+                if !this.state.favorites_collapsed {
+                    this.server_state.update(cx, |state, cx| {
+                        state.check_favorites(cx);
+                    });
+                }
+                cx.notify();
+            }));
+        let items = favorites.iter().enumerate().map(|(index, key)| {
+            let missing = server_state.is_missing_favorite(key);
+            let reveal_key = key.clone();
+            let unpin_key = key.clone();
+            h_flex()
+                .id(("key-tree-favorite-item", index))
+                .pl_6()
+                .pr_2()
+                .gap_2()
+                .w_full()
+                .items_center()
+                .cursor_pointer()
+                .hover(|this| this.bg(cx.theme().secondary_hover))
+                .child(
+                    Button::new(("key-tree-favorite-unpin", index))
+                        .ghost()
+                        .xsmall()
+                        .icon(Icon::new(IconName::StarFill).text_color(yellow))
+                        .on_click(cx.listener(move |this, _, _window, cx| {
+                            cx.stop_propagation();
+                            let key = unpin_key.clone();
+                            this.server_state.update(cx, |state, cx| {
+                                state.toggle_favorite(key, cx);
+                            });
+                        })),
+                )
+                .child(
+                    Label::new(key.clone())
+                        .flex_1()
+                        .min_w_0()
+                        .text_sm()
+                        .text_ellipsis()
+                        .when(missing, |this| {
+                            this.line_through().text_color(cx.theme().muted_foreground)
+                        }),
+                )
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    let key = reveal_key.clone();
+                    this.server_state.update(cx, |state, cx| {
+                        // Not necessarily scanned yet, the key is looked up and added to the tree
+                        state.reveal_key(key, cx);
+                    });
+                }))
+        });
 
-        div()
-            .p_1()
-            .bg(cx.theme().sidebar)
-            .text_color(cx.theme().sidebar_foreground)
-            .h_full()
-            .child(List::new(&self.key_tree_list_state))
-            .into_any_element()
+        Some(
+            v_flex()
+                .flex_shrink_0()
+                .w_full()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .child(header)
+                .when(!collapsed, |this| {
+                    this.child(
+                        v_flex()
+                            .id("key-tree-favorites")
+                            .w_full()
+                            .pb_1()
+                            .max_h(px(FAVORITES_MAX_HEIGHT))
+                            .overflow_y_scroll()
+                            .children(items),
+                    )
+                }),
+        )
     }
     /// Render the search/filter input bar with query mode selector
     ///
     /// Features:
     /// - Query mode dropdown (All/Prefix/Exact) with visual indicators
     /// - Search input field with placeholder
-    /// - Search button (with loading state during scan)
+    /// - Search button, replaced by a cancel button during scan
     /// - Clearable input (X button appears when text entered)
     fn render_keyword_input(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state_clone = self.server_state.clone();
         let server_state = self.server_state.read(cx);
         let scanning = server_state.scanning();
+        let scan_cancelled = server_state.scan_cancelled();
         let readonly = server_state.readonly();
         let server_id = server_state.server_id().to_string();
         let server_id_changed = server_id.as_str() != self.state.server_id.as_str();
         let _ = server_state;
         if server_id_changed {
-            self.state.server_id = server_id.clone().into();
+            self.state.server_id = server_id.into();
             self.keyword_state.update(cx, |state, cx| {
                 state.set_value(SharedString::default(), window, cx);
             });
         }
         let query_mode = self.state.query_mode;
+        let key_type_filter = self.server_state.read(cx).key_type_filter();
+        // JSON documents only exist with the RedisJSON module
+        let supports_json = self.server_state.read(cx).supports(Capability::Json);
+        let dbsize = self.server_state.read(cx).dbsize();
 
         // Select icon based on query mode
         let icon = match query_mode {
             QueryMode::All => Icon::new(IconName::Asterisk), // * for all keys
             QueryMode::Prefix => Icon::new(CustomIconName::ChevronUp), // ~ for prefix
             QueryMode::Exact => Icon::new(CustomIconName::Equal), // = for exact match
+            QueryMode::Pattern => Icon::new(CustomIconName::Regex), // glob anywhere in the key
+            QueryMode::Regex => Icon::new(IconName::Search), // regex matched client-side
         };
-        let server_id_for_favorites: SharedString = server_id.clone().into();
         let query_mode_dropdown = DropdownButton::new("dropdown")
-            .button(Button::new("key-tree-query-mode-btn").ghost().px_2().icon(icon))
+            .button(
+                Button::new("key-tree-query-mode-btn")
+                    .ghost()
+                    .px_2()
+                    .tooltip(format!(
+                        "{} ({})",
+                        i18n_key_tree(cx, "query_mode"),
+                        humanize_keystroke("cmd-shift-m")
+                    ))
+                    .icon(icon)
+                    .when_some(key_type_filter, |this, key_type| this.label(key_type.as_str())),
+            )
             .dropdown_menu_with_anchor(Corner::TopLeft, move |menu, window, cx| {
-                let favorites = get_favorites_manager()
-                    .records(server_id_for_favorites.as_ref())
-                    .unwrap_or_default();
                 let server_state_for_history = server_state_clone.clone();
+                let server_state_for_recent_keys = server_state_clone.clone();
                 menu.submenu_with_icon(
                     Some(Icon::new(CustomIconName::Clock3)),
                     i18n_key_tree(cx, "search_history"),
@@ -1088,26 +2959,26 @@ impl ZedisKeyTree {
                     },
                 )
                 .submenu_with_icon(
-                    Some(Icon::new(IconName::Star)),
-                    i18n_key_tree(cx, "favorite_keys"),
+                    Some(Icon::new(CustomIconName::Key)),
+                    i18n_key_tree(cx, "recent_keys"),
                     window,
                     cx,
                     move |submenu, _window, cx| {
                         let mut submenu = submenu;
-                        if favorites.is_empty() {
-                            submenu = submenu.label(i18n_key_tree(cx, "no_favorite_keys"));
+                        let keys = server_state_for_recent_keys.read(cx).recent_keys().to_vec();
+                        if keys.is_empty() {
+                            submenu = submenu.label(i18n_key_tree(cx, "no_recent_keys"));
                         } else {
-                            for key in &favorites {
-                                let key_clone = key.clone();
+                            for key in keys {
                                 submenu = submenu.menu_element(
-                                    Box::new(KeyTreeAction::SelectFavoriteKey(key.clone())),
-                                    move |_, _cx| Label::new(key_clone.clone()).text_ellipsis(),
+                                    Box::new(KeyTreeAction::SelectRecentKey(key.clone())),
+                                    move |_, _cx| Label::new(key.clone()).text_ellipsis(),
                                 );
                             }
                             submenu = submenu.separator().menu_element_with_icon(
                                 CustomIconName::Eraser,
-                                Box::new(KeyTreeAction::ClearFavorites),
-                                move |_, cx| Label::new(i18n_key_tree(cx, "clear_favorites")),
+                                Box::new(KeyTreeAction::ClearRecentKeys),
+                                move |_, cx| Label::new(i18n_key_tree(cx, "clear_history")),
                             );
                         }
                         submenu
@@ -1133,17 +3004,80 @@ impl ZedisKeyTree {
                                 Box::new(QueryMode::Exact),
                                 |_, cx| Label::new(i18n_key_tree(cx, "query_mode_exact")),
                             )
+                            .menu_element_with_check(
+                                query_mode == QueryMode::Pattern,
+                                Box::new(QueryMode::Pattern),
+                                |_, cx| Label::new(i18n_key_tree(cx, "query_mode_pattern")),
+                            )
+                            .menu_element_with_check(
+                                query_mode == QueryMode::Regex,
+                                Box::new(QueryMode::Regex),
+                                |_, cx| Label::new(i18n_key_tree(cx, "query_mode_regex")),
+                            )
+                    },
+                )
+                .submenu_with_icon(
+                    Some(Icon::new(IconName::LayoutDashboard)),
+                    i18n_key_tree(cx, "key_type_filter"),
+                    window,
+                    cx,
+                    move |submenu, _window, _cx| {
+                        let mut submenu = submenu.menu_element_with_check(
+                            key_type_filter.is_none(),
+                            Box::new(KeyTreeAction::FilterKeyType(SharedString::default())),
+                            |_, cx| Label::new(i18n_key_tree(cx, "key_type_filter_all")),
+                        );
+                        for key_type in [
+                            KeyType::String,
+                            KeyType::List,
+                            KeyType::Set,
+                            KeyType::Zset,
+                            KeyType::Hash,
+                            KeyType::Stream,
+                            KeyType::Vectorset,
+                            KeyType::Json,
+                        ]
+                        .into_iter()
+                        .filter(|key_type| *key_type != KeyType::Json || supports_json)
+                        {
+                            let name = key_type.redis_type_name().unwrap_or_default();
+                            submenu = submenu.menu_element_with_check(
+                                key_type_filter == Some(key_type),
+                                Box::new(KeyTreeAction::FilterKeyType(name.into())),
+                                move |_, _cx| Label::new(name),
+                            );
+                        }
+                        submenu
                     },
                 )
             });
-        let search_btn = Button::new("key-tree-search-btn")
-            .ghost()
-            .loading(scanning)
-            .disabled(scanning)
-            .icon(IconName::Search)
-            .on_click(cx.listener(|this, _, _, cx| {
-                this.handle_filter(cx);
-            }));
+        // While scanning the search button is replaced by a cancel button,
+        // the spinner only shows until the cancelled scan returns
+        let search_btn = if scanning && !scan_cancelled {
+            Button::new("key-tree-scan-cancel")
+                .ghost()
+                .icon(IconName::CircleX)
+                .tooltip(i18n_key_tree(cx, "cancel_scan_tooltip"))
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.server_state.update(cx, |state, cx| {
+                        state.cancel_scan(cx);
+                    });
+                }))
+        } else {
+            Button::new("key-tree-search-btn")
+                .ghost()
+                .loading(scanning)
+                .disabled(scanning)
+                .icon(IconName::Search)
+                .tooltip(format!(
+                    "{} ({})",
+                    i18n_key_tree(cx, "search_tooltip"),
+                    humanize_keystroke("cmd-shift-r")
+                ))
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.handle_filter(cx);
+                }))
+        };
         // keyword input
         let keyword_input = Input::new(&self.keyword_state)
             .w_full()
@@ -1155,16 +3089,115 @@ impl ZedisKeyTree {
             .cleanable(true);
         let enabled_multiple_selection = self.key_tree_list_state.read(cx).delegate().enabled_multiple_selection;
         let refresh_interval_sec = self.state.refresh_interval_sec;
+        let show_folder_memory = self.state.show_folder_memory;
+        let show_key_memory = self.state.show_key_memory;
+        let sort_mode = self.state.sort_mode;
+        let flat_view = self.state.flat_view;
+        let live_search = self.state.live_search;
+        let watching_key_events = self.server_state.read(cx).watching_key_events();
+        let key_separator = self.server_state.read(cx).server_key_separator().cloned();
+        let global_separator: SharedString = cx
+            .global::<ZedisGlobalStore>()
+            .read(cx)
+            .key_separator()
+            .to_string()
+            .into();
+
+        let sort_dropdown = Button::new("key-tree-sort-dropdown")
+            .outline()
+            .icon(Icon::new(IconName::SortAscending))
+            .tooltip(i18n_key_tree(cx, "sort_keys"))
+            .dropdown_menu_with_anchor(Corner::TopRight, move |menu, _window, cx| {
+                let mut menu = menu;
+                for (mode, name) in [
+                    (KeySortMode::NameAsc, "sort_name_asc"),
+                    (KeySortMode::NameDesc, "sort_name_desc"),
+                    (KeySortMode::Type, "sort_type"),
+                    (KeySortMode::Memory, "sort_memory"),
+                ] {
+                    // Sizes are only loaded when the keys show their memory usage
+                    if mode == KeySortMode::Memory && !show_key_memory {
+                        continue;
+                    }
+                    let label = i18n_key_tree(cx, name);
+                    menu = menu.menu_element_with_check(
+                        sort_mode == mode,
+                        Box::new(KeyTreeAction::SortKeys(mode)),
+                        move |_, _cx| Label::new(label.clone()),
+                    );
+                }
+                menu
+            });
 
         let more_dropdown = Button::new("key-tree-more-dropdown")
             .outline()
             .icon(Icon::new(IconName::Ellipsis))
             .dropdown_menu_with_anchor(Corner::TopRight, move |menu, window, cx| {
+                let key_separator = key_separator.clone();
+                let global_separator = global_separator.clone();
                 menu.menu_element_with_icon(
                     Icon::new(CustomIconName::ListChecvronsDownUp),
                     Box::new(KeyTreeAction::CollapseAllKeys),
                     move |_, cx| Label::new(i18n_key_tree(cx, "collapse_keys")),
                 )
+                .menu_element_with_icon(
+                    if show_folder_memory {
+                        Icon::new(IconName::Check)
+                    } else {
+                        Icon::new(CustomIconName::MemoryStick)
+                    },
+                    Box::new(KeyTreeAction::ToggleFolderMemory),
+                    move |_, cx| Label::new(i18n_key_tree(cx, "show_folder_memory")),
+                )
+                .menu_element_with_icon(
+                    if show_key_memory {
+                        Icon::new(IconName::Check)
+                    } else {
+                        Icon::new(CustomIconName::MemoryStick)
+                    },
+                    Box::new(KeyTreeAction::ToggleKeyMemory),
+                    move |_, cx| Label::new(i18n_key_tree(cx, "show_key_memory")),
+                )
+                .menu_element_with_icon(
+                    if flat_view {
+                        Icon::new(IconName::Check)
+                    } else {
+                        Icon::new(CustomIconName::Equal)
+                    },
+                    Box::new(KeyTreeAction::ToggleFlatView),
+                    move |_, cx| Label::new(i18n_key_tree(cx, "flat_view")),
+                )
+                .menu_element_with_icon(
+                    if live_search {
+                        Icon::new(IconName::Check)
+                    } else {
+                        Icon::new(IconName::Search)
+                    },
+                    Box::new(KeyTreeAction::ToggleLiveSearch),
+                    move |_, cx| Label::new(i18n_key_tree(cx, "live_search")),
+                )
+                .submenu_with_icon(
+                    Some(Icon::new(IconName::Folder)),
+                    i18n_key_tree(cx, "key_separator"),
+                    window,
+                    cx,
+                    move |submenu, _window, cx| {
+                        let label = format!("{} ({global_separator})", i18n_key_tree(cx, "key_separator_default"));
+                        let mut submenu = submenu.menu_element_with_check(
+                            key_separator.is_none(),
+                            Box::new(KeyTreeAction::SetKeySeparator(SharedString::default())),
+                            move |_, _cx| Label::new(label.clone()),
+                        );
+                        for separator in KEY_SEPARATORS {
+                            submenu = submenu.menu_element_with_check(
+                                key_separator.as_ref().map(|s| s.as_str()) == Some(separator),
+                                Box::new(KeyTreeAction::SetKeySeparator(separator.into())),
+                                move |_, _cx| Label::new(separator),
+                            );
+                        }
+                        submenu
+                    },
+                )
                 .when(!readonly, |this| {
                     let icon = if enabled_multiple_selection {
                         Icon::new(IconName::Check)
@@ -1198,23 +3231,77 @@ impl ZedisKeyTree {
                         submenu
                     },
                 )
+                .menu_element_with_check(
+                    watching_key_events,
+                    Box::new(KeyTreeAction::ToggleWatchKeyEvents),
+                    move |_, cx| Label::new(i18n_key_tree(cx, "watch_key_events")),
+                )
                 .menu_element_with_icon(
                     Icon::new(CustomIconName::Rss),
                     Box::new(KeyTreeAction::ChangeChannelMode),
                     move |_, cx| Label::new(i18n_key_tree(cx, "pubsub_mode")),
                 )
+                .menu_element_with_icon(
+                    Icon::new(CustomIconName::Activity),
+                    Box::new(KeyTreeAction::ChangeMonitorMode),
+                    move |_, cx| Label::new(i18n_key_tree(cx, "monitor_mode")),
+                )
+                .menu_element_with_icon(
+                    Icon::new(IconName::Settings),
+                    Box::new(KeyTreeAction::ChangeConfigMode),
+                    move |_, cx| Label::new(i18n_key_tree(cx, "config_mode")),
+                )
+                .menu_element_with_icon(
+                    Icon::new(IconName::Search),
+                    Box::new(KeyTreeAction::ChangeSearchMode),
+                    move |_, cx| Label::new(i18n_key_tree(cx, "search_mode")),
+                )
+                .when(!readonly, |this| {
+                    this.menu_element_with_icon(
+                        Icon::new(CustomIconName::FilePenLine),
+                        Box::new(KeyTreeAction::ImportCommands),
+                        move |_, cx| Label::new(i18n_key_tree(cx, "import_commands")),
+                    )
+                    .menu_element_with_icon(
+                        Icon::new(IconName::FolderOpen),
+                        Box::new(KeyTreeAction::ImportKeys),
+                        move |_, cx| Label::new(i18n_key_tree(cx, "import_keys")),
+                    )
+                })
             });
 
         h_flex()
             .flex_shrink_0()
             .px_2()
-            .h(KEY_TREE_KEYWORD_INPUT_HEIGHT)
+            .min_h(KEY_TREE_KEYWORD_INPUT_HEIGHT)
             .border_b_1()
             .border_color(cx.theme().border)
             .items_center()
             .w_full()
             .gap_x_2()
             .child(keyword_input)
+            .when_some(dbsize.filter(|_| !scanning), |this, dbsize| {
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                this.child(
+                    Label::new(t!("key_tree.dbsize", count = dbsize, locale = locale).to_string())
+                        .flex_shrink_0()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground),
+                )
+            })
+            .when(readonly, |this| {
+                this.child(
+                    div()
+                        .flex_shrink_0()
+                        .px_1p5()
+                        .rounded_sm()
+                        .bg(cx.theme().danger)
+                        .text_color(cx.theme().danger_foreground)
+                        .text_xs()
+                        .font_semibold()
+                        .child(i18n_key_tree(cx, "readonly_badge")),
+                )
+            })
             .child(
                 Button::new("key-tree-add-btn")
                     .disabled(readonly)
@@ -1233,6 +3320,7 @@ impl ZedisKeyTree {
                         this.handle_add_key(window, cx);
                     })),
             )
+            .child(sort_dropdown)
             .child(more_dropdown)
     }
 }
@@ -1254,23 +3342,18 @@ impl Render for ZedisKeyTree {
             .h_full()
             .w_full()
             .child(self.render_keyword_input(window, cx))
+            .children(self.render_selection_bar(cx))
+            .children(self.render_scan_progress(cx))
+            .children(self.render_export_progress(cx))
+            .children(self.render_favorites(cx))
             .child(self.render_tree(cx))
-            .on_action(cx.listener(|this, e: &QueryMode, _window, cx| {
-                let new_mode = *e;
-
-                let server_id = this.server_state.read(cx).server_id();
-                if let Ok(mut option) = get_session_option(server_id) {
-                    option.query_mode = Some(new_mode.to_string());
-                    save_session_option(server_id, option, cx);
+            .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
+                if event.keystroke.key == "escape" && this.clear_selected_keys(cx) {
+                    cx.stop_propagation();
                 }
-
-                // Step 1: Update server state with new query mode
-                this.server_state.update(cx, |state, cx| {
-                    state.set_query_mode(new_mode, cx);
-                });
-
-                // Step 2: Update local UI state
-                this.state.query_mode = new_mode;
+            }))
+            .on_action(cx.listener(|this, e: &QueryMode, window, cx| {
+                this.handle_query_mode(*e, window, cx);
             }))
             .on_action(cx.listener(|this, e: &KeyTreeAction, window, cx| match e {
                 KeyTreeAction::ChangeChannelMode => {
@@ -1278,6 +3361,26 @@ impl Render for ZedisKeyTree {
                         state.change_channel_mode(cx);
                     });
                 }
+                KeyTreeAction::ChangeMonitorMode => {
+                    this.server_state.update(cx, |state, cx| {
+                        state.change_monitor_mode(cx);
+                    });
+                }
+                KeyTreeAction::ChangeConfigMode => {
+                    this.server_state.update(cx, |state, cx| {
+                        state.change_config_mode(cx);
+                    });
+                }
+                KeyTreeAction::ChangeSearchMode => {
+                    this.server_state.update(cx, |state, cx| {
+                        state.change_search_mode(cx);
+                    });
+                }
+                KeyTreeAction::ToggleWatchKeyEvents => {
+                    this.server_state.update(cx, |state, cx| {
+                        state.toggle_watch_key_events(cx);
+                    });
+                }
                 KeyTreeAction::AutoRefresh(interval) => {
                     this.state.refresh_interval_sec = *interval;
                     this.start_auto_refresh(cx);
@@ -1287,11 +3390,149 @@ impl Render for ZedisKeyTree {
                         save_session_option(server_id, option, cx);
                     }
                 }
+                KeyTreeAction::FilterKeyType(name) => {
+                    let key_type = Some(KeyType::from(name.as_str())).filter(|t| *t != KeyType::Unknown);
+                    let keyword = this.state.keyword.clone();
+                    this.server_state.update(cx, |state, cx| {
+                        state.set_key_type_filter(key_type, cx);
+                        // Exact mode shows a single key, no rescan needed
+                        if state.query_mode() != QueryMode::Exact {
+                            state.handle_filter(keyword, cx);
+                        }
+                    });
+                }
+                KeyTreeAction::CopyKey(id) => {
+                    cx.write_to_clipboard(ClipboardItem::new_string(id.to_string()));
+                    window.push_notification(Notification::info(i18n_common(cx, "copied_to_clipboard")), cx);
+                }
+                KeyTreeAction::CopyPrefix(id) => {
+                    let separator = this.server_state.read(cx).key_separator(cx).to_string();
+                    cx.write_to_clipboard(ClipboardItem::new_string(format!("{id}{separator}")));
+                    window.push_notification(Notification::info(i18n_common(cx, "copied_to_clipboard")), cx);
+                }
+                KeyTreeAction::ScanPrefix(id) => {
+                    let separator = this.server_state.read(cx).key_separator(cx).to_string();
+                    let prefix: SharedString = format!("{id}{separator}").into();
+                    this.set_query_mode(QueryMode::Prefix, cx);
+                    this.keyword_state.update(cx, |state, cx| {
+                        state.set_value(prefix, window, cx);
+                    });
+                    this.handle_filter(cx);
+                }
+                KeyTreeAction::FindDuplicates(id) => {
+                    let separator = this.server_state.read(cx).key_separator(cx).to_string();
+                    let prefix: SharedString = format!("{id}{separator}").into();
+                    this.server_state.update(cx, |state, cx| {
+                        state.find_duplicates(prefix, cx);
+                    });
+                }
+                KeyTreeAction::FindColdKeys(id) => {
+                    let separator = this.server_state.read(cx).key_separator(cx).to_string();
+                    let prefix: SharedString = format!("{id}{separator}").into();
+                    this.server_state.update(cx, |state, cx| {
+                        state.find_cold_keys(prefix, cx);
+                    });
+                }
+                KeyTreeAction::DuplicateKey(id) => {
+                    this.handle_duplicate_key(id.clone(), window, cx);
+                }
+                KeyTreeAction::CopyToServer(id) => {
+                    this.handle_copy_to_server(id.clone(), window, cx);
+                }
+                KeyTreeAction::ExportPrefix(id) => {
+                    this.handle_export_prefix(id.clone(), cx);
+                }
+                KeyTreeAction::ImportCommands => {
+                    this.handle_import_commands(window, cx);
+                }
+                KeyTreeAction::ImportKeys => {
+                    this.handle_import_keys(window, cx);
+                }
                 KeyTreeAction::CollapseAllKeys => {
                     this.server_state.update(cx, |state, cx| {
                         state.collapse_all_keys(cx);
                     });
                 }
+                KeyTreeAction::ToggleFolderMemory => {
+                    let show_folder_memory = !this.state.show_folder_memory;
+                    this.state.show_folder_memory = show_folder_memory;
+                    let folders = this.key_tree_list_state.update(cx, |state, cx| {
+                        state.delegate_mut().show_folder_memory = show_folder_memory;
+                        cx.notify();
+                        state.delegate().folder_ids()
+                    });
+                    this.server_state.update(cx, |state, cx| {
+                        if show_folder_memory {
+                            state.load_folder_memory(folders, cx);
+                        }
+                        if let Ok(mut option) = get_session_option(state.server_id()) {
+                            option.show_folder_memory = Some(show_folder_memory);
+                            save_session_option(state.server_id(), option, cx);
+                        }
+                    });
+                }
+                KeyTreeAction::ToggleKeyMemory => {
+                    let show_key_memory = !this.state.show_key_memory;
+                    this.state.show_key_memory = show_key_memory;
+                    let keys = this.key_tree_list_state.update(cx, |state, cx| {
+                        state.delegate_mut().show_key_memory = show_key_memory;
+                        cx.notify();
+                        state.delegate().key_ids()
+                    });
+                    this.server_state.update(cx, |state, cx| {
+                        if show_key_memory {
+                            state.load_key_memory(keys, cx);
+                        }
+                        if let Ok(mut option) = get_session_option(state.server_id()) {
+                            option.show_key_memory = Some(show_key_memory);
+                            save_session_option(state.server_id(), option, cx);
+                        }
+                    });
+                    if this.state.sort_mode == KeySortMode::Memory {
+                        this.update_key_tree(true, cx);
+                    }
+                }
+                KeyTreeAction::ToggleFlatView => {
+                    let flat_view = !this.state.flat_view;
+                    this.state.flat_view = flat_view;
+                    this.server_state.update(cx, |state, cx| {
+                        if let Ok(mut option) = get_session_option(state.server_id()) {
+                            option.flat_view = Some(flat_view);
+                            save_session_option(state.server_id(), option, cx);
+                        }
+                    });
+                    this.update_key_tree(true, cx);
+                }
+                KeyTreeAction::ToggleLiveSearch => {
+                    let live_search = !this.state.live_search;
+                    this.state.live_search = live_search;
+                    if !live_search {
+                        this.live_search_task = None;
+                    }
+                    this.server_state.update(cx, |state, cx| {
+                        if let Ok(mut option) = get_session_option(state.server_id()) {
+                            option.live_search = Some(live_search);
+                            save_session_option(state.server_id(), option, cx);
+                        }
+                    });
+                    cx.notify();
+                }
+                KeyTreeAction::SetKeySeparator(separator) => {
+                    this.server_state.update(cx, |state, cx| {
+                        state.set_key_separator(separator.clone(), cx);
+                    });
+                }
+                KeyTreeAction::SortKeys(sort_mode) => {
+                    let sort_mode = *sort_mode;
+                    this.state.sort_mode = sort_mode;
+                    this.server_state.update(cx, |state, cx| {
+                        if let Ok(mut option) = get_session_option(state.server_id()) {
+                            option.key_sort_mode = Some(sort_mode);
+                            save_session_option(state.server_id(), option, cx);
+                        }
+                    });
+                    this.update_key_tree(true, cx);
+                }
                 KeyTreeAction::ToggleMultiSelectMode => {
                     this.key_tree_list_state.update(cx, |state, cx| {
                         state.delegate_mut().toggle_multiple_selection(cx);
@@ -1306,74 +3547,34 @@ impl Render for ZedisKeyTree {
                 KeyTreeAction::Clear => {
                     this.handle_clear_history(cx);
                 }
-                KeyTreeAction::SelectFavoriteKey(key) => {
-                    this.select_item(key.clone(), false, false, cx);
+                KeyTreeAction::SelectRecentKey(key) => {
+                    // The key may be outside of the scanned keys, e.g. after a restart
+                    this.server_state.update(cx, |state, cx| {
+                        state.reveal_key(key.clone(), cx);
+                    });
                 }
-                KeyTreeAction::ClearFavorites => {
-                    let server_id = this.server_state.read(cx).server_id().to_string();
-                    cx.spawn(async move |_, cx| {
-                        let _ = cx
-                            .background_spawn(async move {
-                                let _ = get_favorites_manager().clear_history(&server_id);
-                            })
-                            .await;
-                    })
-                    .detach();
+                KeyTreeAction::ClearRecentKeys => {
+                    this.server_state.update(cx, |state, cx| {
+                        state.clear_recent_keys(cx);
+                    });
                 }
                 KeyTreeAction::DeleteMultipleKeys => {
-                    let keys = this.key_tree_list_state.update(cx, |state, _cx| {
-                        state
-                            .delegate()
-                            .selected_items
-                            .iter()
-                            .cloned()
-                            .collect::<Vec<SharedString>>()
-                    });
-                    let server_state = this.server_state.clone();
-                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
-                    let text = t!("key_tree.delete_keys_prompt", keys = keys.join(", "), locale = locale).to_string();
-
-                    ZedisDialog::new_alert(i18n_key_tree(cx, "delete_keys_title"), text)
-                        .button_props(dialog_button_props(cx))
-                        .on_ok(move |_, _, cx| {
-                            server_state.update(cx, |state, cx| {
-                                state.unlink_key(keys.clone(), cx);
-                            });
-                            true
-                        })
-                        .open(window, cx);
+                    this.handle_delete_selected_keys(window, cx);
                 }
                 KeyTreeAction::DeleteKey(id) => {
-                    let id = id.clone();
-                    let server_state = this.server_state.clone();
-                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
-                    let text = t!("key_tree.delete_key_prompt", key = id.clone(), locale = locale).to_string();
-
-                    ZedisDialog::new_alert(i18n_key_tree(cx, "delete_key_title"), text)
-                        .button_props(dialog_button_props(cx))
-                        .on_ok(move |_, _, cx| {
-                            server_state.update(cx, |state, cx| {
-                                state.delete_key(id.clone(), cx);
-                            });
-                            true
-                        })
-                        .open(window, cx);
+                    this.handle_delete_key(id.clone(), window, cx);
+                }
+                KeyTreeAction::RenameKey(id) => {
+                    this.handle_rename_key(id.clone(), window, cx);
                 }
                 KeyTreeAction::DeleteFolder(id) => {
-                    let id = id.clone();
-                    let server_state = this.server_state.clone();
-                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
-                    let text = t!("key_tree.delete_folder_prompt", folder = id.clone(), locale = locale).to_string();
-
-                    ZedisDialog::new_alert(i18n_key_tree(cx, "delete_folder_title"), text)
-                        .button_props(dialog_button_props(cx))
-                        .on_ok(move |_, _, cx| {
-                            server_state.update(cx, |state, cx| {
-                                state.delete_folder(id.clone(), cx);
-                            });
-                            true
-                        })
-                        .open(window, cx);
+                    // The confirmation lists the keys found by the scan
+                    this.server_state.update(cx, |state, cx| {
+                        state.preview_bulk_operation(BulkOperation::DeleteFolder(id.clone()), cx);
+                    });
+                }
+                KeyTreeAction::ExpireFolder(id) => {
+                    this.handle_expire_folder(id.clone(), window, cx);
                 }
             }))
             .on_action(cx.listener(|this, event: &EditorAction, window, cx| match event {
@@ -1386,3 +3587,116 @@ impl Render for ZedisKeyTree {
             }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYS: [&str; 6] = ["a:1", "a:2", "b:1", "b:2:x", "b:2:y", "c"];
+
+    fn tree(keys: &[&str], expanded: &[&str]) -> Vec<KeyTreeItem> {
+        new_key_tree_items(
+            keys.iter()
+                .map(|key| (SharedString::from(key.to_string()), KeyType::String))
+                .collect(),
+            SharedString::default(),
+            None,
+            expanded
+                .iter()
+                .map(|folder| SharedString::from(folder.to_string()))
+                .collect(),
+            ":",
+            10,
+            TreeSort {
+                mode: KeySortMode::NameAsc,
+                memory: None,
+            },
+        )
+    }
+
+    /// Rows of `folder` as built by `update_folder_rows`
+    fn folder_rows(folder: &str, expanded: &[&str]) -> Vec<KeyTreeItem> {
+        let prefix = format!("{folder}:");
+        let keys: Vec<&str> = KEYS.into_iter().filter(|key| key.starts_with(&prefix)).collect();
+        tree(&keys, expanded)
+            .into_iter()
+            .filter(|item| item.id.starts_with(&prefix))
+            .collect()
+    }
+
+    fn ids(items: &[KeyTreeItem]) -> Vec<&str> {
+        items.iter().map(|item| item.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_expand_folder_keeps_other_rows() {
+        let mut items = tree(&KEYS, &[]);
+        assert_eq!(ids(&items), ["a", "b", "c"]);
+        let before: Vec<(String, *const u8)> = items
+            .iter()
+            .map(|item| (item.id.to_string(), item.id.as_ptr()))
+            .collect();
+
+        assert!(expand_folder_rows(&mut items, "b", folder_rows("b", &["b"])));
+        assert_eq!(ids(&items), ids(&tree(&KEYS, &["b"])));
+        assert_eq!(ids(&items), ["a", "b", "b:2", "b:1", "c"]);
+
+        // The other rows are neither rebuilt nor reordered
+        let after: Vec<(String, *const u8)> = items
+            .iter()
+            .filter(|item| !item.id.starts_with("b:"))
+            .map(|item| (item.id.to_string(), item.id.as_ptr()))
+            .collect();
+        assert_eq!(before, after);
+        assert!(
+            items
+                .iter()
+                .find(|item| item.id == "b")
+                .is_some_and(|item| item.expanded)
+        );
+    }
+
+    #[test]
+    fn test_expand_deep_folder() {
+        let mut items = tree(&KEYS, &["b"]);
+        let before: Vec<(String, *const u8)> = items
+            .iter()
+            .map(|item| (item.id.to_string(), item.id.as_ptr()))
+            .collect();
+
+        assert!(expand_folder_rows(&mut items, "b:2", folder_rows("b:2", &["b", "b:2"])));
+        assert_eq!(ids(&items), ids(&tree(&KEYS, &["b", "b:2"])));
+        let after: Vec<(String, *const u8)> = items
+            .iter()
+            .filter(|item| !item.id.starts_with("b:2:"))
+            .map(|item| (item.id.to_string(), item.id.as_ptr()))
+            .collect();
+        assert_eq!(before, after);
+
+        // Already expanded or missing folders are left as they are
+        assert!(!expand_folder_rows(
+            &mut items,
+            "b:2",
+            folder_rows("b:2", &["b", "b:2"])
+        ));
+        assert!(!expand_folder_rows(&mut items, "d", vec![]));
+        assert_eq!(ids(&items), ids(&tree(&KEYS, &["b", "b:2"])));
+    }
+
+    #[test]
+    fn test_collapse_folder() {
+        let mut items = tree(&KEYS, &["a", "b", "b:2"]);
+        assert!(collapse_folder_rows(&mut items, "b", "b:"));
+        assert_eq!(ids(&items), ["a", "a:1", "a:2", "b", "c"]);
+        assert!(
+            items
+                .iter()
+                .find(|item| item.id == "b")
+                .is_some_and(|item| !item.expanded)
+        );
+
+        assert!(collapse_folder_rows(&mut items, "a", "a:"));
+        assert_eq!(ids(&items), ids(&tree(&KEYS, &[])));
+        assert!(!collapse_folder_rows(&mut items, "d", "d:"));
+    }
+}
@@ -19,8 +19,8 @@ use crate::{
     components::{INDEX_COLUMN_NAME, KvTableColumn, KvTableColumnType, KvTableMode, ZedisKvDelegate, ZedisKvFetcher},
     helpers::{EditorAction, humanize_keystroke},
     states::{
-        KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, dialog_button_props, i18n_common, i18n_kv_table,
-        i18n_list_editor,
+        KeyType, ServerEvent, SortOrder, ZedisGlobalStore, ZedisServerState, dialog_button_props, i18n_common,
+        i18n_kv_table, i18n_list_editor,
     },
 };
 use gpui::{Entity, SharedString, Subscription, TextAlign, Window, div, prelude::*, px};
@@ -32,7 +32,8 @@ use gpui_component::{
     h_flex,
     input::{Escape, Input, InputEvent, InputState},
     label::Label,
-    table::{DataTable, TableEvent, TableState},
+    notification::Notification,
+    table::{DataTable, TableDelegate, TableEvent, TableState},
     v_flex,
 };
 use indexmap::IndexMap;
@@ -46,11 +47,18 @@ const FOOTER_HEIGHT: f32 = 50.0;
 /// Width of the keyword search input field in pixels
 const KEYWORD_INPUT_WIDTH: f32 = 200.0;
 
+/// Width of the sample size input field in pixels
+const SAMPLE_INPUT_WIDTH: f32 = 110.0;
+
+/// Default number of items loaded by a random sample
+const DEFAULT_SAMPLE_SIZE: usize = 100;
+
 /// A generic table view for displaying Redis key-value data.
 ///
 /// This component handles:
 /// - Displaying paginated Redis data in a table format
 /// - Keyword search/filtering
+/// - Random sampling of huge collections
 /// - Real-time updates via server events
 /// - Loading states and pagination indicators
 pub struct ZedisKvTable<T: ZedisKvFetcher> {
@@ -58,12 +66,16 @@ pub struct ZedisKvTable<T: ZedisKvFetcher> {
     table_state: Entity<TableState<ZedisKvDelegate<T>>>,
     /// Input field state for keyword search/filter
     keyword_state: Entity<InputState>,
+    /// Input field state for the random sample size
+    sample_state: Entity<InputState>,
     /// Number of currently loaded items
     items_count: usize,
     /// Total number of items available
     total_count: usize,
     /// Whether all data has been loaded
     done: bool,
+    /// Whether the loaded rows are a random sample
+    sample: bool,
     /// Whether a filter operation is in progress
     loading: bool,
     /// Flag indicating the selected key has changed (triggers input reset)
@@ -89,6 +101,8 @@ pub struct ZedisKvTable<T: ZedisKvFetcher> {
     editor_form: Option<Entity<ZedisForm>>,
     /// Fetcher instance
     fetcher: Arc<T>,
+    /// Server state, to check the selected key before undoing a removal
+    server_state: Entity<ZedisServerState>,
     /// Event subscriptions for server state and input changes
     _subscriptions: Vec<Subscription>,
 }
@@ -201,6 +215,7 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
                     this.fetcher = fetcher.clone();
                     this.loading = false;
                     this.done = fetcher.is_done();
+                    this.sample = fetcher.is_sample();
                     this.items_count = fetcher.rows_count();
                     this.total_count = fetcher.count();
                     this.table_state.update(cx, |state, _| {
@@ -223,11 +238,26 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
                 .placeholder(i18n_common(cx, "keyword_placeholder"))
         });
 
-        // Subscribe to input events to trigger search on Enter
-        subscriptions.push(cx.subscribe(&keyword_state, |this, _, event, cx| {
-            if matches!(event, InputEvent::PressEnter { .. }) {
+        // Subscribe to input events to trigger search on Enter,
+        // clearing the input resets an active filter
+        subscriptions.push(cx.subscribe(&keyword_state, |this, state, event, cx| match event {
+            InputEvent::PressEnter { .. } => this.handle_filter(cx),
+            InputEvent::Change if state.read(cx).value().is_empty() && this.fetcher.keyword().is_some() => {
                 this.handle_filter(cx);
             }
+            _ => {}
+        }));
+
+        // Initialize sample size input field, Enter loads a new sample
+        let sample_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .default_value(DEFAULT_SAMPLE_SIZE.to_string())
+                .placeholder(i18n_kv_table(cx, "sample_placeholder"))
+        });
+        subscriptions.push(cx.subscribe(&sample_state, |this, _, event, cx| {
+            if matches!(event, InputEvent::PressEnter { .. }) {
+                this.handle_sample(cx);
+            }
         }));
 
         let readonly = server_state.read(cx).readonly();
@@ -240,8 +270,9 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
         };
 
         // Initialize table data and state
-        let fetcher = Arc::new(Self::new_values(server_state, cx));
+        let fetcher = Arc::new(Self::new_values(server_state.clone(), cx));
         let done = fetcher.is_done();
+        let sample = fetcher.is_sample();
         let items_count = fetcher.rows_count();
         let total_count = fetcher.count();
         let delegate = ZedisKvDelegate::new(
@@ -291,9 +322,11 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
         Self {
             table_state,
             keyword_state,
+            sample_state,
             items_count,
             total_count,
             done,
+            sample,
             loading: false,
             key_changed: None,
             edit_row: None,
@@ -304,6 +337,7 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
             readonly,
             mode,
             fetcher,
+            server_state,
             columns,
             editor_form: None,
             list_push_mode_state: cx.new(|_cx| 0),
@@ -414,6 +448,53 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
         });
     }
 
+    /// Loads the next page, like scrolling near the bottom of the table does.
+    fn handle_load_more(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.loading || self.done || self.sample {
+            return;
+        }
+        self.loading = true;
+        self.table_state.update(cx, |state, cx| {
+            state.delegate_mut().load_more(window, cx);
+        });
+    }
+
+    /// Reverses the order of the rows, reloading them from the start.
+    fn handle_toggle_sort(&mut self, cx: &mut Context<Self>) {
+        if self.loading {
+            return;
+        }
+        let sort_order = match self.fetcher.sort_order() {
+            Some(SortOrder::Asc) => SortOrder::Desc,
+            Some(SortOrder::Desc) => SortOrder::Asc,
+            None => return,
+        };
+        self.loading = true;
+        self.table_state.update(cx, |state, cx| {
+            state.delegate().fetcher().sort(sort_order, cx);
+        });
+    }
+
+    /// Replaces the loaded rows with a random sample, the size comes from the sample input.
+    fn handle_sample(&mut self, cx: &mut Context<Self>) {
+        if self.loading || !self.fetcher.can_sample(cx) {
+            return;
+        }
+        let count = self
+            .sample_state
+            .read(cx)
+            .value()
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|count| *count > 0)
+            .unwrap_or(DEFAULT_SAMPLE_SIZE);
+        self.loading = true;
+        self.table_state.update(cx, |state, cx| {
+            state.delegate().fetcher().sample(count, cx);
+        });
+    }
+
     fn handle_remove_row(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         // Only allow removing if REMOVE mode is enabled
         if !self.mode.contains(KvTableMode::REMOVE) {
@@ -423,6 +504,10 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
         let Some(row_ix) = self.edit_row else {
             return;
         };
+        if !cx.global::<ZedisGlobalStore>().read(cx).confirm_remove_value() {
+            self.remove_row(row_ix, window, cx);
+            return;
+        }
         let fetcher = self.fetcher.clone();
         let value = fetcher.get(row_ix, fetcher.primary_index()).unwrap_or_default();
         let entity = cx.entity().clone();
@@ -439,15 +524,49 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
         ZedisDialog::new_alert(title, message.to_string())
             .button_props(dialog_button_props(cx))
             .on_ok(move |_, window, cx| {
-                fetcher.remove(row_ix, cx);
-                entity.update(cx, |this, _cx| {
-                    this.edit_row = None;
+                entity.update(cx, |this, cx| {
+                    this.remove_row(row_ix, window, cx);
                 });
                 window.close_dialog(cx);
                 true
             })
             .open(window, cx);
     }
+    /// Removes the row, offering to undo it from the notification when the
+    /// fetcher can restore the removed values.
+    fn remove_row(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let fetcher = self.fetcher.clone();
+        let restore_values = fetcher.restore_values(row_ix);
+        fetcher.remove(row_ix, cx);
+        self.edit_row = None;
+        cx.notify();
+
+        let Some(restore_values) = restore_values else {
+            return;
+        };
+        let key = self.server_state.read(cx).key();
+        let server_state = self.server_state.clone();
+        let undo_label = i18n_kv_table(cx, "undo");
+        let notification = Notification::info(i18n_kv_table(cx, "removed_tips")).action(move |_, _, cx| {
+            let fetcher = fetcher.clone();
+            let restore_values = restore_values.clone();
+            let key = key.clone();
+            let server_state = server_state.clone();
+            Button::new("kv-table-undo-remove")
+                .ghost()
+                .label(undo_label.clone())
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    // The removed values belong to the key selected when removing
+                    if server_state.read(cx).key() == key {
+                        fetcher.restore(restore_values.clone(), cx);
+                    } else {
+                        window.push_notification(Notification::warning(i18n_kv_table(cx, "undo_key_changed")), cx);
+                    }
+                    this.dismiss(window, cx);
+                }))
+        });
+        window.push_notification(notification, cx);
+    }
     fn enhance_handle_add_or_update_value(
         &mut self,
         data: IndexMap<SharedString, SharedString>,
@@ -536,7 +655,7 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
             let mut field = ZedisFormField::new(column.name.clone(), column.name.clone())
                 .focus()
                 .font_family(get_font_family());
-            if self.fetcher.fields_required() {
+            if self.fetcher.fields_required() && !column.optional {
                 field = field.required();
             }
             if first {
@@ -553,6 +672,9 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
                 }
                 field = field.field_type(field_type);
             }
+            if let Some(validate) = column.validate {
+                field = field.validate(validate);
+            }
 
             if !is_adding && let Some(value) = self.original_values.get(&column.name) {
                 field = field.default_value(value.clone());
@@ -626,6 +748,31 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
         // Determine if operations are allowed based on mode
         let can_add = self.mode.contains(KvTableMode::ADD);
         let can_filter = self.mode.contains(KvTableMode::FILTER);
+        // Sampling only reads, so it is available in readonly mode too
+        let can_sample = self.fetcher.can_sample(cx);
+        let sort_order = self.fetcher.sort_order();
+        let count_label: SharedString = if self.sample {
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            t!(
+                "kv_table.sample_count",
+                count = self.items_count,
+                total = self.total_count,
+                locale = locale
+            )
+            .into()
+        } else if self.fetcher.keyword().is_some() {
+            // The total is the size of the whole collection, not the number of matches
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            t!(
+                "kv_table.filtered_count",
+                count = self.items_count,
+                total = self.total_count,
+                locale = locale
+            )
+            .into()
+        } else {
+            format!("{} / {}", self.items_count, self.total_count).into()
+        };
 
         // Search button with loading state
         let search_btn = Button::new("kv-table-search-btn")
@@ -639,7 +786,9 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
             }));
 
         // Completion indicator icon
-        let status_icon = if self.done {
+        let status_icon = if self.sample {
+            Icon::new(CustomIconName::Shuffle) // Random sample, not the full contents
+        } else if self.done {
             Icon::new(CustomIconName::CircleCheckBig) // All data loaded
         } else {
             Icon::new(CustomIconName::CircleDotDashed) // More data available
@@ -691,15 +840,57 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
                                                 .cleanable(true),
                                         )
                                     })
+                                    .when(can_sample, |this| {
+                                        this.child(
+                                            Input::new(&self.sample_state).w(px(SAMPLE_INPUT_WIDTH)).suffix(
+                                                Button::new("kv-table-sample-btn")
+                                                    .ghost()
+                                                    .icon(CustomIconName::Shuffle)
+                                                    .tooltip(i18n_kv_table(cx, "sample_tooltip"))
+                                                    .loading(self.loading)
+                                                    .disabled(self.loading)
+                                                    .on_click(cx.listener(|this, _, _, cx| {
+                                                        this.handle_sample(cx);
+                                                    })),
+                                            ),
+                                        )
+                                    })
+                                    .when_some(sort_order, |this, sort_order| {
+                                        let icon = if sort_order == SortOrder::Asc {
+                                            IconName::SortAscending
+                                        } else {
+                                            IconName::SortDescending
+                                        };
+                                        this.child(
+                                            Button::new("kv-table-sort-btn")
+                                                .ghost()
+                                                .icon(icon)
+                                                .tooltip(i18n_kv_table(cx, "sort_tooltip"))
+                                                .disabled(self.loading)
+                                                .on_click(cx.listener(|this, _, _, cx| {
+                                                    this.handle_toggle_sort(cx);
+                                                })),
+                                        )
+                                    })
                                     .flex_1(),
                             )
-                            // Right side: Status icon and count
+                            // Right side: Load more button, status icon and count
+                            .when(!self.done && !self.sample, |this| {
+                                this.child(
+                                    Button::new("kv-table-load-more-btn")
+                                        .ghost()
+                                        .mr_2()
+                                        .label(i18n_kv_table(cx, "load_more"))
+                                        .tooltip(i18n_kv_table(cx, "load_more_tooltip"))
+                                        .loading(self.loading)
+                                        .disabled(self.loading)
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.handle_load_more(window, cx);
+                                        })),
+                                )
+                            })
                             .child(status_icon.text_color(text_color).mr_2())
-                            .child(
-                                Label::new(format!("{} / {}", self.items_count, self.total_count))
-                                    .text_sm()
-                                    .text_color(text_color),
-                            ),
+                            .child(Label::new(count_label).text_sm().text_color(text_color)),
                     ),
             )
             // Right side: edit panel (full height)
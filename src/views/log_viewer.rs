@@ -0,0 +1,278 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    assets::CustomIconName,
+    helpers::{LOG_LEVELS, LogRecord, clear_log_records, get_font_family, get_log_level, get_log_records, log_version},
+    states::{i18n_common, i18n_log_viewer, update_app_state_and_save},
+};
+use gpui::{
+    Action, App, ClipboardItem, Entity, FocusHandle, Hsla, Subscription, Task, Window, prelude::*, px, uniform_list,
+};
+use gpui_component::{
+    ActiveTheme, IconName, WindowExt,
+    button::{Button, ButtonVariants, DropdownButton},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    notification::Notification,
+    v_flex,
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Level;
+
+/// Interval to check for new events while the viewer is open
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+const MODULE_INPUT_WIDTH: f32 = 240.0;
+const LEVEL_LABEL_WIDTH: f32 = 48.0;
+
+#[derive(Clone, Copy, PartialEq, Debug, Deserialize, JsonSchema, Action)]
+enum LogViewerAction {
+    /// Show the events at least as severe as `LOG_LEVELS[index]`
+    FilterLevel(usize),
+    /// Capture the events at least as severe as `LOG_LEVELS[index]`
+    SetLogLevel(usize),
+}
+
+/// In-app panel listing the recent `tracing` events, newest first.
+pub struct ZedisLogViewer {
+    module_state: Entity<InputState>,
+    /// Least severe level shown
+    level_filter: Level,
+    /// Events matching the filters, from the oldest to the newest
+    records: Arc<Vec<LogRecord>>,
+    /// `log_version` when `records` was loaded
+    version: u64,
+    focus_handle: FocusHandle,
+    _refresh_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisLogViewer {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        focus_handle.focus(window, cx);
+        let module_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .clean_on_escape()
+                .placeholder(i18n_log_viewer(cx, "module_placeholder"))
+        });
+        let subscriptions = vec![
+            cx.subscribe_in(&module_state, window, |view, _state, event, _window, cx| {
+                if let InputEvent::Change = event {
+                    view.load_records(cx);
+                }
+            }),
+        ];
+        let refresh_task = cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor().timer(REFRESH_INTERVAL).await;
+                let result = this.update(cx, |this, cx| {
+                    if this.version != log_version() {
+                        this.load_records(cx);
+                    }
+                });
+                if result.is_err() {
+                    break;
+                }
+            }
+        });
+        let mut this = Self {
+            module_state,
+            level_filter: Level::TRACE,
+            records: Arc::new(vec![]),
+            version: 0,
+            focus_handle,
+            _refresh_task: refresh_task,
+            _subscriptions: subscriptions,
+        };
+        this.load_records(cx);
+        this
+    }
+    fn load_records(&mut self, cx: &mut Context<Self>) {
+        self.version = log_version();
+        let module = self.module_state.read(cx).value().trim().to_lowercase();
+        let level_filter = self.level_filter;
+        let records = get_log_records()
+            .into_iter()
+            .filter(|record| record.level <= level_filter)
+            .filter(|record| module.is_empty() || record.target.to_lowercase().contains(&module))
+            .collect();
+        self.records = Arc::new(records);
+        cx.notify();
+    }
+    /// Copy the shown events as plain text lines, e.g. to attach them to a bug report
+    fn handle_copy(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let content = self
+            .records
+            .iter()
+            .map(|record| record.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        cx.write_to_clipboard(ClipboardItem::new_string(content));
+        window.push_notification(Notification::info(i18n_common(cx, "copied_to_clipboard")), cx);
+    }
+    fn handle_action(&mut self, action: &LogViewerAction, cx: &mut Context<Self>) {
+        match *action {
+            LogViewerAction::FilterLevel(index) => {
+                if let Some(level) = LOG_LEVELS.get(index) {
+                    self.level_filter = *level;
+                    self.load_records(cx);
+                }
+            }
+            LogViewerAction::SetLogLevel(index) => {
+                if let Some(level) = LOG_LEVELS.get(index).copied() {
+                    update_app_state_and_save(cx, "save_log_level", move |state, _cx| {
+                        state.set_log_level(level);
+                    });
+                    cx.notify();
+                }
+            }
+        }
+    }
+    fn level_color(level: Level, cx: &App) -> Hsla {
+        match level {
+            Level::ERROR => cx.theme().danger,
+            Level::WARN => cx.theme().warning,
+            Level::INFO => cx.theme().primary,
+            _ => cx.theme().muted_foreground,
+        }
+    }
+}
+
+impl Render for ZedisLogViewer {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let level_filter = self.level_filter;
+        let log_level = get_log_level();
+        let records = self.records.clone();
+        let font_family = get_font_family();
+
+        let level_menu = |id: &'static str, label: String, current: Level, action: fn(usize) -> LogViewerAction| {
+            DropdownButton::new(id)
+                .button(Button::new((id, 0)).outline().label(label))
+                .dropdown_menu(move |menu, _, _cx| {
+                    let mut menu = menu;
+                    for (index, level) in LOG_LEVELS.iter().enumerate() {
+                        menu = menu.menu_with_check(level.as_str(), *level == current, Box::new(action(index)));
+                    }
+                    menu
+                })
+        };
+
+        v_flex()
+            .id("log-viewer")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_5()
+            .gap_3()
+            .child(
+                h_flex()
+                    .w_full()
+                    .justify_between()
+                    .gap_2()
+                    .child(Label::new(i18n_log_viewer(cx, "title")).text_xl())
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Input::new(&self.module_state).w(px(MODULE_INPUT_WIDTH)).cleanable(true))
+                            .child(level_menu(
+                                "log-viewer-filter-level",
+                                format!("{}: {level_filter}", i18n_log_viewer(cx, "show_level")),
+                                level_filter,
+                                LogViewerAction::FilterLevel,
+                            ))
+                            .child(level_menu(
+                                "log-viewer-log-level",
+                                format!("{}: {log_level}", i18n_log_viewer(cx, "log_level")),
+                                log_level,
+                                LogViewerAction::SetLogLevel,
+                            ))
+                            .child(
+                                Button::new("log-viewer-copy")
+                                    .outline()
+                                    .icon(IconName::Copy)
+                                    .tooltip(i18n_log_viewer(cx, "copy_tooltip"))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.handle_copy(window, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("log-viewer-clear")
+                                    .outline()
+                                    .icon(CustomIconName::Eraser)
+                                    .tooltip(i18n_log_viewer(cx, "clear_tooltip"))
+                                    .on_click(cx.listener(|this, _, _window, cx| {
+                                        clear_log_records();
+                                        this.load_records(cx);
+                                    })),
+                            ),
+                    ),
+            )
+            .when(records.is_empty(), |this| {
+                this.child(
+                    Label::new(i18n_log_viewer(cx, "no_logs"))
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground),
+                )
+            })
+            .child(
+                uniform_list("log-viewer-records", records.len(), move |range, _window, cx| {
+                    range
+                        .filter_map(|index| {
+                            // Newest first
+                            let record = records.get(records.len() - 1 - index)?;
+                            Some(
+                                h_flex()
+                                    .id(("log-viewer-record", index))
+                                    .w_full()
+                                    .gap_2()
+                                    .text_xs()
+                                    .font_family(font_family.clone())
+                                    .child(
+                                        Label::new(record.time.clone())
+                                            .flex_none()
+                                            .text_color(cx.theme().muted_foreground),
+                                    )
+                                    .child(
+                                        Label::new(record.level.as_str())
+                                            .flex_none()
+                                            .w(px(LEVEL_LABEL_WIDTH))
+                                            .text_color(Self::level_color(record.level, cx)),
+                                    )
+                                    .child(
+                                        Label::new(record.target.clone())
+                                            .flex_none()
+                                            .text_color(cx.theme().muted_foreground),
+                                    )
+                                    .child(
+                                        Label::new(record.message.clone())
+                                            .flex_1()
+                                            .text_ellipsis()
+                                            .whitespace_nowrap(),
+                                    ),
+                            )
+                        })
+                        .collect()
+                })
+                .flex_1()
+                .w_full(),
+            )
+            .on_action(cx.listener(|this, action: &LogViewerAction, _window, cx| {
+                this.handle_action(action, cx);
+            }))
+    }
+}
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::connection::get_server;
-use crate::states::{RedisMetrics, get_metrics_cache};
+use crate::states::{RedisInfo, RedisMetrics, get_metrics_cache};
 use crate::states::{ZedisServerState, i18n_common, i18n_metrics};
 use chrono::{Local, LocalResult, TimeZone};
 use core::f64;
@@ -33,6 +33,7 @@ use zedis_ui::ZedisSkeletonLoading;
 
 const TIME_FORMAT: &str = "%H:%M:%S";
 const CHART_CARD_HEIGHT: Pixels = px(300.);
+const INFO_CARD_MAX_HEIGHT: Pixels = px(300.);
 const HEARTBEAT_INTERVAL_SECS: u64 = 2;
 const BYTES_TO_MB: f64 = 1_000_000.;
 const Y_LABEL_WIDTH: f32 = 45.;
@@ -151,7 +152,10 @@ struct MetricsChartData {
 
 pub struct ZedisMetrics {
     title: SharedString,
+    server_state: Entity<ZedisServerState>,
     latest_metrics: Option<RedisMetrics>,
+    /// Latest `INFO` reply, for the keyspace and the raw sections
+    info: Option<RedisInfo>,
     metrics_chart_data: MetricsChartData,
     tick_margin: usize,
     heartbeat_task: Option<Task<()>>,
@@ -571,16 +575,20 @@ impl ZedisMetrics {
         let metrics_history = get_metrics_cache().list_metrics(server_id);
         let latest_metrics = metrics_history.last().copied();
         let (metrics_chart_data, tick_margin) = convert_metrics_to_chart_data(metrics_history);
+        let info = state.redis_info().cloned();
+        let server_id = server_id.to_string();
 
         let mut this = Self {
             title,
+            server_state,
             latest_metrics,
+            info,
             metrics_chart_data,
             tick_margin,
             heartbeat_task: None,
             _subscriptions: vec![],
         };
-        this.start_heartbeat(server_id.to_string(), cx);
+        this.start_heartbeat(server_id, cx);
         this
     }
     /// Start the heartbeat task
@@ -594,6 +602,7 @@ impl ZedisMetrics {
                 let metrics_history = get_metrics_cache().list_metrics(&server_id);
                 let _ = this.update(cx, |state, cx| {
                     state.latest_metrics = metrics_history.last().copied();
+                    state.info = state.server_state.read(cx).redis_info().cloned();
                     let (metrics_chart_data, tick_margin) = convert_metrics_to_chart_data(metrics_history);
                     state.metrics_chart_data = metrics_chart_data;
                     state.tick_margin = tick_margin;
@@ -695,6 +704,73 @@ impl ZedisMetrics {
             .into_any_element()
     }
 
+    /// Keys, expiring keys and average TTL of each database
+    fn render_keyspace_card(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let title = i18n_metrics(cx, "keyspace");
+        let mut keyspace: Vec<_> = self
+            .info
+            .as_ref()
+            .map(|info| info.keyspace.iter().collect())
+            .unwrap_or_default();
+        keyspace.sort_by_key(|(db, _)| db.trim_start_matches("db").parse::<usize>().unwrap_or(usize::MAX));
+        let theme = cx.theme();
+        let rows = keyspace.into_iter().map(|(db, stats)| {
+            h_flex()
+                .justify_between()
+                .gap_2()
+                .text_sm()
+                .child(Label::new(db.clone()).text_color(theme.muted_foreground))
+                .child(Label::new(format!(
+                    "keys={}, expires={}, avg_ttl={}",
+                    stats.keys, stats.expires, stats.avg_ttl
+                )))
+        });
+        v_flex()
+            .flex_1()
+            .border_1()
+            .border_color(theme.border)
+            .rounded(theme.radius_lg)
+            .p_4()
+            .gap_1()
+            .child(div().font_semibold().child(title).mb_2())
+            .children(rows)
+    }
+
+    /// Every field of the `INFO` reply, grouped by section
+    fn render_info_sections(&self, cx: &mut Context<Self>) -> Vec<impl IntoElement> {
+        let theme = cx.theme();
+        let sections = self
+            .info
+            .as_ref()
+            .map(|info| info.sections.as_slice())
+            .unwrap_or_default();
+        sections
+            .iter()
+            .enumerate()
+            .map(|(index, section)| {
+                v_flex()
+                    .id(("metrics-info-section", index))
+                    .flex_1()
+                    .max_h(INFO_CARD_MAX_HEIGHT)
+                    .overflow_y_scroll()
+                    .border_1()
+                    .border_color(theme.border)
+                    .rounded(theme.radius_lg)
+                    .p_4()
+                    .gap_1()
+                    .child(Label::new(section.name.clone()).font_semibold().mb_2())
+                    .children(section.fields.iter().map(|(key, value)| {
+                        h_flex()
+                            .justify_between()
+                            .gap_2()
+                            .text_xs()
+                            .child(Label::new(key.clone()).text_color(theme.muted_foreground))
+                            .child(Label::new(value.clone()).text_ellipsis())
+                    }))
+            })
+            .collect()
+    }
+
     fn render_cpu_usage_chart(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let label = format!(
             "{}: {:.2}% - {:.2}%",
@@ -978,7 +1054,9 @@ impl Render for ZedisMetrics {
                             .child(self.render_total_commands_processed_chart(cx))
                             .child(self.render_key_hit_rate_chart(cx))
                             .child(self.render_evicted_keys_chart(cx))
-                    }),
+                    })
+                    .child(self.render_keyspace_card(cx))
+                    .children(self.render_info_sections(cx)),
             )
             .overflow_y_scrollbar()
             .into_any_element()
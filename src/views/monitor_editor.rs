@@ -0,0 +1,273 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    assets::CustomIconName,
+    connection::get_connection_manager,
+    error::Error,
+    helpers::get_font_family,
+    states::{ZedisServerState, dialog_button_props, i18n_monitor},
+};
+use futures::StreamExt;
+use gpui::{Entity, SharedString, Subscription, Task, Window, div, prelude::*, uniform_list};
+use gpui_component::{
+    ActiveTheme, Icon, IconName,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    v_flex,
+};
+use std::collections::VecDeque;
+use std::pin::pin;
+use std::sync::Arc;
+use tracing::{error, info};
+use zedis_ui::ZedisDialog;
+
+/// Commands kept in the scrollback, the oldest are dropped beyond it
+const MAX_MONITOR_LINES: usize = 5000;
+/// Commands read at most from the stream per update, so that a busy server
+/// doesn't re-render the view for every command
+const MONITOR_BATCH_SIZE: usize = 256;
+
+/// Live `MONITOR` log of the server, newest first, with a client-side text filter.
+///
+/// The stream is read over a dedicated connection owned by `monitor_task`,
+/// dropping the task closes the connection, which ends MONITOR on the server.
+pub struct ZedisMonitorEditor {
+    server_state: Entity<ZedisServerState>,
+    filter_state: Entity<InputState>,
+    /// Commands received, from the oldest to the newest
+    lines: VecDeque<SharedString>,
+    /// True while the MONITOR connection is being opened
+    starting: bool,
+    /// True while the commands are streamed
+    running: bool,
+    monitor_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisMonitorEditor {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let filter_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .clean_on_escape()
+                .placeholder(i18n_monitor(cx, "filter_placeholder"))
+        });
+        let subscriptions = vec![cx.subscribe(&filter_state, |_this, _state, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                cx.notify();
+            }
+        })];
+
+        info!("Creating new monitor editor");
+
+        Self {
+            server_state,
+            filter_state,
+            lines: VecDeque::new(),
+            starting: false,
+            running: false,
+            monitor_task: None,
+            _subscriptions: subscriptions,
+        }
+    }
+    /// Ask for confirmation first, MONITOR degrades the performance of the server
+    fn handle_start(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let entity = cx.entity();
+        ZedisDialog::new_alert(i18n_monitor(cx, "start_title"), i18n_monitor(cx, "start_prompt"))
+            .button_props(dialog_button_props(cx))
+            .on_ok(move |_, _, cx| {
+                entity.update(cx, |this, cx| {
+                    this.start(cx);
+                });
+                true
+            })
+            .open(window, cx);
+    }
+    fn start(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_state.read(cx).server_id().to_string();
+        self.starting = true;
+        cx.notify();
+
+        self.monitor_task = Some(cx.spawn(async move |this, cx| {
+            let result: Result<_, Error> = cx
+                .background_spawn(async move {
+                    let mut monitor = get_connection_manager().get_monitor_connection(&server_id).await?;
+                    monitor.monitor().await?;
+                    Ok(monitor)
+                })
+                .await;
+            let monitor = match result {
+                Ok(monitor) => monitor,
+                Err(e) => {
+                    error!(error = %e, "start monitor fail");
+                    let _ = this.update(cx, |this, cx| {
+                        this.starting = false;
+                        let message = format!("{}: {e}", i18n_monitor(cx, "start_failed"));
+                        this.server_state.update(cx, |state, cx| {
+                            state.emit_error_notification(message.into(), cx);
+                        });
+                        cx.notify();
+                    });
+                    return;
+                }
+            };
+            let _ = this.update(cx, |this, cx| {
+                this.starting = false;
+                this.running = true;
+                cx.notify();
+            });
+
+            let mut stream = pin!(monitor.into_on_message::<String>().ready_chunks(MONITOR_BATCH_SIZE));
+            while let Some(commands) = stream.next().await {
+                let result = this.update(cx, |this, cx| {
+                    this.lines.extend(commands.into_iter().map(SharedString::from));
+                    let overflow = this.lines.len().saturating_sub(MAX_MONITOR_LINES);
+                    this.lines.drain(..overflow);
+                    cx.notify();
+                });
+                // Entity was dropped – stop the loop.
+                if result.is_err() {
+                    return;
+                }
+            }
+            // The connection was closed by the server
+            let _ = this.update(cx, |this, cx| {
+                this.running = false;
+                cx.notify();
+            });
+        }));
+    }
+    /// Drops the task, which closes the MONITOR connection
+    fn handle_stop(&mut self, cx: &mut Context<Self>) {
+        self.monitor_task.take();
+        self.starting = false;
+        self.running = false;
+        let message = i18n_monitor(cx, "stopped");
+        self.server_state.update(cx, |state, cx| {
+            state.emit_info_notification(message, cx);
+        });
+        cx.notify();
+    }
+    /// Renders the warning about the cost of MONITOR, shown the whole time the panel is open.
+    fn render_warning(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .items_center()
+            .bg(cx.theme().warning.opacity(0.15))
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(Icon::new(IconName::TriangleAlert).text_color(cx.theme().warning))
+            .child(Label::new(i18n_monitor(cx, "warning")).text_sm())
+    }
+    /// Renders the toolbar: the filter input, a clear button and a start/stop toggle.
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let toggle_btn = if self.starting || self.running {
+            Button::new("monitor-stop-btn")
+                .outline()
+                .label(i18n_monitor(cx, "stop"))
+                .on_click(cx.listener(|this, _, _window, cx| {
+                    this.handle_stop(cx);
+                }))
+        } else {
+            Button::new("monitor-start-btn")
+                .outline()
+                .label(i18n_monitor(cx, "start"))
+                .on_click(cx.listener(|this, _, window, cx| {
+                    this.handle_start(window, cx);
+                }))
+        };
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .items_center()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(Input::new(&self.filter_state).w_full().flex_1().cleanable(true))
+            .child(
+                Button::new("monitor-clear-btn")
+                    .outline()
+                    .icon(CustomIconName::Eraser)
+                    .tooltip(i18n_monitor(cx, "clear_tooltip"))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.lines.clear();
+                        cx.notify();
+                    })),
+            )
+            .child(toggle_btn)
+    }
+}
+
+impl Render for ZedisMonitorEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let filter = self.filter_state.read(cx).value().trim().to_lowercase();
+        // Newest first
+        let lines: Arc<Vec<SharedString>> = Arc::new(
+            self.lines
+                .iter()
+                .rev()
+                .filter(|line| filter.is_empty() || line.to_lowercase().contains(&filter))
+                .cloned()
+                .collect(),
+        );
+        let is_empty = lines.is_empty();
+        let font_family = get_font_family();
+
+        v_flex()
+            .size_full()
+            .overflow_hidden()
+            .child(self.render_warning(cx))
+            .child(self.render_toolbar(cx))
+            .when(is_empty, |this| {
+                this.child(
+                    div()
+                        .flex_1()
+                        .w_full()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .child(Label::new(i18n_monitor(cx, "no_commands")).text_color(cx.theme().muted_foreground)),
+                )
+            })
+            .when(!is_empty, |this| {
+                this.child(
+                    uniform_list("monitor-lines", lines.len(), move |range, _window, _cx| {
+                        range
+                            .filter_map(|index| {
+                                let line = lines.get(index)?;
+                                Some(
+                                    div()
+                                        .id(("monitor-line", index))
+                                        .w_full()
+                                        .px_3()
+                                        .text_xs()
+                                        .font_family(font_family.clone())
+                                        .child(Label::new(line.clone()).text_ellipsis().whitespace_nowrap()),
+                                )
+                            })
+                            .collect()
+                    })
+                    .flex_1()
+                    .w_full(),
+                )
+            })
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    connection::get_server,
+    states::{NotificationCategory, Route, ZedisGlobalStore, i18n_notification_center},
+};
+use gpui::{App, Hsla, SharedString, Subscription, Window, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Icon, IconName, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    tooltip::Tooltip,
+    v_flex,
+};
+use zedis_ui::ZedisDialog;
+
+const LIST_MAX_HEIGHT: f32 = 420.0;
+
+/// Notifications of the session, newest first, an entry about a server opens it on click.
+struct ZedisNotificationCenter {
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisNotificationCenter {
+    fn new(cx: &mut Context<Self>) -> Self {
+        // Notifications shown while the center is open are listed right away
+        let global_state = cx.global::<ZedisGlobalStore>().state();
+        let subscriptions = vec![cx.observe(&global_state, |_this, _state, cx| cx.notify())];
+        Self {
+            _subscriptions: subscriptions,
+        }
+    }
+    fn category_icon(category: &NotificationCategory, cx: &App) -> (IconName, Hsla) {
+        match category {
+            NotificationCategory::Info => (IconName::Info, cx.theme().primary),
+            NotificationCategory::Success => (IconName::CircleCheck, cx.theme().success),
+            NotificationCategory::Warning => (IconName::TriangleAlert, cx.theme().warning),
+            NotificationCategory::Error => (IconName::CircleX, cx.theme().danger),
+        }
+    }
+    fn open_server(server_id: SharedString, db: usize, window: &mut Window, cx: &mut App) {
+        let window_id = window.window_handle().window_id();
+        window.close_dialog(cx);
+        cx.global::<ZedisGlobalStore>().clone().update(cx, |state, cx| {
+            state.go_to(window_id, Route::Editor, cx);
+            state.set_selected_server(window_id, (server_id.to_string(), db), cx);
+        });
+    }
+}
+
+impl Render for ZedisNotificationCenter {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let history = cx.global::<ZedisGlobalStore>().read(cx).notification_history();
+        let open_server_tooltip = i18n_notification_center(cx, "open_server_tooltip");
+
+        let items = history.iter().rev().enumerate().map(|(index, record)| {
+            let notification = &record.notification;
+            let (icon, color) = Self::category_icon(&notification.category, cx);
+            // The server may have been removed since
+            let server = notification
+                .server
+                .clone()
+                .filter(|(server_id, _)| get_server(server_id).is_ok());
+            let text = match notification.title.as_ref() {
+                Some(title) => format!("{title}: {}", notification.message),
+                None => notification.message.to_string(),
+            };
+            h_flex()
+                .id(("notification-center-item", index))
+                .w_full()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .items_start()
+                .rounded_sm()
+                .child(Icon::new(icon).text_color(color).flex_none())
+                .child(
+                    Label::new(record.time.clone())
+                        .flex_none()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground),
+                )
+                .child(Label::new(text).text_sm().flex_1())
+                .when_some(server, |this, (server_id, db)| {
+                    this.cursor_pointer()
+                        .hover(|this| this.bg(cx.theme().secondary_hover))
+                        .tooltip({
+                            let tooltip = open_server_tooltip.clone();
+                            move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx)
+                        })
+                        .on_click(move |_, window, cx| {
+                            Self::open_server(server_id.clone(), db, window, cx);
+                        })
+                })
+        });
+
+        v_flex()
+            .gap_2()
+            .w_full()
+            .child(
+                h_flex().w_full().justify_end().child(
+                    Button::new("notification-center-clear")
+                        .ghost()
+                        .small()
+                        .label(i18n_notification_center(cx, "clear"))
+                        .disabled(history.is_empty())
+                        .on_click(|_, _window, cx| {
+                            cx.global::<ZedisGlobalStore>().clone().update(cx, |state, cx| {
+                                state.clear_notification_history();
+                                cx.notify();
+                            });
+                        }),
+                ),
+            )
+            .when(history.is_empty(), |this| {
+                this.child(
+                    Label::new(i18n_notification_center(cx, "no_notifications"))
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground),
+                )
+            })
+            .child(
+                v_flex()
+                    .id("notification-center-list")
+                    .w_full()
+                    .max_h(px(LIST_MAX_HEIGHT))
+                    .overflow_y_scroll()
+                    .children(items),
+            )
+    }
+}
+
+/// Open the notifications shown during the session, so that a missed error can be reviewed.
+pub fn open_notification_center(window: &mut Window, cx: &mut App) {
+    let center = cx.new(ZedisNotificationCenter::new);
+
+    ZedisDialog::new(i18n_notification_center(cx, "title"))
+        .alert()
+        .icon(IconName::Bell)
+        .child(move || center.clone())
+        .open(window, cx);
+}
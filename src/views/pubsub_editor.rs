@@ -33,9 +33,15 @@ use gpui_component::{
     table::{Column, DataTable, TableDelegate, TableState},
     v_flex,
 };
+use rust_i18n::t;
 use std::sync::Arc;
 use tracing::{error, info};
 
+/// Whether the channel contains glob characters, so it must be subscribed with `PSUBSCRIBE`.
+fn is_channel_pattern(channel: &str) -> bool {
+    channel.contains(['*', '?', '['])
+}
+
 /// A single message received from a Redis Pub/Sub channel.
 #[derive(Clone, Debug)]
 struct PubsubMessage {
@@ -297,8 +303,8 @@ impl ZedisPubsubEditor {
         }
     }
 
-    /// Starts a pattern-based subscription (`PSUBSCRIBE`).
-    /// The channel input supports space-separated patterns (e.g. "news.* alerts.*").
+    /// Subscribes to the channels of the input, `SUBSCRIBE` for exact names and
+    /// `PSUBSCRIBE` for glob patterns, separated by spaces (e.g. "orders news.* alerts.*").
     /// A background task is spawned that opens a dedicated Pub/Sub connection,
     /// subscribes, and then loops forever reading incoming messages until the
     /// stream ends or the entity is dropped.
@@ -322,11 +328,21 @@ impl ZedisPubsubEditor {
             let result: Result<_, Error> = cx
                 .background_spawn(async move {
                     let mut pubsub = get_connection_manager().get_pubsub_connection(&server_id).await?;
-                    let channels = channel_clone.split(' ').collect::<Vec<&str>>();
-                    pubsub
-                        .psubscribe(channels)
-                        .await
-                        .map_err(|e| Error::Invalid { message: e.to_string() })?;
+                    let (patterns, channels): (Vec<&str>, Vec<&str>) = channel_clone
+                        .split_whitespace()
+                        .partition(|channel| is_channel_pattern(channel));
+                    if !channels.is_empty() {
+                        pubsub
+                            .subscribe(channels)
+                            .await
+                            .map_err(|e| Error::Invalid { message: e.to_string() })?;
+                    }
+                    if !patterns.is_empty() {
+                        pubsub
+                            .psubscribe(patterns)
+                            .await
+                            .map_err(|e| Error::Invalid { message: e.to_string() })?;
+                    }
                     Ok(pubsub)
                 })
                 .await;
@@ -335,6 +351,15 @@ impl ZedisPubsubEditor {
                 Ok(mut pubsub) => {
                     let _ = entity.update(cx, |this, cx| {
                         this.subscribing = false;
+                        let message = t!(
+                            "pubsub_editor.subscribed",
+                            channel = channel,
+                            locale = cx.global::<ZedisGlobalStore>().read(cx).locale()
+                        )
+                        .to_string();
+                        this.server_state.update(cx, |state, cx| {
+                            state.emit_info_notification(message.into(), cx);
+                        });
                         cx.notify();
                     });
 
@@ -384,6 +409,10 @@ impl ZedisPubsubEditor {
                     error!("Pubsub subscribe error: {:?}", e);
                     let _ = entity.update(cx, |this, cx| {
                         this.subscribing = false;
+                        let message = format!("{}: {e}", i18n_pubsub_editor(cx, "subscribe_failed"));
+                        this.server_state.update(cx, |state, cx| {
+                            state.emit_error_notification(message.into(), cx);
+                        });
                         cx.notify();
                     });
                 }
@@ -0,0 +1,500 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    assets::CustomIconName,
+    connection::Capability,
+    helpers::{format_duration, get_font_family, get_home_dir},
+    states::{
+        KeyType, SearchPage, SearchResult, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common,
+        i18n_search_editor,
+    },
+};
+use ahash::AHashSet;
+use gpui::{Entity, SharedString, Subscription, Window, div, prelude::*, px, uniform_list};
+use gpui_component::{
+    ActiveTheme, Disableable, Icon, IconName, IndexPath, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    select::{Select, SelectState},
+    v_flex,
+};
+use humansize::{DECIMAL, format_size};
+use rust_i18n::t;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+/// Rows shown per page of the table
+const ROWS_PER_PAGE: usize = 100;
+const TYPE_COLUMN_WIDTH: f32 = 90.0;
+const TTL_COLUMN_WIDTH: f32 = 140.0;
+const SIZE_COLUMN_WIDTH: f32 = 100.0;
+/// Types of the type filter, after "all types"
+const SEARCH_KEY_TYPES: &[KeyType] = &[
+    KeyType::String,
+    KeyType::List,
+    KeyType::Set,
+    KeyType::Zset,
+    KeyType::Hash,
+    KeyType::Stream,
+    KeyType::Vectorset,
+    KeyType::Json,
+];
+
+/// Column the results are sorted by
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SearchColumn {
+    Key,
+    Type,
+    Ttl,
+    Size,
+}
+
+/// Advanced search: the keys matching a glob and an optional type, as a flat table
+/// with their TTL and memory usage.
+///
+/// Pages are scanned on demand with the cursors of the previous one, the loaded
+/// results are sorted client-side. Clicking a row opens the key in the editor,
+/// the results are kept until the search is opened again.
+pub struct ZedisSearchEditor {
+    server_state: Entity<ZedisServerState>,
+    pattern_state: Entity<InputState>,
+    type_state: Entity<SelectState<Vec<SharedString>>>,
+    /// Pattern and type of the results shown, pages of another search are ignored
+    search: Option<(SharedString, Option<KeyType>)>,
+    results: Arc<Vec<SearchResult>>,
+    /// Cursors of the next page, `None` once every key was scanned
+    cursors: Option<Vec<u64>>,
+    loading: bool,
+    sort: (SearchColumn, bool),
+    page: usize,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisSearchEditor {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let pattern_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .clean_on_escape()
+                .placeholder(i18n_search_editor(cx, "pattern_placeholder"))
+        });
+        let supports_json = server_state.read(cx).supports(Capability::Json);
+        let mut types = vec![i18n_search_editor(cx, "all_types")];
+        types.extend(
+            SEARCH_KEY_TYPES
+                .iter()
+                .filter(|key_type| **key_type != KeyType::Json || supports_json)
+                .filter_map(|key_type| key_type.redis_type_name())
+                .map(SharedString::from),
+        );
+        let type_state = cx.new(|cx| SelectState::new(types, Some(IndexPath::new(0)), window, cx));
+
+        let subscriptions = vec![
+            cx.subscribe(&server_state, |this, _server_state, event, cx| match event {
+                ServerEvent::SearchResultsLoaded(page) => {
+                    this.apply_page(page, cx);
+                }
+                ServerEvent::SearchFailed => {
+                    this.loading = false;
+                    cx.notify();
+                }
+                _ => {}
+            }),
+            cx.subscribe(&pattern_state, |this, _state, event: &InputEvent, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    this.handle_search(cx);
+                }
+            }),
+        ];
+        info!("Creating new search editor");
+
+        Self {
+            server_state,
+            pattern_state,
+            type_state,
+            search: None,
+            results: Arc::new(vec![]),
+            cursors: None,
+            loading: false,
+            sort: (SearchColumn::Key, false),
+            page: 0,
+            _subscriptions: subscriptions,
+        }
+    }
+    /// Start a new search with the pattern and the type of the toolbar
+    fn handle_search(&mut self, cx: &mut Context<Self>) {
+        let pattern: SharedString = self.pattern_state.read(cx).value().trim().to_string().into();
+        let key_type = self
+            .type_state
+            .read(cx)
+            .selected_value()
+            .map(|name| KeyType::from(name.as_str()))
+            .filter(|key_type| *key_type != KeyType::Unknown);
+        self.search = Some((pattern.clone(), key_type));
+        self.results = Arc::new(vec![]);
+        self.cursors = None;
+        self.page = 0;
+        self.loading = true;
+        self.server_state.update(cx, |state, cx| {
+            state.search_keys(pattern, key_type, None, cx);
+        });
+        cx.notify();
+    }
+    /// Scan the next page of the current search
+    fn handle_load_more(&mut self, cx: &mut Context<Self>) {
+        let (Some((pattern, key_type)), Some(cursors)) = (self.search.clone(), self.cursors.clone()) else {
+            return;
+        };
+        self.loading = true;
+        self.server_state.update(cx, |state, cx| {
+            state.search_keys(pattern, key_type, Some(cursors), cx);
+        });
+        cx.notify();
+    }
+    fn apply_page(&mut self, page: &SearchPage, cx: &mut Context<Self>) {
+        if self.search.as_ref() != Some(&(page.pattern.clone(), page.key_type)) {
+            return;
+        }
+        // SCAN may return a key more than once
+        let mut seen: AHashSet<SharedString> = self.results.iter().map(|result| result.key.clone()).collect();
+        let mut results = (*self.results).clone();
+        results.extend(
+            page.results
+                .iter()
+                .filter(|result| seen.insert(result.key.clone()))
+                .cloned(),
+        );
+        self.results = Arc::new(results);
+        self.cursors = page.cursors.clone();
+        self.loading = false;
+        self.sort_results();
+        cx.notify();
+    }
+    /// Sort by the column, clicking the sorted column again reverses the order
+    fn handle_sort(&mut self, column: SearchColumn, cx: &mut Context<Self>) {
+        self.sort = if self.sort.0 == column {
+            (column, !self.sort.1)
+        } else {
+            (column, false)
+        };
+        self.page = 0;
+        self.sort_results();
+        cx.notify();
+    }
+    fn sort_results(&mut self) {
+        let (column, descending) = self.sort;
+        let mut results = (*self.results).clone();
+        results.sort_by(|a, b| {
+            let order = match column {
+                SearchColumn::Key => a.key.cmp(&b.key),
+                SearchColumn::Type => a.key_type.as_str().cmp(b.key_type.as_str()),
+                // Keys without expiry last
+                SearchColumn::Ttl => (a.ttl < 0, a.ttl).cmp(&(b.ttl < 0, b.ttl)),
+                SearchColumn::Size => a.size.cmp(&b.size),
+            };
+            let order = order.then_with(|| a.key.cmp(&b.key));
+            if descending { order.reverse() } else { order }
+        });
+        self.results = Arc::new(results);
+    }
+    /// Pick the file, then write the loaded results to it as CSV
+    fn handle_export(&mut self, cx: &mut Context<Self>) {
+        if self.results.is_empty() {
+            return;
+        }
+        let results = self.results.clone();
+        let dir = get_home_dir().unwrap_or_default();
+        let path = cx.prompt_for_new_path(&dir, Some("search-results.csv"));
+        let server_state = self.server_state.clone();
+        cx.spawn(async move |_, cx| {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            server_state.update(cx, |state, cx| {
+                state.export_search_results(path, results, cx);
+            });
+        })
+        .detach();
+    }
+    fn page_count(&self) -> usize {
+        self.results.len().div_ceil(ROWS_PER_PAGE).max(1)
+    }
+    /// Renders the toolbar: the pattern input, the type filter, search and export.
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .items_center()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(Input::new(&self.pattern_state).w_full().flex_1().cleanable(true))
+            .child(Select::new(&self.type_state).w(px(160.)))
+            .child(
+                Button::new("search-editor-search-btn")
+                    .outline()
+                    .icon(IconName::Search)
+                    .label(i18n_search_editor(cx, "search"))
+                    .loading(self.loading)
+                    .disabled(self.loading)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.handle_search(cx);
+                    })),
+            )
+            .child(
+                Button::new("search-editor-export-btn")
+                    .outline()
+                    .icon(CustomIconName::Download)
+                    .tooltip(i18n_search_editor(cx, "export_tooltip"))
+                    .disabled(self.results.is_empty())
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.handle_export(cx);
+                    })),
+            )
+    }
+    /// Renders a column header, clicking it sorts the results by the column.
+    fn render_header_cell(&self, column: SearchColumn, name: &str, cx: &mut Context<Self>) -> impl IntoElement {
+        let (sorted_column, descending) = self.sort;
+        let icon = if descending {
+            IconName::ArrowDown
+        } else {
+            IconName::ArrowUp
+        };
+        h_flex()
+            .id(SharedString::from(format!("search-editor-header-{name}")))
+            .gap_1()
+            .items_center()
+            .cursor_pointer()
+            .text_color(cx.theme().primary)
+            .child(Label::new(i18n_search_editor(cx, name)).text_sm())
+            .when(sorted_column == column, |this| this.child(Icon::new(icon).xsmall()))
+            .on_click(cx.listener(move |this, _, _window, cx| {
+                this.handle_sort(column, cx);
+            }))
+    }
+    /// Renders the pagination: the range shown, previous/next and loading more keys.
+    fn render_footer(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let total = self.results.len();
+        let start = (self.page * ROWS_PER_PAGE).min(total);
+        let end = (start + ROWS_PER_PAGE).min(total);
+        // Rows are numbered from 1
+        let first = if total == 0 { 0 } else { start + 1 };
+        let mut summary = t!(
+            "search_editor.summary",
+            start = first,
+            end = end,
+            total = total,
+            locale = locale
+        )
+        .to_string();
+        if self.cursors.is_some() {
+            summary.push_str(&i18n_search_editor(cx, "more_keys"));
+        }
+        let page = self.page;
+        let page_count = self.page_count();
+
+        h_flex()
+            .w_full()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .items_center()
+            .border_t_1()
+            .border_color(cx.theme().border)
+            .child(
+                Label::new(summary)
+                    .flex_1()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground),
+            )
+            .child(
+                Button::new("search-editor-prev-btn")
+                    .ghost()
+                    .icon(IconName::ChevronLeft)
+                    .tooltip(i18n_search_editor(cx, "previous_page"))
+                    .disabled(page == 0)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.page = this.page.saturating_sub(1);
+                        cx.notify();
+                    })),
+            )
+            .child(Label::new(format!("{} / {page_count}", page + 1)).text_xs())
+            .child(
+                Button::new("search-editor-next-btn")
+                    .ghost()
+                    .icon(IconName::ChevronRight)
+                    .tooltip(i18n_search_editor(cx, "next_page"))
+                    .disabled(page + 1 >= page_count)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.page = (this.page + 1).min(this.page_count() - 1);
+                        cx.notify();
+                    })),
+            )
+            .when(self.cursors.is_some(), |this| {
+                this.child(
+                    Button::new("search-editor-load-more-btn")
+                        .outline()
+                        .label(i18n_search_editor(cx, "load_more"))
+                        .loading(self.loading)
+                        .disabled(self.loading)
+                        .on_click(cx.listener(|this, _, _window, cx| {
+                            this.handle_load_more(cx);
+                        })),
+                )
+            })
+    }
+}
+
+impl Render for ZedisSearchEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // The results may have shrunk since the page was chosen
+        self.page = self.page.min(self.page_count() - 1);
+        let start = (self.page * ROWS_PER_PAGE).min(self.results.len());
+        let end = (start + ROWS_PER_PAGE).min(self.results.len());
+        let results = self.results.clone();
+        let is_empty = results.is_empty();
+        let hover_color = cx.theme().secondary_hover;
+        let muted_color = cx.theme().muted_foreground;
+        let permanent = i18n_common(cx, "permanent");
+        let font_family = get_font_family();
+        let server_state = self.server_state.clone();
+
+        v_flex()
+            .size_full()
+            .overflow_hidden()
+            .child(self.render_toolbar(cx))
+            .child(
+                h_flex()
+                    .w_full()
+                    .px_3()
+                    .py_1()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        div()
+                            .flex_1()
+                            .child(self.render_header_cell(SearchColumn::Key, "key", cx)),
+                    )
+                    .child(
+                        div()
+                            .flex_none()
+                            .w(px(TYPE_COLUMN_WIDTH))
+                            .child(self.render_header_cell(SearchColumn::Type, "type", cx)),
+                    )
+                    .child(div().flex_none().w(px(TTL_COLUMN_WIDTH)).child(self.render_header_cell(
+                        SearchColumn::Ttl,
+                        "ttl",
+                        cx,
+                    )))
+                    .child(
+                        div()
+                            .flex_none()
+                            .w(px(SIZE_COLUMN_WIDTH))
+                            .child(self.render_header_cell(SearchColumn::Size, "size", cx)),
+                    ),
+            )
+            .when(is_empty, |this| {
+                let message = if self.search.is_some() && !self.loading {
+                    i18n_search_editor(cx, "no_results")
+                } else {
+                    i18n_search_editor(cx, "search_hint")
+                };
+                this.child(
+                    div()
+                        .flex_1()
+                        .w_full()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .child(Label::new(message).text_color(muted_color)),
+                )
+            })
+            .when(!is_empty, |this| {
+                this.child(
+                    uniform_list("search-editor-results", end - start, move |range, _window, _cx| {
+                        range
+                            .filter_map(|index| {
+                                let result = results.get(start + index)?;
+                                let key = result.key.clone();
+                                let ttl: SharedString = if result.ttl < 0 {
+                                    permanent.clone()
+                                } else {
+                                    format_duration(Duration::from_secs(result.ttl as u64)).into()
+                                };
+                                let size: SharedString = result
+                                    .size
+                                    .map(|size| format_size(size, DECIMAL))
+                                    .unwrap_or_else(|| "--".to_string())
+                                    .into();
+                                let server_state = server_state.clone();
+                                Some(
+                                    h_flex()
+                                        .id(("search-editor-result", start + index))
+                                        .w_full()
+                                        .px_3()
+                                        .py_1()
+                                        .gap_2()
+                                        .text_sm()
+                                        .cursor_pointer()
+                                        .hover(|this| this.bg(hover_color))
+                                        .child(
+                                            Label::new(key.clone())
+                                                .flex_1()
+                                                .font_family(font_family.clone())
+                                                .text_ellipsis()
+                                                .whitespace_nowrap(),
+                                        )
+                                        .child(
+                                            Label::new(result.key_type.as_str())
+                                                .flex_none()
+                                                .w(px(TYPE_COLUMN_WIDTH))
+                                                .text_xs()
+                                                .text_color(result.key_type.color()),
+                                        )
+                                        .child(
+                                            Label::new(ttl)
+                                                .flex_none()
+                                                .w(px(TTL_COLUMN_WIDTH))
+                                                .text_xs()
+                                                .text_color(muted_color),
+                                        )
+                                        .child(
+                                            Label::new(size)
+                                                .flex_none()
+                                                .w(px(SIZE_COLUMN_WIDTH))
+                                                .text_xs()
+                                                .text_color(muted_color),
+                                        )
+                                        .on_click(move |_, _window, cx| {
+                                            server_state.update(cx, |state, cx| {
+                                                state.reveal_key(key.clone(), cx);
+                                            });
+                                        }),
+                                )
+                            })
+                            .collect()
+                    })
+                    .flex_1()
+                    .w_full(),
+                )
+            })
+            .child(self.render_footer(cx))
+    }
+}
@@ -13,20 +13,23 @@
 // limitations under the License.
 
 use crate::assets::CustomIconName;
-use crate::connection::{RedisServer, get_servers};
+use crate::connection::{
+    MAX_POOL_SIZE, MAX_SCAN_COUNT, RedisServer, get_servers, test_connection, validate_connection_url,
+};
 use crate::states::{Route, ZedisGlobalStore, dialog_button_props, i18n_common, i18n_servers};
-use gpui::{SharedString, Window, div, prelude::*, px};
+use gpui::{AnyElement, Entity, SharedString, Window, div, prelude::*, px};
 use gpui_component::{
-    ActiveTheme, Colorize, Icon, IconName, WindowExt,
+    ActiveTheme, Colorize, Disableable, Icon, IconName, WindowExt,
     button::{Button, ButtonVariants},
     label::Label,
 };
 use rust_i18n::t;
+use std::time::Duration;
 use substring::Substring;
 use tracing::info;
 use zedis_ui::ZedisCard;
 use zedis_ui::ZedisDialog;
-use zedis_ui::{ZedisFormField, ZedisFormFieldType, ZedisFormOptions};
+use zedis_ui::{ZedisForm, ZedisFormField, ZedisFormFieldType, ZedisFormOptions};
 
 // Constants for UI layout
 const DEFAULT_REDIS_PORT: u16 = 6379;
@@ -35,6 +38,36 @@ const VIEWPORT_BREAKPOINT_MEDIUM: f32 = 1200.0; // Two columns
 const UPDATED_AT_SUBSTRING_LENGTH: usize = 10; // Length of date string to display
 const THEME_LIGHTEN_AMOUNT_DARK: f32 = 1.0;
 const THEME_DARKEN_AMOUNT_LIGHT: f32 = 0.02;
+const TEST_RESULT_MAX_WIDTH: f32 = 360.0;
+
+/// State of the "Test" button of the server form
+enum ConnectionTest {
+    Testing,
+    /// Latency of the `PING`
+    Succeeded(Duration),
+    Failed(SharedString),
+}
+
+/// Server card being dragged to change the display order of the servers
+#[derive(Clone)]
+struct DraggedServer {
+    id: String,
+    name: SharedString,
+}
+
+impl Render for DraggedServer {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_3()
+            .py_1()
+            .text_sm()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .bg(cx.theme().background)
+            .child(self.name.clone())
+    }
+}
 
 /// Server management view component
 ///
@@ -43,6 +76,7 @@ const THEME_DARKEN_AMOUNT_LIGHT: f32 = 0.02;
 /// - Action buttons (edit, delete)
 /// - Add new server card
 /// - Click to connect functionality
+/// - Drag a card onto another one to reorder the servers
 ///
 /// Uses a responsive grid layout that adjusts columns based on viewport width.
 pub struct ZedisServers {}
@@ -91,10 +125,40 @@ impl ZedisServers {
         let server_type_list = i18n_servers(cx, "server_type_list");
         let validate_host = |s: &str| {
             if s.len() <= 1024 && s.is_ascii() {
-                return None;
+                return validate_connection_url(s).map(Into::into);
             }
             Some("host is invalid".into())
         };
+        let validate_timeout = |s: &str| {
+            if s.is_empty() || s.parse::<u64>().is_ok_and(|v| v > 0) {
+                return None;
+            }
+            Some("timeout should be a positive number of seconds".into())
+        };
+        let validate_db = |s: &str| {
+            if s.is_empty() || s.parse::<usize>().is_ok() {
+                return None;
+            }
+            Some("db should be a non-negative number".into())
+        };
+        let validate_pool_size = |s: &str| {
+            if s.is_empty() || s.parse::<usize>().is_ok_and(|v| (1..=MAX_POOL_SIZE).contains(&v)) {
+                return None;
+            }
+            Some(format!("pool size should be between 1 and {MAX_POOL_SIZE}").into())
+        };
+        let validate_idle_timeout = |s: &str| {
+            if s.is_empty() || s.parse::<u64>().is_ok_and(|v| v > 0) {
+                return None;
+            }
+            Some("idle timeout should be a positive number of seconds".into())
+        };
+        let validate_scan_count = |s: &str| {
+            if s.is_empty() || s.parse::<usize>().is_ok_and(|v| (1..=MAX_SCAN_COUNT).contains(&v)) {
+                return None;
+            }
+            Some(format!("scan count should be between 1 and {MAX_SCAN_COUNT}").into())
+        };
 
         let fields = vec![
             ZedisFormField::new("name", i18n_common(cx, "name"))
@@ -197,7 +261,43 @@ impl ZedisServers {
                 .placeholder(i18n_servers(cx, "readonly_check_label"))
                 .tab_index(3)
                 .field_type(ZedisFormFieldType::Checkbox),
+            ZedisFormField::new("pool_size", i18n_servers(cx, "pool_size"))
+                .default_value(redis_server.pool_size.map(|v| v.to_string()).unwrap_or_default())
+                .placeholder(i18n_servers(cx, "pool_size_placeholder"))
+                .tab_index(3)
+                .validate(validate_pool_size),
+            ZedisFormField::new("idle_timeout", i18n_servers(cx, "idle_timeout"))
+                .default_value(redis_server.idle_timeout.map(|v| v.to_string()).unwrap_or_default())
+                .placeholder(i18n_servers(cx, "idle_timeout_placeholder"))
+                .tab_index(3)
+                .validate(validate_idle_timeout),
+            ZedisFormField::new("scan_count", i18n_servers(cx, "scan_count"))
+                .default_value(redis_server.scan_count.map(|v| v.to_string()).unwrap_or_default())
+                .placeholder(i18n_servers(cx, "scan_count_placeholder"))
+                .tab_index(3)
+                .validate(validate_scan_count),
+            ZedisFormField::new("db", i18n_servers(cx, "db"))
+                .default_value(redis_server.db.map(|v| v.to_string()).unwrap_or_default())
+                .placeholder(i18n_servers(cx, "db_placeholder"))
+                .tab_index(3)
+                .validate(validate_db),
+            ZedisFormField::new("connection_timeout", i18n_servers(cx, "connection_timeout"))
+                .default_value(
+                    redis_server
+                        .connection_timeout
+                        .map(|v| v.to_string())
+                        .unwrap_or_default(),
+                )
+                .placeholder(i18n_servers(cx, "connection_timeout_placeholder"))
+                .tab_index(3)
+                .validate(validate_timeout),
+            ZedisFormField::new("response_timeout", i18n_servers(cx, "response_timeout"))
+                .default_value(redis_server.response_timeout.map(|v| v.to_string()).unwrap_or_default())
+                .placeholder(i18n_servers(cx, "response_timeout_placeholder"))
+                .tab_index(3)
+                .validate(validate_timeout),
         ];
+        let connection_test: Entity<Option<ConnectionTest>> = cx.new(|_| None);
         let title = if is_new {
             i18n_servers(cx, "add_server_title")
         } else {
@@ -216,6 +316,7 @@ impl ZedisServers {
             .confirm_label(i18n_common(cx, "confirm"))
             .cancel_label(i18n_common(cx, "cancel"))
             .dialog_max_height(max_h)
+            .foot_actions(move |_window, cx| Self::render_connection_test(&connection_test, cx))
             .on_dialog_submit(move |values, _window, cx| {
                 let redis_server = RedisServer::from_form_data(&server_id, &values);
                 cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
@@ -227,6 +328,77 @@ impl ZedisServers {
             })
             .open_dialog(window, cx);
     }
+    /// Connect with the values of the form, without saving them, and show the result inline
+    fn test_connection(
+        connection_test: &Entity<Option<ConnectionTest>>,
+        form: &mut ZedisForm,
+        cx: &mut Context<ZedisForm>,
+    ) {
+        let Some(values) = form.try_get_values(cx) else {
+            return;
+        };
+        let redis_server = RedisServer::from_form_data("", &values);
+        connection_test.update(cx, |state, _cx| {
+            *state = Some(ConnectionTest::Testing);
+        });
+        cx.notify();
+        let connection_test = connection_test.clone();
+        cx.spawn(async move |form, cx| {
+            let result = cx
+                .background_spawn(async move { test_connection(&redis_server).await })
+                .await;
+            let _ = form.update(cx, |_form, cx| {
+                connection_test.update(cx, |state, _cx| {
+                    *state = Some(match result {
+                        Ok(latency) => ConnectionTest::Succeeded(latency),
+                        Err(e) => ConnectionTest::Failed(e.to_string().into()),
+                    });
+                });
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+    fn render_connection_test(
+        connection_test: &Entity<Option<ConnectionTest>>,
+        cx: &mut Context<ZedisForm>,
+    ) -> Vec<AnyElement> {
+        let is_testing = matches!(connection_test.read(cx), Some(ConnectionTest::Testing));
+        let result = match connection_test.read(cx) {
+            Some(ConnectionTest::Succeeded(latency)) => {
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                let message = t!(
+                    "servers.test_connection_success",
+                    latency = latency.as_millis(),
+                    locale = locale
+                );
+                Some((SharedString::from(message.to_string()), cx.theme().success))
+            }
+            Some(ConnectionTest::Failed(message)) => Some((message.clone(), cx.theme().danger)),
+            _ => None,
+        };
+        let state = connection_test.clone();
+        let button = Button::new("server-test-connection")
+            .outline()
+            .label(i18n_servers(cx, "test_connection"))
+            .loading(is_testing)
+            .disabled(is_testing)
+            .on_click(cx.listener(move |form, _, _window, cx| {
+                Self::test_connection(&state, form, cx);
+            }));
+        let mut elements = vec![button.into_any_element()];
+        if let Some((message, color)) = result {
+            elements.push(
+                div()
+                    .max_w(px(TEST_RESULT_MAX_WIDTH))
+                    .text_sm()
+                    .text_color(color)
+                    .child(message)
+                    .into_any_element(),
+            );
+        }
+        elements
+    }
 }
 
 impl Render for ZedisServers {
@@ -264,8 +436,14 @@ impl Render for ZedisServers {
             .map(|(index, server)| {
                 // Clone values for use in closures
                 let select_server_id = server.id.clone();
+                let select_db = server.db();
                 let update_server = server.clone();
                 let remove_server_id = server.id.clone();
+                let drop_server_id = server.id.clone();
+                let dragged_server = DraggedServer {
+                    id: server.id.clone(),
+                    name: server.name.clone().into(),
+                };
 
                 let description = server.description.as_deref().unwrap_or_default();
 
@@ -301,20 +479,21 @@ impl Render for ZedisServers {
                 ];
 
                 // Card click handler - connect to server and navigate to editor
-                let handle_select_server = cx.listener(move |_this, _, _, cx| {
+                let handle_select_server = cx.listener(move |_this, _, window, cx| {
                     let select_server_id = select_server_id.clone();
+                    let window_id = window.window_handle().window_id();
 
                     // Navigate to editor view
                     cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
                         store.update(cx, |state, cx| {
-                            state.go_to(Route::Editor, cx);
-                            state.set_selected_server((select_server_id.clone(), 0), cx);
+                            state.go_to(window_id, Route::Editor, cx);
+                            state.set_selected_server(window_id, (select_server_id.clone(), select_db), cx);
                         });
                     });
                 });
 
                 // Build server card with conditional footer
-                ZedisCard::new(("servers-card", index))
+                let card = ZedisCard::new(("servers-card", index))
                     .icon(Icon::new(CustomIconName::DatabaseZap))
                     .title(title)
                     .bg(bg)
@@ -331,7 +510,24 @@ impl Render for ZedisServers {
                         )
                     })
                     .actions(actions)
-                    .on_click(Box::new(handle_select_server))
+                    .on_click(Box::new(handle_select_server));
+
+                // Dropping a card on another one moves it to that position
+                div()
+                    .id(("servers-card-drag", index))
+                    .rounded(cx.theme().radius)
+                    .on_drag(dragged_server, |dragged, _, _, cx| cx.new(|_| dragged.clone()))
+                    .drag_over::<DraggedServer>(|style, _, _, cx| style.bg(cx.theme().drop_target))
+                    .on_drop(cx.listener(move |_this, dragged: &DraggedServer, _window, cx| {
+                        let id = dragged.id.clone();
+                        let target_id = drop_server_id.clone();
+                        cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
+                            store.update(cx, |state, cx| {
+                                state.move_server(&id, &target_id, cx);
+                            });
+                        });
+                    }))
+                    .child(card)
             })
             .collect();
 
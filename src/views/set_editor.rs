@@ -96,6 +96,21 @@ impl ZedisKvFetcher for ZedisSetValues {
         });
     }
 
+    fn can_sample(&self, _cx: &App) -> bool {
+        true
+    }
+
+    /// Loads a random sample of members via SRANDMEMBER.
+    fn sample(&self, count: usize, cx: &mut App) {
+        self.server_state.update(cx, |this, cx| {
+            this.sample_set_value(count, cx);
+        });
+    }
+
+    fn is_sample(&self) -> bool {
+        self.value.set_value().is_some_and(|v| v.sample)
+    }
+
     /// Applies a filter to SET members by pattern matching.
     ///
     /// Resets the scan and loads members matching the keyword pattern.
@@ -135,6 +150,22 @@ impl ZedisKvFetcher for ZedisSetValues {
             this.remove_set_value(value.clone(), cx);
         });
     }
+
+    /// The removed member.
+    fn restore_values(&self, index: usize) -> Option<Vec<SharedString>> {
+        let value = self.value.set_value()?.values.get(index)?;
+        Some(vec![value.clone()])
+    }
+
+    /// Adds the removed member again with SADD.
+    fn restore(&self, values: Vec<SharedString>, cx: &mut App) {
+        let Some(value) = values.into_iter().next() else {
+            return;
+        };
+        self.server_state.update(cx, |this, cx| {
+            this.add_set_value(value, cx);
+        });
+    }
 }
 
 define_kv_editor!(ZedisSetEditor, ZedisSetValues);
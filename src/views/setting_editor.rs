@@ -13,21 +13,30 @@
 // limitations under the License.
 
 use crate::{
-    helpers::{get_or_create_config_dir, parse_duration},
-    states::{ZedisGlobalStore, i18n_settings, update_app_state_and_save},
+    helpers::{get_home_dir, get_or_create_config_dir, parse_duration},
+    states::{ImportMode, ZedisGlobalStore, i18n_common, i18n_settings, update_app_state_and_save},
 };
-use gpui::{Entity, Subscription, Window, prelude::*, px};
+use gpui::{App, Entity, PathPromptOptions, Subscription, Window, prelude::*, px};
 use gpui_component::{
+    button::Button,
+    checkbox::Checkbox,
     form::{Field, field, v_form},
+    h_flex,
     input::{Input, InputEvent, InputState, NumberInput, NumberInputEvent, StepAction},
     label::Label,
     v_flex,
 };
+use zedis_ui::{ZedisFormField, ZedisFormFieldType, ZedisFormOptions};
+
+const SETTINGS_FILE_NAME: &str = "zedis-settings.json";
 
 pub struct ZedisSettingEditor {
     max_key_tree_depth_state: Entity<InputState>,
     key_separator_state: Entity<InputState>,
     max_truncate_length_state: Entity<InputState>,
+    large_value_threshold_state: Entity<InputState>,
+    large_collection_threshold_state: Entity<InputState>,
+    ttl_warning_threshold_state: Entity<InputState>,
     config_dir_state: Entity<InputState>,
     key_scan_count_state: Entity<InputState>,
     auto_expand_threshold_state: Entity<InputState>,
@@ -77,6 +86,9 @@ impl ZedisSettingEditor {
         let key_separator = store.key_separator().to_string();
         let auto_expand_threshold = store.auto_expand_threshold();
         let max_truncate_length = store.max_truncate_length();
+        let large_value_threshold = store.large_value_threshold_kb();
+        let large_collection_threshold = store.large_collection_threshold();
+        let ttl_warning_threshold = store.ttl_warning_threshold();
         let redis_connection_timeout = store.redis_connection_timeout();
         let redis_response_timeout = store.redis_response_timeout();
         let key_scan_count = store.key_scan_count();
@@ -110,6 +122,27 @@ impl ZedisSettingEditor {
             max_truncate_length.to_string(),
             Some(|s| s.parse::<usize>().is_ok()),
         );
+        let large_value_threshold_state = Self::create_input_state(
+            window,
+            cx,
+            "large_value_threshold_placeholder",
+            large_value_threshold.to_string(),
+            Some(|s| s.parse::<usize>().is_ok()),
+        );
+        let large_collection_threshold_state = Self::create_input_state(
+            window,
+            cx,
+            "large_collection_threshold_placeholder",
+            large_collection_threshold.to_string(),
+            Some(|s| s.parse::<usize>().is_ok()),
+        );
+        let ttl_warning_threshold_state = Self::create_input_state(
+            window,
+            cx,
+            "ttl_warning_threshold_placeholder",
+            ttl_warning_threshold.to_string(),
+            Some(|s| s.parse::<u64>().is_ok()),
+        );
         let redis_connection_timeout_state = Self::create_input_state(
             window,
             cx,
@@ -233,6 +266,50 @@ impl ZedisSettingEditor {
                 }
             },
         ));
+
+        // Large Value Threshold (KB)
+        subscriptions.push(Self::bind_blur_save(
+            cx,
+            &large_value_threshold_state,
+            window,
+            |text, cx| {
+                if let Ok(value) = text.parse::<usize>()
+                    && value >= 1
+                {
+                    update_app_state_and_save(cx, "save_large_value_threshold", move |state, _| {
+                        state.set_large_value_threshold(value);
+                    });
+                }
+            },
+        ));
+        // Large Collection Threshold (members)
+        subscriptions.push(Self::bind_blur_save(
+            cx,
+            &large_collection_threshold_state,
+            window,
+            |text, cx| {
+                if let Ok(value) = text.parse::<usize>()
+                    && value >= 1
+                {
+                    update_app_state_and_save(cx, "save_large_collection_threshold", move |state, _| {
+                        state.set_large_collection_threshold(value);
+                    });
+                }
+            },
+        ));
+        // TTL Warning Threshold (seconds), 0 disables the warning
+        subscriptions.push(Self::bind_blur_save(
+            cx,
+            &ttl_warning_threshold_state,
+            window,
+            |text, cx| {
+                if let Ok(value) = text.parse::<u64>() {
+                    update_app_state_and_save(cx, "save_ttl_warning_threshold", move |state, _| {
+                        state.set_ttl_warning_threshold(value);
+                    });
+                }
+            },
+        ));
         let config_dir_state =
             cx.new(|cx| InputState::new(window, cx).default_value(config_dir.to_string_lossy().to_string()));
 
@@ -242,6 +319,9 @@ impl ZedisSettingEditor {
             config_dir_state,
             auto_expand_threshold_state,
             max_truncate_length_state,
+            large_value_threshold_state,
+            large_collection_threshold_state,
+            ttl_warning_threshold_state,
             key_separator_state,
             max_key_tree_depth_state,
             redis_response_timeout_state,
@@ -251,6 +331,83 @@ impl ZedisSettingEditor {
     fn render_field(cx: &Context<Self>, label_key: &str, input_element: impl IntoElement) -> Field {
         field().label(i18n_settings(cx, label_key)).child(input_element)
     }
+    /// Ask how secrets are stored, then where the settings file is written.
+    fn handle_export_settings(window: &mut Window, cx: &mut App) {
+        let fields = vec![
+            ZedisFormField::new("secrets", i18n_settings(cx, "export_secrets"))
+                .field_type(ZedisFormFieldType::RadioGroup)
+                .options(vec![
+                    i18n_settings(cx, "export_secrets_redact"),
+                    i18n_settings(cx, "export_secrets_encrypt"),
+                ]),
+        ];
+        ZedisFormOptions::new(fields)
+            .title(i18n_settings(cx, "export_settings"))
+            .description(i18n_settings(cx, "export_secrets_warning"))
+            .confirm_label(i18n_common(cx, "confirm"))
+            .cancel_label(i18n_common(cx, "cancel"))
+            .on_dialog_submit(|values, _window, cx| {
+                let encrypt_secrets = values.get("secrets").is_some_and(|v| v == "1");
+                let dir = get_home_dir().unwrap_or_default();
+                let path = cx.prompt_for_new_path(&dir, Some(SETTINGS_FILE_NAME));
+                let store = cx.global::<ZedisGlobalStore>().clone();
+                cx.spawn(async move |cx| {
+                    let Ok(Ok(Some(path))) = path.await else {
+                        return;
+                    };
+                    store.update(cx, |state, cx| {
+                        state.export_settings(path, encrypt_secrets, cx);
+                    });
+                })
+                .detach();
+                true
+            })
+            .open_dialog(window, cx);
+    }
+    /// Ask whether to merge or replace, then pick the settings file to import.
+    fn handle_import_settings(window: &mut Window, cx: &mut App) {
+        let fields = vec![
+            ZedisFormField::new("mode", i18n_settings(cx, "import_mode"))
+                .field_type(ZedisFormFieldType::RadioGroup)
+                .options(vec![
+                    i18n_settings(cx, "import_mode_merge"),
+                    i18n_settings(cx, "import_mode_replace"),
+                ]),
+        ];
+        ZedisFormOptions::new(fields)
+            .title(i18n_settings(cx, "import_settings"))
+            .description(i18n_settings(cx, "import_settings_tips"))
+            .confirm_label(i18n_common(cx, "confirm"))
+            .cancel_label(i18n_common(cx, "cancel"))
+            .on_dialog_submit(|values, _window, cx| {
+                let mode = if values.get("mode").is_some_and(|v| v == "1") {
+                    ImportMode::Replace
+                } else {
+                    ImportMode::Merge
+                };
+                let paths = cx.prompt_for_paths(PathPromptOptions {
+                    files: true,
+                    directories: false,
+                    multiple: false,
+                    prompt: None,
+                });
+                let store = cx.global::<ZedisGlobalStore>().clone();
+                cx.spawn(async move |cx| {
+                    let Ok(Ok(Some(paths))) = paths.await else {
+                        return;
+                    };
+                    let Some(path) = paths.into_iter().next() else {
+                        return;
+                    };
+                    store.update(cx, |state, cx| {
+                        state.import_settings(path, mode, cx);
+                    });
+                })
+                .detach();
+                true
+            })
+            .open_dialog(window, cx);
+    }
 }
 
 impl Render for ZedisSettingEditor {
@@ -289,6 +446,21 @@ impl Render for ZedisSettingEditor {
                         "max_truncate_length",
                         Input::new(&self.max_truncate_length_state),
                     ))
+                    .child(Self::render_field(
+                        cx,
+                        "large_value_threshold",
+                        Input::new(&self.large_value_threshold_state),
+                    ))
+                    .child(Self::render_field(
+                        cx,
+                        "large_collection_threshold",
+                        Input::new(&self.large_collection_threshold_state),
+                    ))
+                    .child(Self::render_field(
+                        cx,
+                        "ttl_warning_threshold",
+                        Input::new(&self.ttl_warning_threshold_state),
+                    ))
                     .child(Self::render_field(
                         cx,
                         "redis_connection_timeout",
@@ -299,11 +471,69 @@ impl Render for ZedisSettingEditor {
                         "redis_response_timeout",
                         Input::new(&self.redis_response_timeout_state),
                     ))
+                    .child(Self::render_field(
+                        cx,
+                        "optimistic_save",
+                        Checkbox::new("optimistic-save")
+                            .label(i18n_settings(cx, "optimistic_save_check_label"))
+                            .checked(cx.global::<ZedisGlobalStore>().read(cx).optimistic_save())
+                            .on_click(|checked, _window, cx| {
+                                let checked = *checked;
+                                update_app_state_and_save(cx, "save_optimistic_save", move |state, _| {
+                                    state.set_optimistic_save(checked);
+                                });
+                            }),
+                    ))
+                    .child(Self::render_field(
+                        cx,
+                        "allow_dangerous_commands",
+                        Checkbox::new("allow-dangerous-commands")
+                            .label(i18n_settings(cx, "allow_dangerous_commands_check_label"))
+                            .checked(cx.global::<ZedisGlobalStore>().read(cx).allow_dangerous_commands())
+                            .on_click(|checked, _window, cx| {
+                                let checked = *checked;
+                                update_app_state_and_save(cx, "save_allow_dangerous_commands", move |state, _| {
+                                    state.set_allow_dangerous_commands(checked);
+                                });
+                            }),
+                    ))
+                    .child(Self::render_field(
+                        cx,
+                        "confirm_remove_value",
+                        Checkbox::new("confirm-remove-value")
+                            .label(i18n_settings(cx, "confirm_remove_value_check_label"))
+                            .checked(cx.global::<ZedisGlobalStore>().read(cx).confirm_remove_value())
+                            .on_click(|checked, _window, cx| {
+                                let checked = *checked;
+                                update_app_state_and_save(cx, "save_confirm_remove_value", move |state, _| {
+                                    state.set_confirm_remove_value(checked);
+                                });
+                            }),
+                    ))
                     .child(
                         field()
                             .col_span(cols as u16)
                             .label(i18n_settings(cx, "config_dir"))
                             .child(Input::new(&self.config_dir_state).disabled(true)),
+                    )
+                    .child(
+                        field()
+                            .col_span(cols as u16)
+                            .label(i18n_settings(cx, "configuration"))
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .child(
+                                        Button::new("settings-export")
+                                            .label(i18n_settings(cx, "export_settings"))
+                                            .on_click(|_, window, cx| Self::handle_export_settings(window, cx)),
+                                    )
+                                    .child(
+                                        Button::new("settings-import")
+                                            .label(i18n_settings(cx, "import_settings"))
+                                            .on_click(|_, window, cx| Self::handle_import_settings(window, cx)),
+                                    ),
+                            ),
                     ),
             )
     }
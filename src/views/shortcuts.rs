@@ -0,0 +1,60 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    helpers::{get_font_family, get_keymap_path, get_shortcuts, humanize_keystroke},
+    states::i18n_shortcuts,
+};
+use gpui::{App, SharedString, Window, prelude::*};
+use gpui_component::{IconName, h_flex, label::Label, v_flex};
+use zedis_ui::ZedisDialog;
+
+/// Open the cheat-sheet listing the bound keyboard shortcuts.
+pub fn open_shortcuts_dialog(window: &mut Window, cx: &mut App) {
+    let rows: Vec<(SharedString, SharedString)> = get_shortcuts()
+        .iter()
+        .map(|shortcut| {
+            let keystrokes = shortcut
+                .keystrokes
+                .split_whitespace()
+                .map(humanize_keystroke)
+                .collect::<Vec<_>>()
+                .join(" ");
+            (i18n_shortcuts(cx, shortcut.name), keystrokes.into())
+        })
+        .collect();
+    let tips: SharedString = match get_keymap_path() {
+        Some(path) => format!("{} {}", i18n_shortcuts(cx, "customize_tips"), path.display()).into(),
+        None => SharedString::default(),
+    };
+
+    ZedisDialog::new(i18n_shortcuts(cx, "title"))
+        .alert()
+        .icon(IconName::Info)
+        .child(move || {
+            v_flex()
+                .gap_2()
+                .children(rows.iter().map(|(label, keystrokes)| {
+                    h_flex()
+                        .justify_between()
+                        .gap_4()
+                        .child(Label::new(label.clone()).text_sm())
+                        .child(Label::new(keystrokes.clone()).text_sm().font_family(get_font_family()))
+                }))
+                .when(!tips.is_empty(), |this| {
+                    this.child(Label::new(tips.clone()).mt_2().text_xs())
+                })
+        })
+        .open(window, cx);
+}
@@ -13,11 +13,19 @@
 // limitations under the License.
 
 use crate::{
-    connection::get_servers,
+    connection::{get_server, get_servers},
     states::{GlobalEvent, Route, ZedisGlobalStore, i18n_sidebar},
+    views::open_notification_center,
+};
+use gpui::{Context, SharedString, Subscription, Window, WindowId, div, prelude::*, px, uniform_list};
+use gpui_component::{
+    ActiveTheme, Icon, IconName,
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    list::ListItem,
+    v_flex,
 };
-use gpui::{Context, SharedString, Subscription, Window, div, prelude::*, px, uniform_list};
-use gpui_component::{ActiveTheme, Icon, IconName, label::Label, list::ListItem, v_flex};
 use tracing::info;
 
 // Constants for UI layout
@@ -34,6 +42,9 @@ struct SidebarState {
 
     /// Currently selected server ID (empty string means home page)
     server_id: SharedString,
+
+    /// Window hosting this sidebar, selections in other windows are ignored
+    window_id: Option<WindowId>,
 }
 
 /// Sidebar navigation component
@@ -41,6 +52,7 @@ struct SidebarState {
 /// Features:
 /// - Star button (link to GitHub)
 /// - Server list for quick navigation between servers and home
+/// - Bell button opening the notifications of the session
 /// - Settings menu with theme and language options
 ///
 /// The sidebar provides quick access to:
@@ -61,7 +73,7 @@ impl ZedisSidebar {
     /// Sets up listeners for:
     /// - Server selection changes (updates current selection)
     /// - Server list updates (refreshes displayed servers)
-    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         let mut subscriptions = vec![];
 
         let global_state = cx.global::<ZedisGlobalStore>().state();
@@ -70,7 +82,7 @@ impl ZedisSidebar {
                 GlobalEvent::ServerListUpdated => {
                     this.update_server_names(cx);
                 }
-                GlobalEvent::ServerSelected(server_id, _) => {
+                GlobalEvent::ServerSelected(window_id, server_id, _) if Some(*window_id) == this.state.window_id => {
                     // Refresh server list when servers are added/removed/updated
                     this.state.server_id = server_id.clone();
                 }
@@ -80,7 +92,10 @@ impl ZedisSidebar {
         }));
 
         let mut this = Self {
-            state: SidebarState::default(),
+            state: SidebarState {
+                window_id: Some(window.window_handle().window_id()),
+                ..Default::default()
+            },
             _subscriptions: subscriptions,
         };
 
@@ -118,11 +133,13 @@ impl ZedisSidebar {
     ///
     /// Current selection is highlighted with background color and border.
     /// Clicking an item navigates to that server or home page.
-    fn render_server_list(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_server_list(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let servers = self.state.server_names.clone();
         let current_server_id_clone = self.state.server_id.clone();
         let is_match_route = matches!(
-            cx.global::<ZedisGlobalStore>().read(cx).route(),
+            cx.global::<ZedisGlobalStore>()
+                .read(cx)
+                .route(window.window_handle().window_id()),
             Route::Home | Route::Editor
         );
 
@@ -157,7 +174,7 @@ impl ZedisSidebar {
                                 .child(Icon::new(IconName::LayoutDashboard))
                                 .child(Label::new(name).text_ellipsis().text_xs()),
                         )
-                        .on_click(move |_, _window, cx| {
+                        .on_click(move |_, window, cx| {
                             // Don't do anything if already selected
                             if is_current {
                                 return;
@@ -166,11 +183,13 @@ impl ZedisSidebar {
                             // Determine target route based on home/server
                             let route = if is_home { Route::Home } else { Route::Editor };
 
-                            // Update global route
+                            let db = get_server(&server_id).map(|server| server.db()).unwrap_or_default();
+                            // Update route of this window
+                            let window_id = window.window_handle().window_id();
                             cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
                                 store.update(cx, |state, cx| {
-                                    state.go_to(route, cx);
-                                    state.set_selected_server((server_id.to_string(), 0), cx);
+                                    state.go_to(window_id, route, cx);
+                                    state.set_selected_server(window_id, (server_id.to_string(), db), cx);
                                 });
                             });
                         })
@@ -190,5 +209,16 @@ impl Render for ZedisSidebar {
             .border_r_1()
             .border_color(cx.theme().border)
             .child(div().flex_1().size_full().child(self.render_server_list(window, cx)))
+            .child(
+                h_flex().flex_none().w_full().py_2().justify_center().child(
+                    Button::new("sidebar-notifications")
+                        .ghost()
+                        .icon(IconName::Bell)
+                        .tooltip(i18n_sidebar(cx, "notifications"))
+                        .on_click(|_, window, cx| {
+                            open_notification_center(window, cx);
+                        }),
+                ),
+            )
     }
 }
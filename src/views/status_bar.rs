@@ -14,12 +14,13 @@
 
 use crate::{
     assets::CustomIconName,
-    connection::RedisClientDescription,
+    connection::{RedisClientDescription, get_connection_manager},
     constants::STATUS_BAR_HEIGHT,
-    helpers::{get_font_family, humanize_keystroke},
+    helpers::{EditorAction, get_font_family, humanize_keystroke},
     states::{
-        ErrorMessage, GlobalEvent, Route, ServerEvent, ServerTask, ViewMode, ZedisGlobalStore, ZedisServerState,
-        get_session_option, i18n_common, i18n_sidebar, i18n_status_bar, save_session_option,
+        ConnectionStatus, ErrorMessage, GlobalEvent, KeyType, Route, ServerEvent, ServerTask, ViewMode,
+        ZedisGlobalStore, ZedisServerState, get_session_option, i18n_common, i18n_sidebar, i18n_status_bar,
+        save_session_option,
     },
 };
 use chrono::{Local, LocalResult, TimeZone};
@@ -28,13 +29,14 @@ use gpui_component::select::{SearchableVec, Select, SelectEvent, SelectItem, Sel
 use gpui_component::{
     ActiveTheme, Disableable, Icon, IconName, IndexPath, Sizable,
     button::{Button, ButtonVariants},
+    dialog::DialogButtonProps,
     h_flex,
     label::Label,
     tooltip::Tooltip,
 };
 use std::{sync::Arc, time::Duration};
 use tracing::{debug, info};
-use zedis_ui::ZedisDivider;
+use zedis_ui::{ZedisDialog, ZedisDivider, ZedisFormField, ZedisFormOptions};
 
 /// Creates a disabled ghost button used as a metric badge in the status bar.
 #[inline]
@@ -95,7 +97,7 @@ fn format_nodes_description(description: Arc<RedisClientDescription>, cx: &Conte
     let t = i18n_sidebar(cx, "server_type");
     let master_nodes = i18n_sidebar(cx, "master_nodes");
     let slave_nodes = i18n_sidebar(cx, "slave_nodes");
-    let mut messages = Vec::with_capacity(4);
+    let mut messages = Vec::with_capacity(5);
 
     messages.push(format!(
         "Valkey: {}",
@@ -106,6 +108,9 @@ fn format_nodes_description(description: Arc<RedisClientDescription>, cx: &Conte
         }
     ));
     messages.push(format!("{t}: {}", description.server_type.as_str()));
+    if !description.protocol.is_empty() {
+        messages.push(format!("{}: {}", i18n_sidebar(cx, "protocol"), description.protocol));
+    }
     messages.push(format!("{master_nodes}: {}", description.master_nodes));
     if !description.slave_nodes.is_empty() {
         messages.push(format!("{slave_nodes}: {}", description.slave_nodes));
@@ -126,6 +131,7 @@ struct StatusBarServerState {
     nodes: SharedString,
     scan_finished: bool,
     soft_wrap: bool,
+    indent_guides: bool,
     nodes_description: SharedString,
     slow_logs: Vec<SharedString>,
 }
@@ -152,7 +158,12 @@ impl SelectItem for DbInfo {
 struct StatusBarState {
     server_state: StatusBarServerState,
     data_format: Option<SharedString>,
+    /// Cursor of the value editor, 0-based line and column
+    editor_cursor: Option<(u32, u32)>,
+    /// Whether the selected value is shown as a table (list, set, zset or hash)
+    table_value: bool,
     error: Option<ErrorMessage>,
+    connection_status: ConnectionStatus,
 }
 
 pub struct ZedisStatusBar {
@@ -181,6 +192,9 @@ impl ZedisStatusBar {
                 ServerEvent::ServerRedisInfoUpdated => {
                     this.fill_state(server_state, cx);
                 }
+                ServerEvent::ConnectionStatusChanged(status) => {
+                    this.state.connection_status = *status;
+                }
                 ServerEvent::ServerInfoUpdated => {
                     this.readonly = server_state.read(cx).readonly();
                     server_state.update(cx, |state, cx| {
@@ -199,6 +213,12 @@ impl ZedisStatusBar {
                     let state = server_state.read(cx);
                     this.state.server_state.size = format_size(state.dbsize(), state.scan_count());
                 }
+                ServerEvent::EditionActionTriggered(EditorAction::ToggleSoftWrap) => {
+                    this.toggle_soft_wrap(cx);
+                }
+                ServerEvent::EditorCursorMoved => {
+                    this.state.editor_cursor = server_state.read(cx).editor_cursor();
+                }
                 ServerEvent::ErrorOccurred(error) => {
                     debug!(
                         message = error.message.as_str(),
@@ -216,6 +236,12 @@ impl ZedisStatusBar {
                 ServerEvent::ValueLoaded => {
                     let state = server_state.read(cx);
                     this.should_reset_viewer_mode = Some(true);
+                    this.state.table_value = state.value().is_some_and(|value| {
+                        matches!(
+                            value.key_type(),
+                            KeyType::List | KeyType::Set | KeyType::Zset | KeyType::Hash
+                        )
+                    });
                     if let Some(value) = state.value().and_then(|item| item.bytes_value()) {
                         let mut format = value.format.as_str().to_string();
                         if let Some(mime) = &value.mime {
@@ -234,11 +260,12 @@ impl ZedisStatusBar {
         }));
         let viewer_mode_state = cx.new(|cx| {
             SelectState::new(
-                SearchableVec::new(vec![
-                    ViewMode::Auto.as_str().into(),
-                    ViewMode::Plain.as_str().into(),
-                    ViewMode::Hex.as_str().into(),
-                ]),
+                SearchableVec::new(
+                    ViewMode::ALL
+                        .iter()
+                        .map(|mode| SharedString::from(mode.as_str()))
+                        .collect::<Vec<_>>(),
+                ),
                 Some(IndexPath::new(0)),
                 window,
                 cx,
@@ -269,23 +296,27 @@ impl ZedisStatusBar {
         subscriptions.push(cx.subscribe_in(
             &db_state,
             window,
-            |view, _state, event: &SelectEvent<Vec<DbInfo>>, _window, cx| match event {
+            |view, _state, event: &SelectEvent<Vec<DbInfo>>, window, cx| match event {
                 SelectEvent::Confirm(value) => {
                     let Some(db) = *value else {
                         return;
                     };
                     let server_id = view.server_state.read(cx).server_id().to_string();
+                    let window_id = window.window_handle().window_id();
                     cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
                         store.update(cx, |state, cx| {
-                            state.set_selected_server((server_id, db), cx);
+                            state.set_selected_server(window_id, (server_id, db), cx);
                         });
                     });
                 }
             },
         ));
         let global_state = cx.global::<ZedisGlobalStore>().state();
-        subscriptions.push(cx.subscribe(&global_state, |this, _global_state, event, _cx| {
-            if let GlobalEvent::ServerSelected(_, _) = event {
+        let window_id = window.window_handle().window_id();
+        subscriptions.push(cx.subscribe(&global_state, move |this, _global_state, event, _cx| {
+            if let GlobalEvent::ServerSelected(selected_window_id, _, _) = event
+                && *selected_window_id == window_id
+            {
                 this.should_reset_db = Some(true);
             }
         }));
@@ -316,6 +347,9 @@ impl ZedisStatusBar {
             self.state.server_state.size = SharedString::default();
         }
         self.state.data_format = None;
+        self.state.editor_cursor = None;
+        self.state.connection_status = ConnectionStatus::Connected;
+        self.state.table_value = false;
         self.state.error = None;
     }
     fn fill_state(&mut self, server_state: Entity<ZedisServerState>, cx: &Context<Self>) {
@@ -368,6 +402,7 @@ impl ZedisStatusBar {
             scan_finished: state.scan_completed(),
             slow_logs,
             soft_wrap: state.soft_wrap(),
+            indent_guides: state.indent_guides(),
             nodes_description: format_nodes_description(state.nodes_description().clone(), cx),
         };
     }
@@ -383,6 +418,43 @@ impl ZedisStatusBar {
             }
         }));
     }
+    /// Lock the server right away, unlocking asks for a confirmation first
+    fn handle_toggle_readonly(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        if !self.readonly {
+            server_state.update(cx, |state, cx| {
+                state.toggle_readonly(cx);
+            });
+            return;
+        }
+        let button_props = DialogButtonProps::default()
+            .cancel_text(i18n_common(cx, "cancel"))
+            .ok_text(i18n_status_bar(cx, "unlock"));
+        ZedisDialog::new_alert(
+            i18n_status_bar(cx, "unlock_readonly_title"),
+            i18n_status_bar(cx, "unlock_readonly_prompt"),
+        )
+        .button_props(button_props)
+        .on_ok(move |_, _, cx| {
+            server_state.update(cx, |state, cx| {
+                state.toggle_readonly(cx);
+            });
+            true
+        })
+        .open(window, cx);
+    }
+    /// Close the connections of the server and go back to the server list
+    fn handle_disconnect(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_id = self.server_state.read(cx).server_id().to_string();
+        let window_id = window.window_handle().window_id();
+        cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
+            store.update(cx, |state, cx| {
+                state.go_to(window_id, Route::Home, cx);
+                state.set_selected_server(window_id, (String::new(), 0), cx);
+            });
+        });
+        get_connection_manager().disconnect(&server_id);
+    }
     /// Render the server status
     fn render_server_status(&self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state = &self.state.server_state;
@@ -394,6 +466,11 @@ impl ZedisStatusBar {
             humanize_keystroke("cmd-j")
         );
         let readonly_tooltip = i18n_status_bar(cx, "toggle_readonly_tooltip");
+        let (connection_color, connection_tooltip) = match self.state.connection_status {
+            ConnectionStatus::Connected => (cx.theme().green, i18n_common(cx, "connection_connected")),
+            ConnectionStatus::Reconnecting => (cx.theme().yellow, i18n_common(cx, "connection_reconnecting")),
+            ConnectionStatus::Disconnected => (cx.theme().red, i18n_common(cx, "connection_disconnected")),
+        };
 
         let slow_logs_tooltips = if server_state.slow_logs.is_empty() {
             i18n_common(cx, "slow_logs")
@@ -428,10 +505,18 @@ impl ZedisStatusBar {
                             .tooltip(readonly_tooltip)
                             .when(self.readonly, |this| this.icon(Icon::new(CustomIconName::Lock)))
                             .when(!self.readonly, |this| this.icon(Icon::new(CustomIconName::LockOpen)))
-                            .on_click(cx.listener(|this, _, _window, cx| {
-                                this.server_state.update(cx, |state, cx| {
-                                    state.toggle_readonly(cx);
-                                });
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.handle_toggle_readonly(window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("zedis-status-bar-server-disconnect")
+                            .outline()
+                            .small()
+                            .tooltip(i18n_status_bar(cx, "disconnect_tooltip"))
+                            .icon(Icon::new(CustomIconName::Unplug))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.handle_disconnect(window, cx);
                             })),
                     ),
             )
@@ -479,17 +564,26 @@ impl ZedisStatusBar {
                             .small()
                             .icon(CustomIconName::Activity)
                             .tooltip(i18n_status_bar(cx, "metrics_tooltip"))
-                            .on_click(cx.listener(|_this, _, _window, cx| {
+                            .on_click(cx.listener(|_this, _, window, cx| {
+                                let window_id = window.window_handle().window_id();
                                 cx.global::<ZedisGlobalStore>().clone().update(cx, |state, cx| {
-                                    let route = if state.route() == Route::Metrics {
+                                    let route = if state.route(window_id) == Route::Metrics {
                                         Route::Editor
                                     } else {
                                         Route::Metrics
                                     };
-                                    state.go_to(route, cx);
+                                    state.go_to(window_id, route, cx);
                                 });
                             })),
                     )
+                    .child(
+                        div()
+                            .id("zedis-status-bar-connection")
+                            .size_2()
+                            .rounded_full()
+                            .bg(connection_color)
+                            .tooltip(move |window, cx| Tooltip::new(connection_tooltip.clone()).build(window, cx)),
+                    )
                     .child(
                         metric_badge(
                             "zedis-status-bar-latency",
@@ -529,28 +623,60 @@ impl ZedisStatusBar {
                     ),
             )
     }
+    /// Toggle the soft wrap of the value editor and remember it for the server
+    fn toggle_soft_wrap(&mut self, cx: &mut Context<Self>) {
+        let soft_wrap = !self.state.server_state.soft_wrap;
+        self.state.server_state.soft_wrap = soft_wrap;
+        self.server_state.update(cx, |state, cx| {
+            state.set_soft_wrap(soft_wrap, cx);
+        });
+        cx.notify();
+
+        let server_id = self.state.server_state.server_id.clone();
+        if let Ok(mut option) = get_session_option(server_id.as_str()) {
+            option.soft_wrap = Some(soft_wrap);
+            save_session_option(server_id.as_str(), option, cx);
+        }
+    }
     fn render_editor_settings(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state = &self.state.server_state;
-        Button::new("soft-wrap")
+        let soft_wrap = Button::new("soft-wrap")
             .ghost()
             .xsmall()
             .when(server_state.soft_wrap, |this| this.icon(IconName::Check))
             .tooltip(i18n_status_bar(cx, "soft_wrap_tooltip"))
             .label(i18n_status_bar(cx, "soft_wrap"))
             .on_click(cx.listener(|this, _, _window, cx| {
-                let soft_wrap = !this.state.server_state.soft_wrap;
-                this.state.server_state.soft_wrap = soft_wrap;
+                this.toggle_soft_wrap(cx);
+            }));
+        // Tables have no code editor to draw the guides in
+        let table_value = self.state.table_value;
+        let indent_guides = Button::new("indent-guides")
+            .ghost()
+            .xsmall()
+            .disabled(table_value)
+            .when(server_state.indent_guides, |this| this.icon(IconName::Check))
+            .tooltip(if table_value {
+                i18n_status_bar(cx, "indent_guides_disabled_tooltip")
+            } else {
+                i18n_status_bar(cx, "indent_guides_tooltip")
+            })
+            .label(i18n_status_bar(cx, "indent_guides"))
+            .on_click(cx.listener(|this, _, _window, cx| {
+                let indent_guides = !this.state.server_state.indent_guides;
+                this.state.server_state.indent_guides = indent_guides;
                 this.server_state.update(cx, |state, cx| {
-                    state.set_soft_wrap(soft_wrap, cx);
+                    state.set_indent_guides(indent_guides, cx);
                 });
                 cx.notify();
 
                 let server_id = this.state.server_state.server_id.clone();
                 if let Ok(mut option) = get_session_option(server_id.as_str()) {
-                    option.soft_wrap = Some(soft_wrap);
+                    option.indent_guides = Some(indent_guides);
                     save_session_option(server_id.as_str(), option, cx);
                 }
-            }))
+            }));
+        h_flex().child(soft_wrap).child(indent_guides)
     }
     fn render_data_format(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let Some(data_format) = self.state.data_format.clone() else {
@@ -565,6 +691,51 @@ impl ZedisStatusBar {
             .label(data_format)
             .into_any_element()
     }
+    /// Live `line:column` of the value editor, click to jump to a line
+    fn render_go_to_line(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some((line, column)) = self.state.editor_cursor else {
+            return h_flex().into_any_element();
+        };
+        Button::new("go-to-line")
+            .ghost()
+            .xsmall()
+            .tooltip(i18n_status_bar(cx, "go_to_line_tooltip"))
+            .label(format!("{}:{}", line + 1, column + 1))
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.handle_go_to_line(window, cx);
+            }))
+            .into_any_element()
+    }
+    fn handle_go_to_line(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let validate_line = |s: &str| {
+            if s.trim().parse::<usize>().is_ok_and(|line| line > 0) {
+                return None;
+            }
+            Some("line should be a positive number".into())
+        };
+        let fields = vec![
+            ZedisFormField::new("line", i18n_status_bar(cx, "go_to_line"))
+                .placeholder(i18n_status_bar(cx, "go_to_line_placeholder"))
+                .required()
+                .focus()
+                .validate(validate_line),
+        ];
+        let server_state = self.server_state.clone();
+        ZedisFormOptions::new(fields)
+            .title(i18n_status_bar(cx, "go_to_line"))
+            .confirm_label(i18n_common(cx, "confirm"))
+            .cancel_label(i18n_common(cx, "cancel"))
+            .on_dialog_submit(move |values, _window, cx| {
+                let Some(line) = values.get("line").and_then(|value| value.trim().parse::<usize>().ok()) else {
+                    return false;
+                };
+                server_state.update(cx, |state, cx| {
+                    state.go_to_line(line, cx);
+                });
+                true
+            })
+            .open_dialog(window, cx);
+    }
     fn render_viewer_mode(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         if self.state.data_format.is_none() {
             return h_flex();
@@ -598,15 +769,23 @@ impl Render for ZedisStatusBar {
             return h_flex();
         }
         if let Some(true) = self.should_reset_viewer_mode.take() {
+            // Select the view mode remembered for the key pattern
+            let index = self
+                .server_state
+                .read(cx)
+                .value()
+                .and_then(|value| value.bytes_value())
+                .and_then(|value| ViewMode::ALL.iter().position(|mode| *mode == value.view_mode))
+                .unwrap_or_default();
             self.viewer_mode_state.update(cx, |state, cx| {
-                state.set_selected_index(Some(IndexPath::new(0)), window, cx);
+                state.set_selected_index(Some(IndexPath::new(index)), window, cx);
             });
         }
         if let Some(true) = self.should_reset_db.take() {
             let db = cx
                 .global::<ZedisGlobalStore>()
                 .read(cx)
-                .selected_server()
+                .selected_server(window.window_handle().window_id())
                 .map(|(_, db)| *db)
                 .unwrap_or_default();
             self.db_state.update(cx, |state, cx| {
@@ -615,7 +794,7 @@ impl Render for ZedisStatusBar {
         }
         h_flex()
             .justify_between()
-            .h(STATUS_BAR_HEIGHT)
+            .min_h(STATUS_BAR_HEIGHT)
             .text_sm()
             .py_1p5()
             .px_4()
@@ -632,7 +811,8 @@ impl Render for ZedisStatusBar {
                             h_flex()
                                 .items_center()
                                 .child(self.render_data_format(window, cx))
-                                .child(self.render_viewer_mode(window, cx)),
+                                .child(self.render_viewer_mode(window, cx))
+                                .child(self.render_go_to_line(window, cx)),
                         )
                     }),
             )
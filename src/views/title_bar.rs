@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::MemuAction;
+use crate::helpers::{MemuAction, ShortcutAction};
 use crate::{
     assets::CustomIconName,
     states::{FontSize, FontSizeAction, LocaleAction, SettingsAction, ThemeAction, ZedisGlobalStore, i18n_sidebar},
@@ -40,6 +40,11 @@ impl ZedisTitleBar {
         this
             // font size menu
             .label(i18n_sidebar(cx, "font_size"))
+            .menu_with_check(
+                i18n_sidebar(cx, "font_size_extra_large"),
+                font_size == FontSize::ExtraLarge,
+                Box::new(FontSizeAction::ExtraLarge),
+            )
             .menu_with_check(
                 i18n_sidebar(cx, "font_size_large"),
                 font_size == FontSize::Large,
@@ -89,6 +94,21 @@ impl ZedisTitleBar {
                 Box::new(SettingsAction::Editor),
                 move |_window, cx| Label::new(i18n_sidebar(cx, "other_settings")),
             )
+            .menu_element_with_icon(
+                Icon::new(IconName::SquareTerminal),
+                Box::new(SettingsAction::Logs),
+                move |_window, cx| Label::new(i18n_sidebar(cx, "logs")),
+            )
+            .menu_element_with_icon(
+                Icon::new(IconName::Plus),
+                Box::new(MemuAction::NewWindow),
+                move |_window, cx| Label::new(i18n_sidebar(cx, "new_window")),
+            )
+            .menu_element_with_icon(
+                Icon::new(CustomIconName::Keyboard),
+                Box::new(ShortcutAction::ShowShortcuts),
+                move |_window, cx| Label::new(i18n_sidebar(cx, "keyboard_shortcuts")),
+            )
             .menu_element_with_icon(
                 Icon::new(IconName::Info),
                 Box::new(MemuAction::About),
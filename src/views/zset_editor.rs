@@ -21,17 +21,30 @@
 //! - Updating scores of existing members (inline editing)
 //! - Removing members
 //! - Filtering members with pattern matching
+//! - Ordering members by ascending or descending score
 //! - Incremental loading of large ZSETs with pagination
 
 use crate::{
     components::KvTableColumn,
     components::ZedisKvFetcher,
-    states::{KeyType, RedisValue, ZedisServerState},
+    states::{KeyType, RedisValue, SortOrder, ZedisServerState},
     views::{ZedisKvTable, kv_table::define_kv_editor},
 };
 use gpui::{App, Entity, SharedString, Window, prelude::*};
 use zedis_ui::ZedisFormFieldType;
 
+/// Parses a ZSET score, Redis rejects NaN and the form doesn't accept infinities.
+fn parse_score(value: &str) -> Option<f64> {
+    value.trim().parse::<f64>().ok().filter(|score| score.is_finite())
+}
+
+fn validate_score(value: &str) -> Option<SharedString> {
+    if parse_score(value).is_some() {
+        return None;
+    }
+    Some("score should be a finite number".into())
+}
+
 /// Data adapter for Redis ZSET values to work with the KV table component.
 ///
 /// This struct implements the `ZedisKvFetcher` trait to provide data access
@@ -115,6 +128,34 @@ impl ZedisKvFetcher for ZedisZsetValues {
         });
     }
 
+    /// The member and its score, formatted to parse back to the same score.
+    fn restore_values(&self, index: usize) -> Option<Vec<SharedString>> {
+        let (member, score) = self.value.zset_value()?.values.get(index)?;
+        Some(vec![member.clone(), score.to_string().into()])
+    }
+
+    /// Adds the removed member again with ZADD.
+    fn restore(&self, values: Vec<SharedString>, cx: &mut App) {
+        let Some(score) = values.get(1).and_then(|score| parse_score(score)) else {
+            return;
+        };
+        let member = values[0].clone();
+        self.server_state.update(cx, |this, cx| {
+            this.add_zset_value(member, score, cx);
+        });
+    }
+
+    fn sort_order(&self) -> Option<SortOrder> {
+        self.value.zset_value().map(|v| v.sort_order)
+    }
+
+    /// Reloads the members by score, ZRANGE or ZREVRANGE.
+    fn sort(&self, sort_order: SortOrder, cx: &mut App) {
+        self.server_state.update(cx, |this, cx| {
+            this.sort_zset_value(sort_order, cx);
+        });
+    }
+
     /// Applies a filter to ZSET members by pattern matching.
     ///
     /// Resets the scan and loads members matching the keyword pattern.
@@ -133,9 +174,10 @@ impl ZedisKvFetcher for ZedisZsetValues {
             return;
         }
 
+        let Some(score) = parse_score(&values[1]) else {
+            return;
+        };
         let server_state = self.server_state.clone();
-        // Parse score from string (default to 0.0 if invalid)
-        let score = values[1].parse::<f64>().unwrap_or(0.0);
 
         // Execute the add operation on server state
         server_state.update(cx, |this, cx| {
@@ -163,8 +205,9 @@ impl ZedisKvFetcher for ZedisZsetValues {
             return;
         };
 
-        // Parse score and execute update operation
-        let score = score_str.parse::<f64>().unwrap_or(0.0);
+        let Some(score) = parse_score(score_str) else {
+            return;
+        };
         self.server_state.update(cx, |state, cx| {
             state.update_zset_value(original_member, member.clone(), score, cx);
         });
@@ -184,7 +227,7 @@ impl ZedisZsetEditor {
             ZedisKvTable::<ZedisZsetValues>::new(
                 vec![
                     KvTableColumn::new_flex("Value").field_type(ZedisFormFieldType::Editor),
-                    KvTableColumn::new("Score", Some(150.)),
+                    KvTableColumn::new("Score", Some(150.)).validate(validate_score),
                 ],
                 server_state,
                 window,